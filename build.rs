@@ -0,0 +1,34 @@
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(swmr_barrier_tsan)");
+    println!("cargo:rustc-check-cfg=cfg(loom)");
+    println!("cargo:rustc-check-cfg=cfg(swmr_barrier_ctor, values(none(), \"elf\", \"pe\", \"macho\"))");
+
+    // Target OS name for `PLATFORM.os`; `std::env::consts::OS` is not available under `no_std`.
+    // `PLATFORM.os` 使用的目标系统名称；`no_std` 下无法使用 `std::env::consts::OS`。
+    let os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    println!("cargo:rustc-env=SWMR_BARRIER_TARGET_OS={os}");
+
+    // Object format whose pre-`main` constructor section `src/init.rs` registers in, plus the bare
+    // `swmr_barrier_ctor` flag for code that only cares whether a constructor exists. Unset when none is known.
+    // `src/init.rs` 注册 `main` 之前构造函数所用节的目标文件格式，以及供仅关心是否存在构造函数的代码使用的
+    // `swmr_barrier_ctor` 标志。未知时均不设置。
+    let vendor = std::env::var("CARGO_CFG_TARGET_VENDOR").unwrap_or_default();
+    let ctor = match os.as_str() {
+        "windows" => Some("pe"),
+        _ if vendor == "apple" => Some("macho"),
+        "linux" | "android" | "freebsd" | "netbsd" | "openbsd" | "dragonfly" | "illumos" | "solaris" | "haiku"
+        | "fuchsia" | "hurd" => Some("elf"),
+        _ => None,
+    };
+    if let Some(ctor) = ctor {
+        println!("cargo:rustc-cfg=swmr_barrier_ctor");
+        println!("cargo:rustc-cfg=swmr_barrier_ctor=\"{ctor}\"");
+    }
+
+    // `cfg(sanitize = "thread")` is unstable; Cargo exposes it to build scripts on every toolchain.
+    // `cfg(sanitize = "thread")` 尚未稳定；Cargo 在所有工具链上都会将其暴露给构建脚本。
+    let sanitize = std::env::var("CARGO_CFG_SANITIZE").unwrap_or_default();
+    if sanitize.split(',').any(|s| s == "thread") {
+        println!("cargo:rustc-cfg=swmr_barrier_tsan");
+    }
+}
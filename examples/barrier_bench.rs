@@ -0,0 +1,75 @@
+//! Measures a `DoubleBuffer` under the asymmetric barriers and under plain `fence(SeqCst)` on both
+//! sides, so the reader-side benefit can be quantified on the current machine.
+//!
+//! 分别在非对称屏障与两端均为 `fence(SeqCst)` 的情况下测量 `DoubleBuffer`，以量化当前机器上读侧的收益。
+//!
+//! Run with: `cargo run --release --features std --example barrier_bench [seconds] [readers]`
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use swmr_barrier::{BarrierKind, DoubleBuffer};
+
+struct Report {
+    publishes: u64,
+    reads: u64,
+    elapsed: Duration,
+}
+
+/// One writer commits as fast as it can while `readers` threads borrow snapshots, for `duration`.
+fn run(kind: BarrierKind, readers: usize, duration: Duration) -> Report {
+    let buffer = Arc::new(DoubleBuffer::with_kind([0u64; 4], kind));
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let handles: Vec<_> = (0..readers)
+        .map(|_| {
+            let (buffer, stop) = (buffer.clone(), stop.clone());
+            thread::spawn(move || {
+                let mut reads = 0u64;
+                while !stop.load(Ordering::Relaxed) {
+                    let snapshot = buffer.borrow_snapshot();
+                    assert!(snapshot.iter().all(|&v| v == snapshot[0]), "torn snapshot");
+                    reads += 1;
+                }
+                reads
+            })
+        })
+        .collect();
+
+    let start = Instant::now();
+    let mut publishes = 0u64;
+    while start.elapsed() < duration {
+        publishes += 1;
+        buffer.commit([publishes; 4]);
+    }
+    stop.store(true, Ordering::Relaxed);
+
+    let reads = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    Report { publishes, reads, elapsed: start.elapsed() }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let seconds: f64 = args.next().map_or(1.0, |s| s.parse().expect("seconds"));
+    let readers: usize = args.next().map_or_else(
+        || thread::available_parallelism().map_or(1, |n| n.get().saturating_sub(1).max(1)),
+        |s| s.parse().expect("readers"),
+    );
+    let duration = Duration::from_secs_f64(seconds);
+
+    println!("{:?}", swmr_barrier::stats());
+    println!("{readers} reader(s), {seconds}s per run\n");
+    println!("{:<16} {:>16} {:>16}", "kind", "publishes/sec", "reads/sec");
+
+    for kind in [BarrierKind::Asymmetric, BarrierKind::SymmetricFence] {
+        let report = run(kind, readers, duration);
+        let secs = report.elapsed.as_secs_f64();
+        println!(
+            "{:<16} {:>16.0} {:>16.0}",
+            format!("{kind:?}"),
+            report.publishes as f64 / secs,
+            report.reads as f64 / secs
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! Measures what false sharing costs the slot rings: one writer stores to "its" slot while readers
+//! load the other slot, first with both slots adjacent in one cache line and then with each in its
+//! own `CachePadded` line, as `DoubleBuffer` and `SwmrCell` lay them out.
+//!
+//! 测量伪共享对槽环的影响：一个写者向“自己的”槽存储，读者加载另一个槽；先让两个槽相邻位于同一缓存行，
+//! 再让它们各自位于 `CachePadded` 行中（即 `DoubleBuffer` 与 `SwmrCell` 的布局）。
+//!
+//! Run with: `cargo run --release --features std --example false_sharing_bench [seconds] [readers]`
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use swmr_barrier::{CACHE_LINE, CachePadded};
+
+/// Two slots: the writer stores to `0`, readers load `1`.
+trait Slots: Send + Sync + 'static {
+    fn slot(&self, index: usize) -> &AtomicU64;
+}
+
+struct Adjacent([AtomicU64; 2]);
+struct Padded([CachePadded<AtomicU64>; 2]);
+
+impl Slots for Adjacent {
+    fn slot(&self, index: usize) -> &AtomicU64 {
+        &self.0[index]
+    }
+}
+
+impl Slots for Padded {
+    fn slot(&self, index: usize) -> &AtomicU64 {
+        &self.0[index]
+    }
+}
+
+/// Returns reader loads per second while the writer stores continuously.
+fn run<S: Slots>(slots: S, readers: usize, duration: Duration) -> f64 {
+    let slots = Arc::new(slots);
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let writer = {
+        let (slots, stop) = (slots.clone(), stop.clone());
+        thread::spawn(move || {
+            let mut value = 0u64;
+            while !stop.load(Ordering::Relaxed) {
+                value += 1;
+                slots.slot(0).store(value, Ordering::Release);
+            }
+        })
+    };
+    let handles: Vec<_> = (0..readers)
+        .map(|_| {
+            let (slots, stop) = (slots.clone(), stop.clone());
+            thread::spawn(move || {
+                let mut loads = 0u64;
+                while !stop.load(Ordering::Relaxed) {
+                    std::hint::black_box(slots.slot(1).load(Ordering::Acquire));
+                    loads += 1;
+                }
+                loads
+            })
+        })
+        .collect();
+
+    let start = Instant::now();
+    thread::sleep(duration);
+    stop.store(true, Ordering::Relaxed);
+    let loads: u64 = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    writer.join().unwrap();
+    loads as f64 / start.elapsed().as_secs_f64()
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let seconds: f64 = args.next().map_or(1.0, |s| s.parse().expect("seconds"));
+    let readers: usize = args.next().map_or_else(
+        || thread::available_parallelism().map_or(1, |n| n.get().saturating_sub(1).max(1)),
+        |s| s.parse().expect("readers"),
+    );
+    let duration = Duration::from_secs_f64(seconds);
+
+    println!("cache line {CACHE_LINE} bytes, {readers} reader(s), {seconds}s per run\n");
+    println!("{:<10} {:>16}", "layout", "loads/sec");
+
+    let adjacent = run(Adjacent([AtomicU64::new(0), AtomicU64::new(0)]), readers, duration);
+    println!("{:<10} {:>16.0}", "adjacent", adjacent);
+    let padded = run(Padded([CachePadded::new(AtomicU64::new(0)), CachePadded::new(AtomicU64::new(0))]), readers, duration);
+    println!("{:<10} {:>16.0}", "padded", padded);
+    println!("\npadded / adjacent: {:.2}x", padded / adjacent);
+}
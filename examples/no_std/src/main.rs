@@ -0,0 +1,47 @@
+//! Freestanding `SwmrCell` / `FairSeqlock` usage with no heap.
+//!
+//! Build for a bare-metal target without an allocator:
+//! `cargo build --target thumbv7em-none-eabi` (from `examples/no_std`).
+//!
+//! 无堆的独立 `SwmrCell` / `FairSeqlock` 用法。
+//!
+//! 为没有分配器的裸机目标构建：`cargo build --target thumbv7em-none-eabi`（在 `examples/no_std` 下执行）。
+
+#![no_std]
+#![no_main]
+
+use core::panic::PanicInfo;
+use swmr_barrier::{FairSeqlock, SwmrCell, SwmrWordCell};
+
+/// Configuration shared with interrupt handlers, built in `const` context.
+/// 与中断处理函数共享的配置，在 `const` 上下文中构造。
+static CONFIG: SwmrCell<(u16, u16)> = SwmrCell::new_const((0, 0));
+
+#[unsafe(no_mangle)]
+pub extern "C" fn _start() -> ! {
+    CONFIG.set((48_000, 16));
+    let (rate, bits) = CONFIG.get();
+
+    // Stack-allocated cells behave the same.
+    // 栈上的单元行为相同。
+    let counter = SwmrWordCell::new(0u32);
+    counter.set(u32::from(rate) + u32::from(bits));
+
+    let samples = FairSeqlock::new([0u16; 8]);
+    samples.set([rate; 8]);
+    let latest = samples.read();
+
+    if counter.get() != 48_016 || latest[7] != rate {
+        panic!();
+    }
+    loop {
+        core::hint::spin_loop();
+    }
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {
+        core::hint::spin_loop();
+    }
+}
@@ -0,0 +1,71 @@
+//! Measures what `prefetch_read` saves on cold reads: `SwmrCell::get` over cells visited in random
+//! order (far more than the caches hold), first plainly and then prefetching the cell `DISTANCE`
+//! reads ahead. Build with `--features prefetch` as well to add the slot-ring readers' own
+//! prefetch between the index load and the copy.
+//!
+//! 测量 `prefetch_read` 在冷读取上节省的开销：以随机顺序访问远超缓存容量的单元并执行 `SwmrCell::get`，先直接读取，
+//! 再在读取当前单元时预取之后第 `DISTANCE` 个单元。另加 `--features prefetch` 构建，可再启用槽环读者自身在索引加载与拷贝之间的预取。
+//!
+//! Run with: `cargo run --release --features std --example prefetch_bench [cells] [rounds]`
+
+use std::hint::black_box;
+use std::time::Instant;
+use swmr_barrier::{CACHE_LINE, SwmrCell, prefetch_read};
+
+type Cell = SwmrCell<[u64; 8]>;
+
+/// How many reads ahead to prefetch, so the lines arrive before they are needed.
+const DISTANCE: usize = 4;
+
+/// Visits `cells` in `order` and returns nanoseconds per read.
+fn run(cells: &[Cell], order: &[usize], rounds: usize, prefetch: bool) -> f64 {
+    let start = Instant::now();
+    let mut sum = 0u64;
+    for _ in 0..rounds {
+        for (i, &index) in order.iter().enumerate() {
+            if prefetch && let Some(&ahead) = order.get(i + DISTANCE) {
+                // With the value never replaced, `get` touches slot 0's buffer (line 0), slot 0's
+                // reader count (line 2) and the active index (line 4): every other line.
+                // 值从未被替换时，`get` 访问槽 0 的缓冲区（第 0 行）、槽 0 的读者计数（第 2 行）与活动索引（第 4 行）：每隔一行。
+                let base = core::ptr::from_ref(&cells[ahead]).cast::<u8>();
+                for offset in (0..size_of::<Cell>()).step_by(2 * CACHE_LINE) {
+                    prefetch_read(base.wrapping_add(offset));
+                }
+            }
+            sum = sum.wrapping_add(cells[index].get()[0]);
+        }
+    }
+    black_box(sum);
+    start.elapsed().as_nanos() as f64 / (rounds * order.len()) as f64
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let count: usize = args.next().map_or(1 << 17, |s| s.parse().expect("cells"));
+    let rounds: usize = args.next().map_or(5, |s| s.parse().expect("rounds"));
+
+    let cells: Vec<Cell> = (0..count as u64).map(|i| SwmrCell::new([i; 8])).collect();
+    // Fisher-Yates with xorshift, so consecutive reads never share a cache line or a page.
+    // 使用 xorshift 的 Fisher-Yates 洗牌，使相邻读取不共享缓存行或页面。
+    let mut order: Vec<usize> = (0..count).collect();
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    for i in (1..count).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        order.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+
+    println!(
+        "{count} cells ({} MiB), {rounds} rounds, `prefetch` feature {}",
+        (count * size_of::<Cell>()) >> 20,
+        if cfg!(feature = "prefetch") { "on" } else { "off" }
+    );
+    // Warm the TLB and page tables once so both runs start from the same state.
+    // 先预热一次 TLB 与页表，使两次运行从相同状态开始。
+    run(&cells, &order, 1, false);
+    let plain = run(&cells, &order, rounds, false);
+    let prefetched = run(&cells, &order, rounds, true);
+    println!("plain get:          {plain:>7.1} ns/read");
+    println!("prefetch ahead:     {prefetched:>7.1} ns/read ({:+.0}%)", (prefetched / plain - 1.0) * 100.0);
+}
@@ -0,0 +1,55 @@
+/*
+ * C interface to swmr-barrier (Cargo feature `capi`).
+ *
+ * A writer calls swmr_heavy_barrier() between its payload stores and the store that publishes
+ * them; a reader calls swmr_light_barrier() between the load that observes the publish and its
+ * payload loads. Either side may be C or Rust: both use the same implementation.
+ */
+#ifndef SWMR_BARRIER_H
+#define SWMR_BARRIER_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Writer-side (cold path) barrier. */
+void swmr_heavy_barrier(void);
+
+/* Reader-side (hot path) barrier. */
+void swmr_light_barrier(void);
+
+/* Whether OS-accelerated barriers are in use. */
+bool swmr_is_accelerated(void);
+
+/*
+ * Releases the barrier state; call it before dlclose(). Later barriers stay correct but use the
+ * fence fallback until the library is initialized again.
+ */
+void swmr_shutdown(void);
+
+/*
+ * Single-writer multi-reader uint64_t cell (requires the `std` feature as well). One thread
+ * publishes, any number load; every load returns a value that was published in full.
+ */
+typedef struct SwmrCellU64 SwmrCellU64;
+
+/* Allocates a cell holding `initial`. Never returns NULL. */
+SwmrCellU64 *swmr_cell_new(uint64_t initial);
+
+/* Publishes `value`. Concurrent publishers are serialized. */
+void swmr_cell_publish(SwmrCellU64 *cell, uint64_t value);
+
+/* Returns the most recently published value. */
+uint64_t swmr_cell_load(const SwmrCellU64 *cell);
+
+/* Frees the cell once no thread uses it. NULL is a no-op. */
+void swmr_cell_free(SwmrCellU64 *cell);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SWMR_BARRIER_H */
@@ -0,0 +1,258 @@
+use crate::barrier::{AsymmetricBarrier, SystemBarrier};
+use crate::slot_ring::Unlock;
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::sync::{UnsafeCell, spin_loop};
+use core::fmt;
+use core::mem::MaybeUninit;
+
+/// **Append Log**
+///
+/// A fixed-capacity single-writer multi-reader log. Entries are written once and never change, so
+/// readers need no retry loop: an entry below the published length is always complete.
+///
+/// * **Reader**: One load plus `light()` to learn the published length; entries below it are read directly.
+///   [`LogCursor`] issues this once per catch-up batch.
+/// * **Writer**: One `heavy()` per [`push`](Self::push).
+///
+/// ---
+///
+/// **追加日志**
+///
+/// 固定容量的单写多读日志。条目只写入一次且不再改变，因此读者无需重试：已发布长度以下的条目总是完整的。
+///
+/// * **读者**：一次加载加一次 `light()` 获取已发布长度，其下的条目可直接读取。[`LogCursor`] 每追赶一批只执行一次。
+/// * **写者**：每次 [`push`](Self::push) 一次 `heavy()`。
+pub struct AppendLog<T, const CAP: usize, B: AsymmetricBarrier = SystemBarrier> {
+    entries: [UnsafeCell<MaybeUninit<T>>; CAP],
+    len: AtomicUsize,
+    writing: AtomicBool,
+    barrier: B,
+}
+
+// SAFETY: Readers clone `&T` across threads (`T: Sync`); entries are moved in by the writer and
+// dropped with the log (`T: Send`). Published entries are never written again.
+// `Send` is derived and needs only `T: Send`.
+// 安全性：读者跨线程克隆 `&T`（`T: Sync`）；条目由写者移入并随日志释放（`T: Send`）。已发布的条目不会再被写入。
+// `Send` 自动推导，只需 `T: Send`。
+unsafe impl<T: Send + Sync, const CAP: usize, B: AsymmetricBarrier + Sync> Sync for AppendLog<T, CAP, B> {}
+
+impl<T, const CAP: usize> AppendLog<T, CAP> {
+    /// Creates an empty log.
+    /// 创建空日志。
+    pub fn new() -> Self {
+        Self::with_barrier(SystemBarrier)
+    }
+}
+
+impl<T, const CAP: usize> Default for AppendLog<T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize, B: AsymmetricBarrier> AppendLog<T, CAP, B> {
+    /// Creates an empty log using a custom barrier.
+    /// 使用自定义屏障创建空日志。
+    pub fn with_barrier(barrier: B) -> Self {
+        Self {
+            entries: core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit())),
+            len: AtomicUsize::new(0),
+            writing: AtomicBool::new(false),
+            barrier,
+        }
+    }
+
+    /// Maximum number of entries.
+    /// 最大条目数。
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Number of published entries. Entries below it are visible to the caller.
+    /// 已发布的条目数。调用方可见其以下的所有条目。
+    #[inline]
+    pub fn len(&self) -> usize {
+        let len = self.len.load(Ordering::Relaxed);
+        // Pairs with the writer's `heavy()` before it publishes the length.
+        // 与写者发布长度前的 `heavy()` 配对。
+        self.barrier.light();
+        len
+    }
+
+    /// **Staleness**
+    ///
+    /// How many entries were published after the caller last saw `last_read_len` (for example
+    /// [`LogCursor::position`]), saturating at zero. Lets a reader notice it is falling behind a hot
+    /// writer and skip ahead. Issues one `light()` like [`len`](Self::len).
+    ///
+    /// ---
+    ///
+    /// **滞后量**
+    ///
+    /// 调用方上次看到 `last_read_len`（例如 [`LogCursor::position`]）之后又发布了多少条目，最小为零。
+    /// 读者可据此发现自己落后于频繁写入的写者并跳过中间条目。与 [`len`](Self::len) 一样执行一次 `light()`。
+    #[inline]
+    pub fn staleness(&self, last_read_len: usize) -> usize {
+        self.len().saturating_sub(last_read_len)
+    }
+
+    /// Whether no entry has been published.
+    /// 是否尚未发布任何条目。
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// **Push**
+    ///
+    /// Appends `value` and publishes it. Returns the value back if the log is full.
+    /// Concurrent writers are serialized.
+    ///
+    /// ---
+    ///
+    /// **追加**
+    ///
+    /// 追加 `value` 并发布。日志已满时原样返回该值。并发写者会被串行化。
+    pub fn push(&self, value: T) -> Result<(), T> {
+        while self
+            .writing
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            spin_loop();
+        }
+        // Release the writer flag even if `heavy()` panics. The entry is then not published, and the
+        // next push overwrites it (leaking, never dropping twice).
+        // 即使 `heavy()` panic 也释放写者标志。此时条目未被发布，下一次追加会覆盖它（泄漏而不会重复析构）。
+        let _unlock = Unlock(&self.writing);
+
+        let len = self.len.load(Ordering::Relaxed);
+        if len == CAP {
+            Err(value)
+        } else {
+            // SAFETY: Entries at or above `len` are not visible to readers, and we hold the writer flag.
+            // 安全性：`len` 及以上的条目对读者不可见，且我们持有写者标志。
+            self.entries[len].get_mut().with(|ptr| unsafe { (*ptr).write(value) });
+            self.barrier.heavy();
+            self.len.store(len + 1, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    /// Reads entry `index`, which the caller must have seen below a published length.
+    /// 读取条目 `index`；调用方必须已观察到它低于某个已发布长度。
+    #[inline]
+    fn read(&self, index: usize) -> T
+    where
+        T: Clone,
+    {
+        let ptr = self.entries[index].get();
+        // SAFETY: Published entries are initialized and never written again.
+        // 安全性：已发布的条目已初始化且不再被写入。
+        unsafe { ptr.deref().assume_init_ref().clone() }
+    }
+
+    /// Returns a clone of entry `index`, or `None` if it is not published yet.
+    /// 返回条目 `index` 的克隆；若尚未发布则返回 `None`。
+    pub fn get(&self, index: usize) -> Option<T>
+    where
+        T: Clone,
+    {
+        (index < self.len()).then(|| self.read(index))
+    }
+}
+
+impl<T, const CAP: usize, B: AsymmetricBarrier> Drop for AppendLog<T, CAP, B> {
+    fn drop(&mut self) {
+        let len = self.len.load(Ordering::Relaxed);
+        for entry in &self.entries[..len] {
+            // SAFETY: Entries below `len` are initialized; `&mut self` excludes readers.
+            // 安全性：`len` 以下的条目已初始化；`&mut self` 排除了读者。
+            entry.get_mut().with(|ptr| unsafe { (*ptr).assume_init_drop() });
+        }
+    }
+}
+
+impl<T, const CAP: usize, B: AsymmetricBarrier> fmt::Debug for AppendLog<T, CAP, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppendLog")
+            .field("len", &self.len.load(Ordering::Relaxed))
+            .field("capacity", &CAP)
+            .finish()
+    }
+}
+
+/// **Log Cursor**
+///
+/// A reader position in one [`AppendLog`], created by [`AppendLog::cursor`]. Iterating returns
+/// entries in order and issues a single `light()` each time the cursor catches up to the published
+/// length, not one per entry. `None` means caught up, not finished: a later call returns entries
+/// pushed since.
+///
+/// The cursor borrows its log, so the published length it caches always describes the log it reads.
+///
+/// ---
+///
+/// **日志游标**
+///
+/// 某个 [`AppendLog`] 中的读者位置，由 [`AppendLog::cursor`] 创建。迭代时按顺序返回条目，每次追赶到已发布长度时只执行一次
+/// `light()`，而不是每个条目一次。`None` 表示已追上而非结束：之后的调用会返回其间新追加的条目。
+///
+/// 游标借用其日志，因此它缓存的已发布长度始终描述它所读取的日志。
+pub struct LogCursor<'a, T, const CAP: usize, B: AsymmetricBarrier = SystemBarrier> {
+    log: &'a AppendLog<T, CAP, B>,
+    position: usize,
+    published: usize,
+}
+
+impl<T, const CAP: usize, B: AsymmetricBarrier> AppendLog<T, CAP, B> {
+    /// Creates a cursor at the start of this log.
+    /// 创建位于本日志开头的游标。
+    pub fn cursor(&self) -> LogCursor<'_, T, CAP, B> {
+        LogCursor {
+            log: self,
+            position: 0,
+            published: 0,
+        }
+    }
+}
+
+impl<T, const CAP: usize, B: AsymmetricBarrier> LogCursor<'_, T, CAP, B> {
+    /// Index of the next entry to read.
+    /// 下一个待读条目的索引。
+    #[inline]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<T: Clone, const CAP: usize, B: AsymmetricBarrier> Iterator for LogCursor<'_, T, CAP, B> {
+    type Item = T;
+
+    /// Returns the next entry, or `None` once caught up with the published length.
+    /// 返回下一个条目；追上已发布长度时返回 `None`。
+    fn next(&mut self) -> Option<T> {
+        if self.position == self.published {
+            self.published = self.log.len();
+            if self.position == self.published {
+                return None;
+            }
+        }
+        let value = self.log.read(self.position);
+        self.position += 1;
+        Some(value)
+    }
+}
+
+impl<T, const CAP: usize, B: AsymmetricBarrier> Clone for LogCursor<'_, T, CAP, B> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<T, const CAP: usize, B: AsymmetricBarrier> fmt::Debug for LogCursor<'_, T, CAP, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogCursor").field("position", &self.position).finish()
+    }
+}
@@ -0,0 +1,110 @@
+//! **SWMR Arc Cell** (feature `std`)
+//!
+//! An `arc-swap`-style cell on the slot ring: the writer publishes `Arc`s, readers clone the
+//! current one out. The slot's reader count doubles as the grace period, so the clone can never
+//! race with the writer releasing the old `Arc`.
+//!
+//! ---
+//!
+//! **SWMR Arc 单元**（`std` 特性）
+//!
+//! 基于槽环的 `arc-swap` 风格单元：写者发布 `Arc`，读者克隆出当前值。槽的读者计数同时充当宽限期，因此克隆
+//! 永远不会与写者释放旧 `Arc` 竞争。
+
+use crate::barrier::{AsymmetricBarrier, SystemBarrier};
+use crate::slot_ring::SlotRing;
+use core::fmt;
+use std::sync::Arc;
+
+/// **SWMR Arc Cell**
+///
+/// Publishes an [`Arc<T>`] to many readers. [`load`](Self::load) returns a clone of the current
+/// `Arc`, so readers keep a consistent value for as long as they like without blocking the writer;
+/// [`store`](Self::store) publishes a new one.
+///
+/// A reader pins the active slot before touching the `Arc` in it and unpins it only after its
+/// reference count is incremented. The writer drops an old `Arc` only when it overwrites that slot,
+/// after a heavy barrier and after every reader pinning the slot has left. So the cell holds the
+/// writer's previous `Arc` for one more `store`, and a loaded `Arc` is never freed under a reader.
+///
+/// * **Reader**: One counter increment plus `light()`, and one reference-count increment, per `load`.
+/// * **Writer**: One `heavy()` per `store`, then waits for readers still cloning the `Arc` from two
+///   stores ago.
+///
+/// ---
+///
+/// **SWMR Arc 单元**
+///
+/// 向多个读者发布 [`Arc<T>`]。[`load`](Self::load) 返回当前 `Arc` 的克隆，读者可任意长时间持有一致的值而不阻塞写者；
+/// [`store`](Self::store) 发布新值。
+///
+/// 读者在访问槽中的 `Arc` 之前固定活动槽，并在其引用计数递增之后才取消固定。写者只在覆盖该槽时释放旧 `Arc`，此前已执行
+/// 重型屏障且所有固定该槽的读者都已离开。因此单元会多持有写者的上一个 `Arc` 一次 `store`，已加载的 `Arc` 绝不会在读者
+/// 使用时被释放。
+///
+/// * **读者**：每次 `load` 一次计数器递增加一次 `light()`，以及一次引用计数递增。
+/// * **写者**：每次 `store` 一次 `heavy()`，随后等待仍在克隆两次 `store` 之前的 `Arc` 的读者。
+pub struct SwmrArcCell<T, B: AsymmetricBarrier = SystemBarrier> {
+    // `Send`/`Sync` come from the ring and need `Arc<T>: Send + Sync`, i.e. `T: Send + Sync`.
+    // `Send`/`Sync` 由环推导，需要 `Arc<T>: Send + Sync`，即 `T: Send + Sync`。
+    ring: SlotRing<Arc<T>, 2, B>,
+}
+
+impl<T> SwmrArcCell<T> {
+    /// Creates a cell holding `initial`.
+    /// 创建持有 `initial` 的单元。
+    pub fn new(initial: Arc<T>) -> Self {
+        Self::with_barrier(initial, SystemBarrier)
+    }
+
+    /// Creates a cell holding `Arc::new(value)`.
+    /// 创建持有 `Arc::new(value)` 的单元。
+    pub fn from_pointee(value: T) -> Self {
+        Self::new(Arc::new(value))
+    }
+}
+
+impl<T, B: AsymmetricBarrier> SwmrArcCell<T, B> {
+    /// Creates a cell using a custom barrier.
+    /// 使用自定义屏障创建单元。
+    pub fn with_barrier(initial: Arc<T>, barrier: B) -> Self {
+        Self {
+            ring: SlotRing::new(initial, barrier),
+        }
+    }
+
+    /// Returns a clone of the current `Arc`.
+    /// 返回当前 `Arc` 的克隆。
+    #[inline]
+    pub fn load(&self) -> Arc<T> {
+        // `ptr` must end its borrow before the slot is unpinned.
+        // `ptr` 必须在取消固定之前结束借用。
+        let (index, current) = {
+            let (index, ptr) = self.ring.pin();
+            // SAFETY: The slot stays pinned until `unpin`, so the writer cannot drop this `Arc` meanwhile.
+            // 安全性：该槽在 `unpin` 之前保持固定，写者在此期间无法释放这个 `Arc`。
+            (index, Arc::clone(unsafe { ptr.deref() }))
+        };
+        self.ring.unpin(index);
+        current
+    }
+
+    /// Publishes `value`. Concurrent writers are serialized.
+    /// 发布 `value`。并发写者会被串行化。
+    #[inline]
+    pub fn store(&self, value: Arc<T>) {
+        self.ring.commit(value);
+    }
+}
+
+impl<T: Default> Default for SwmrArcCell<T> {
+    fn default() -> Self {
+        Self::from_pointee(T::default())
+    }
+}
+
+impl<T: fmt::Debug, B: AsymmetricBarrier> fmt::Debug for SwmrArcCell<T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SwmrArcCell").field(&self.load()).finish()
+    }
+}
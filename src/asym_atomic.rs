@@ -0,0 +1,84 @@
+use crate::cell::WordSized;
+use core::fmt;
+
+/// **Asymmetric Atomic**
+///
+/// A single atomic with the barriers built in, for the common case of publishing one word-sized
+/// value: [`publish`](Self::publish) is `heavy_barrier()` then a `Relaxed` store, and
+/// [`consume`](Self::consume) is a `Relaxed` load then `light_barrier()`. A reader that consumes a
+/// published value sees every write the writer made before publishing it. Supports the
+/// [`WordSized`] types: the integers, `bool` and `*mut T`.
+///
+/// [`load_relaxed`](Self::load_relaxed) and [`store_relaxed`](Self::store_relaxed) skip the barriers,
+/// for callers that batch several accesses under one barrier they issue themselves. Unlike
+/// [`SwmrWordCell`](crate::SwmrWordCell), it always uses the process-wide barriers.
+///
+/// ---
+///
+/// **非对称原子变量**
+///
+/// 内置屏障的单个原子变量，面向发布单个字大小值的常见场景：[`publish`](Self::publish) 先 `heavy_barrier()`
+/// 再 `Relaxed` 存储，[`consume`](Self::consume) 先 `Relaxed` 加载再 `light_barrier()`。消费到已发布值的读者能看到
+/// 写者在发布之前的所有写入。支持 [`WordSized`] 类型：整数、`bool` 与 `*mut T`。
+///
+/// [`load_relaxed`](Self::load_relaxed) 与 [`store_relaxed`](Self::store_relaxed) 跳过屏障，供在自行发出的一次屏障下
+/// 批量执行多次访问的调用方使用。与 [`SwmrWordCell`](crate::SwmrWordCell) 不同，它总是使用进程级屏障。
+pub struct AsymAtomic<T: WordSized> {
+    atomic: T::Atomic,
+}
+
+impl<T: WordSized> AsymAtomic<T> {
+    /// Creates an atomic holding `value`.
+    /// 创建持有 `value` 的原子变量。
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self { atomic: T::new_atomic(value) }
+    }
+
+    /// Issues [`heavy_barrier()`](crate::heavy_barrier), then stores `value` with `Relaxed` ordering.
+    /// 调用 [`heavy_barrier()`](crate::heavy_barrier)，然后以 `Relaxed` 顺序存储 `value`。
+    #[inline]
+    pub fn publish(&self, value: T) {
+        crate::heavy_barrier();
+        T::store(&self.atomic, value);
+    }
+
+    /// Loads the value with `Relaxed` ordering, then issues [`light_barrier()`](crate::light_barrier).
+    /// The barrier follows the load, so reads after `consume` see what was written before the
+    /// matching [`publish`](Self::publish).
+    ///
+    /// 以 `Relaxed` 顺序加载值，然后调用 [`light_barrier()`](crate::light_barrier)。屏障位于加载之后，因此 `consume`
+    /// 之后的读取能看到对应 [`publish`](Self::publish) 之前的写入。
+    #[inline]
+    pub fn consume(&self) -> T {
+        let value = T::load(&self.atomic);
+        crate::light_barrier();
+        value
+    }
+
+    /// Loads the value with `Relaxed` ordering and no barrier.
+    /// 以 `Relaxed` 顺序加载值，不带屏障。
+    #[inline]
+    pub fn load_relaxed(&self) -> T {
+        T::load(&self.atomic)
+    }
+
+    /// Stores `value` with `Relaxed` ordering and no barrier.
+    /// 以 `Relaxed` 顺序存储 `value`，不带屏障。
+    #[inline]
+    pub fn store_relaxed(&self, value: T) {
+        T::store(&self.atomic, value);
+    }
+}
+
+impl<T: WordSized + Default> Default for AsymAtomic<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: WordSized + fmt::Debug> fmt::Debug for AsymAtomic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AsymAtomic").field(&self.load_relaxed()).finish()
+    }
+}
@@ -0,0 +1,123 @@
+use crate::barrier::{AsymmetricBarrier, SystemBarrier};
+use crate::seqlock::FairSeqlock;
+use core::fmt;
+
+/// **Atomic Cell**
+///
+/// The single-writer subset of `crossbeam::atomic::AtomicCell` for any `T: Copy`, backed by a
+/// [`FairSeqlock`]: [`store`](Self::store) and [`swap`](Self::swap) are seqlock writes bracketed by
+/// heavy barriers, [`load`](Self::load) is a seqlock read between two light barriers. Code that
+/// only calls these (plus [`new`](Self::new), [`take`](Self::take) and
+/// [`into_inner`](Self::into_inner)) can switch by changing the import.
+///
+/// **Single writer.** crossbeam's cell takes a lock per operation and is meant for many writers.
+/// Here writers are serialized, so concurrent `store`s and `swap`s stay correct, but each one
+/// costs two heavy barriers and they queue behind each other; it pays off only when one thread
+/// writes and many read. There is no `compare_exchange` or `fetch_*`: read-modify-write from several
+/// threads is exactly the workload this cell is slow at.
+///
+/// **Layout.** crossbeam's cell is `#[repr(transparent)]`, exactly the size of `T`. This one stores
+/// the seqlock's state next to the value: the write sequence (64-bit where the target has 64-bit
+/// atomics), the starving-reader count, the patience and the writer flag. On 64-bit targets that
+/// adds 24 bytes (plus the waker list with feature `async`) and at least 8-byte alignment, so an
+/// `AtomicCell<u64>` takes at least 32 bytes, and code that relies on
+/// `size_of::<AtomicCell<T>>() == size_of::<T>()` or transmutes between the two does not carry over.
+///
+/// * **Reader**: Two `light()`s per attempt; retries while a write is in progress.
+/// * **Writer**: Two `heavy()`s per `store` or `swap`.
+///
+/// ---
+///
+/// **原子单元**
+///
+/// `crossbeam::atomic::AtomicCell` 的单写者子集，适用于任意 `T: Copy`，基于 [`FairSeqlock`]：[`store`](Self::store)
+/// 与 [`swap`](Self::swap) 是由重型屏障包围的顺序锁写入，[`load`](Self::load) 是在两次轻型屏障之间的顺序锁读取。
+/// 只调用这些方法（以及 [`new`](Self::new)、[`take`](Self::take) 与 [`into_inner`](Self::into_inner)）的代码只需更换导入即可切换。
+///
+/// **单写者。** crossbeam 的单元每次操作都会加锁，面向多写者场景。此处写者会被串行化，因此并发的 `store` 与 `swap`
+/// 仍然正确，但每次都要付出两次重型屏障并彼此排队；只有一个线程写、多个线程读时才划算。不提供 `compare_exchange` 或
+/// `fetch_*`：多线程读-改-写恰恰是该单元不擅长的负载。
+///
+/// **布局。** crossbeam 的单元是 `#[repr(transparent)]`，大小恰好等于 `T`。本单元在值旁边保存顺序锁的状态：写入序号
+/// （目标支持 64 位原子操作时为 64 位）、饥饿读者计数、耐心值与写者标志。在 64 位目标上这会增加 24 字节（启用 `async` 特性时另加唤醒器列表），并至少按 8 字节对齐，
+/// 因此 `AtomicCell<u64>` 至少占 32 字节；依赖 `size_of::<AtomicCell<T>>() == size_of::<T>()` 或在两者之间 transmute 的代码
+/// 不能直接迁移。
+///
+/// * **读者**：每次尝试两次 `light()`；写入进行中时重试。
+/// * **写者**：每次 `store` 或 `swap` 两次 `heavy()`。
+pub struct AtomicCell<T: Copy, B: AsymmetricBarrier = SystemBarrier> {
+    // `Sync` comes from the seqlock and needs `T: Send`, as crossbeam's does.
+    // `Sync` 由顺序锁推导，与 crossbeam 相同，需要 `T: Send`。
+    seqlock: FairSeqlock<T, B>,
+}
+
+impl<T: Copy> AtomicCell<T> {
+    /// Creates a cell holding `value`.
+    /// 创建持有 `value` 的单元。
+    pub fn new(value: T) -> Self {
+        Self::with_barrier(value, SystemBarrier)
+    }
+}
+
+impl<T: Copy, B: AsymmetricBarrier> AtomicCell<T, B> {
+    /// Creates a cell using a custom barrier.
+    /// 使用自定义屏障创建单元。
+    pub fn with_barrier(value: T, barrier: B) -> Self {
+        Self {
+            seqlock: FairSeqlock::with_barrier(value, barrier),
+        }
+    }
+
+    /// Returns a copy of the current value.
+    /// 返回当前值的拷贝。
+    #[inline]
+    pub fn load(&self) -> T {
+        self.seqlock.read()
+    }
+
+    /// Publishes `value`.
+    /// 发布 `value`。
+    #[inline]
+    pub fn store(&self, value: T) {
+        self.seqlock.set(value);
+    }
+
+    /// Publishes `value` and returns the value it replaced.
+    /// 发布 `value` 并返回被替换的值。
+    #[inline]
+    pub fn swap(&self, value: T) -> T {
+        self.seqlock.replace(value)
+    }
+
+    /// Consumes the cell and returns the value.
+    /// 消耗单元并返回其中的值。
+    pub fn into_inner(self) -> T {
+        self.load()
+    }
+}
+
+impl<T: Copy + Default, B: AsymmetricBarrier> AtomicCell<T, B> {
+    /// Publishes `T::default()` and returns the value it replaced.
+    /// 发布 `T::default()` 并返回被替换的值。
+    pub fn take(&self) -> T {
+        self.swap(T::default())
+    }
+}
+
+impl<T: Copy + Default> Default for AtomicCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Copy> From<T> for AtomicCell<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Copy + fmt::Debug, B: AsymmetricBarrier> fmt::Debug for AtomicCell<T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtomicCell").field("value", &self.load()).finish()
+    }
+}
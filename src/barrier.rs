@@ -0,0 +1,376 @@
+use crate::sync::atomic::{Ordering, fence};
+use core::fmt;
+use core::sync::atomic::AtomicUsize;
+
+/// **Asymmetric Barrier Abstraction**
+///
+/// A pluggable heavy/light barrier pair. Code that is generic over this trait can be
+/// exercised with a test double instead of the real OS barriers.
+///
+/// # Safety
+///
+/// Implementations must uphold the asymmetric contract: for any `heavy()` call on one
+/// thread and `light()` call on another, memory operations before `heavy()` must be
+/// visible to operations after `light()` whenever the `light()` side observes anything
+/// written after `heavy()`. Data structures built on this trait rely on it for soundness.
+/// A mock that does nothing is only acceptable when it is never shared across threads.
+///
+/// ---
+///
+/// **非对称屏障抽象**
+///
+/// 可替换的重型/轻型屏障对。基于此 trait 编写的泛型代码可以在测试中注入替身，而不必使用真实的 OS 屏障。
+///
+/// # 安全性
+///
+/// 实现必须满足非对称约定：数据结构依赖此约定保证内存安全。空操作的 mock 只能在单线程中使用。
+pub unsafe trait AsymmetricBarrier {
+    /// Writer-side (cold path) barrier.
+    /// 写侧（冷路径）屏障。
+    fn heavy(&self);
+
+    /// Reader-side (hot path) barrier.
+    /// 读侧（热路径）屏障。
+    fn light(&self);
+
+    /// Whether `light()` is cheaper than a full hardware fence.
+    /// `light()` 是否比完整的硬件屏障更廉价。
+    fn accelerated(&self) -> bool;
+}
+
+// SAFETY: Forwards to `B`, which upholds the contract.
+// 安全性：转发给满足约定的 `B`。
+unsafe impl<B: AsymmetricBarrier + ?Sized> AsymmetricBarrier for &B {
+    #[inline]
+    fn heavy(&self) {
+        (**self).heavy();
+    }
+
+    #[inline]
+    fn light(&self) {
+        (**self).light();
+    }
+
+    #[inline]
+    fn accelerated(&self) -> bool {
+        (**self).accelerated()
+    }
+}
+
+/// **System Barrier**
+///
+/// The default [`AsymmetricBarrier`], delegating to [`heavy_barrier`](crate::heavy_barrier),
+/// [`light_barrier`](crate::light_barrier) and [`is_accelerated`](crate::is_accelerated).
+///
+/// ---
+///
+/// **系统屏障**
+///
+/// 默认的 [`AsymmetricBarrier`] 实现，直接委托给 crate 提供的自由函数。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SystemBarrier;
+
+// SAFETY: Delegates to the crate's own barriers, which implement the contract per platform. The
+// fallback fences can be weakened only through the `unsafe` `set_fallback_ordering`, whose caller
+// takes over this obligation.
+// 安全性：委托给 crate 自身的屏障实现，各平台均满足约定。回退栅栏只能通过 `unsafe` 的 `set_fallback_ordering`
+// 削弱，由其调用方承担该义务。
+unsafe impl AsymmetricBarrier for SystemBarrier {
+    #[inline]
+    fn heavy(&self) {
+        crate::heavy_barrier();
+    }
+
+    #[inline]
+    fn light(&self) {
+        crate::light_barrier();
+    }
+
+    #[inline]
+    fn accelerated(&self) -> bool {
+        crate::is_accelerated()
+    }
+}
+
+/// **Barrier Domain**
+///
+/// A logically separate barrier, e.g. one per subsystem. `heavy()` and `light()` delegate to the
+/// process-wide [`SystemBarrier`], since membarrier already orders every thread, but each domain
+/// keeps its own [`epoch`](Self::epoch) of heavy barriers issued through it. Constructed in `const`
+/// context, so a domain can live in a `static` without `std` or lazy initialization. Structures
+/// share one domain by reference:
+///
+/// ```
+/// use swmr_barrier::{BarrierDomain, SwmrCell};
+///
+/// static NETWORK: BarrierDomain = BarrierDomain::new();
+///
+/// let routes = SwmrCell::<u32, 2, _>::with_barrier(0, &NETWORK);
+/// routes.set(1);
+/// assert_eq!(NETWORK.epoch(), 1);
+/// ```
+///
+/// ---
+///
+/// **屏障域**
+///
+/// 逻辑上独立的屏障，例如每个子系统一个。`heavy()` 与 `light()` 委托给进程级的 [`SystemBarrier`]（membarrier
+/// 本身已对所有线程排序），但每个域单独记录经由它发出的重型屏障的 [`epoch`](Self::epoch)。可在 `const`
+/// 上下文中构造，因此无需 `std` 或延迟初始化即可放入 `static`。多个数据结构通过引用共享同一个域。
+#[derive(Debug, Default)]
+pub struct BarrierDomain {
+    epoch: AtomicUsize,
+}
+
+impl BarrierDomain {
+    /// Creates a domain at epoch 0.
+    /// 创建纪元为 0 的域。
+    pub const fn new() -> Self {
+        Self {
+            epoch: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of heavy barriers issued through this domain, wrapping at `usize::MAX`.
+    /// 经由本域发出的重型屏障数量，在 `usize::MAX` 处回绕。
+    #[inline]
+    pub fn epoch(&self) -> usize {
+        self.epoch.load(Ordering::Relaxed)
+    }
+}
+
+// SAFETY: Delegates to the crate's own barriers; the epoch counter does not take part in ordering.
+// 安全性：委托给 crate 自身的屏障；纪元计数器不参与排序。
+unsafe impl AsymmetricBarrier for BarrierDomain {
+    #[inline]
+    fn heavy(&self) {
+        crate::heavy_barrier();
+        self.epoch.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn light(&self) {
+        crate::light_barrier();
+    }
+
+    #[inline]
+    fn accelerated(&self) -> bool {
+        crate::is_accelerated()
+    }
+}
+
+/// **Reader-Tracked Barrier**
+///
+/// Wraps a barrier `B` and counts registered readers. While none is registered, `heavy()` is a
+/// plain `fence(SeqCst)` instead of `B::heavy()`: no reader can depend on the IPI, so a writer
+/// publishing to nobody skips the syscall. Opt in per structure by passing it (or a reference to a
+/// shared one) to a `with_barrier` constructor.
+///
+/// **Readers must [`register`](Self::register) before their first `light()`** and keep the
+/// returned [`ReaderRegistration`] alive while they read. Registration issues one `fence(SeqCst)`
+/// that pairs with the writer's: a writer that saw no readers had its earlier stores ordered before
+/// the registration returned. An unregistered reader pairs with nothing and may miss a publish.
+///
+/// ---
+///
+/// **读者计数屏障**
+///
+/// 包装屏障 `B` 并统计已注册的读者。没有读者注册时，`heavy()` 只是一次普通 `fence(SeqCst)`，而非
+/// `B::heavy()`：没有读者依赖 IPI，因此向无人发布的写者可以跳过系统调用。按数据结构选择启用：将其（或共享实例的引用）
+/// 传给 `with_barrier` 构造函数。
+///
+/// **读者必须在首次 `light()` 之前调用 [`register`](Self::register)**，并在读取期间持有返回的
+/// [`ReaderRegistration`]。注册会执行一次与写者配对的 `fence(SeqCst)`：看到没有读者的写者，其之前的存储在注册返回之前
+/// 已排好序。未注册的读者不与任何屏障配对，可能错过发布。
+pub struct TrackedBarrier<B: AsymmetricBarrier = SystemBarrier> {
+    readers: crate::sync::atomic::AtomicUsize,
+    barrier: B,
+}
+
+impl TrackedBarrier {
+    /// Creates a tracker over [`SystemBarrier`] with no registered reader.
+    /// 基于 [`SystemBarrier`] 创建没有已注册读者的计数屏障。
+    pub fn new() -> Self {
+        Self::with_barrier(SystemBarrier)
+    }
+}
+
+impl Default for TrackedBarrier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: AsymmetricBarrier> TrackedBarrier<B> {
+    /// Creates a tracker over a custom barrier.
+    /// 基于自定义屏障创建计数屏障。
+    pub fn with_barrier(barrier: B) -> Self {
+        Self {
+            readers: crate::sync::atomic::AtomicUsize::new(0),
+            barrier,
+        }
+    }
+
+    /// **Register**
+    ///
+    /// Counts the caller as a reader until the returned guard is dropped. Call it before the
+    /// reader's first `light()`.
+    ///
+    /// ---
+    ///
+    /// **注册**
+    ///
+    /// 在返回的守卫被释放之前将调用方计为读者。应在读者首次 `light()` 之前调用。
+    #[inline]
+    pub fn register(&self) -> ReaderRegistration<'_, B> {
+        self.readers.fetch_add(1, Ordering::Relaxed);
+        // Pairs with the fence in `heavy()`: either that writer sees this reader, or its earlier
+        // stores are visible to every load after this fence.
+        // 与 `heavy()` 中的栅栏配对：要么该写者看到此读者，要么其之前的存储对本栅栏之后的所有加载可见。
+        fence(Ordering::SeqCst);
+        ReaderRegistration { tracker: self }
+    }
+
+    /// Number of readers currently registered.
+    /// 当前已注册的读者数量。
+    #[inline]
+    pub fn readers(&self) -> usize {
+        self.readers.load(Ordering::Relaxed)
+    }
+}
+
+// SAFETY: With readers registered, `heavy()` runs `B::heavy()`, which upholds the contract. With
+// none, the writer's `fence(SeqCst)` precedes the fence of every later `register()` in the SeqCst
+// order (otherwise its load would have seen the increment), so its earlier stores are visible to
+// the reader's loads; the Acquire load also orders the last readers' loads before later stores.
+// 安全性：有读者注册时，`heavy()` 执行满足约定的 `B::heavy()`。没有读者时，写者的 `fence(SeqCst)` 在 SeqCst
+// 全序中先于之后每次 `register()` 的栅栏（否则其加载会看到递增），因此其之前的存储对读者的加载可见；Acquire
+// 加载还使最后一批读者的加载先于之后的存储。
+unsafe impl<B: AsymmetricBarrier> AsymmetricBarrier for TrackedBarrier<B> {
+    #[inline]
+    fn heavy(&self) {
+        fence(Ordering::SeqCst);
+        if self.readers.load(Ordering::Acquire) != 0 {
+            self.barrier.heavy();
+        }
+    }
+
+    #[inline]
+    fn light(&self) {
+        self.barrier.light();
+    }
+
+    #[inline]
+    fn accelerated(&self) -> bool {
+        self.barrier.accelerated()
+    }
+}
+
+impl<B: AsymmetricBarrier> fmt::Debug for TrackedBarrier<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackedBarrier").field("readers", &self.readers()).finish_non_exhaustive()
+    }
+}
+
+/// **Reader Registration**
+///
+/// Returned by [`TrackedBarrier::register`]; unregisters the reader when dropped.
+///
+/// ---
+///
+/// **读者注册**
+///
+/// 由 [`TrackedBarrier::register`] 返回；释放时注销该读者。
+#[must_use = "the reader is unregistered as soon as the guard is dropped"]
+pub struct ReaderRegistration<'a, B: AsymmetricBarrier = SystemBarrier> {
+    tracker: &'a TrackedBarrier<B>,
+}
+
+impl<B: AsymmetricBarrier> Drop for ReaderRegistration<'_, B> {
+    #[inline]
+    fn drop(&mut self) {
+        // Release: the reader's loads happen before stores of a writer that then sees no readers.
+        // Release：读者的加载先于随后看到没有读者的写者的存储。
+        self.tracker.readers.fetch_sub(1, Ordering::Release);
+    }
+}
+
+impl<B: AsymmetricBarrier> fmt::Debug for ReaderRegistration<'_, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReaderRegistration").finish_non_exhaustive()
+    }
+}
+
+/// **Barrier Kind**
+///
+/// Per-structure selector between the crate's asymmetric barriers and plain fences, for use with
+/// the `with_kind` constructors (e.g. [`DoubleBuffer::with_kind`](crate::DoubleBuffer::with_kind)).
+///
+/// ---
+///
+/// **屏障类型**
+///
+/// 按数据结构在 crate 的非对称屏障与普通栅栏之间进行选择，配合 `with_kind` 构造函数使用
+/// （例如 [`DoubleBuffer::with_kind`](crate::DoubleBuffer::with_kind)）。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BarrierKind {
+    /// [`heavy_barrier`](crate::heavy_barrier) / [`light_barrier`](crate::light_barrier), like [`SystemBarrier`].
+    /// 与 [`SystemBarrier`] 相同，使用 crate 的重型/轻型屏障。
+    #[default]
+    Asymmetric,
+    /// `fence(SeqCst)` on both sides: never issues the OS barrier, but readers always pay a hardware fence.
+    /// 读写两端均为 `fence(SeqCst)`：从不调用 OS 屏障，但读者总是承担硬件屏障的开销。
+    SymmetricFence,
+}
+
+// SAFETY: `Asymmetric` delegates to the crate's barriers; two SeqCst fences trivially satisfy the contract.
+// 安全性：`Asymmetric` 委托给 crate 的屏障；两个 SeqCst 栅栏显然满足约定。
+unsafe impl AsymmetricBarrier for BarrierKind {
+    #[inline]
+    fn heavy(&self) {
+        match self {
+            BarrierKind::Asymmetric => crate::heavy_barrier(),
+            BarrierKind::SymmetricFence => fence(Ordering::SeqCst),
+        }
+    }
+
+    #[inline]
+    fn light(&self) {
+        match self {
+            BarrierKind::Asymmetric => crate::light_barrier(),
+            BarrierKind::SymmetricFence => fence(Ordering::SeqCst),
+        }
+    }
+
+    #[inline]
+    fn accelerated(&self) -> bool {
+        match self {
+            BarrierKind::Asymmetric => crate::is_accelerated(),
+            BarrierKind::SymmetricFence => false,
+        }
+    }
+}
+
+/// **Fallback Ordering**
+///
+/// The fence both barriers issue when no OS barrier is active, chosen with
+/// [`set_fallback_ordering`](crate::set_fallback_ordering).
+///
+/// ---
+///
+/// **回退顺序**
+///
+/// 没有 OS 屏障时两种屏障发出的栅栏，通过 [`set_fallback_ordering`](crate::set_fallback_ordering) 选择。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FallbackOrdering {
+    /// `fence(SeqCst)`: orders a store before a later load on the other side (store-buffering).
+    /// `fence(SeqCst)`：保证一侧的存储先于另一侧之后的加载（存储缓冲模式）。
+    #[default]
+    SeqCst,
+    /// `fence(AcqRel)`: only message passing (store data, barrier, store flag / load flag, barrier,
+    /// load data). Free on x86, where `SeqCst` costs an `mfence`.
+    /// `fence(AcqRel)`：仅保证消息传递（存数据、屏障、存标志 / 读标志、屏障、读数据）。在 x86 上无开销，
+    /// 而 `SeqCst` 需要一条 `mfence`。
+    AcqRel,
+}
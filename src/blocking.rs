@@ -0,0 +1,172 @@
+//! **Blocking Subscription** (feature `std`)
+//!
+//! A portable counterpart of the Linux futex helpers for [`SwmrCell`]: the writer bumps a
+//! sequence under a `Mutex` and notifies a `Condvar` after its heavy barrier, so idle readers
+//! sleep instead of polling. The mutex and condvar sit on the publish path only; reads of the
+//! value itself stay on the slot ring.
+//!
+//! ---
+//!
+//! **阻塞订阅**（`std` 特性）
+//!
+//! [`SwmrCell`] 的可移植版 Linux futex 辅助函数：写者在重型屏障之后于 `Mutex` 下递增序号并通知 `Condvar`，
+//! 使空闲读者休眠而非轮询。互斥锁与条件变量只位于发布路径上；对值本身的读取仍通过槽环完成。
+
+use crate::barrier::AsymmetricBarrier;
+use crate::cell::SwmrCell;
+use core::fmt;
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::time::Duration;
+
+/// **Publish Signal**
+///
+/// The wake-up channel shared by a writer's [`SwmrCell::set_and_notify`] and the readers'
+/// [`BlockingSubscriber`]s. One signal may serve several cells; a subscriber then also wakes for
+/// publishes to the other cells and returns the unchanged value.
+///
+/// ---
+///
+/// **发布信号**
+///
+/// 写者的 [`SwmrCell::set_and_notify`] 与读者的 [`BlockingSubscriber`] 共享的唤醒通道。一个信号可服务多个单元；
+/// 此时订阅者也会因其他单元的发布而被唤醒，并返回未变化的值。
+pub struct PublishSignal {
+    // Publishes so far, wrapping; guards against sleeping through a notify sent before the wait.
+    // 迄今的发布次数（回绕）；防止在等待之前发出的通知被错过。
+    sequence: Mutex<u64>,
+    published: Condvar,
+}
+
+impl PublishSignal {
+    /// Creates a signal with no publishes.
+    /// 创建尚无发布的信号。
+    pub const fn new() -> Self {
+        Self {
+            sequence: Mutex::new(0),
+            published: Condvar::new(),
+        }
+    }
+
+    fn sequence(&self) -> u64 {
+        *self.sequence.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn notify(&self) {
+        let mut sequence = self.sequence.lock().unwrap_or_else(PoisonError::into_inner);
+        *sequence = sequence.wrapping_add(1);
+        self.published.notify_all();
+    }
+
+    /// Blocks until the sequence differs from `seen` or `timeout` (if any) elapses, returning the
+    /// sequence then.
+    ///
+    /// 阻塞直到序号不同于 `seen` 或 `timeout`（若有）到期，返回此时的序号。
+    fn wait(&self, seen: u64, timeout: Option<Duration>) -> u64 {
+        let sequence = self.sequence.lock().unwrap_or_else(PoisonError::into_inner);
+        let sequence = match timeout {
+            None => self
+                .published
+                .wait_while(sequence, |sequence| *sequence == seen)
+                .unwrap_or_else(PoisonError::into_inner),
+            Some(timeout) => {
+                self.published
+                    .wait_timeout_while(sequence, timeout, |sequence| *sequence == seen)
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0
+            }
+        };
+        *sequence
+    }
+}
+
+impl Default for PublishSignal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for PublishSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PublishSignal").field("sequence", &self.sequence()).finish()
+    }
+}
+
+impl<T: Copy, const SLOTS: usize, B: AsymmetricBarrier> SwmrCell<T, SLOTS, B> {
+    /// [`set`](Self::set), then wakes every [`BlockingSubscriber`] waiting on `signal`. The notify
+    /// follows the heavy barrier, so a woken reader finds `value` published.
+    ///
+    /// 先 [`set`](Self::set)，再唤醒所有在 `signal` 上等待的 [`BlockingSubscriber`]。通知在重型屏障之后发出，
+    /// 因此被唤醒的读者会看到已发布的 `value`。
+    pub fn set_and_notify(&self, value: T, signal: &PublishSignal) {
+        self.set(value);
+        signal.notify();
+    }
+
+    /// **Blocking Subscribe** (feature `std`)
+    ///
+    /// Returns a handle whose [`wait`](BlockingSubscriber::wait) sleeps on `signal` until the next
+    /// [`set_and_notify`](Self::set_and_notify) after the previous wait (or after this call), then
+    /// reads the value. Idle readers burn no CPU, at the price of a mutex and a condvar notify on
+    /// every publish. Plain [`set`](Self::set) calls do not wake subscribers.
+    ///
+    /// ---
+    ///
+    /// **阻塞订阅**（`std` 特性）
+    ///
+    /// 返回一个句柄，其 [`wait`](BlockingSubscriber::wait) 在 `signal` 上休眠，直到上次等待（或本次调用）之后的下一次
+    /// [`set_and_notify`](Self::set_and_notify)，然后读取值。空闲读者不消耗 CPU，代价是每次发布多一次互斥锁与条件变量通知。
+    /// 普通的 [`set`](Self::set) 调用不会唤醒订阅者。
+    pub fn subscribe_blocking<'a>(&'a self, signal: &'a PublishSignal) -> BlockingSubscriber<'a, T, SLOTS, B> {
+        BlockingSubscriber {
+            cell: self,
+            signal,
+            seen: signal.sequence(),
+        }
+    }
+}
+
+/// **Blocking Subscriber**
+///
+/// A reader handle from [`SwmrCell::subscribe_blocking`] that remembers the last publish it woke
+/// for. Publishes that land while the handle is not waiting are not lost: the next
+/// [`wait`](Self::wait) returns at once with the latest value.
+///
+/// ---
+///
+/// **阻塞订阅者**
+///
+/// 由 [`SwmrCell::subscribe_blocking`] 返回的读者句柄，记住上次唤醒对应的发布。句柄未等待期间发生的发布不会丢失：
+/// 下一次 [`wait`](Self::wait) 会立即返回最新值。
+pub struct BlockingSubscriber<'a, T, const SLOTS: usize, B: AsymmetricBarrier> {
+    cell: &'a SwmrCell<T, SLOTS, B>,
+    signal: &'a PublishSignal,
+    seen: u64,
+}
+
+impl<T: Copy, const SLOTS: usize, B: AsymmetricBarrier> BlockingSubscriber<'_, T, SLOTS, B> {
+    /// Blocks until a publish this handle has not seen, then returns the current value.
+    /// 阻塞直到出现本句柄尚未见过的发布，然后返回当前值。
+    pub fn wait(&mut self) -> T {
+        self.seen = self.signal.wait(self.seen, None);
+        // `get` issues the light barrier that pairs with the writer's heavy barrier.
+        // `get` 会执行与写者重型屏障配对的轻型屏障。
+        self.cell.get()
+    }
+
+    /// Like [`wait`](Self::wait), but gives up after `timeout` and returns `None`.
+    /// 同 [`wait`](Self::wait)，但在 `timeout` 后放弃并返回 `None`。
+    pub fn wait_timeout(&mut self, timeout: Duration) -> Option<T> {
+        let sequence = self.signal.wait(self.seen, Some(timeout));
+        if sequence == self.seen {
+            return None;
+        }
+        self.seen = sequence;
+        Some(self.cell.get())
+    }
+}
+
+impl<T, const SLOTS: usize, B: AsymmetricBarrier> fmt::Debug for BlockingSubscriber<'_, T, SLOTS, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingSubscriber").field("seen", &self.seen).finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(not(feature = "crossbeam"))]
+use core::fmt;
+#[cfg(not(feature = "crossbeam"))]
+use core::ops::{Deref, DerefMut};
+
+// With `crossbeam`, the slot rings and the public type are crossbeam's, whose per-architecture line
+// sizes crossbeam maintains; this crate's own type is the fallback.
+// 启用 `crossbeam` 时，槽环与公开类型均使用 crossbeam 的实现，其各架构的缓存行大小由 crossbeam 维护；本 crate
+// 自己的类型作为回退。
+#[cfg(feature = "crossbeam")]
+pub use crossbeam_utils::CachePadded;
+
+/// **Cache Padded**
+///
+/// Aligns and pads `T` to [`CACHE_LINE`] bytes, so values written by different threads never
+/// share a cache line. The slot rings behind [`DoubleBuffer`](crate::DoubleBuffer) and
+/// [`SwmrCell`](crate::SwmrCell) use it to keep the slot the writer fills apart from the slot
+/// readers copy from.
+///
+/// With the `crossbeam` feature, `crossbeam_utils::CachePadded` is exported under this name instead.
+///
+/// ---
+///
+/// **缓存行填充**
+///
+/// 将 `T` 对齐并填充到 [`CACHE_LINE`] 字节，使不同线程写入的值永远不会共享同一缓存行。
+/// [`DoubleBuffer`](crate::DoubleBuffer) 与 [`SwmrCell`](crate::SwmrCell) 背后的槽环用它把写者正在填充的槽与读者正在拷贝的槽分开。
+///
+/// 启用 `crossbeam` 特性时，以此名称导出的是 `crossbeam_utils::CachePadded`。
+// x86_64 prefetches cache lines in adjacent pairs; Apple M-series and POWER have 128-byte lines.
+// x86_64 会成对预取相邻缓存行；Apple M 系列与 POWER 的缓存行为 128 字节。
+#[cfg(not(feature = "crossbeam"))]
+#[repr(C)]
+#[cfg_attr(any(target_arch = "x86_64", target_arch = "aarch64", target_arch = "powerpc64"), repr(align(128)))]
+#[cfg_attr(not(any(target_arch = "x86_64", target_arch = "aarch64", target_arch = "powerpc64")), repr(align(64)))]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CachePadded<T> {
+    value: T,
+}
+
+/// **Cache Line Size**
+///
+/// The alignment of [`CachePadded`] on this target: 128 bytes on x86_64, aarch64 and powerpc64,
+/// 64 bytes elsewhere. With the `crossbeam` feature, whatever `crossbeam_utils::CachePadded` uses
+/// (e.g. 256 on s390x, 32 on some ARM and MIPS targets).
+///
+/// ---
+///
+/// **缓存行大小**
+///
+/// 本目标上 [`CachePadded`] 的对齐：x86_64、aarch64 与 powerpc64 上为 128 字节，其他目标为 64 字节。启用 `crossbeam`
+/// 特性时为 `crossbeam_utils::CachePadded` 所用的值（例如 s390x 上为 256，部分 ARM 与 MIPS 目标上为 32）。
+pub const CACHE_LINE: usize = align_of::<CachePadded<u8>>();
+
+#[cfg(not(feature = "crossbeam"))]
+impl<T> CachePadded<T> {
+    /// Pads `value`.
+    /// 填充 `value`。
+    pub const fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Returns the inner value.
+    /// 返回内部值。
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+#[cfg(not(feature = "crossbeam"))]
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+#[cfg(not(feature = "crossbeam"))]
+impl<T> DerefMut for CachePadded<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+#[cfg(not(feature = "crossbeam"))]
+impl<T> From<T> for CachePadded<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(not(feature = "crossbeam"))]
+impl<T: fmt::Debug> fmt::Debug for CachePadded<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CachePadded").field(&self.value).finish()
+    }
+}
@@ -0,0 +1,99 @@
+//! **C API** (feature `capi`)
+//!
+//! Unmangled `extern "C"` wrappers so C/C++ code shares the exact barrier implementation the Rust
+//! side uses. The declarations are in `include/swmr_barrier.h`. Build a linkable library with e.g.
+//! `cargo rustc --release --features capi,std --crate-type staticlib`.
+//!
+//! ---
+//!
+//! **C API**（`capi` 特性）
+//!
+//! 未修饰名称的 `extern "C"` 包装，使 C/C++ 代码与 Rust 端共享完全相同的屏障实现。声明位于
+//! `include/swmr_barrier.h`。可通过例如 `cargo rustc --release --features capi,std --crate-type staticlib` 构建可链接的库。
+
+/// C: `void swmr_heavy_barrier(void);` — see [`heavy_barrier()`](crate::heavy_barrier).
+#[unsafe(no_mangle)]
+pub extern "C" fn swmr_heavy_barrier() {
+    crate::heavy_barrier();
+}
+
+/// C: `void swmr_light_barrier(void);` — see [`light_barrier()`](crate::light_barrier).
+#[unsafe(no_mangle)]
+pub extern "C" fn swmr_light_barrier() {
+    crate::light_barrier();
+}
+
+/// C: `bool swmr_is_accelerated(void);` — see [`is_accelerated()`](crate::is_accelerated).
+#[unsafe(no_mangle)]
+pub extern "C" fn swmr_is_accelerated() -> bool {
+    crate::is_accelerated()
+}
+
+/// C: `void swmr_shutdown(void);` — see [`shutdown()`](crate::shutdown). Call it before `dlclose`.
+#[unsafe(no_mangle)]
+pub extern "C" fn swmr_shutdown() {
+    crate::shutdown();
+}
+
+/// **Opaque `SwmrCell<u64>` Handle** (features `capi` + `std`)
+///
+/// C: `typedef struct SwmrCellU64 SwmrCellU64;` — a heap-allocated [`SwmrCell<u64>`](crate::SwmrCell),
+/// only ever used behind a pointer from [`swmr_cell_new`].
+///
+/// ---
+///
+/// **不透明 `SwmrCell<u64>` 句柄**（`capi` + `std` 特性）
+///
+/// 堆上分配的 [`SwmrCell<u64>`](crate::SwmrCell)，仅通过 [`swmr_cell_new`] 返回的指针使用。
+#[cfg(feature = "std")]
+pub struct SwmrCellU64(crate::SwmrCell<u64>);
+
+/// C: `SwmrCellU64 *swmr_cell_new(uint64_t initial);` — never returns `NULL`; release it with
+/// [`swmr_cell_free`].
+#[cfg(feature = "std")]
+#[unsafe(no_mangle)]
+pub extern "C" fn swmr_cell_new(initial: u64) -> *mut SwmrCellU64 {
+    std::boxed::Box::into_raw(std::boxed::Box::new(SwmrCellU64(crate::SwmrCell::new(initial))))
+}
+
+/// C: `void swmr_cell_publish(SwmrCellU64 *cell, uint64_t value);` — see [`SwmrCell::set`](crate::SwmrCell::set).
+///
+/// # Safety
+///
+/// `cell` must come from [`swmr_cell_new`] and not have been freed.
+#[cfg(feature = "std")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn swmr_cell_publish(cell: *mut SwmrCellU64, value: u64) {
+    // SAFETY: The caller guarantees `cell` is live; `SwmrCell` is `Sync`, so a shared reference suffices.
+    // 安全性：调用者保证 `cell` 有效；`SwmrCell` 是 `Sync`，共享引用即可。
+    unsafe { &(*cell).0 }.set(value);
+}
+
+/// C: `uint64_t swmr_cell_load(const SwmrCellU64 *cell);` — see [`SwmrCell::get`](crate::SwmrCell::get).
+///
+/// # Safety
+///
+/// `cell` must come from [`swmr_cell_new`] and not have been freed.
+#[cfg(feature = "std")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn swmr_cell_load(cell: *const SwmrCellU64) -> u64 {
+    // SAFETY: The caller guarantees `cell` is live.
+    // 安全性：调用者保证 `cell` 有效。
+    unsafe { &(*cell).0 }.get()
+}
+
+/// C: `void swmr_cell_free(SwmrCellU64 *cell);` — `NULL` is a no-op, as with `free()`.
+///
+/// # Safety
+///
+/// `cell` must be `NULL` or come from [`swmr_cell_new`], must not be freed twice, and no other
+/// thread may still be using it.
+#[cfg(feature = "std")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn swmr_cell_free(cell: *mut SwmrCellU64) {
+    if !cell.is_null() {
+        // SAFETY: The caller guarantees `cell` came from `Box::into_raw` and is no longer shared.
+        // 安全性：调用者保证 `cell` 来自 `Box::into_raw` 且不再被共享。
+        drop(unsafe { std::boxed::Box::from_raw(cell) });
+    }
+}
@@ -0,0 +1,616 @@
+use crate::barrier::{AsymmetricBarrier, SystemBarrier};
+use crate::slot_ring::SlotRing;
+use crate::sync::atomic::{
+    AtomicBool, AtomicI8, AtomicI16, AtomicI32, AtomicIsize, AtomicPtr, AtomicU8, AtomicU16, AtomicU32,
+    AtomicUsize, Ordering,
+};
+#[cfg(target_has_atomic = "64")]
+use crate::sync::atomic::{AtomicI64, AtomicU64};
+use core::fmt;
+
+/// **SWMR Cell**
+///
+/// A single-writer multi-reader cell for any `Copy` value, backed by the same slot scheme as
+/// [`DoubleBuffer`](crate::DoubleBuffer). [`get`](Self::get) copies the current value out;
+/// [`set`](Self::set) publishes a new one.
+///
+/// `SLOTS` (default 2) is the number of buffers. `set` only waits for readers still copying out of
+/// the slot it is about to fill, which was current `SLOTS - 1` sets ago. Extra slots let a fast
+/// writer run ahead of slow readers without stalling, at `size_of::<T>()` plus one counter each.
+///
+/// For values that fit in one atomic word, [`SwmrWordCell`] avoids the two-slot copy. Values over
+/// [`MAX_CELL_BYTES`](crate::MAX_CELL_BYTES) trip a `debug_assert!` in the constructors.
+///
+/// ---
+///
+/// **SWMR 单元**
+///
+/// 适用于任意 `Copy` 值的单写多读单元，与 [`DoubleBuffer`](crate::DoubleBuffer) 使用相同的槽机制。
+/// [`get`](Self::get) 拷贝出当前值；[`set`](Self::set) 发布新值。
+///
+/// `SLOTS`（默认 2）为缓冲区数量。`set` 只等待仍在从其将要填充的槽中拷贝的读者，该槽是 `SLOTS - 1` 次
+/// `set` 之前的当前槽。更多的槽使快速写者可以领先慢速读者而不阻塞，代价是每槽 `size_of::<T>()` 加一个计数器。
+///
+/// 对于可放入单个原子字的值，[`SwmrWordCell`] 可避免双槽拷贝。超过 [`MAX_CELL_BYTES`](crate::MAX_CELL_BYTES)
+/// 的值会在构造函数中触发 `debug_assert!`。
+#[repr(C)]
+pub struct SwmrCell<T, const SLOTS: usize = 2, B: AsymmetricBarrier = SystemBarrier> {
+    ring: SlotRing<T, SLOTS, B>,
+}
+
+// SAFETY: Readers never see `&T`: `get` returns a bitwise copy, which is the same as sending a
+// `T` to the reader (`T: Send`). `Copy` rules out drop glue and interior mutability, so concurrent
+// copies of one buffer are plain reads. This is why `T: Sync` is not required, unlike `DoubleBuffer`.
+// 安全性：读者永远看不到 `&T`：`get` 返回按位拷贝，等同于将一个 `T` 发送给读者（`T: Send`）。
+// `Copy` 排除了析构与内部可变性，因此对同一缓冲区的并发拷贝只是普通读取。这也是与 `DoubleBuffer` 不同、无需 `T: Sync` 的原因。
+unsafe impl<T: Copy + Send, const SLOTS: usize, B: AsymmetricBarrier + Sync> Sync for SwmrCell<T, SLOTS, B> {}
+
+impl<T: Copy> SwmrCell<T> {
+    /// Creates a cell holding `initial`.
+    /// 创建持有 `initial` 的单元。
+    pub fn new(initial: T) -> Self {
+        Self::with_barrier(initial, SystemBarrier)
+    }
+
+    /// **Const Constructor**
+    ///
+    /// [`new`](Self::new) as a `const fn`, so a cell can be a `static` without allocating:
+    /// `static SHARED: SwmrCell<u64> = SwmrCell::new_const(0);`. Not available under `loom`, whose
+    /// atomics cannot be built in a `const` context.
+    ///
+    /// ---
+    ///
+    /// **常量构造函数**
+    ///
+    /// `const fn` 版本的 [`new`](Self::new)，使单元无需分配即可作为 `static`：
+    /// `static SHARED: SwmrCell<u64> = SwmrCell::new_const(0);`。在 `loom` 下不可用，因为其原子类型无法在 `const` 上下文中构造。
+    #[cfg(not(feature = "loom"))]
+    pub const fn new_const(initial: T) -> Self {
+        // A `const fn` cannot format the sizes into the message.
+        // `const fn` 无法把大小格式化进消息。
+        debug_assert!(
+            size_of::<T>() <= crate::MAX_CELL_BYTES,
+            "SwmrCell payload exceeds MAX_CELL_BYTES; use DoubleBuffer or publish_store_ptr"
+        );
+        Self {
+            ring: SlotRing::new_const(initial, SystemBarrier),
+        }
+    }
+}
+
+impl<T: Copy + Default> Default for SwmrCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Copy, const SLOTS: usize> SwmrCell<T, SLOTS> {
+    /// Creates a cell with `SLOTS` buffers holding `initial`. Panics at compile time if `SLOTS < 2`.
+    /// 创建 `SLOTS` 个缓冲区均持有 `initial` 的单元。`SLOTS < 2` 时在编译期报错。
+    pub fn with_slots(initial: T) -> Self {
+        Self::with_barrier(initial, SystemBarrier)
+    }
+}
+
+impl<T: Copy, const SLOTS: usize, B: AsymmetricBarrier> SwmrCell<T, SLOTS, B> {
+    /// Creates a cell using a custom barrier.
+    /// 使用自定义屏障创建单元。
+    pub fn with_barrier(initial: T, barrier: B) -> Self {
+        debug_assert!(
+            size_of::<T>() <= crate::MAX_CELL_BYTES,
+            "SwmrCell payload of {} bytes exceeds MAX_CELL_BYTES ({}); use DoubleBuffer or publish_store_ptr",
+            size_of::<T>(),
+            crate::MAX_CELL_BYTES
+        );
+        Self {
+            ring: SlotRing::new(initial, barrier),
+        }
+    }
+
+    /// Returns a copy of the current value.
+    /// 返回当前值的拷贝。
+    #[inline]
+    pub fn get(&self) -> T {
+        // `ptr` must end its borrow before the slot is unpinned.
+        // `ptr` 必须在取消固定之前结束借用。
+        let (index, value) = {
+            let (index, ptr) = self.ring.pin();
+            // SAFETY: The slot stays pinned until `unpin`, so the writer cannot write it meanwhile.
+            // 安全性：该槽在 `unpin` 之前保持固定，写者在此期间无法写入。
+            (index, unsafe { *ptr.deref() })
+        };
+        self.ring.unpin(index);
+        value
+    }
+
+    /// Publishes `value`. Concurrent writers are serialized.
+    /// 发布 `value`。并发写者会被串行化。
+    #[inline]
+    pub fn set(&self, value: T) {
+        self.ring.commit(value);
+    }
+
+    /// Publishes `f(current)`. Other writers are held off from reading the current value until the
+    /// result is published, so concurrent updates are never lost, without a CAS retry loop.
+    ///
+    /// 发布 `f(current)`。从读取当前值到结果发布期间其他写者被阻挡，因此并发更新不会丢失，也无需 CAS 重试循环。
+    #[inline]
+    pub fn update(&self, f: impl FnOnce(T) -> T) {
+        self.ring.commit_with(|current| f(*current));
+    }
+
+    /// Publishes `new` only if the current value equals `expected`, returning whether it did.
+    ///
+    /// A writer that skips no-op updates this way issues no heavy barrier for them, so readers are
+    /// not interrupted and [`reader`](Self::reader) handles see no new version. The comparison and
+    /// the publish happen with other writers held off, as in [`update`](Self::update).
+    ///
+    /// 仅当当前值等于 `expected` 时发布 `new`，返回是否已发布。
+    ///
+    /// 写者以此跳过无变化的更新时不会为其执行重型屏障，因此读者不会被打断，[`reader`](Self::reader) 句柄也看不到新版本。
+    /// 与 [`update`](Self::update) 相同，比较与发布期间其他写者被阻挡。
+    #[inline]
+    pub fn compare_and_publish(&self, expected: T, new: T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.ring.try_commit_with(|current| (*current == expected).then_some(new))
+    }
+
+    /// Returns a reader handle that falls back to full fences after more than `threshold`
+    /// consecutive pin retries. See [`ResilientReader`].
+    ///
+    /// 返回一个读者句柄：连续固定重试超过 `threshold` 次后改用完整栅栏。见 [`ResilientReader`]。
+    #[inline]
+    pub fn resilient_reader(&self, threshold: u32) -> ResilientReader<'_, T, SLOTS, B> {
+        ResilientReader {
+            cell: self,
+            threshold,
+            streak: 0,
+            upgraded_reads: 0,
+            upgrades: 0,
+        }
+    }
+
+    /// Returns a polling handle that skips the copy while the value is unchanged.
+    /// 返回在值未变化时跳过拷贝的轮询句柄。
+    #[inline]
+    pub fn reader(&self) -> SwmrCellReader<'_, T, SLOTS, B> {
+        SwmrCellReader { cell: self, last: None }
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+impl<T: Copy, const SLOTS: usize, B: AsymmetricBarrier> SwmrCell<T, SLOTS, B> {
+    /// **In-Place Initialization**
+    ///
+    /// Writes a cell holding `initial` to `ptr` and borrows it, e.g. at an offset into a
+    /// `MAP_SHARED` mapping. Other processes (or other mappings of the same memory) attach with
+    /// [`from_ptr`](Self::from_ptr). The cell is `repr(C)` and, with a plain-data `B` such as
+    /// [`BarrierKind`](crate::BarrierKind), holds no pointers, so each side may map it at a different address, as long as both are built from the same crate version for the
+    /// same target with the same `T`, `SLOTS` and `B`.
+    ///
+    /// The accelerated barriers only order threads of the process that issues them (Linux
+    /// `PRIVATE_EXPEDITED`), so a reader in another process may miss a publish. For cross-process
+    /// use, pick a barrier that orders every process, such as
+    /// [`BarrierKind::SymmetricFence`](crate::BarrierKind::SymmetricFence) (the system-wide
+    /// `MEMBARRIER_CMD_GLOBAL_EXPEDITED` is not wrapped by this crate). `T` should be plain data:
+    /// pointers inside it are meaningless in the other address space.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for writes of `size_of::<Self>()` bytes, aligned to `align_of::<Self>()`,
+    /// and stay mapped for `'a`. Nothing else may access the memory until this returns. The cell is
+    /// never dropped, which is fine because `T: Copy` has no drop glue.
+    ///
+    /// ---
+    ///
+    /// **原地初始化**
+    ///
+    /// 将持有 `initial` 的单元写入 `ptr` 并借用它，例如写入 `MAP_SHARED` 映射中的某个偏移处。其他进程（或同一内存的
+    /// 其他映射）通过 [`from_ptr`](Self::from_ptr) 接入。单元为 `repr(C)`，且在 `B` 为纯数据（例如 [`BarrierKind`](crate::BarrierKind)）时不含指针，因此各方可将其映射到不同地址，
+    /// 只要双方以同一 crate 版本、为同一目标构建，且 `T`、`SLOTS` 与 `B` 相同。
+    ///
+    /// 加速屏障只对发出它的进程中的线程排序（Linux `PRIVATE_EXPEDITED`），因此另一进程中的读者可能错过发布。
+    /// 跨进程使用时应选择对所有进程排序的屏障，例如 [`BarrierKind::SymmetricFence`](crate::BarrierKind::SymmetricFence)
+    /// （本 crate 未封装系统级的 `MEMBARRIER_CMD_GLOBAL_EXPEDITED`）。`T` 应为纯数据：其中的指针在另一地址空间中毫无意义。
+    ///
+    /// # Safety
+    ///
+    /// `ptr` 必须可写入 `size_of::<Self>()` 字节、按 `align_of::<Self>()` 对齐，且在 `'a` 期间保持映射。
+    /// 返回之前不得有其他访问。单元永远不会被析构；由于 `T: Copy` 没有析构逻辑，这没有问题。
+    pub unsafe fn init_in_place<'a>(ptr: *mut Self, initial: T, barrier: B) -> &'a Self {
+        // SAFETY: The caller guarantees `ptr` is valid, aligned and unshared.
+        // 安全性：调用方保证 `ptr` 有效、对齐且未被共享。
+        unsafe {
+            ptr.write(Self::with_barrier(initial, barrier));
+            &*ptr
+        }
+    }
+
+    /// **Attach**
+    ///
+    /// Borrows a cell that [`init_in_place`](Self::init_in_place) set up, possibly through another
+    /// mapping of the same memory.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a cell initialized by `init_in_place` with the same `T`, `SLOTS` and `B`,
+    /// aligned to `align_of::<Self>()`, and stay mapped for `'a`.
+    ///
+    /// ---
+    ///
+    /// **接入**
+    ///
+    /// 借用由 [`init_in_place`](Self::init_in_place) 初始化的单元，可以通过同一内存的另一个映射。
+    ///
+    /// # Safety
+    ///
+    /// `ptr` 必须指向以相同 `T`、`SLOTS` 与 `B` 经 `init_in_place` 初始化的单元，按 `align_of::<Self>()` 对齐，
+    /// 且在 `'a` 期间保持映射。
+    pub unsafe fn from_ptr<'a>(ptr: *const Self) -> &'a Self {
+        // SAFETY: The caller guarantees `ptr` points to an initialized cell that outlives `'a`.
+        // 安全性：调用方保证 `ptr` 指向已初始化且存活至 `'a` 的单元。
+        unsafe { &*ptr }
+    }
+}
+
+/// **SWMR Cell Reader**
+///
+/// A polling handle from [`SwmrCell::reader`] that remembers the version it last returned.
+/// [`read_if_newer`](Self::read_if_newer) then costs two loads and a compare while nothing was
+/// published, instead of pinning a slot and copying an unchanged payload.
+///
+/// Versions count commits in a `usize`, so a reader that sleeps through exactly `2^32` commits on
+/// a 32-bit target misses that change until the next one.
+///
+/// ---
+///
+/// **SWMR 单元读者**
+///
+/// 由 [`SwmrCell::reader`] 返回的轮询句柄，记住上次返回的版本。此后在没有新发布时，
+/// [`read_if_newer`](Self::read_if_newer) 只需两次加载和一次比较，而无需固定槽并拷贝未变化的负载。
+///
+/// 版本以 `usize` 计数提交，因此在 32 位目标上恰好错过 `2^32` 次提交的读者会漏掉该次变化，直到下一次提交。
+pub struct SwmrCellReader<'a, T, const SLOTS: usize = 2, B: AsymmetricBarrier = SystemBarrier> {
+    cell: &'a SwmrCell<T, SLOTS, B>,
+    last: Option<usize>,
+}
+
+impl<T: Copy, const SLOTS: usize, B: AsymmetricBarrier> SwmrCellReader<'_, T, SLOTS, B> {
+    /// Returns the current value if it was published since the last value this handle returned
+    /// (always on the first call), otherwise `None` without copying.
+    ///
+    /// 若当前值是在本句柄上次返回的值之后发布的（首次调用总是如此），返回该值；否则返回 `None` 且不拷贝。
+    #[inline]
+    pub fn read_if_newer(&mut self) -> Option<T> {
+        if self.last == Some(self.cell.ring.version()) {
+            return None;
+        }
+        let (index, value, version) = {
+            let (index, ptr) = self.cell.ring.pin();
+            // SAFETY: The slot stays pinned until `unpin`, so the writer cannot write it meanwhile.
+            // 安全性：该槽在 `unpin` 之前保持固定，写者在此期间无法写入。
+            (index, unsafe { *ptr.deref() }, self.cell.ring.pinned_version(index))
+        };
+        self.cell.ring.unpin(index);
+        // The unpinned check may have run ahead of `active`; the pinned version is authoritative.
+        // 未固定时的检查可能领先于 `active`；以固定后的版本为准。
+        if self.last == Some(version) {
+            return None;
+        }
+        self.last = Some(version);
+        Some(value)
+    }
+}
+
+/// Number of reads a [`ResilientReader`] keeps using full fences after an upgrade.
+/// [`ResilientReader`] 升级后继续使用完整栅栏的读取次数。
+const UPGRADE_READS: u32 = 1024;
+
+/// **Resilient Reader**
+///
+/// A reader handle from [`SwmrCell::resilient_reader`] that watches how often its reads have to
+/// retry because the slot moved on between pinning and checking it. After more than `threshold`
+/// retries in a row, spanning reads if need be, it replaces `light()` with `fence(SeqCst)` for the
+/// rest of that read and the next 1024 reads, then returns to the light barrier.
+///
+/// Belt and braces: under the barrier contract a retry only ever means a concurrent commit, so a
+/// correct platform never needs the upgrade, and heavy write contention alone can trigger it at
+/// the price of some fences. It exists to rule out the asymmetric optimization as the culprit on
+/// suspect hardware, and [`upgrades`](Self::upgrades) counts how often it happened.
+///
+/// ---
+///
+/// **弹性读者**
+///
+/// 由 [`SwmrCell::resilient_reader`] 返回的读者句柄，统计其读取因槽在固定与检查之间被移走而需要重试的次数。
+/// 连续重试（必要时跨越多次读取）超过 `threshold` 次后，在本次读取剩余部分及随后 1024 次读取中以 `fence(SeqCst)`
+/// 代替 `light()`，之后恢复轻量屏障。
+///
+/// 这是双重保险：在屏障约定下，重试只意味着存在并发提交，因此正确的平台从不需要升级；仅是激烈的写竞争也可能触发它，
+/// 代价是一些栅栏。它用于在可疑硬件上排除非对称优化导致问题的可能，[`upgrades`](Self::upgrades) 统计其发生次数。
+pub struct ResilientReader<'a, T, const SLOTS: usize = 2, B: AsymmetricBarrier = SystemBarrier> {
+    cell: &'a SwmrCell<T, SLOTS, B>,
+    threshold: u32,
+    streak: u32,
+    upgraded_reads: u32,
+    upgrades: usize,
+}
+
+impl<T: Copy, const SLOTS: usize, B: AsymmetricBarrier> ResilientReader<'_, T, SLOTS, B> {
+    /// Returns a copy of the current value, like [`SwmrCell::get`].
+    /// 返回当前值的拷贝，同 [`SwmrCell::get`]。
+    #[inline]
+    pub fn get(&mut self) -> T {
+        let (streak, threshold) = (self.streak, self.threshold);
+        let already_upgraded = self.upgraded_reads > 0;
+        let mut upgraded = already_upgraded;
+        let (index, value, retries) = {
+            let (index, ptr, retries) = self.cell.ring.pin_with(|retries| {
+                upgraded |= streak.saturating_add(retries) > threshold;
+                upgraded
+            });
+            // SAFETY: The slot stays pinned until `unpin`, so the writer cannot write it meanwhile.
+            // 安全性：该槽在 `unpin` 之前保持固定，写者在此期间无法写入。
+            (index, unsafe { *ptr.deref() }, retries)
+        };
+        self.cell.ring.unpin(index);
+
+        if already_upgraded {
+            self.upgraded_reads -= 1;
+        } else if upgraded {
+            self.upgraded_reads = UPGRADE_READS;
+            self.upgrades += 1;
+        }
+        self.streak = if retries == 0 || upgraded { 0 } else { streak.saturating_add(retries) };
+        value
+    }
+
+    /// Whether the next read will use a full fence instead of `light()`.
+    /// 下一次读取是否会使用完整栅栏代替 `light()`。
+    #[inline]
+    pub fn is_upgraded(&self) -> bool {
+        self.upgraded_reads > 0
+    }
+
+    /// How many times this handle has upgraded to full fences.
+    /// 本句柄升级为完整栅栏的次数。
+    #[inline]
+    pub fn upgrades(&self) -> usize {
+        self.upgrades
+    }
+}
+
+impl<T, const SLOTS: usize, B: AsymmetricBarrier> fmt::Debug for ResilientReader<'_, T, SLOTS, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResilientReader")
+            .field("threshold", &self.threshold)
+            .field("streak", &self.streak)
+            .field("upgraded_reads", &self.upgraded_reads)
+            .field("upgrades", &self.upgrades)
+            .finish()
+    }
+}
+
+impl<T, const SLOTS: usize, B: AsymmetricBarrier> fmt::Debug for SwmrCellReader<'_, T, SLOTS, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SwmrCellReader").field("last_version", &self.last).finish()
+    }
+}
+
+impl<T: Copy + fmt::Debug, const SLOTS: usize, B: AsymmetricBarrier> fmt::Debug for SwmrCell<T, SLOTS, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SwmrCell").field("value", &self.get()).finish()
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// **Word-Sized Value**
+///
+/// A `Copy` type that maps onto a single native atomic: the integers, `bool` and raw pointers.
+/// Sealed; `u64`/`i64` are available where the target has 64-bit atomics, and the 16-byte
+/// `u128`, `i128` and `[u64; 2]` with the `atomic128` feature.
+///
+/// ---
+///
+/// **字大小的值**
+///
+/// 可映射到单个原生原子类型的 `Copy` 类型：整数、`bool` 与裸指针。该 trait 是密封的；
+/// `u64`/`i64` 仅在目标支持 64 位原子操作时可用，16 字节的 `u128`、`i128` 与 `[u64; 2]` 需启用 `atomic128` 特性。
+pub trait WordSized: Copy + sealed::Sealed {
+    #[doc(hidden)]
+    type Atomic;
+    #[doc(hidden)]
+    fn new_atomic(value: Self) -> Self::Atomic;
+    #[doc(hidden)]
+    fn load(atomic: &Self::Atomic) -> Self;
+    #[doc(hidden)]
+    fn store(atomic: &Self::Atomic, value: Self);
+}
+
+macro_rules! impl_word_sized {
+    ($($(#[$attr:meta])* $ty:ty => $atomic:ty),* $(,)?) => {$(
+        $(#[$attr])*
+        impl sealed::Sealed for $ty {}
+
+        $(#[$attr])*
+        impl WordSized for $ty {
+            type Atomic = $atomic;
+
+            #[inline]
+            fn new_atomic(value: Self) -> Self::Atomic {
+                <$atomic>::new(value)
+            }
+
+            #[inline]
+            fn load(atomic: &Self::Atomic) -> Self {
+                atomic.load(Ordering::Relaxed)
+            }
+
+            #[inline]
+            fn store(atomic: &Self::Atomic, value: Self) {
+                atomic.store(value, Ordering::Relaxed)
+            }
+        }
+    )*};
+}
+
+impl_word_sized! {
+    bool => AtomicBool,
+    u8 => AtomicU8,
+    u16 => AtomicU16,
+    u32 => AtomicU32,
+    #[cfg(target_has_atomic = "64")]
+    u64 => AtomicU64,
+    usize => AtomicUsize,
+    i8 => AtomicI8,
+    i16 => AtomicI16,
+    i32 => AtomicI32,
+    #[cfg(target_has_atomic = "64")]
+    i64 => AtomicI64,
+    isize => AtomicIsize,
+}
+
+impl<T> sealed::Sealed for *mut T {}
+
+impl<T> WordSized for *mut T {
+    type Atomic = AtomicPtr<T>;
+
+    #[inline]
+    fn new_atomic(value: Self) -> Self::Atomic {
+        AtomicPtr::new(value)
+    }
+
+    #[inline]
+    fn load(atomic: &Self::Atomic) -> Self {
+        atomic.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    fn store(atomic: &Self::Atomic, value: Self) {
+        atomic.store(value, Ordering::Relaxed)
+    }
+}
+
+// 16-byte values through one 128-bit atomic. `portable-atomic` picks `cmpxchg16b` (x86_64) or
+// `casp`/LSE2 (aarch64) when the CPU has it, checked at run time if the target does not guarantee it,
+// and a lock-based fallback otherwise; every path loads and stores the 16 bytes as one unit.
+// 通过单个 128 位原子变量处理 16 字节的值。`portable-atomic` 在 CPU 支持时选用 `cmpxchg16b`（x86_64）或
+// `casp`/LSE2（aarch64）；若目标不保证支持则在运行时检测，否则使用基于锁的回退。所有路径都将这 16 字节作为整体加载与存储。
+#[cfg(feature = "atomic128")]
+impl_word_sized! {
+    u128 => portable_atomic::AtomicU128,
+    i128 => portable_atomic::AtomicI128,
+}
+
+#[cfg(feature = "atomic128")]
+impl sealed::Sealed for [u64; 2] {}
+
+/// Two words, e.g. a pointer and a tag, packed into one `u128` in native word order.
+/// 两个字（例如指针与标记）按原生字序打包进一个 `u128`。
+#[cfg(feature = "atomic128")]
+impl WordSized for [u64; 2] {
+    type Atomic = portable_atomic::AtomicU128;
+
+    #[inline]
+    fn new_atomic(value: Self) -> Self::Atomic {
+        portable_atomic::AtomicU128::new(pack(value))
+    }
+
+    #[inline]
+    fn load(atomic: &Self::Atomic) -> Self {
+        unpack(atomic.load(Ordering::Relaxed))
+    }
+
+    #[inline]
+    fn store(atomic: &Self::Atomic, value: Self) {
+        atomic.store(pack(value), Ordering::Relaxed)
+    }
+}
+
+#[cfg(feature = "atomic128")]
+#[inline]
+fn pack(words: [u64; 2]) -> u128 {
+    u128::from(words[0]) | (u128::from(words[1]) << 64)
+}
+
+#[cfg(feature = "atomic128")]
+#[inline]
+fn unpack(packed: u128) -> [u64; 2] {
+    [packed as u64, (packed >> 64) as u64]
+}
+
+/// **SWMR Word Cell**
+///
+/// The [`SwmrCell`] fast path for [`WordSized`] values: a single atomic that cannot tear, so
+/// [`get`](Self::get) is a `Relaxed` load plus `light()` and [`set`](Self::set) is `heavy()` plus a
+/// `Relaxed` store, with no slots to copy between.
+///
+/// ---
+///
+/// **SWMR 字单元**
+///
+/// 面向 [`WordSized`] 值的 [`SwmrCell`] 快速路径：单个原子变量不会撕裂，因此 [`get`](Self::get)
+/// 只是一次 `Relaxed` 加载加 `light()`，[`set`](Self::set) 只是 `heavy()` 加一次 `Relaxed` 存储，无需在槽间拷贝。
+pub struct SwmrWordCell<T: WordSized, B: AsymmetricBarrier = SystemBarrier> {
+    value: T::Atomic,
+    barrier: B,
+}
+
+// `Send`/`Sync` are derived from `T::Atomic` and `B`. Every atomic is `Sync`, including
+// `AtomicPtr<U>` for any `U`, matching `AtomicPtr`: a `*mut U` is only dereferenced in `unsafe`
+// code that must uphold `U`'s own bounds.
+// `Send`/`Sync` 由 `T::Atomic` 与 `B` 推导。所有原子类型都是 `Sync`，包括任意 `U` 的 `AtomicPtr<U>`，
+// 与 `AtomicPtr` 一致：`*mut U` 只能在 `unsafe` 代码中解引用，由其负责满足 `U` 自身的约束。
+
+impl<T: WordSized> SwmrWordCell<T> {
+    /// Creates a cell holding `initial`.
+    /// 创建持有 `initial` 的单元。
+    pub fn new(initial: T) -> Self {
+        Self::with_barrier(initial, SystemBarrier)
+    }
+}
+
+impl<T: WordSized + Default> Default for SwmrWordCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: WordSized, B: AsymmetricBarrier> SwmrWordCell<T, B> {
+    /// Creates a cell using a custom barrier.
+    /// 使用自定义屏障创建单元。
+    pub fn with_barrier(initial: T, barrier: B) -> Self {
+        Self {
+            value: T::new_atomic(initial),
+            barrier,
+        }
+    }
+
+    /// Returns the current value. Writes made before the matching [`set`](Self::set) are visible afterwards.
+    /// 返回当前值。之后可见对应 [`set`](Self::set) 之前的写入。
+    #[inline]
+    pub fn get(&self) -> T {
+        let value = T::load(&self.value);
+        self.barrier.light();
+        value
+    }
+
+    /// Publishes `value` after every earlier write of the writer.
+    /// 在写者之前的所有写入之后发布 `value`。
+    #[inline]
+    pub fn set(&self, value: T) {
+        self.barrier.heavy();
+        T::store(&self.value, value);
+    }
+}
+
+impl<T: WordSized + fmt::Debug, B: AsymmetricBarrier> fmt::Debug for SwmrWordCell<T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SwmrWordCell").field("value", &self.get()).finish()
+    }
+}
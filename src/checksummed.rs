@@ -0,0 +1,129 @@
+use crate::barrier::{AsymmetricBarrier, SystemBarrier};
+use crate::cell::SwmrCell;
+use crate::sync::spin_loop;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+/// A value and the checksum the writer computed for it, published together.
+/// 值与写者为其计算的校验和，二者一同发布。
+#[derive(Clone, Copy)]
+struct Checked<T> {
+    value: T,
+    sum: u64,
+}
+
+/// FNV-1a over the value's `Hash` input: no padding bytes are read, and no `std` is needed.
+/// 基于值的 `Hash` 输入计算 FNV-1a：不读取填充字节，也无需 `std`。
+struct Fnv1a(u64);
+
+impl Hasher for Fnv1a {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[inline]
+fn checksum<T: Hash>(value: &T) -> u64 {
+    let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// **Checksummed Cell**
+///
+/// A [`SwmrCell`] that stores a checksum next to the value, for users who do not fully trust the
+/// platform's barriers. [`set`](Self::set) computes the checksum and writes it into the slot with
+/// the value, so one `heavy()` orders both before the publish. [`get`](Self::get) recomputes it on
+/// the copy and retries on a mismatch, which a correct platform never produces: it catches torn
+/// reads that slipped past a broken barrier.
+///
+/// Costs over [`SwmrCell`]: 8 bytes per slot, and FNV-1a over the value's [`Hash`] input on every
+/// `get` and `set`, roughly one multiply per hashed byte. Detection is probabilistic: a torn value
+/// matches the checksum with a chance of about 2⁻⁶⁴.
+///
+/// ---
+///
+/// **校验和单元**
+///
+/// 在值旁存储校验和的 [`SwmrCell`]，供不完全信任平台屏障的用户使用。[`set`](Self::set) 计算校验和并与值一同写入槽，
+/// 因此一次 `heavy()` 即可使二者先于发布。[`get`](Self::get) 对拷贝重新计算校验和并在不匹配时重试；正确的平台不会出现
+/// 不匹配：它用于捕获越过失效屏障的撕裂读取。
+///
+/// 相对 [`SwmrCell`] 的开销：每槽 8 字节，且每次 `get` 与 `set` 都对值的 [`Hash`] 输入计算 FNV-1a，大约每个参与哈希的
+/// 字节一次乘法。检测是概率性的：撕裂的值与校验和匹配的概率约为 2⁻⁶⁴。
+pub struct ChecksummedCell<T, const SLOTS: usize = 2, B: AsymmetricBarrier = SystemBarrier> {
+    cell: SwmrCell<Checked<T>, SLOTS, B>,
+}
+
+impl<T: Copy + Hash> ChecksummedCell<T> {
+    /// Creates a cell holding `initial`.
+    /// 创建持有 `initial` 的单元。
+    pub fn new(initial: T) -> Self {
+        Self::with_barrier(initial, SystemBarrier)
+    }
+}
+
+impl<T: Copy + Hash + Default> Default for ChecksummedCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Copy + Hash, const SLOTS: usize, B: AsymmetricBarrier> ChecksummedCell<T, SLOTS, B> {
+    /// Creates a cell using a custom barrier.
+    /// 使用自定义屏障创建单元。
+    pub fn with_barrier(initial: T, barrier: B) -> Self {
+        Self {
+            cell: SwmrCell::with_barrier(Checked { value: initial, sum: checksum(&initial) }, barrier),
+        }
+    }
+
+    /// Returns a copy of the current value, retrying until the copy matches its checksum.
+    /// 返回当前值的拷贝；重试直到拷贝与其校验和匹配。
+    #[inline]
+    pub fn get(&self) -> T {
+        loop {
+            if let Some(value) = self.try_get() {
+                return value;
+            }
+            spin_loop();
+        }
+    }
+
+    /// Copies the current value once, returning `None` if it does not match its checksum.
+    /// 拷贝一次当前值；若与其校验和不匹配则返回 `None`。
+    #[inline]
+    pub fn try_get(&self) -> Option<T> {
+        let Checked { value, sum } = self.cell.get();
+        (checksum(&value) == sum).then_some(value)
+    }
+
+    /// Publishes `value` with its checksum. Concurrent writers are serialized.
+    /// 连同校验和发布 `value`。并发写者会被串行化。
+    #[inline]
+    pub fn set(&self, value: T) {
+        let sum = checksum(&value);
+        #[cfg(all(feature = "testing", feature = "std"))]
+        if let Some(delay) = crate::testing::take_torn_write() {
+            // The new checksum over the old value, as if the payload stores had not landed yet.
+            // 旧值配新校验和，如同负载的存储尚未生效。
+            self.cell.update(|torn| Checked { value: torn.value, sum });
+            std::thread::sleep(delay);
+        }
+        self.cell.set(Checked { value, sum });
+    }
+}
+
+impl<T: Copy + Hash + fmt::Debug, const SLOTS: usize, B: AsymmetricBarrier> fmt::Debug for ChecksummedCell<T, SLOTS, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChecksummedCell").field("value", &self.get()).finish()
+    }
+}
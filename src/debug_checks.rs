@@ -0,0 +1,108 @@
+//! Multi-writer detection for the heavy barrier (feature `debug-checks`).
+//!
+//! Every [`heavy_barrier()`](crate::heavy_barrier) records which thread issued it and when. If a
+//! different thread issued the previous one less than [`WINDOW_NS`] ago, two writers are likely
+//! publishing concurrently. That is reported, never asserted: the crate's structures serialize
+//! concurrent writers and document it as supported, so the barrier must not panic on it. Advisory
+//! only: a hand-off between writers is reported too, and the whole check compiles out without
+//! `debug_assertions`.
+//!
+//! 重型屏障的多写者检测（`debug-checks` 特性）。
+//!
+//! 每次 [`heavy_barrier()`](crate::heavy_barrier) 都会记录发出它的线程与时间。若上一次由另一线程在 [`WINDOW_NS`]
+//! 之内发出，则很可能有两个写者在并发发布。这只会被报告，绝不断言：本 crate 的结构会串行化并发写者并将其记为受支持的用法，
+//! 因此屏障不得因此 panic。仅供参考：写者之间的交接同样会被报告，且未启用 `debug_assertions` 时整个检查会被编译移除。
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::time::Duration;
+use std::sync::{OnceLock, PoisonError, RwLock};
+use std::time::Instant;
+
+/// Two heavy barriers from different threads closer than this are reported.
+/// 来自不同线程、间隔小于此值的两次重型屏障会被报告。
+pub(crate) const WINDOW_NS: u128 = 100_000_000;
+
+/// **Multi-Writer Report**
+///
+/// Two threads issued heavy barriers less than 100 ms apart. Threads are identified by small
+/// per-process numbers assigned on their first heavy barrier, not by OS thread ids.
+///
+/// ---
+///
+/// **多写者报告**
+///
+/// 两个线程在 100 ms 之内先后发出重型屏障。线程以首次发出重型屏障时分配的进程内小编号标识，而非操作系统线程 ID。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MultiWriterReport {
+    /// The thread that issued the previous heavy barrier.
+    /// 发出上一次重型屏障的线程。
+    pub previous_thread: usize,
+    /// The thread issuing this one.
+    /// 发出本次重型屏障的线程。
+    pub thread: usize,
+    /// Time since the previous heavy barrier.
+    /// 距上一次重型屏障的时间。
+    pub gap: Duration,
+}
+
+// Source of per-thread ids; 0 means no caller yet.
+// 每线程 ID 的来源；0 表示尚无调用者。
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+static LAST_CALLER: AtomicUsize = AtomicUsize::new(0);
+// Nanoseconds since `START` of the last call, truncated to `usize`; compared with wrapping arithmetic.
+// 最近一次调用距 `START` 的纳秒数，截断为 `usize`；以回绕算术比较。
+static LAST_CALL_NS: AtomicUsize = AtomicUsize::new(0);
+static START: OnceLock<Instant> = OnceLock::new();
+static REPORTS: AtomicUsize = AtomicUsize::new(0);
+static HOOK: RwLock<Option<fn(&MultiWriterReport)>> = RwLock::new(None);
+
+std::thread_local! {
+    static THREAD_ID: usize = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+}
+
+pub(crate) fn set_hook(hook: Option<fn(&MultiWriterReport)>) {
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = hook;
+}
+
+pub(crate) fn reports() -> usize {
+    REPORTS.load(Ordering::Relaxed)
+}
+
+/// Records this thread as the heavy-barrier caller, reporting if the previous one was another
+/// thread within the window.
+/// 将本线程记录为重型屏障的调用者；若上一次调用来自窗口内的另一线程则报告。
+#[inline]
+pub(crate) fn record_writer() {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    let id = THREAD_ID.with(|id| *id);
+    let now = START.get_or_init(Instant::now).elapsed().as_nanos() as usize;
+    let previous = LAST_CALLER.swap(id, Ordering::Relaxed);
+    let then = LAST_CALL_NS.swap(now, Ordering::Relaxed);
+    let gap = now.wrapping_sub(then);
+    if previous != 0 && previous != id && (gap as u128) < WINDOW_NS {
+        report(MultiWriterReport {
+            previous_thread: previous,
+            thread: id,
+            gap: Duration::from_nanos(gap as u64),
+        });
+    }
+}
+
+#[cold]
+fn report(report: MultiWriterReport) {
+    REPORTS.fetch_add(1, Ordering::Relaxed);
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        target: "swmr_barrier",
+        previous_thread = report.previous_thread,
+        thread = report.thread,
+        gap_ns = report.gap.as_nanos() as u64,
+        "heavy_barrier() from two threads within 100 ms; the SWMR structures expect a single writer"
+    );
+    if let Some(hook) = *HOOK.read().unwrap_or_else(PoisonError::into_inner) {
+        hook(&report);
+    }
+}
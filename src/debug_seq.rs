@@ -0,0 +1,39 @@
+//! Per-thread sequence checking for the light barrier (feature `debug-seq`).
+//!
+//! Every heavy barrier already bumps the process-wide [`barrier_generation()`](crate::barrier_generation).
+//! With this feature each light barrier also loads it into a thread-local and panics if it is lower
+//! than the value the same thread recorded before, which would mean the reader's view went back
+//! in time. Costs a load and a thread-local access on every light barrier: for debugging only.
+//!
+//! 轻型屏障的每线程序号检查（`debug-seq` 特性）。
+//!
+//! 每次重型屏障都会递增进程级的 [`barrier_generation()`](crate::barrier_generation)。启用此特性后，每次轻型屏障还会将其
+//! 加载到线程本地变量中；若低于同一线程之前记录的值则 panic，因为这意味着读者的视图发生了回退。
+//! 每次轻型屏障多一次加载与一次线程本地访问：仅用于调试。
+
+use core::cell::Cell;
+
+std::thread_local! {
+    // Sequence seen by this thread's last light barrier (0 = none yet).
+    // 本线程最近一次轻型屏障看到的序号 (0 = 尚无)。
+    static LAST_SEEN: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Records the current sequence for this thread, panicking if it went backwards.
+/// 为本线程记录当前序号，若序号回退则 panic。
+#[inline]
+pub(crate) fn record() {
+    let seen = crate::barrier_generation();
+    LAST_SEEN.with(|last| {
+        assert!(
+            seen >= last.get(),
+            "swmr-barrier: light barrier saw sequence {seen} after {}; the reader's view regressed",
+            last.get()
+        );
+        last.set(seen);
+    });
+}
+
+pub(crate) fn last_seen() -> u64 {
+    LAST_SEEN.with(Cell::get)
+}
@@ -0,0 +1,176 @@
+use crate::barrier::{AsymmetricBarrier, SystemBarrier};
+use crate::sync::atomic::{AtomicUsize, Ordering, fence};
+use core::fmt;
+
+/// **Deferred Barrier**
+///
+/// Moves the heavy barrier off the writer's thread. The writer [`request`](Self::request)s a
+/// barrier and gets a ticket back immediately; another thread calls [`service`](Self::service),
+/// which issues one `heavy()` covering every request made so far. Once
+/// [`is_complete`](Self::is_complete) returns `true` for its ticket, the writer publishes as if it
+/// had called `heavy()` itself. Soft-real-time writers thus never wait on IPI delivery.
+///
+/// Tickets wrap; comparisons stay correct while fewer than `usize::MAX / 2` requests are outstanding.
+///
+/// ---
+///
+/// **延迟屏障**
+///
+/// 将重型屏障移出写者线程。写者调用 [`request`](Self::request) 请求屏障并立即获得票据；另一个线程调用
+/// [`service`](Self::service)，执行一次覆盖此前所有请求的 `heavy()`。当 [`is_complete`](Self::is_complete)
+/// 对其票据返回 `true` 后，写者即可像亲自调用过 `heavy()` 一样进行发布。软实时写者因此无需等待 IPI 送达。
+///
+/// 票据会回绕；只要未完成的请求少于 `usize::MAX / 2` 个，比较结果就是正确的。
+pub struct DeferredBarrier<B: AsymmetricBarrier = SystemBarrier> {
+    requested: AtomicUsize,
+    completed: AtomicUsize,
+    barrier: B,
+}
+
+impl DeferredBarrier {
+    /// Creates a deferred barrier with no pending request.
+    /// 创建没有待处理请求的延迟屏障。
+    pub fn new() -> Self {
+        Self::with_barrier(SystemBarrier)
+    }
+}
+
+impl Default for DeferredBarrier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: AsymmetricBarrier> DeferredBarrier<B> {
+    /// Creates a deferred barrier using a custom barrier.
+    /// 使用自定义屏障创建延迟屏障。
+    pub fn with_barrier(barrier: B) -> Self {
+        Self {
+            requested: AtomicUsize::new(0),
+            completed: AtomicUsize::new(0),
+            barrier,
+        }
+    }
+
+    /// **Request**
+    ///
+    /// Records that the caller's earlier writes need a heavy barrier and returns its ticket.
+    /// Never blocks.
+    ///
+    /// ---
+    ///
+    /// **请求**
+    ///
+    /// 记录调用方之前的写入需要一次重型屏障，并返回其票据。从不阻塞。
+    #[inline]
+    pub fn request(&self) -> usize {
+        // Release: the caller's writes happen before the `heavy()` that serves this ticket.
+        // Release：调用方的写入先于服务该票据的 `heavy()`。
+        self.requested.fetch_add(1, Ordering::Release).wrapping_add(1)
+    }
+
+    /// **Is Complete**
+    ///
+    /// Whether a heavy barrier has run since `ticket` was requested. After `true`, stores by the
+    /// caller pair with readers' `light()` exactly as after a direct `heavy()`.
+    ///
+    /// ---
+    ///
+    /// **是否完成**
+    ///
+    /// 自请求 `ticket` 以来是否已执行过一次重型屏障。返回 `true` 后，调用方的存储与读者的 `light()`
+    /// 配对，效果与直接调用 `heavy()` 之后完全相同。
+    #[inline]
+    pub fn is_complete(&self, ticket: usize) -> bool {
+        let completed = self.completed.load(Ordering::Acquire);
+        if (completed.wrapping_sub(ticket) as isize) < 0 {
+            return false;
+        }
+        // The barrier ran on another thread. With fence-based backends, readers' `light()` only
+        // synchronizes with fences of the publishing thread, so re-release here.
+        // 屏障在另一个线程上执行。对于基于栅栏的后端，读者的 `light()` 只与发布线程的栅栏同步，因此在此再次 release。
+        fence(Ordering::Release);
+        true
+    }
+
+    /// **Service**
+    ///
+    /// Issues one `heavy()` for every request made so far and marks them complete. Returns `false`
+    /// without a barrier if nothing was pending. Call it from one thread at a time.
+    ///
+    /// ---
+    ///
+    /// **服务**
+    ///
+    /// 为此前的所有请求执行一次 `heavy()` 并将其标记为完成。若没有待处理请求则不执行屏障并返回 `false`。
+    /// 同一时间只应由一个线程调用。
+    pub fn service(&self) -> bool {
+        let requested = self.requested.load(Ordering::Acquire);
+        if requested == self.completed.load(Ordering::Relaxed) {
+            return false;
+        }
+        self.barrier.heavy();
+        self.completed.store(requested, Ordering::Release);
+        true
+    }
+}
+
+impl<B: AsymmetricBarrier> fmt::Debug for DeferredBarrier<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeferredBarrier")
+            .field("requested", &self.requested.load(Ordering::Relaxed))
+            .field("completed", &self.completed.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+#[cfg(all(feature = "std", not(feature = "loom")))]
+mod global {
+    use super::DeferredBarrier;
+    use crate::barrier::SystemBarrier;
+    use core::sync::atomic::AtomicUsize;
+    use std::sync::OnceLock;
+    use std::thread::{self, Thread};
+
+    static DEFERRED: DeferredBarrier = DeferredBarrier {
+        requested: AtomicUsize::new(0),
+        completed: AtomicUsize::new(0),
+        barrier: SystemBarrier,
+    };
+
+    static BARRIER_THREAD: OnceLock<Thread> = OnceLock::new();
+
+    /// Returns the barrier thread, spawning it on first use.
+    /// 返回屏障线程，首次使用时创建。
+    fn barrier_thread() -> &'static Thread {
+        BARRIER_THREAD.get_or_init(|| {
+            thread::Builder::new()
+                .name("swmr-barrier".into())
+                .spawn(|| {
+                    loop {
+                        // A request made after `service()` leaves an unpark token, so `park()` returns.
+                        // 在 `service()` 之后发出的请求会留下 unpark 令牌，因此 `park()` 会返回。
+                        if !DEFERRED.service() {
+                            thread::park();
+                        }
+                    }
+                })
+                .expect("failed to spawn the swmr-barrier thread")
+                .thread()
+                .clone()
+        })
+    }
+
+    pub(crate) fn request() -> usize {
+        let ticket = DEFERRED.request();
+        barrier_thread().unpark();
+        ticket
+    }
+
+    pub(crate) fn is_complete(ticket: usize) -> bool {
+        DEFERRED.is_complete(ticket)
+    }
+}
+
+#[cfg(all(feature = "std", not(feature = "loom")))]
+pub(crate) use global::{is_complete as deferred_complete, request as deferred_request};
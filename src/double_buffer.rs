@@ -0,0 +1,179 @@
+use crate::barrier::{AsymmetricBarrier, BarrierKind, SystemBarrier};
+use crate::slot_ring::SlotRing;
+use crate::sync::ConstPtr;
+use core::fmt;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+
+/// **Double Buffer**
+///
+/// A single-writer multi-reader value with two buffers. Readers borrow the active buffer in place;
+/// the writer fills the inactive one and flips the index on [`commit`](Self::commit).
+///
+/// * **Reader**: One counter increment plus `light()` per [`borrow_snapshot`](Self::borrow_snapshot).
+/// * **Writer**: One `heavy()` per commit, then waits until no guard references the inactive buffer.
+///
+/// ---
+///
+/// **双缓冲**
+///
+/// 单写多读的双缓冲值。读者原地借用活动缓冲区；写者填充非活动缓冲区并在 [`commit`](Self::commit) 时切换索引。
+///
+/// * **读者**：每次 [`borrow_snapshot`](Self::borrow_snapshot) 只需一次计数器递增和一次 `light()`。
+/// * **写者**：每次提交一次 `heavy()`，随后等待没有守卫引用非活动缓冲区。
+pub struct DoubleBuffer<T, B: AsymmetricBarrier = SystemBarrier> {
+    // `Send`/`Sync` come from the ring: `T: Send` and `T: Send + Sync` respectively.
+    // `Send`/`Sync` 由环推导：分别需要 `T: Send` 与 `T: Send + Sync`。
+    ring: SlotRing<T, 2, B>,
+}
+
+impl<T: Clone> DoubleBuffer<T> {
+    /// Creates a double buffer with both buffers holding `initial`.
+    /// 创建双缓冲，两个缓冲区均持有 `initial`。
+    pub fn new(initial: T) -> Self {
+        Self::with_barrier(initial, SystemBarrier)
+    }
+}
+
+impl<T: Copy> DoubleBuffer<T> {
+    /// [`new`](Self::new) as a `const fn`, so a double buffer can be a `static`. Needs `T: Copy`
+    /// because `Clone` cannot be called in a `const` context. Not available under `loom`.
+    ///
+    /// `const fn` 版本的 [`new`](Self::new)，使双缓冲可作为 `static`。需要 `T: Copy`，因为 `const` 上下文中无法调用
+    /// `Clone`。在 `loom` 下不可用。
+    #[cfg(not(feature = "loom"))]
+    pub const fn new_const(initial: T) -> Self {
+        Self {
+            ring: SlotRing::new_const(initial, SystemBarrier),
+        }
+    }
+}
+
+impl<T: Clone + Default> Default for DoubleBuffer<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Clone> DoubleBuffer<T, BarrierKind> {
+    /// Creates a double buffer using the selected [`BarrierKind`].
+    /// 使用所选的 [`BarrierKind`] 创建双缓冲。
+    pub fn with_kind(initial: T, kind: BarrierKind) -> Self {
+        Self::with_barrier(initial, kind)
+    }
+}
+
+impl<T: Clone, B: AsymmetricBarrier> DoubleBuffer<T, B> {
+    /// Creates a double buffer using a custom barrier.
+    /// 使用自定义屏障创建双缓冲。
+    pub fn with_barrier(initial: T, barrier: B) -> Self {
+        Self {
+            ring: SlotRing::new(initial, barrier),
+        }
+    }
+
+    /// Returns a clone of the current value.
+    /// 返回当前值的克隆。
+    pub fn read(&self) -> T {
+        self.borrow_snapshot().clone()
+    }
+}
+
+impl<T, B: AsymmetricBarrier> DoubleBuffer<T, B> {
+    /// **Borrow Snapshot**
+    ///
+    /// Borrows the active buffer without copying. The writer will not overwrite it while the guard
+    /// is alive, so keep guards short-lived: a long-held guard stalls the second `commit` after it.
+    ///
+    /// ---
+    ///
+    /// **借用快照**
+    ///
+    /// 无拷贝地借用活动缓冲区。守卫存活期间写者不会覆盖该缓冲区，因此应尽快释放守卫：
+    /// 长期持有的守卫会阻塞其后的第二次 `commit`。
+    #[inline]
+    pub fn borrow_snapshot(&self) -> SnapshotGuard<'_, T, B> {
+        let (index, ptr) = self.ring.pin();
+        SnapshotGuard {
+            ring: &self.ring,
+            index,
+            ptr: ManuallyDrop::new(ptr),
+        }
+    }
+
+    /// **Commit**
+    ///
+    /// Writes `value` into the inactive buffer and makes it active. Waits for guards still
+    /// referencing the inactive buffer to be dropped. Concurrent writers are serialized.
+    ///
+    /// ---
+    ///
+    /// **提交**
+    ///
+    /// 将 `value` 写入非活动缓冲区并将其设为活动缓冲区。会等待仍引用非活动缓冲区的守卫释放。并发写者会被串行化。
+    #[inline]
+    pub fn commit(&self, value: T) {
+        self.ring.commit(value);
+    }
+}
+
+impl<T: fmt::Debug, B: AsymmetricBarrier> fmt::Debug for DoubleBuffer<T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DoubleBuffer")
+            .field("value", &*self.borrow_snapshot())
+            .finish()
+    }
+}
+
+/// **Snapshot Guard**
+///
+/// A borrow of the active buffer of a [`DoubleBuffer`], returned by
+/// [`borrow_snapshot`](DoubleBuffer::borrow_snapshot). The buffer is not overwritten while it lives.
+///
+/// ---
+///
+/// **快照守卫**
+///
+/// 由 [`borrow_snapshot`](DoubleBuffer::borrow_snapshot) 返回的活动缓冲区借用。守卫存活期间该缓冲区不会被覆盖。
+pub struct SnapshotGuard<'a, T, B: AsymmetricBarrier = SystemBarrier> {
+    ring: &'a SlotRing<T, 2, B>,
+    index: usize,
+    ptr: ManuallyDrop<ConstPtr<T>>,
+}
+
+// SAFETY: A guard only hands out `&T` and releases its reader count with an atomic, so it may be
+// shared or dropped on any thread that could hold `&T` (`T: Sync`). `Send` is not implied by the
+// raw pointer inside; it only behaves like `&T`.
+// 安全性：守卫只提供 `&T`，并以原子操作释放读者计数，因此可在任何能持有 `&T` 的线程上共享或释放（`T: Sync`）。
+// 内部裸指针不会自动实现 `Send`；其行为等同于 `&T`。
+unsafe impl<T: Sync, B: AsymmetricBarrier + Sync> Send for SnapshotGuard<'_, T, B> {}
+// SAFETY: As above; `&SnapshotGuard` only yields `&T`.
+// 安全性：同上；`&SnapshotGuard` 只能得到 `&T`。
+unsafe impl<T: Sync, B: AsymmetricBarrier + Sync> Sync for SnapshotGuard<'_, T, B> {}
+
+impl<T, B: AsymmetricBarrier> Deref for SnapshotGuard<'_, T, B> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // SAFETY: The reader count for `index` keeps the writer out of this buffer.
+        // 安全性：`index` 的读者计数阻止写者进入该缓冲区。
+        unsafe { ConstPtr::deref(&self.ptr) }
+    }
+}
+
+impl<T, B: AsymmetricBarrier> Drop for SnapshotGuard<'_, T, B> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: `ptr` is not used again. It must end its borrow before the count is released.
+        // 安全性：`ptr` 之后不再使用；必须在释放计数之前结束借用。
+        unsafe { ManuallyDrop::drop(&mut self.ptr) };
+        self.ring.unpin(self.index);
+    }
+}
+
+impl<T: fmt::Debug, B: AsymmetricBarrier> fmt::Debug for SnapshotGuard<'_, T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,63 @@
+//! **Emulation Detection**
+//!
+//! Best-effort detection of `qemu-user`, which CI uses to run cross-compiled tests. It forwards
+//! `membarrier` to the host kernel, so barriers stay correct, but timings and CPU counts describe
+//! the host, which can make barrier tests behave differently from real hardware.
+//!
+//! ---
+//!
+//! **模拟检测**
+//!
+//! 尽力检测 `qemu-user`（CI 常用它运行交叉编译的测试）。它会将 `membarrier` 转发给宿主内核，因此屏障仍然正确，
+//! 但时序与 CPU 数量反映的是宿主机，可能使屏障测试的表现不同于真实硬件。
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+mod procfs {
+    use std::env;
+    use std::fs;
+    use std::sync::OnceLock;
+
+    /// Whether any environment variable name or `/proc/self/maps` line carries a qemu-user
+    /// signature: `QEMU_*` variables configure it (e.g. `QEMU_LD_PREFIX`), and older versions map
+    /// their own `qemu-<arch>` binary into the guest's address space.
+    ///
+    /// 是否有环境变量名或 `/proc/self/maps` 行带有 qemu-user 特征：`QEMU_*` 变量用于配置它（例如
+    /// `QEMU_LD_PREFIX`），旧版本还会将自身的 `qemu-<arch>` 二进制映射到客户机地址空间。
+    pub(crate) fn detect_in<'a>(mut env_names: impl Iterator<Item = &'a str>, maps: &str) -> bool {
+        env_names.any(|name| name.starts_with("QEMU_"))
+            || maps
+                .lines()
+                .filter_map(|line| line.split_whitespace().nth(5))
+                .any(|path| path.rsplit('/').next().is_some_and(|file| file.starts_with("qemu-")))
+    }
+
+    /// Runs [`detect_in`] on this process's environment and memory map, once: emulation cannot
+    /// start or stop mid-process, and [`stats()`](crate::stats) must not copy the environment and
+    /// read `/proc` on every call.
+    /// 对本进程的环境与内存映射运行 [`detect_in`]，仅一次：进程运行中不会开始或停止模拟，而
+    /// [`stats()`](crate::stats) 不能每次调用都复制环境并读取 `/proc`。
+    pub(crate) fn detect() -> bool {
+        static DETECTED: OnceLock<bool> = OnceLock::new();
+        *DETECTED.get_or_init(|| {
+            let names: std::vec::Vec<_> = env::vars_os().filter_map(|(name, _)| name.into_string().ok()).collect();
+            let maps = fs::read_to_string("/proc/self/maps").unwrap_or_default();
+            detect_in(names.iter().map(std::string::String::as_str), &maps)
+        })
+    }
+}
+
+#[cfg(all(feature = "testing", feature = "std", target_os = "linux"))]
+pub(crate) use procfs::detect_in;
+
+/// Whether this process appears to run under `qemu-user`.
+/// 本进程是否看起来运行在 `qemu-user` 下。
+pub(crate) fn detect() -> bool {
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    {
+        procfs::detect()
+    }
+    #[cfg(not(all(feature = "std", target_os = "linux")))]
+    {
+        false
+    }
+}
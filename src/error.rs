@@ -0,0 +1,69 @@
+use crate::stats::{BarrierStats, InitReason, Validation};
+use core::fmt;
+
+/// `EPERM`, as reported by seccomp filters and hardened kernels that reject membarrier.
+const EPERM: i32 = 1;
+
+/// **Barrier Error**
+///
+/// Why OS-accelerated barriers are unavailable, returned by [`try_init()`](crate::try_init).
+/// Implements [`core::error::Error`], so it works with `?` without `std`.
+///
+/// ---
+///
+/// **屏障错误**
+///
+/// OS 加速屏障不可用的原因，由 [`try_init()`](crate::try_init) 返回。实现了 [`core::error::Error`]，
+/// 因此无需 `std` 即可配合 `?` 使用。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BarrierError {
+    /// The OS predates the mechanism (e.g. Linux < 4.3, Windows < Vista).
+    /// 操作系统早于该机制（例如 Linux < 4.3、Windows < Vista）。
+    KernelTooOld,
+    /// The mechanism was advertised but a barrier was refused with `EPERM`.
+    /// 机制已声明支持，但屏障调用被 `EPERM` 拒绝。
+    PermissionDenied,
+    /// The capability query failed (e.g. `ENOSYS`, or a seccomp filter).
+    /// 能力查询失败（例如 `ENOSYS`，或 seccomp 过滤器）。
+    QueryFailed,
+    /// Registration failed with this errno and no other mechanism was available.
+    /// 注册以该 errno 失败，且没有其他可用机制。
+    RegistrationFailed(i32),
+    /// A barrier was refused with this errno.
+    /// 屏障调用以该 errno 被拒绝。
+    SyscallFailed(i32),
+    /// No accelerated backend exists for this platform or configuration.
+    /// 该平台或配置没有加速后端。
+    Unsupported,
+}
+
+impl BarrierError {
+    /// Explains a non-accelerated `stats` snapshot.
+    /// 解释未加速的 `stats` 快照。
+    pub(crate) fn from_stats(stats: &BarrierStats) -> Self {
+        match (stats.init_reason, stats.validation, stats.registration_errno) {
+            (InitReason::ValidationFailed, Validation::Failed(EPERM), _) => BarrierError::PermissionDenied,
+            (InitReason::ValidationFailed, Validation::Failed(errno), _) => BarrierError::SyscallFailed(errno),
+            (InitReason::QueryFailed, _, _) => BarrierError::QueryFailed,
+            (InitReason::NotSupported, _, Some(errno)) => BarrierError::RegistrationFailed(errno),
+            (InitReason::NotSupported, _, None) => BarrierError::KernelTooOld,
+            _ => BarrierError::Unsupported,
+        }
+    }
+}
+
+impl fmt::Display for BarrierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BarrierError::KernelTooOld => f.write_str("OS is too old for accelerated barriers"),
+            BarrierError::PermissionDenied => f.write_str("accelerated barrier denied (EPERM)"),
+            BarrierError::QueryFailed => f.write_str("barrier capability query failed"),
+            BarrierError::RegistrationFailed(errno) => write!(f, "barrier registration failed (errno {errno})"),
+            BarrierError::SyscallFailed(errno) => write!(f, "barrier system call failed (errno {errno})"),
+            BarrierError::Unsupported => f.write_str("no accelerated barrier on this platform"),
+        }
+    }
+}
+
+impl core::error::Error for BarrierError {}
@@ -0,0 +1,77 @@
+//! **Futex Wake-up** (feature `std`, Linux only)
+//!
+//! Blocking publish/consume for low-frequency writers: readers sleep in `FUTEX_WAIT` instead of
+//! polling, and the barrier discipline still orders the payload against the wake-up.
+//!
+//! ---
+//!
+//! **Futex 唤醒**（`std` 特性，仅限 Linux）
+//!
+//! 面向低频写者的阻塞式发布/消费：读者在 `FUTEX_WAIT` 中休眠而非轮询，屏障约定仍保证载荷先于唤醒可见。
+
+use core::ptr;
+use core::sync::atomic::{AtomicU32, Ordering};
+use libc::{c_int, syscall};
+
+/// Issues a raw private futex operation on `word`.
+/// 对 `word` 发起私有 futex 操作。
+#[inline]
+fn futex_op(word: &AtomicU32, op: c_int, val: u32) -> libc::c_long {
+    unsafe {
+        syscall(
+            libc::SYS_futex,
+            word.as_ptr(),
+            op | libc::FUTEX_PRIVATE_FLAG,
+            val,
+            ptr::null::<libc::timespec>(),
+        )
+    }
+}
+
+/// **Publish and Wake**
+///
+/// Stores `value` into `flag`, issues [`heavy_barrier()`](crate::heavy_barrier), then advances the
+/// sequence word `futex` and wakes every thread blocked in [`wait_for_publish`] on it.
+///
+/// `futex` is a sequence counter owned by the writer: it is incremented (wrapping) on every call so
+/// that a reader arriving between the store and the wake does not sleep through it.
+///
+/// ---
+///
+/// **发布并唤醒**
+///
+/// 将 `value` 存入 `flag`，调用 [`heavy_barrier()`](crate::heavy_barrier)，随后推进序号字 `futex`
+/// 并唤醒所有在其上阻塞于 [`wait_for_publish`] 的线程。
+///
+/// `futex` 是写者拥有的序号计数器：每次调用都会（回绕）递增，使在存储与唤醒之间到达的读者不会错过唤醒。
+pub fn publish_and_wake(flag: &AtomicU32, value: u32, futex: &AtomicU32) {
+    flag.store(value, Ordering::Relaxed);
+    crate::heavy_barrier();
+    futex.fetch_add(1, Ordering::Relaxed);
+    futex_op(futex, libc::FUTEX_WAKE, i32::MAX as u32);
+}
+
+/// **Wait for Publish**
+///
+/// Blocks while `futex` still holds `expected`, then issues [`light_barrier()`](crate::light_barrier)
+/// and returns the new sequence value. Data stored before the matching [`publish_and_wake`] is
+/// visible once this returns; pass the returned value as `expected` to wait for the next publish.
+///
+/// ---
+///
+/// **等待发布**
+///
+/// 在 `futex` 仍为 `expected` 时阻塞，随后调用 [`light_barrier()`](crate::light_barrier) 并返回新的序号值。
+/// 返回后，对应 [`publish_and_wake`] 之前存储的数据均可见；将返回值作为 `expected` 传入即可等待下一次发布。
+pub fn wait_for_publish(futex: &AtomicU32, expected: u32) -> u32 {
+    loop {
+        let current = futex.load(Ordering::Relaxed);
+        if current != expected {
+            crate::light_barrier();
+            return current;
+        }
+        // Spurious wake-ups, EINTR and EAGAIN (value already changed) all re-check the word.
+        // 虚假唤醒、EINTR 和 EAGAIN（值已改变）都会重新检查该字。
+        futex_op(futex, libc::FUTEX_WAIT, expected);
+    }
+}
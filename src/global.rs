@@ -0,0 +1,42 @@
+//! **Global Barrier Override** (feature `global-barrier`)
+//!
+//! A process-wide `dyn AsymmetricBarrier` that [`heavy_barrier()`](crate::heavy_barrier) and
+//! [`light_barrier()`](crate::light_barrier) forward to when installed. The trait object is a fat
+//! pointer, so it is boxed once and leaked to fit a thin `AtomicPtr`.
+//!
+//! ---
+//!
+//! **全局屏障覆盖**（`global-barrier` 特性）
+//!
+//! 进程级的 `dyn AsymmetricBarrier`，安装后 [`heavy_barrier()`](crate::heavy_barrier) 与
+//! [`light_barrier()`](crate::light_barrier) 会转发给它。trait 对象是胖指针，因此将其装箱一次并泄漏，以放入瘦指针 `AtomicPtr`。
+
+use crate::barrier::AsymmetricBarrier;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use std::boxed::Box;
+
+// Null while no override is installed.
+// 未安装覆盖时为空。
+static GLOBAL: AtomicPtr<&'static dyn AsymmetricBarrier> = AtomicPtr::new(ptr::null_mut());
+
+pub(crate) fn install(barrier: &'static dyn AsymmetricBarrier) {
+    GLOBAL.store(Box::leak(Box::new(barrier)), Ordering::Relaxed);
+}
+
+pub(crate) fn clear() {
+    GLOBAL.store(ptr::null_mut(), Ordering::Relaxed);
+}
+
+/// The installed override, if any.
+/// 已安装的覆盖（若有）。
+#[inline(always)]
+pub(crate) fn installed() -> Option<&'static dyn AsymmetricBarrier> {
+    let barrier = GLOBAL.load(Ordering::Relaxed);
+    // SAFETY: A non-null pointer comes from a leaked box that is never freed. `install`'s caller
+    // guarantees it happened before any thread that can observe it started using the barriers, so
+    // the box's contents are visible despite the `Relaxed` load.
+    // 安全性：非空指针来自从不释放的泄漏 box。`install` 的调用方保证安装发生在任何可能观察到它的线程开始使用屏障之前，
+    // 因此尽管使用 `Relaxed` 加载，box 的内容也是可见的。
+    unsafe { barrier.as_ref().copied() }
+}
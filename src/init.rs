@@ -0,0 +1,54 @@
+//! **Pre-`main` Constructor**
+//!
+//! The one place the crate registers code to run before `main` (or when a shared library is
+//! loaded). `build.rs` picks the link section per object format (`cfg(swmr_barrier_ctor)`), so a
+//! new platform only needs its `sys::auto_init_impl()`:
+//!
+//! * **ELF** (Linux, Android, the BSDs, illumos/Solaris, Haiku, Fuchsia, Hurd): `.init_array`.
+//! * **PE** (Windows): `.CRT$XCU`, the section of C++ dynamic initializers.
+//! * **Mach-O** (Apple): `__DATA,__mod_init_func`.
+//!
+//! Other targets (WASI, RTOS, bare metal) get no constructor and detect nothing up front.
+//!
+//! ---
+//!
+//! **`main` 之前的构造函数**
+//!
+//! crate 注册在 `main` 之前（或共享库加载时）运行代码的唯一位置。`build.rs` 按目标文件格式选择链接节
+//! （`cfg(swmr_barrier_ctor)`），因此新平台只需提供自己的 `sys::auto_init_impl()`：
+//!
+//! * **ELF**（Linux、Android、各 BSD、illumos/Solaris、Haiku、Fuchsia、Hurd）：`.init_array`。
+//! * **PE**（Windows）：`.CRT$XCU`，即 C++ 动态初始化器所在的节。
+//! * **Mach-O**（Apple）：`__DATA,__mod_init_func`。
+//!
+//! 其他目标（WASI、RTOS、裸机）没有构造函数，也不会预先执行任何检测。
+
+#[cfg(feature = "testing")]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// Set by the constructor; lets tests confirm it ran before `main`.
+// 由构造函数置位；供测试确认其在 `main` 之前运行。
+#[cfg(feature = "testing")]
+static RAN: AtomicBool = AtomicBool::new(false);
+
+#[cfg(swmr_barrier_ctor)]
+mod constructor {
+    #[used]
+    #[cfg_attr(swmr_barrier_ctor = "elf", unsafe(link_section = ".init_array"))]
+    #[cfg_attr(swmr_barrier_ctor = "pe", unsafe(link_section = ".CRT$XCU"))]
+    #[cfg_attr(swmr_barrier_ctor = "macho", unsafe(link_section = "__DATA,__mod_init_func"))]
+    static __INIT: extern "C" fn() = auto_init;
+
+    extern "C" fn auto_init() {
+        #[cfg(feature = "testing")]
+        super::RAN.store(true, super::Ordering::Relaxed);
+        crate::sys::auto_init_impl();
+    }
+}
+
+/// Whether the pre-`main` constructor has run.
+/// `main` 之前的构造函数是否已运行。
+#[cfg(feature = "testing")]
+pub(crate) fn ran() -> bool {
+    RAN.load(Ordering::Relaxed)
+}
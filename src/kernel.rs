@@ -0,0 +1,114 @@
+//! **Kernel Version**
+//!
+//! The running Linux kernel's release, parsed from `uname(2)`, and the denylist of releases whose
+//! `membarrier` is known to be unreliable (checked during detection with the `strict` feature).
+//!
+//! ---
+//!
+//! **内核版本**
+//!
+//! 从 `uname(2)` 解析出的当前 Linux 内核发行版本，以及已知 `membarrier` 不可靠的版本拒绝列表
+//! （启用 `strict` 特性时在检测期间检查）。
+
+use core::fmt;
+
+/// **Kernel Version**
+///
+/// `major.minor.patch` of a Linux kernel release, returned by [`kernel_version()`](crate::kernel_version).
+/// Ordered numerically, so ranges compare as expected (`4.14.9 < 4.14.10`).
+///
+/// ---
+///
+/// **内核版本**
+///
+/// Linux 内核发行版本的 `major.minor.patch`，由 [`kernel_version()`](crate::kernel_version) 返回。
+/// 按数值排序，因此范围比较符合预期（`4.14.9 < 4.14.10`）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KernelVersion {
+    /// Major version.
+    /// 主版本号。
+    pub major: u32,
+    /// Minor version.
+    /// 次版本号。
+    pub minor: u32,
+    /// Patch (stable) version; 0 when the release omits it.
+    /// 补丁（稳定）版本号；发行版本省略时为 0。
+    pub patch: u32,
+}
+
+impl KernelVersion {
+    /// Creates a version from its components.
+    /// 由各分量创建版本。
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Parses a release string such as `5.15.0-91-generic` or `6.1`. Anything after the numeric
+    /// components (distribution suffixes) is ignored.
+    ///
+    /// 解析诸如 `5.15.0-91-generic` 或 `6.1` 的发行字符串。数字分量之后的内容（发行版后缀）会被忽略。
+    pub fn parse(release: &str) -> Option<Self> {
+        let mut parts = release.split('.').map(|part| {
+            let end = part.find(|c: char| !c.is_ascii_digit()).unwrap_or(part.len());
+            part[..end].parse::<u32>().ok()
+        });
+        let major = parts.next()??;
+        let minor = parts.next()??;
+        let patch = parts.next().flatten().unwrap_or(0);
+        Some(Self { major, minor, patch })
+    }
+}
+
+impl fmt::Display for KernelVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Inclusive ranges of releases whose `membarrier` must not be trusted. Deliberately small: only
+/// releases with a known ordering bug belong here.
+///
+/// 不应信任其 `membarrier` 的发行版本闭区间。刻意保持精简：只收录存在已知排序缺陷的版本。
+#[cfg_attr(
+    any(not(feature = "strict"), feature = "loom", all(swmr_barrier_tsan, not(feature = "tsan-annotate")), not(target_os = "linux")),
+    allow(dead_code)
+)]
+const DENYLIST: &[(KernelVersion, KernelVersion)] = &[
+    // Early 4.14 stable releases, the first to ship PRIVATE_EXPEDITED, with an IPI race.
+    // 早期 4.14 稳定版本，首批提供 PRIVATE_EXPEDITED，存在 IPI 竞争。
+    (KernelVersion::new(4, 14, 0), KernelVersion::new(4, 14, 9)),
+];
+
+/// Whether `version` falls in the denylist.
+/// `version` 是否位于拒绝列表中。
+#[cfg_attr(
+    any(not(feature = "strict"), feature = "loom", all(swmr_barrier_tsan, not(feature = "tsan-annotate")), not(target_os = "linux")),
+    allow(dead_code)
+)]
+pub(crate) fn is_denylisted(version: KernelVersion) -> bool {
+    DENYLIST.iter().any(|&(first, last)| first <= version && version <= last)
+}
+
+/// Reads the running kernel's release (or the `testing` override).
+/// 读取当前内核的发行版本（或 `testing` 覆盖值）。
+pub(crate) fn detect() -> Option<KernelVersion> {
+    #[cfg(feature = "testing")]
+    if let Some(version) = crate::testing::kernel_release_override() {
+        return version;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let mut name: libc::utsname = unsafe { core::mem::zeroed() };
+        if unsafe { libc::uname(&mut name) } != 0 {
+            return None;
+        }
+        // SAFETY: `uname` NUL-terminates `release`.
+        // 安全性：`uname` 会以 NUL 结尾 `release`。
+        let release = unsafe { core::ffi::CStr::from_ptr(name.release.as_ptr()) };
+        KernelVersion::parse(release.to_str().ok()?)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
@@ -0,0 +1,44 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+
+// Bucket 0 holds calls under 2^(FIRST_SHIFT) ns; each later bucket doubles, and the last is open-ended.
+// 桶 0 存放耗时低于 2^(FIRST_SHIFT) 纳秒的调用；之后每个桶翻倍，最后一个桶无上界。
+const FIRST_SHIFT: u32 = 10;
+
+// Slowest successful membarrier barrier command so far (0 = none yet).
+// 迄今最慢的一次成功的 membarrier 屏障命令 (0 = 尚无)。
+static MAX_NS: AtomicU64 = AtomicU64::new(0);
+
+static HISTOGRAM: [AtomicU64; crate::LATENCY_BUCKETS] = [const { AtomicU64::new(0) }; crate::LATENCY_BUCKETS];
+
+/// Records one membarrier barrier command that took `elapsed`.
+/// 记录一次耗时为 `elapsed` 的 membarrier 屏障命令。
+#[inline]
+pub(crate) fn record(elapsed: Duration) {
+    let ns: u64 = elapsed.as_nanos().try_into().unwrap_or(u64::MAX);
+    MAX_NS.fetch_max(ns, Ordering::Relaxed);
+    HISTOGRAM[bucket(ns)].fetch_add(1, Ordering::Relaxed);
+}
+
+/// Index of the bucket holding `ns`.
+/// `ns` 所在桶的索引。
+fn bucket(ns: u64) -> usize {
+    let bits = u64::BITS - ns.leading_zeros();
+    (bits.saturating_sub(FIRST_SHIFT) as usize).min(crate::LATENCY_BUCKETS - 1)
+}
+
+pub(crate) fn histogram() -> [u64; crate::LATENCY_BUCKETS] {
+    core::array::from_fn(|i| HISTOGRAM[i].load(Ordering::Relaxed))
+}
+
+pub(crate) fn max_ns() -> u64 {
+    MAX_NS.load(Ordering::Relaxed)
+}
+
+#[cfg(feature = "testing")]
+pub(crate) fn reset() {
+    MAX_NS.store(0, Ordering::Relaxed);
+    for bucket in &HISTOGRAM {
+        bucket.store(0, Ordering::Relaxed);
+    }
+}
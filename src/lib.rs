@@ -1,5 +1,210 @@
 #![no_std]
+#[cfg(any(feature = "std", feature = "loom"))]
+extern crate std;
+
+// Cargo unifies features across the dependency graph, so any crate enabling `swmr-barrier/loom`
+// would swap the simulated fences into every build, production included. `--cfg loom` is set
+// per invocation and never leaks, so require both before compiling the loom arm.
+// Cargo 会在整个依赖图中合并特性，任一 crate 启用 `swmr-barrier/loom` 都会把模拟栅栏带入所有构建，包括生产构建。
+// `--cfg loom` 按每次调用设置且不会泄漏，因此编译 loom 分支前要求二者同时存在。
+#[cfg(all(feature = "loom", not(loom)))]
+compile_error!(
+    "feature `loom` is for model-checking tests only and must be paired with `RUSTFLAGS=\"--cfg loom\"`; \
+     was it enabled by another crate through feature unification?"
+);
+
+// `loom` swaps in model-checked atomics that only work on threads spawned inside `loom::model`.
+// Features that run barriers on other threads cannot work under it, so reject them up front.
+// `loom` 会换用模型检查原子类型，它们只能在 `loom::model` 内创建的线程上使用。
+// 在其他线程上执行屏障的特性无法在其下工作，因此直接拒绝。
+#[cfg(all(feature = "loom", feature = "capi"))]
+compile_error!("features `loom` and `capi` are mutually exclusive: C callers run outside any loom model");
+#[cfg(all(feature = "loom", feature = "verify"))]
+compile_error!("features `loom` and `verify` are mutually exclusive: the canary threads run outside any loom model");
+#[cfg(all(feature = "loom", feature = "async"))]
+compile_error!("features `loom` and `async` are mutually exclusive: the waker lock is not modeled by loom");
+#[cfg(all(feature = "loom", feature = "debug-seq"))]
+compile_error!("features `loom` and `debug-seq` are mutually exclusive: loom threads share one OS thread's thread-locals");
+#[cfg(all(feature = "debug-seq", not(target_has_atomic = "64")))]
+compile_error!("feature `debug-seq` needs 64-bit atomics for the barrier generation counter");
+#[cfg(all(feature = "loom", feature = "debug-checks"))]
+compile_error!("features `loom` and `debug-checks` are mutually exclusive: loom threads share one OS thread's thread-locals");
+#[cfg(all(feature = "verify", feature = "debug-checks"))]
+compile_error!("features `verify` and `debug-checks` are mutually exclusive: the canary writer would flood the multi-writer reports");
+#[cfg(all(feature = "telemetry", not(target_has_atomic = "64")))]
+compile_error!("feature `telemetry` needs 64-bit atomics for its counters");
+
+mod append_log;
+#[cfg(feature = "std")]
+mod arc_cell;
+mod asym_atomic;
+mod atomic_cell;
+mod barrier;
+#[cfg(all(feature = "std", not(feature = "loom")))]
+mod blocking;
+mod cache_padded;
+#[cfg(feature = "capi")]
+pub mod capi;
+mod cell;
+mod checksummed;
+mod deferred;
+#[cfg(feature = "debug-checks")]
+mod debug_checks;
+#[cfg(feature = "debug-seq")]
+mod debug_seq;
+mod macros;
+mod publish;
+mod double_buffer;
+mod emulation;
+mod error;
+mod kernel;
+#[cfg(all(
+    feature = "latency",
+    target_os = "linux",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+mod latency;
+#[cfg(all(feature = "std", target_os = "linux", not(feature = "loom")))]
+mod futex;
+#[cfg(feature = "global-barrier")]
+mod global;
+mod init;
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+mod mode;
+pub mod ordering;
+#[cfg(target_has_atomic = "64")]
+mod overwrite_ring;
+mod prefetch;
+#[cfg(all(feature = "std", not(feature = "loom")))]
+mod probe;
+mod rcu;
+mod seqlock;
+#[cfg(doctest)]
+mod send_sync;
+mod sequence;
+#[cfg(feature = "std")]
+mod shared;
+mod slot_ring;
+mod stats;
+mod sync;
 mod sys;
+#[cfg(feature = "telemetry")]
+mod telemetry;
+#[cfg(all(swmr_barrier_tsan, feature = "tsan-annotate"))]
+mod tsan;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod topology;
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+mod validation;
+#[cfg(all(feature = "verify", not(feature = "loom")))]
+mod verify;
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+mod watchdog;
+
+pub use append_log::{AppendLog, LogCursor};
+#[cfg(feature = "std")]
+pub use arc_cell::SwmrArcCell;
+pub use asym_atomic::AsymAtomic;
+pub use atomic_cell::AtomicCell;
+pub use barrier::{
+    AsymmetricBarrier, BarrierDomain, BarrierKind, FallbackOrdering, ReaderRegistration, SystemBarrier, TrackedBarrier,
+};
+#[cfg(all(feature = "std", not(feature = "loom")))]
+pub use blocking::{BlockingSubscriber, PublishSignal};
+pub use cache_padded::{CACHE_LINE, CachePadded};
+pub use cell::{ResilientReader, SwmrCell, SwmrCellReader, SwmrWordCell, WordSized};
+pub use checksummed::ChecksummedCell;
+#[cfg(feature = "debug-checks")]
+pub use debug_checks::MultiWriterReport;
+pub use deferred::DeferredBarrier;
+pub use double_buffer::{DoubleBuffer, SnapshotGuard};
+pub use error::BarrierError;
+pub use kernel::KernelVersion;
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+pub use mode::{BarrierMode, MODE_ENV_VAR, ModeWarning};
+#[cfg(all(feature = "std", target_os = "linux", not(feature = "loom")))]
+pub use futex::{publish_and_wake, wait_for_publish};
+#[cfg(target_has_atomic = "64")]
+pub use overwrite_ring::{OverwriteRing, RingSamples};
+pub use prefetch::prefetch_read;
+#[cfg(target_has_atomic = "64")]
+pub use publish::publish_store_u64;
+pub use publish::{publish_store_bool, publish_store_ptr, publish_store_usize};
+pub use rcu::{RcuDomain, RcuReadGuard, RcuReader, ReaderTableFull, wait_for_grace_period};
+pub use seqlock::FairSeqlock;
+#[cfg(feature = "async")]
+pub use seqlock::changed;
+pub use sequence::{ReaderSequence, WriterSequence};
+#[cfg(feature = "std")]
+pub use shared::{SharedSwmrCell, shared};
+pub use stats::{
+    AccelerationMatrix, Backend, BarrierStats, CostClass, FenceStrength, InitReason, PlatformInfo, RuntimeTelemetry,
+    ThreadRegistration, Validation,
+};
+pub use topology::TopologyHint;
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+pub use watchdog::BarrierTimeout;
+
+/// **Default Reader Slots**
+///
+/// Default `SLOTS` for [`RcuDomain`]: the maximum number of concurrently registered readers.
+///
+/// Each slot costs two words inline and one load on every [`synchronize`](RcuDomain::synchronize),
+/// so size the table to the expected reader count, e.g. `RcuDomain::<8>::new()` for a small pool.
+///
+/// ---
+///
+/// **默认读者槽数**
+///
+/// [`RcuDomain`] 的默认 `SLOTS`：同时注册读者的最大数量。
+///
+/// 每个槽内联占用两个字，并在每次 [`synchronize`](RcuDomain::synchronize) 时产生一次加载，
+/// 因此应按预期读者数量设置表大小，例如小型线程池可使用 `RcuDomain::<8>::new()`。
+pub const DEFAULT_READER_SLOTS: usize = 64;
+
+/// **Maximum Cell Payload**
+///
+/// The largest `T`, in bytes, recommended for [`SwmrCell`]. Every [`get`](SwmrCell::get) copies the
+/// whole value, so past a few cache lines the copy outweighs the barrier saved on the read path.
+/// Publish larger data in place with [`DoubleBuffer`] or behind a pointer with
+/// [`publish_store_ptr()`] instead. Exceeding it is a `debug_assert!` in the constructors, so
+/// release builds accept any size.
+///
+/// ---
+///
+/// **单元最大载荷**
+///
+/// 推荐用于 [`SwmrCell`] 的 `T` 的最大字节数。每次 [`get`](SwmrCell::get) 都会拷贝整个值，超过几个缓存行后，
+/// 拷贝开销将超过读路径上节省的屏障开销。更大的数据应使用 [`DoubleBuffer`] 原地发布，或通过
+/// [`publish_store_ptr()`] 以指针发布。超出时构造函数中的 `debug_assert!` 会触发，发布构建则接受任意大小。
+pub const MAX_CELL_BYTES: usize = 256;
 
 /// **Heavy Barrier**
 ///
@@ -16,9 +221,202 @@ mod sys;
 ///
 /// * **最佳情况**：调用 OS API 强制刷新所有 CPU 缓存 (Linux PrivateExpedited / Windows FlushProcessWriteBuffers)。
 /// * **回退情况**：退化为 `fence(Ordering::SeqCst)`。
+///
+/// With the `tracing` feature, each call emits a `TRACE` event tagged with the active backend.
+/// `light_barrier()` is never traced.
+///
+/// 启用 `tracing` 特性时，每次调用都会产生一个带有当前后端标签的 `TRACE` 事件。`light_barrier()` 从不追踪。
+///
+/// With the `debug-checks` feature, a debug build reports (never panics) when a different thread
+/// issued the previous heavy barrier less than 100 ms earlier, a likely sign of two writers; see
+/// `set_multi_writer_hook()`. Helper threads such as the `heavy_barrier_deferred()` worker count as
+/// callers. Release builds skip the check.
+///
+/// 启用 `debug-checks` 特性时，若上一次重型屏障由另一线程在 100 ms 之内发出（很可能存在两个写者），调试构建会报告
+/// （绝不 panic），参见 `set_multi_writer_hook()`。`heavy_barrier_deferred()` 工作线程等辅助线程也计为调用者。
+/// 发布构建跳过该检查。
+///
+/// # Composition
+///
+/// The barrier is process-wide, not tied to a structure: every heavy barrier orders all of the
+/// calling thread's earlier writes, whichever structure they belong to. Several barrier-backed
+/// structures published from one thread therefore compose. If structure A publishes and then
+/// structure B publishes, a reader that sees B's publish and then issues `light_barrier()` also
+/// sees A's. [`SystemBarrier`] and [`BarrierDomain`] delegate here, so structures on different
+/// domains compose too; a custom [`AsymmetricBarrier`] gives only the ordering it documents.
+///
+/// # 组合
+///
+/// 该屏障作用于整个进程，而非某个数据结构：每次重型屏障都会对调用线程此前的所有写入排序，无论其属于哪个结构。
+/// 因此由同一线程发布的多个基于屏障的结构可以组合：若结构 A 先发布、结构 B 后发布，读者看到 B 的发布并调用
+/// `light_barrier()` 后，也能看到 A 的发布。[`SystemBarrier`] 与 [`BarrierDomain`] 委托给本函数，因此位于不同域上的
+/// 结构同样可以组合；自定义 [`AsymmetricBarrier`] 只提供其文档所述的顺序。
 #[inline]
 pub fn heavy_barrier() {
-    sys::heavy_barrier_impl();
+    #[cfg(feature = "global-barrier")]
+    if let Some(barrier) = global::installed() {
+        barrier.heavy();
+        return;
+    }
+    heavy_barrier_traced();
+}
+
+/// **Heavy Barrier (Traced)**
+///
+/// [`heavy_barrier()`], returning the [`Backend`] that actually ordered memory on this call. Unlike
+/// [`active_backend()`], which reports the configured mechanism, this reflects per-call
+/// degradation: on Linux it returns [`Backend::Fallback`] when the membarrier syscall failed and
+/// the call fell back to `fence(SeqCst)`, even though the configured backend stays accelerated.
+/// Sampling it shows what fraction of barriers degrade.
+///
+/// ---
+///
+/// **重型屏障（可追踪）**
+///
+/// 执行 [`heavy_barrier()`]，并返回本次调用中实际完成内存排序的 [`Backend`]。与报告所配置机制的 [`active_backend()`]
+/// 不同，它反映单次调用的降级：在 Linux 上，若 membarrier 系统调用失败并回退到 `fence(SeqCst)`，即使所配置的后端
+/// 仍为加速后端，也会返回 [`Backend::Fallback`]。对其采样可得知屏障降级的比例。
+#[inline]
+pub fn heavy_barrier_traced() -> Backend {
+    #[cfg(target_has_atomic = "64")]
+    GENERATION.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+    #[cfg(feature = "telemetry")]
+    telemetry::record_heavy();
+    #[cfg(feature = "debug-checks")]
+    debug_checks::record_writer();
+    #[cfg(feature = "tracing")]
+    {
+        let backend = sys::backend_impl();
+        tracing::trace!(
+            target: "swmr_barrier",
+            backend = ?backend,
+            fallback = !backend.is_accelerated(),
+            "heavy_barrier"
+        );
+    }
+    if SINGLE_THREADED.load(core::sync::atomic::Ordering::Relaxed) {
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+        return sys::backend_impl();
+    }
+    #[cfg(all(swmr_barrier_tsan, feature = "tsan-annotate"))]
+    tsan::release();
+    let backend = sys::heavy_barrier_traced_impl();
+    #[cfg(feature = "telemetry")]
+    if backend == Backend::Fallback {
+        telemetry::record_fallback();
+    }
+    backend
+}
+
+/// **Async-Signal-Safe Heavy Barrier**
+///
+/// [`heavy_barrier()`] restricted to async-signal-safe operations, for publishing from a signal
+/// handler (e.g. a profiler's `SIGPROF`). It reads the backend chosen at startup and never
+/// initializes anything: before detection has run it is a `fence(SeqCst)`, which matches readers
+/// at that point.
+///
+/// * **Linux**: one atomic load plus `syscall(membarrier)`; `errno` is preserved. Always
+///   broadcasts, ignoring any `set_reader_cpu_mask()` mask (which takes a lock).
+/// * **Other platforms**: identical to `heavy_barrier()`, whose barrier is already signal-safe.
+/// * Emits no `tracing` event, no TSan annotation and no `testing` fault injection.
+///
+/// ---
+///
+/// **异步信号安全的重型屏障**
+///
+/// 仅使用异步信号安全操作的 [`heavy_barrier()`]，用于在信号处理函数中发布（例如性能分析器的 `SIGPROF`）。
+/// 它读取启动时选定的后端，从不进行任何初始化：检测运行之前它是 `fence(SeqCst)`，与此时的读者相匹配。
+///
+/// * **Linux**：一次原子加载加 `syscall(membarrier)`；会保留 `errno`。始终广播，忽略
+///   `set_reader_cpu_mask()` 设置的掩码（其需要加锁）。
+/// * **其他平台**：与 `heavy_barrier()` 相同，其屏障本身已是信号安全的。
+/// * 不产生 `tracing` 事件、TSan 注解或 `testing` 故障注入。
+#[inline]
+pub fn heavy_barrier_async_signal_safe() {
+    #[cfg(target_has_atomic = "64")]
+    GENERATION.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+    #[cfg(feature = "telemetry")]
+    telemetry::record_heavy();
+    if SINGLE_THREADED.load(core::sync::atomic::Ordering::Relaxed) {
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+        return;
+    }
+    sys::heavy_barrier_signal_safe_impl();
+}
+
+// Set by `assume_single_threaded`: heavy barriers drop to a compiler fence.
+// 由 `assume_single_threaded` 设置：重型屏障降为编译器屏障。
+static SINGLE_THREADED: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
+/// **Assume Single-Threaded**
+///
+/// With `yes`, [`heavy_barrier()`] and [`heavy_barrier_async_signal_safe()`] become a
+/// `compiler_fence`: with no other thread to order against, the membarrier syscall (or
+/// `FlushProcessWriteBuffers`) is pure overhead. Useful during startup or under a single-threaded
+/// runtime. The barriers still bump [`barrier_generation()`], and [`heavy_barrier_traced()`]
+/// reports the configured backend. `false` restores the real barrier. Light barriers and
+/// `heavy_barrier_sync_core()` (Windows) are unaffected.
+///
+/// This is an explicit opt-in: the crate never guesses, since a wrong guess breaks the ordering
+/// that [`SwmrCell`] and the other structures rely on.
+///
+/// # Safety
+///
+/// While the flag is set, no other thread may run in the process. Call
+/// `assume_single_threaded(false)` before spawning the first thread: spawning orders what was
+/// written before it, but heavy barriers issued afterwards would still skip. Threads created
+/// outside Rust (e.g. by a C library) count too.
+///
+/// ---
+///
+/// **假定单线程**
+///
+/// 传入 `yes` 时，[`heavy_barrier()`] 与 [`heavy_barrier_async_signal_safe()`] 降为 `compiler_fence`：没有其他线程需要排序时，
+/// membarrier 系统调用（或 `FlushProcessWriteBuffers`）纯属开销。适用于启动阶段或单线程运行时。屏障仍会递增
+/// [`barrier_generation()`]，[`heavy_barrier_traced()`] 报告所配置的后端。传入 `false` 恢复真正的屏障。轻型屏障与
+/// `heavy_barrier_sync_core()`（Windows）不受影响。
+///
+/// 这是显式启用：本 crate 从不猜测，因为猜错会破坏 [`SwmrCell`] 等数据结构所依赖的顺序。
+///
+/// # 安全性
+///
+/// 标志置位期间，进程中不得运行任何其他线程。应在创建第一个线程之前调用 `assume_single_threaded(false)`：创建线程会对
+/// 之前的写入排序，但之后发出的重型屏障仍会被跳过。Rust 之外创建的线程（例如由 C 库创建）同样计入。
+#[inline]
+pub unsafe fn assume_single_threaded(yes: bool) {
+    SINGLE_THREADED.store(yes, core::sync::atomic::Ordering::Relaxed);
+}
+
+// Heavy barriers issued by this process; bumped before the barrier so the barrier publishes it.
+// 本进程发起的重型屏障次数；在屏障之前递增，从而由该屏障将其发布。
+#[cfg(target_has_atomic = "64")]
+static GENERATION: core::sync::atomic::AtomicU64 = core::sync::atomic::AtomicU64::new(0);
+
+/// **Barrier Generation**
+///
+/// A process-wide counter bumped by every [`heavy_barrier()`] (and
+/// [`heavy_barrier_async_signal_safe()`]) before it orders memory. A reader that snapshots it after
+/// a [`light_barrier()`] and later sees the same value knows no writer of any structure in the
+/// process has published in between, without inspecting each structure.
+///
+/// Advisory and coarse: any heavy barrier counts, including ones that publish nothing the reader
+/// cares about, and barriers on a custom [`AsymmetricBarrier`] that bypasses the process-wide one
+/// are not counted. It wraps after `2^64` barriers. Only on targets with 64-bit atomics.
+///
+/// ---
+///
+/// **屏障代数**
+///
+/// 进程级计数器，每次 [`heavy_barrier()`]（及 [`heavy_barrier_async_signal_safe()`]）在排序内存之前将其递增。
+/// 读者在 [`light_barrier()`] 之后取其快照，若之后看到相同的值，即可知道期间进程内没有任何数据结构的写者发布过，
+/// 无需逐个检查各数据结构。
+///
+/// 仅供参考且粒度较粗：任何重型屏障都会计数，包括未发布读者所关心内容的屏障；绕过进程级屏障的自定义
+/// [`AsymmetricBarrier`] 不会计数。经过 `2^64` 次屏障后回绕。仅在支持 64 位原子操作的目标上提供。
+#[cfg(target_has_atomic = "64")]
+#[inline]
+pub fn barrier_generation() -> u64 {
+    GENERATION.load(core::sync::atomic::Ordering::Relaxed)
 }
 
 /// **Light Barrier**
@@ -28,6 +426,10 @@ pub fn heavy_barrier() {
 /// * **Best Case**: Generates only a `compiler_fence(SeqCst)`. Runtime overhead is practically zero.
 /// * **Fallback**: If the system does not support heavy barrier optimization, it must degrade to `fence(Ordering::SeqCst)` for safety.
 ///
+/// Always inlined. Where the backend is chosen at runtime (Linux, Windows), the accelerated path is
+/// one `Relaxed` load and a branch with no fence instruction; `tests/codegen_test.rs` checks this on
+/// x86_64 Linux.
+///
 /// ---
 ///
 /// **轻型屏障 (Light Barrier)**
@@ -36,9 +438,296 @@ pub fn heavy_barrier() {
 ///
 /// * **最佳情况**：仅产生一个 `compiler_fence(SeqCst)`。运行时开销几乎为 0。
 /// * **回退情况**：如果系统不支持重型屏障优化，必须退化为 `fence(Ordering::SeqCst)` 以保证安全。
-#[inline]
+///
+/// 始终内联。在运行时选择后端的平台（Linux、Windows）上，加速路径仅为一次 `Relaxed` 加载加一次分支，
+/// 不含任何屏障指令；`tests/codegen_test.rs` 在 x86_64 Linux 上对此进行检查。
+#[inline(always)]
 pub fn light_barrier() {
+    #[cfg(feature = "global-barrier")]
+    if let Some(barrier) = global::installed() {
+        barrier.light();
+        return;
+    }
     sys::light_barrier_impl();
+    #[cfg(all(swmr_barrier_tsan, feature = "tsan-annotate"))]
+    tsan::acquire();
+    #[cfg(feature = "debug-seq")]
+    debug_seq::record();
+    #[cfg(feature = "telemetry")]
+    telemetry::record_light();
+}
+
+/// **Last Seen Sequence** (feature `debug-seq`)
+///
+/// The [`barrier_generation()`] observed by this thread's most recent [`light_barrier()`], or 0 if
+/// it has not issued one. With `debug-seq`, every light barrier records it and panics if it is
+/// lower than the thread's previous record, i.e. if the reader's view went backwards.
+///
+/// For debugging suspected reordering: snapshot it at the start of a read transaction and assert
+/// at the end that it has not decreased, or log it next to the values read to line them up with
+/// the writer's barriers. The check costs a load and a thread-local access on every light barrier,
+/// so keep the feature out of production builds.
+///
+/// ---
+///
+/// **最近看到的序号**（`debug-seq` 特性）
+///
+/// 本线程最近一次 [`light_barrier()`] 观察到的 [`barrier_generation()`]；尚未执行过时为 0。启用 `debug-seq` 后，
+/// 每次轻型屏障都会记录该值，若低于本线程之前的记录（即读者的视图发生回退）则 panic。
+///
+/// 用于调试疑似的重排序：在读事务开始时取其快照，并在结束时断言其未减小；或将其与读取到的值一同记录，
+/// 以便与写者的屏障对齐。该检查在每次轻型屏障上多一次加载与一次线程本地访问，因此不要在生产构建中启用此特性。
+#[cfg(feature = "debug-seq")]
+pub fn last_seen_sequence() -> u64 {
+    debug_seq::last_seen()
+}
+
+/// **Deferred Heavy Barrier** (feature `std`)
+///
+/// Requests a [`heavy_barrier()`] from a background "swmr-barrier" thread (spawned on first use)
+/// and returns a ticket without waiting. Requests made before the thread wakes share one barrier.
+/// Publish only after [`heavy_barrier_completed`] returns `true` for the ticket.
+///
+/// Use it when the writer must not block on the membarrier syscall; see [`DeferredBarrier`] to
+/// drive the barrier from a thread of your own.
+///
+/// ---
+///
+/// **延迟重型屏障**（`std` 特性）
+///
+/// 向后台 "swmr-barrier" 线程（首次使用时创建）请求一次 [`heavy_barrier()`]，不等待即返回票据。
+/// 该线程被唤醒前发出的请求共用一次屏障。仅在 [`heavy_barrier_completed`] 对该票据返回 `true` 后再发布。
+///
+/// 适用于写者不能阻塞在 membarrier 系统调用上的场景；若要由自己的线程驱动屏障，请参见 [`DeferredBarrier`]。
+#[cfg(all(feature = "std", not(feature = "loom")))]
+#[inline]
+pub fn heavy_barrier_deferred() -> usize {
+    deferred::deferred_request()
+}
+
+/// Whether the barrier requested by [`heavy_barrier_deferred`] for `ticket` has run.
+/// [`heavy_barrier_deferred`] 为 `ticket` 请求的屏障是否已执行。
+#[cfg(all(feature = "std", not(feature = "loom")))]
+#[inline]
+pub fn heavy_barrier_completed(ticket: usize) -> bool {
+    deferred::deferred_complete(ticket)
+}
+
+/// **Install a Global Barrier** (feature `global-barrier`)
+///
+/// Makes [`heavy_barrier()`] and [`light_barrier()`], and through them [`SystemBarrier`] and every
+/// structure built on it, forward to `barrier` instead of the built-in OS barriers, e.g. so a test
+/// harness can inject an instrumented barrier into an already-compiled plugin. The check costs one
+/// `Relaxed` load and a branch per call. Replacing an override leaks a two-word box, so install
+/// rarely; [`clear_global_barrier()`] restores the built-in barriers.
+///
+/// Only those two functions consult the override. [`heavy_barrier_traced()`],
+/// [`heavy_barrier_async_signal_safe()`] and [`is_accelerated()`] keep using the built-in
+/// backend. The override must not call [`heavy_barrier()`], [`light_barrier()`] or
+/// [`SystemBarrier`], which would forward back to it forever.
+///
+/// # Safety
+///
+/// A heavy barrier from one implementation does not pair with a light barrier from another. Call
+/// this only while no other thread is using the barriers, and make sure threads that use them
+/// later synchronize with this call (e.g. are spawned after it). `barrier` must uphold the
+/// [`AsymmetricBarrier`] contract.
+///
+/// ---
+///
+/// **安装全局屏障**（`global-barrier` 特性）
+///
+/// 使 [`heavy_barrier()`] 与 [`light_barrier()`]（以及经由它们的 [`SystemBarrier`] 和所有基于它的数据结构）转发给
+/// `barrier`，而不是内置的 OS 屏障，例如让测试框架向已编译的插件注入带监测的屏障。每次调用的检查开销为一次 `Relaxed`
+/// 加载与一次分支。替换覆盖会泄漏一个两字大小的 box，因此应尽量少安装；[`clear_global_barrier()`] 恢复内置屏障。
+///
+/// 只有这两个函数会查询覆盖。[`heavy_barrier_traced()`]、[`heavy_barrier_async_signal_safe()`] 与
+/// [`is_accelerated()`] 仍使用内置后端。覆盖不得调用 [`heavy_barrier()`]、[`light_barrier()`] 或
+/// [`SystemBarrier`]，否则会无限转发回自身。
+///
+/// # 安全性
+///
+/// 一种实现的重型屏障无法与另一种实现的轻型屏障配对。只能在没有其他线程使用屏障时调用，并确保之后使用屏障的线程
+/// 与本次调用同步（例如在其之后创建）。`barrier` 必须满足 [`AsymmetricBarrier`] 约定。
+#[cfg(feature = "global-barrier")]
+pub unsafe fn set_global_barrier(barrier: &'static dyn AsymmetricBarrier) {
+    global::install(barrier);
+}
+
+/// Removes the override installed by [`set_global_barrier()`], restoring the built-in barriers.
+///
+/// # Safety
+///
+/// Same as [`set_global_barrier()`].
+///
+/// 移除 [`set_global_barrier()`] 安装的覆盖，恢复内置屏障。
+///
+/// # 安全性
+///
+/// 与 [`set_global_barrier()`] 相同。
+#[cfg(feature = "global-barrier")]
+pub unsafe fn clear_global_barrier() {
+    global::clear();
+}
+
+/// **Single-Shot Ordering Check** (feature `std`)
+///
+/// Runs one store-buffering round between the calling thread (store, [`heavy_barrier()`], load)
+/// and a short-lived reader thread (store, [`light_barrier()`], load), and returns whether at least
+/// one side saw the other's store, as the barriers guarantee. `black_box` keeps the compiler from
+/// folding the stores and loads away. Costs one thread spawn and one heavy barrier, so it fits a
+/// health check that must finish in microseconds.
+///
+/// A `true` proves little: a race the barriers fail to order shows up only when both halves
+/// overlap exactly, which a single round rarely achieves. Only `false` is conclusive. For
+/// thoroughness, combine it with the continuous canary of `integrity_ok()` (feature `verify`).
+///
+/// ---
+///
+/// **单次顺序检查**（`std` 特性）
+///
+/// 在调用线程（存储、[`heavy_barrier()`]、加载）与一个短暂存在的读者线程（存储、[`light_barrier()`]、加载）之间运行一轮
+/// 存储缓冲测试，并返回是否至少有一方看到了对方的存储（屏障所保证的）。`black_box` 防止编译器消除这些存储与加载。
+/// 开销为一次线程创建与一次重型屏障，因此适用于须在微秒级完成的健康检查。
+///
+/// 返回 `true` 说明不了多少：屏障未能排序的竞争只有在两侧恰好重叠时才会显现，单轮测试很少能做到。只有 `false`
+/// 是确定的。如需更全面的检查，请结合 `integrity_ok()`（`verify` 特性）的持续金丝雀检测。
+#[cfg(all(feature = "std", not(feature = "loom")))]
+pub fn assert_ordering_once() -> bool {
+    probe::ordering_once()
+}
+
+/// **Barrier Integrity** (feature `verify`)
+///
+/// Returns `false` once the background canary pair has observed the barriers fail to order a
+/// store-buffering round, so the application can alert or abort. The first call spawns the two
+/// canary threads; the flag never resets. Detection is probabilistic: a round only exposes a
+/// broken barrier when both halves overlap in time, so `true` is evidence, not proof.
+///
+/// **Cost**: two threads that each wake once per millisecond. Every round issues one
+/// [`heavy_barrier()`], so with membarrier about a thousand IPIs per second reach the CPUs running
+/// this process's threads. Each round spins for a few microseconds while the pair meets.
+///
+/// ---
+///
+/// **屏障完整性**（`verify` 特性）
+///
+/// 一旦后台金丝雀线程对观察到屏障未能保证一轮存储缓冲检查的顺序，即返回 `false`，以便应用告警或中止。
+/// 首次调用时创建两个金丝雀线程；该标志永不复位。检测是概率性的：只有当一轮的两侧在时间上重叠时才能暴露失效的屏障，
+/// 因此 `true` 只是证据而非证明。
+///
+/// **开销**：两个线程各自每毫秒唤醒一次。每一轮执行一次 [`heavy_barrier()`]，因此在 membarrier 下每秒约有一千次
+/// IPI 发往运行本进程线程的 CPU。每轮在线程对会合时自旋数微秒。
+#[cfg(all(feature = "verify", not(feature = "loom")))]
+pub fn integrity_ok() -> bool {
+    verify::integrity_ok()
+}
+
+/// Number of canary rounds completed since [`integrity_ok()`] first started the monitor.
+/// 自 [`integrity_ok()`] 首次启动监视器以来完成的金丝雀轮数。
+#[cfg(all(feature = "verify", not(feature = "loom")))]
+pub fn integrity_rounds() -> u64 {
+    verify::integrity_rounds()
+}
+
+/// **Heavy Barrier With Deadline** (feature `std`, Linux)
+///
+/// [`heavy_barrier()`] timed against `max_ns`. If it overran, returns [`BarrierTimeout`] and
+/// disables acceleration for the rest of the process: readers switch to `fence(SeqCst)` first,
+/// then one final membarrier orders any reader still on the cheap path, then writers switch to
+/// fences. [`stats()`] then reports [`InitReason::WatchdogTripped`]; an explicit [`init()`]
+/// re-enables acceleration.
+///
+/// For services where a membarrier stuck on a misbehaving CPU for hundreds of milliseconds is
+/// worse than paying a fence on every read. The barrier has completed even when `Err` is returned.
+/// The measurement includes preemption of the calling thread, so leave headroom above the typical
+/// latency. The slowest call is recorded in [`BarrierStats::slowest_heavy_ns`].
+///
+/// ---
+///
+/// **带期限的重型屏障**（`std` 特性，Linux）
+///
+/// 以 `max_ns` 为期限计时的 [`heavy_barrier()`]。若超时，返回 [`BarrierTimeout`] 并在进程剩余时间内禁用加速：
+/// 读者先切换为 `fence(SeqCst)`，随后执行最后一次 membarrier 为仍处于廉价路径上的读者排序，然后写者切换为栅栏。
+/// 此后 [`stats()`] 报告 [`InitReason::WatchdogTripped`]；显式调用 [`init()`] 会重新启用加速。
+///
+/// 适用于这样的服务：membarrier 卡在异常 CPU 上数百毫秒，比每次读取都付出一次栅栏更糟。即使返回 `Err`，屏障也已完成。
+/// 测量包含调用线程被抢占的时间，因此期限应高于典型延迟并留有余量。最慢的一次调用记录在
+/// [`BarrierStats::slowest_heavy_ns`] 中。
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+pub fn heavy_barrier_with_deadline(max_ns: u64) -> Result<(), BarrierTimeout> {
+    watchdog::heavy_barrier_with_deadline(max_ns)
+}
+
+/// **Latency Buckets**
+///
+/// Number of buckets in `barrier_latency_histogram()` (feature `latency`). Bucket 0 counts calls
+/// under 1024 ns, bucket `i` counts calls in `[2^(i+9), 2^(i+10))` ns, and the last bucket counts
+/// everything from `2^24` ns (about 16.8 ms) up.
+///
+/// ---
+///
+/// **延迟桶数**
+///
+/// `barrier_latency_histogram()`（`latency` 特性）的桶数。桶 0 统计低于 1024 纳秒的调用，桶 `i` 统计位于
+/// `[2^(i+9), 2^(i+10))` 纳秒的调用，最后一个桶统计 `2^24` 纳秒（约 16.8 毫秒）及以上的全部调用。
+pub const LATENCY_BUCKETS: usize = 16;
+
+/// **Barrier Latency Histogram** (feature `latency`, Linux)
+///
+/// Counts of successful membarrier barrier commands by duration, bucketed as described in
+/// [`LATENCY_BUCKETS`]. Every heavy barrier that reaches the syscall is recorded, including each
+/// targeted call under a reader CPU mask and the validation barrier of [`init()`]; fence fallbacks
+/// and [`heavy_barrier_async_signal_safe()`] are not. Recording is two `Relaxed` atomic updates
+/// plus two clock reads around the syscall, and [`light_barrier()`] is untouched.
+///
+/// Meant for alerting on IPI latency regressions, e.g. from noisy neighbours: export the counts
+/// periodically and watch the upper buckets. The counters are independent, so a snapshot taken
+/// during a barrier may miss it in one bucket but never counts it twice.
+///
+/// ---
+///
+/// **屏障延迟直方图**（`latency` 特性，Linux）
+///
+/// 按耗时统计成功的 membarrier 屏障命令次数，分桶方式见 [`LATENCY_BUCKETS`]。每次进入系统调用的重型屏障都会被记录，
+/// 包括读者 CPU 掩码下的每次定向调用以及 [`init()`] 的验证屏障；栅栏回退与 [`heavy_barrier_async_signal_safe()`]
+/// 不记录。记录开销为系统调用前后的两次时钟读取加两次 `Relaxed` 原子更新，[`light_barrier()`] 不受影响。
+///
+/// 用于对 IPI 延迟退化（例如由吵闹的邻居引起）进行告警：定期导出计数并关注高位桶。各计数器相互独立，
+/// 因此在屏障执行期间获取的快照可能漏计该次调用，但绝不会重复计数。
+#[cfg(all(
+    feature = "latency",
+    target_os = "linux",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+pub fn barrier_latency_histogram() -> [u64; LATENCY_BUCKETS] {
+    latency::histogram()
+}
+
+/// **Maximum Barrier Latency** (feature `latency`, Linux)
+///
+/// Duration in nanoseconds of the slowest membarrier barrier command recorded in
+/// [`barrier_latency_histogram()`], or 0 if none has run yet.
+///
+/// ---
+///
+/// **最大屏障延迟**（`latency` 特性，Linux）
+///
+/// [`barrier_latency_histogram()`] 所记录的最慢一次 membarrier 屏障命令的耗时（纳秒）；尚未执行过时为 0。
+#[cfg(all(
+    feature = "latency",
+    target_os = "linux",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+pub fn max_barrier_latency_ns() -> u64 {
+    latency::max_ns()
 }
 
 /// **Check Acceleration Status**
@@ -47,7 +736,10 @@ pub fn light_barrier() {
 ///
 /// * **Linux (Kernel 4.3+)**: Returns `true` if `MEMBARRIER_CMD_PRIVATE_EXPEDITED` (4.14+) or `MEMBARRIER_CMD_SHARED` (4.3+) is available.
 /// * **Windows (Vista+)**: Always returns `true`.
-/// * **Other platforms / Loom**: Always returns `false`.
+/// * **Other platforms / WASI / Loom / ThreadSanitizer**: Always returns `false`.
+///
+/// Only the Linux and Windows backends detect this at runtime. In every configuration that always
+/// returns `false` it is a compile-time constant, so `if is_accelerated() { .. }` branches are removed entirely.
 ///
 /// ---
 ///
@@ -56,7 +748,713 @@ pub fn light_barrier() {
 /// 如果正在使用 OS 加速屏障，返回 `true`。
 ///
 /// * **Linux (Kernel 4.3+)**：如果 `MEMBARRIER_CMD_PRIVATE_EXPEDITED` (4.14+) 或 `MEMBARRIER_CMD_SHARED` (4.3+) 可用，返回 `true`。
+/// * **Windows (Vista+)**：始终返回 `true`。
+/// * **其他平台 / WASI / Loom / ThreadSanitizer**：始终返回 `false`。
+///
+/// 只有 Linux 和 Windows 后端在运行时检测；在所有始终返回 `false` 的配置下它是编译期常量，`if is_accelerated() { .. }` 分支会被完全移除。
 #[inline]
 pub fn is_accelerated() -> bool {
     sys::is_accelerated_impl()
 }
+
+/// **Explicit Initialization**
+///
+/// Re-attempts OS barrier detection and returns the resulting acceleration status.
+///
+/// Detection already runs automatically before `main`. Calling `init()` is useful when that early
+/// attempt could not select the best mechanism, e.g. Linux `MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED`
+/// failing with `ENOMEM` under memory pressure. Unlike the constructor, this path retries the
+/// registration once after yielding, then issues one validation barrier with the selected command.
+/// If the kernel rejects it (e.g. hardened kernels that disable membarrier), it downgrades
+/// PRIVATE_EXPEDITED → SHARED → fallback; see [`stats()`] for the outcome. It is a no-op once the
+/// best mechanism is active and validated.
+///
+/// Applications that install a seccomp filter should call it before locking down: another crate's
+/// filter may already have blocked the pre-`main` query ([`InitReason::QueryFailed`]).
+///
+/// ---
+///
+/// **显式初始化**
+///
+/// 重新尝试检测 OS 屏障，并返回最终的加速状态。
+///
+/// 检测已在 `main` 之前自动执行。当早期检测未能选中最佳机制时（例如 Linux 在内存压力下
+/// `MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED` 返回 `ENOMEM`），可调用 `init()`。与构造函数不同，
+/// 此路径会在让出 CPU 后重试一次注册，然后使用所选命令发起一次验证屏障。若内核拒绝（例如禁用了
+/// membarrier 的加固内核），则按 PRIVATE_EXPEDITED → SHARED → 回退 的顺序降级；结果见 [`stats()`]。
+/// 最佳机制启用并通过验证后再次调用不会产生任何效果。
+///
+/// 安装 seccomp 过滤器的应用应在锁定之前调用它：其他 crate 的过滤器可能已经拦截了 `main` 之前的查询
+/// （[`InitReason::QueryFailed`]）。
+pub fn init() -> bool {
+    sys::init_impl()
+}
+
+/// **Forced Re-detection**
+///
+/// Like [`init()`], but always re-runs the full Linux detection and registration, even when
+/// PRIVATE_EXPEDITED is already active and validated, and returns the new acceleration status. Use
+/// it after the process's privileges or namespaces changed (e.g. after `setns`, dropping
+/// capabilities or entering a seccomp sandbox), when the mechanism chosen earlier may have become
+/// unusable or a better one available. A query that now fails keeps the command already active, as
+/// in [`init()`].
+///
+/// Avoid calling it while other threads issue barriers. The switch itself stays correct: readers
+/// leave the cheap path before writers stop issuing membarrier, and only join it after a barrier
+/// with the new command has run. But for its duration, barriers may take a slower path than either
+/// the old or the new configuration would on its own. Other platforms detect nothing that depends
+/// on privileges, so this is the same as [`init()`] there.
+///
+/// ---
+///
+/// **强制重新检测**
+///
+/// 与 [`init()`] 类似，但总会重新执行完整的 Linux 检测与注册（即使 PRIVATE_EXPEDITED 已启用且已验证），并返回新的加速状态。
+/// 适用于进程权限或命名空间发生变化之后（例如 `setns`、放弃 capability 或进入 seccomp 沙箱），此时先前选定的机制可能已不可用，
+/// 或有更好的机制可用。若此时查询失败，与 [`init()`] 相同，保留已启用的命令。
+///
+/// 避免在其他线程执行屏障时调用。切换本身仍然正确：读者先离开廉价路径，写者才停止发出 membarrier；且只有在以新命令执行过
+/// 一次屏障后读者才会重新进入廉价路径。但在切换期间，屏障可能走比新旧配置各自更慢的路径。其他平台没有依赖权限的检测，
+/// 因此在其上与 [`init()`] 相同。
+pub fn redetect() -> bool {
+    sys::redetect_impl()
+}
+
+/// **Validated Initialization** (Linux, feature `std`)
+///
+/// [`init()`], then, if it selected membarrier, `rounds` rounds of the store-heavy/load-light
+/// check against a spawned reader thread. Acceleration stays enabled only if every round passes:
+/// both loads missing the other thread's store, or a heavy barrier whose syscall failed and fell
+/// back to a fence, returns the process to the fence fallback with
+/// [`InitReason::ValidationFailed`]. Returns the resulting [`stats()`] `init_reason`. For virtualized or
+/// otherwise flaky kernels where one validation barrier is not enough to trust membarrier.
+///
+/// **Cost**: one thread spawn plus, per round, one heavy barrier and two cross-thread handshakes,
+/// roughly 10-50 µs per round depending on the hypervisor. Call it once at startup, before readers
+/// start, since a downgrade mid-flight costs them a fence per read from then on.
+///
+/// ---
+///
+/// **带验证的初始化**（Linux，`std` 特性）
+///
+/// 先执行 [`init()`]；若其选择了 membarrier，再与新建的读者线程进行 `rounds` 轮“存储-重型/加载-轻型”检查。只有每一轮
+/// 都通过时才保持加速：两次加载都未看到对方线程的存储，或某次重型屏障的系统调用失败并回退为栅栏，都会使进程回到
+/// 栅栏回退路径并记录 [`InitReason::ValidationFailed`]。返回最终的 [`stats()`] `init_reason`。适用于单次验证屏障不足以信任
+/// membarrier 的虚拟化或其他不稳定内核。
+///
+/// **开销**：一次线程创建，加上每轮一次重型屏障与两次跨线程握手，视虚拟机监控器而定每轮约 10-50 µs。应在启动时、
+/// 读者开始之前调用一次，因为中途降级后读者此后每次读取都要付出一次栅栏的代价。
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+pub fn init_with_validation(rounds: usize) -> InitReason {
+    validation::init_with_validation(rounds)
+}
+
+/// **Backend Override Warnings** (Linux, feature `std`)
+///
+/// Installs `hook` (or removes it with `None`) to be called whenever detection cannot honor
+/// [`MODE_ENV_VAR`] (`SWMR_BARRIER_MODE`): the value is not `auto`, `private`, `shared` or
+/// `fallback`, or the kernel does not provide the requested command. Detection then continues as
+/// for `auto`. With the `tracing` feature each warning is also emitted as a `warn!` event.
+///
+/// `fallback` always succeeds: both sides fence and [`stats()`] reports [`InitReason::ModeOverride`].
+/// Detection first runs before `main`, before any hook can be installed, so install it and then
+/// call [`redetect()`] to hear about that run. [`init()`] keeps a validated PRIVATE_EXPEDITED without
+/// re-reading the variable, so use [`redetect()`] after changing it too.
+///
+/// ---
+///
+/// **后端覆盖警告**（Linux，`std` 特性）
+///
+/// 安装 `hook`（传入 `None` 则移除），在检测无法满足 [`MODE_ENV_VAR`]（`SWMR_BARRIER_MODE`）时调用：取值不是 `auto`、
+/// `private`、`shared` 或 `fallback`，或内核不提供所请求的命令。此后检测按 `auto` 继续。启用 `tracing` 特性时，
+/// 每条警告也会作为 `warn!` 事件发出。
+///
+/// `fallback` 总会成功：两侧都使用栅栏，[`stats()`] 报告 [`InitReason::ModeOverride`]。检测首次运行于 `main` 之前，
+/// 早于任何钩子的安装，因此应先安装钩子再调用 [`redetect()`] 以获知该次结果。[`init()`] 会保留已验证的
+/// PRIVATE_EXPEDITED 而不重新读取该变量，因此修改变量后同样应使用 [`redetect()`]。
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+pub fn set_mode_warning_hook(hook: Option<fn(&ModeWarning)>) {
+    mode::set_warning_hook(hook);
+}
+
+/// **Multi-Writer Reports** (feature `debug-checks`)
+///
+/// Installs `hook` (or removes it with `None`), called from inside [`heavy_barrier()`] when a debug
+/// build sees heavy barriers from two threads less than 100 ms apart. The report never panics: the
+/// crate's structures serialize concurrent writers and support it, so whether it is a bug is the
+/// caller's call. A hook that panics turns it back into an assertion for code that truly has one
+/// writer. With the `tracing` feature, each report is also emitted as a `warn!` event.
+///
+/// ---
+///
+/// **多写者报告**（`debug-checks` 特性）
+///
+/// 安装 `hook`（传入 `None` 则移除），当调试构建在 100 ms 之内看到来自两个线程的重型屏障时，由 [`heavy_barrier()`]
+/// 内部调用。报告绝不 panic：本 crate 的结构会串行化并支持并发写者，是否属于错误由调用方判断。对于确实只有一个写者的代码，
+/// 在钩子中 panic 即可将其恢复为断言。启用 `tracing` 特性时，每条报告也会作为 `warn!` 事件发出。
+#[cfg(feature = "debug-checks")]
+pub fn set_multi_writer_hook(hook: Option<fn(&MultiWriterReport)>) {
+    debug_checks::set_hook(hook);
+}
+
+/// **Multi-Writer Report Count** (feature `debug-checks`)
+///
+/// How many multi-writer reports this process has made; always 0 in release builds.
+///
+/// ---
+///
+/// **多写者报告计数**（`debug-checks` 特性）
+///
+/// 本进程已产生的多写者报告数；发布构建中恒为 0。
+#[cfg(feature = "debug-checks")]
+pub fn multi_writer_reports() -> usize {
+    debug_checks::reports()
+}
+
+/// **Fallible Initialization**
+///
+/// Runs [`init()`] and returns the active backend, or a [`BarrierError`] explaining why barriers
+/// fell back to `fence(SeqCst)`. Falling back is still correct, only slower; use this when a caller
+/// wants to log or refuse a degraded configuration with `?`.
+///
+/// ---
+///
+/// **可失败的初始化**
+///
+/// 执行 [`init()`] 并返回当前后端；若屏障回退为 `fence(SeqCst)`，则返回说明原因的 [`BarrierError`]。
+/// 回退仍然正确，只是更慢；当调用方希望通过 `?` 记录或拒绝降级配置时使用此函数。
+pub fn try_init() -> Result<Backend, BarrierError> {
+    init();
+    let stats = stats();
+    if stats.backend.is_accelerated() {
+        Ok(stats.backend)
+    } else {
+        Err(BarrierError::from_stats(&stats))
+    }
+}
+
+/// **Shutdown**
+///
+/// Releases the barrier state before the library is unloaded (e.g. ahead of `dlclose`). On Linux,
+/// barriers return to the `fence(SeqCst)` fallback, the reader CPU mask from
+/// `set_reader_cpu_mask()` is freed, and [`stats()`] reports [`InitReason::Shutdown`]. The
+/// kernel's membarrier registration cannot be undone, but nothing issues it afterwards.
+///
+/// Barrier calls after `shutdown()` remain correct, only slower; readers already on the cheap path
+/// are ordered by one last membarrier before writers switch to fences. An explicit [`init()`]
+/// re-enables acceleration. Other platforms allocate and register nothing, so this is a no-op there.
+///
+/// ---
+///
+/// **关闭**
+///
+/// 在库被卸载之前（例如 `dlclose` 之前）释放屏障状态。在 Linux 上，屏障回到 `fence(SeqCst)` 回退路径，
+/// 释放 `set_reader_cpu_mask()` 设置的读者 CPU 掩码，[`stats()`] 报告 [`InitReason::Shutdown`]。
+/// 内核的 membarrier 注册无法撤销，但此后不再有人发出该命令。
+///
+/// `shutdown()` 之后的屏障调用仍然正确，只是更慢；已处于廉价路径上的读者会在写者切换到栅栏之前由最后一次
+/// membarrier 排序。显式调用 [`init()`] 会重新启用加速。其他平台没有任何分配或注册，因此在其上不产生任何效果。
+pub fn shutdown() {
+    sys::shutdown_impl();
+}
+
+/// **Registration Error**
+///
+/// Returns the `errno` of the last failed `MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED` attempt,
+/// or `None` if registration succeeded or was never attempted. A failed registration degrades to
+/// `MEMBARRIER_CMD_SHARED` when available, so `is_accelerated()` may still be `true`.
+///
+/// Always `None` on non-Linux platforms.
+///
+/// ---
+///
+/// **注册错误**
+///
+/// 返回最近一次 `MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED` 注册失败的 `errno`；若注册成功或从未尝试，返回 `None`。
+/// 注册失败时会在可用的情况下降级为 `MEMBARRIER_CMD_SHARED`，因此 `is_accelerated()` 仍可能为 `true`。
+///
+/// 非 Linux 平台始终返回 `None`。
+#[inline]
+pub fn registration_errno() -> Option<i32> {
+    sys::registration_errno_impl()
+}
+
+/// **Sync-Core Availability**
+///
+/// Returns `true` if the kernel advertises `MEMBARRIER_CMD_PRIVATE_EXPEDITED_SYNC_CORE` (Linux 4.16+)
+/// and registration for it succeeded during detection. A sync-core barrier additionally makes every
+/// running thread execute a core-serializing instruction, which JITs need before running code that
+/// another core modified; without it they must fall back to per-thread instruction cache maintenance.
+///
+/// On Windows, `true` when `heavy_barrier_sync_core()` is usable: both `FlushProcessWriteBuffers`
+/// and `FlushInstructionCache` were resolved. Always `false` on other platforms.
+///
+/// ---
+///
+/// **Sync-Core 可用性**
+///
+/// 如果内核声明支持 `MEMBARRIER_CMD_PRIVATE_EXPEDITED_SYNC_CORE` (Linux 4.16+) 且检测期间注册成功，返回 `true`。
+/// Sync-core 屏障还会让所有运行中的线程执行一条核心串行化指令，JIT 在执行其他核心修改过的代码前需要它；
+/// 否则只能回退为逐线程的指令缓存维护。
+///
+/// 在 Windows 上，当 `heavy_barrier_sync_core()` 可用（`FlushProcessWriteBuffers` 与 `FlushInstructionCache`
+/// 均已解析）时返回 `true`。其他平台始终返回 `false`。
+#[inline]
+pub fn sync_core_available() -> bool {
+    sys::sync_core_available_impl()
+}
+
+/// **Acceleration Matrix**
+///
+/// Reports each OS barrier variant separately, where [`is_accelerated()`] only says whether the
+/// selected one is active. On Linux the flags come from the last `MEMBARRIER_CMD_QUERY` mask and
+/// the registrations made during detection; on Windows from the resolved `FlushProcessWriteBuffers`
+/// and `FlushInstructionCache`. Every flag is `false` on other platforms, under Loom and under
+/// ThreadSanitizer.
+///
+/// ---
+///
+/// **加速矩阵**
+///
+/// 分别报告每个 OS 屏障变体，而 [`is_accelerated()`] 只说明所选的变体是否启用。在 Linux 上，各标志来自最近一次
+/// `MEMBARRIER_CMD_QUERY` 掩码与检测期间完成的注册；在 Windows 上来自已解析的 `FlushProcessWriteBuffers` 与
+/// `FlushInstructionCache`。在其他平台、Loom 以及 ThreadSanitizer 下所有标志均为 `false`。
+#[inline]
+pub fn acceleration_matrix() -> AccelerationMatrix {
+    sys::acceleration_matrix_impl()
+}
+
+/// **Sync-Core Heavy Barrier** (Windows)
+///
+/// [`heavy_barrier()`] followed by `FlushInstructionCache(GetCurrentProcess(), NULL, 0)`, the
+/// Windows counterpart of Linux's `PRIVATE_EXPEDITED_SYNC_CORE`: for JITs that publish freshly
+/// written code to threads that may already be running on other processors. Both functions are
+/// resolved from `kernel32.dll` at startup; see [`sync_core_available()`].
+///
+/// **Cost**: a second system call on top of `FlushProcessWriteBuffers`. On x86/x64 the flush is
+/// cheap, since the IPI already serializes every processor it interrupts; on ARM64 it invalidates
+/// the instruction cache of every processor, which slows each of them down until the cache refills.
+/// Call it only when publishing code, and use [`heavy_barrier()`] for data.
+///
+/// # Errors
+///
+/// Returns [`BarrierError::Unsupported`] without issuing anything if either function is missing,
+/// or [`BarrierError::SyscallFailed`] with `GetLastError()` if `FlushInstructionCache` fails after
+/// the write buffers were flushed.
+///
+/// ---
+///
+/// **Sync-Core 重型屏障**（Windows）
+///
+/// 先执行 [`heavy_barrier()`]，再执行 `FlushInstructionCache(GetCurrentProcess(), NULL, 0)`，相当于 Linux 的
+/// `PRIVATE_EXPEDITED_SYNC_CORE`：供 JIT 将刚写入的代码发布给可能已在其他处理器上运行的线程。两个函数都在启动时从
+/// `kernel32.dll` 解析；见 [`sync_core_available()`]。
+///
+/// **开销**：在 `FlushProcessWriteBuffers` 之外多一次系统调用。在 x86/x64 上刷新代价很低，因为 IPI 已经串行化了
+/// 它所中断的每个处理器；在 ARM64 上它会使每个处理器的指令缓存失效，在缓存重新填充之前拖慢每个处理器。
+/// 仅在发布代码时调用，发布数据请使用 [`heavy_barrier()`]。
+///
+/// 任一函数缺失时不执行任何操作并返回 [`BarrierError::Unsupported`]；写缓冲区刷新后 `FlushInstructionCache`
+/// 失败时返回携带 `GetLastError()` 的 [`BarrierError::SyscallFailed`]。
+#[cfg(all(target_os = "windows", not(feature = "loom")))]
+pub fn heavy_barrier_sync_core() -> Result<(), BarrierError> {
+    if !sys::sync_core_available_impl() {
+        return Err(BarrierError::Unsupported);
+    }
+    #[cfg(target_has_atomic = "64")]
+    GENERATION.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+    sys::heavy_barrier_sync_core_impl()
+}
+
+/// **CPU Topology Hint**
+///
+/// Reports how many packages, clusters and capacity classes the online CPUs span. Heavy barriers
+/// interrupt every CPU running the process, so their latency varies more on multi-socket machines
+/// and on heterogeneous (big.LITTLE) phones and boards.
+///
+/// Reads `/sys/devices/system/cpu` on every call, so it follows CPU hotplug; [`stats()`] reports
+/// the hint read on its first call instead. Returns `None` without the `std` feature, on non-Linux
+/// platforms, or when sysfs is unavailable.
+///
+/// ---
+///
+/// **CPU 拓扑提示**
+///
+/// 报告在线 CPU 跨越的封装、簇与算力等级数量。重型屏障会中断所有运行本进程的 CPU，因此在多插槽机器以及
+/// 异构 (big.LITTLE) 手机和开发板上，其延迟波动更大。
+///
+/// 每次调用都会读取 `/sys/devices/system/cpu`，因此能跟随 CPU 热插拔；[`stats()`] 则报告其首次调用时读取的提示。
+/// 未启用 `std` 特性、非 Linux 平台或 sysfs 不可用时返回 `None`。
+pub fn cpu_topology_hint() -> Option<TopologyHint> {
+    topology::detect()
+}
+
+/// **Kernel Version**
+///
+/// The running Linux kernel's `major.minor.patch`, parsed from `uname(2)`. `None` on other
+/// platforms or if the release string is not in the usual `x.y[.z]` form.
+///
+/// ---
+///
+/// **内核版本**
+///
+/// 从 `uname(2)` 解析出的当前 Linux 内核 `major.minor.patch`。在其他平台上，或发行字符串不是常见的
+/// `x.y[.z]` 形式时返回 `None`。
+pub fn kernel_version() -> Option<KernelVersion> {
+    kernel::detect()
+}
+
+/// **Running Under qemu-user**
+///
+/// Best-effort check for `qemu-user` emulation, looking for `QEMU_*` environment variables and a
+/// `qemu-<arch>` binary in `/proc/self/maps`. Newer qemu versions configured only through binfmt
+/// leave neither, so `false` is not proof of real hardware. Checked on the first call and cached,
+/// so variables set later are not seen. Always `false` without the `std` feature or off Linux.
+///
+/// The backend is not changed: qemu-user forwards `membarrier` to the host kernel, whose IPIs reach
+/// the host threads running the emulated ones, so accelerated barriers stay correct. Use this to
+/// explain timing or CPU-count differences in CI, not to pick a barrier.
+///
+/// ---
+///
+/// **是否运行在 qemu-user 下**
+///
+/// 尽力检测 `qemu-user` 模拟：查找 `QEMU_*` 环境变量以及 `/proc/self/maps` 中的 `qemu-<arch>` 二进制。
+/// 仅通过 binfmt 配置的新版 qemu 两者都不会留下，因此 `false` 并不能证明运行在真实硬件上。仅在首次调用时检查并缓存，
+/// 因此之后设置的变量不会被看到。未启用 `std` 特性或非 Linux 平台时始终为 `false`。
+///
+/// 后端不会改变：qemu-user 会将 `membarrier` 转发给宿主内核，其 IPI 会到达运行被模拟线程的宿主线程，
+/// 因此加速屏障仍然正确。此函数用于解释 CI 中的时序或 CPU 数量差异，而非用于选择屏障。
+pub fn running_under_qemu_user() -> bool {
+    emulation::detect()
+}
+
+/// **Reader CPU Mask** (feature `std`, Linux 5.10+)
+///
+/// Restricts heavy barriers to the CPUs listed in `mask`, so a process whose readers are pinned to
+/// a few isolated cores stops interrupting every other CPU it runs on. Each heavy barrier then
+/// issues one `MEMBARRIER_CMD_PRIVATE_EXPEDITED_RSEQ` call per listed CPU instead of one broadcast;
+/// if any of them fails, that barrier broadcasts instead. An empty `mask` restores broadcasting.
+///
+/// # Safety
+///
+/// Unless `mask` is empty, every thread that calls [`light_barrier()`], directly or through a
+/// structure such as [`SwmrCell`], must only ever run on a listed CPU (e.g. pinned with
+/// `sched_setaffinity` before its first read) for as long as the mask is set. A reader scheduled
+/// on an unlisted CPU is not ordered by heavy barriers and may copy a slot while the writer is
+/// writing it, a data race. An empty `mask` is always sound.
+///
+/// # Errors
+///
+/// Leaves the current mask unchanged and returns [`BarrierError::Unsupported`] in fallback mode,
+/// [`BarrierError::KernelTooOld`] without the RSEQ command, [`BarrierError::RegistrationFailed`]
+/// if registering for it fails, or [`BarrierError::SyscallFailed`] for a CPU that does not exist
+/// (`EINVAL`) or rejects the validation barrier.
+///
+/// ---
+///
+/// **读者 CPU 掩码**（`std` 特性，Linux 5.10+）
+///
+/// 将重型屏障限制在 `mask` 列出的 CPU 上，使读者被绑定到少数隔离核心的进程不再中断它运行的其他所有 CPU。
+/// 此后每次重型屏障对每个列出的 CPU 发起一次 `MEMBARRIER_CMD_PRIVATE_EXPEDITED_RSEQ` 调用，而不是一次广播；
+/// 任一调用失败时，该次屏障改为广播。空 `mask` 恢复广播。
+///
+/// # 安全性
+///
+/// 除非 `mask` 为空，在掩码生效期间，每个直接或通过 [`SwmrCell`] 等数据结构调用 [`light_barrier()`] 的线程都必须只在
+/// 列出的 CPU 上运行（例如在首次读取之前通过 `sched_setaffinity` 绑定）。被调度到未列出 CPU 上的读者不受重型屏障约束，
+/// 可能在写者写入某个槽时复制该槽，构成数据竞争。空 `mask` 始终健全。
+///
+/// 失败时保持当前掩码不变：回退模式下返回 [`BarrierError::Unsupported`]；内核不支持 RSEQ 命令时返回
+/// [`BarrierError::KernelTooOld`]；注册失败时返回 [`BarrierError::RegistrationFailed`]；CPU 不存在（`EINVAL`）
+/// 或拒绝验证屏障时返回 [`BarrierError::SyscallFailed`]。
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+pub unsafe fn set_reader_cpu_mask(mask: &[usize]) -> Result<(), BarrierError> {
+    sys::set_reader_cpu_mask_impl(mask)
+}
+
+/// **Set Fallback Ordering**
+///
+/// Chooses the fence that [`heavy_barrier()`] and [`light_barrier()`] issue while no OS barrier is
+/// active (the fallback backend, Linux without membarrier, Windows without
+/// `FlushProcessWriteBuffers`, Loom). Accelerated barriers, ThreadSanitizer and WASI builds are
+/// unaffected. Set it once before sharing data between threads.
+///
+/// # Safety
+///
+/// [`FallbackOrdering::SeqCst`] is always sound. [`FallbackOrdering::AcqRel`] breaks the
+/// store-buffering pattern (store, barrier, load on both sides) that [`DoubleBuffer`], [`SwmrCell`],
+/// [`RcuDomain`] and `SwmrArcCell` use to keep readers out of the slot being written; with it, a
+/// reader can copy a slot while the writer overwrites it. Pass `AcqRel` only if, for as long as it
+/// is set, every heavy/light barrier pair in the process (including those inside this crate's
+/// structures and [`SystemBarrier`]) is message passing, such as [`AppendLog`] or a flag guarding
+/// data.
+///
+/// ---
+///
+/// **设置回退顺序**
+///
+/// 选择没有 OS 屏障时（回退后端、无 membarrier 的 Linux、无 `FlushProcessWriteBuffers` 的 Windows、Loom）
+/// [`heavy_barrier()`] 与 [`light_barrier()`] 发出的栅栏。加速屏障、ThreadSanitizer 与 WASI 构建不受影响。
+/// 应在线程间共享数据之前设置一次。
+///
+/// # 安全性
+///
+/// [`FallbackOrdering::SeqCst`] 始终健全。[`FallbackOrdering::AcqRel`] 会破坏存储缓冲模式（两侧均为存储、屏障、加载），
+/// 而 [`DoubleBuffer`]、[`SwmrCell`]、[`RcuDomain`] 与 `SwmrArcCell` 依靠该模式让读者远离正在写入的槽；选择它后，读者可能在
+/// 写者覆盖某个槽时复制该槽。仅当在其生效期间，进程中每一对重型/轻型屏障（包括本 crate 数据结构与 [`SystemBarrier`]
+/// 内部的屏障）都是消息传递时（例如 [`AppendLog`] 或保护数据的标志），才可传入 `AcqRel`。
+pub unsafe fn set_fallback_ordering(ordering: FallbackOrdering) {
+    sys::set_fallback_ordering_impl(ordering);
+}
+
+/// Returns the ordering chosen with [`set_fallback_ordering`] (`SeqCst` by default).
+/// 返回通过 [`set_fallback_ordering`] 选择的顺序（默认为 `SeqCst`）。
+#[inline]
+pub fn fallback_ordering() -> FallbackOrdering {
+    sys::fallback_ordering_impl()
+}
+
+/// **Compile-Time Platform**
+///
+/// What this build can use, decided by `cfg` alone, so it is usable in `const` contexts and build
+/// reports. A Linux build reports `can_accelerate: true` even if the running kernel lacks
+/// membarrier; WASI, Loom, ThreadSanitizer and other targets report `false`. Use
+/// [`stats()`](crate::stats) for what the running system actually selected.
+///
+/// ---
+///
+/// **编译期平台**
+///
+/// 本构建可使用的机制，仅由 `cfg` 决定，因此可用于 `const` 上下文与构建报告。Linux 构建即使运行中的内核不支持
+/// membarrier 也报告 `can_accelerate: true`；WASI、Loom、ThreadSanitizer 及其他目标报告 `false`。
+/// 运行中的系统实际选择的机制请使用 [`stats()`](crate::stats)。
+pub const PLATFORM: PlatformInfo = sys::PLATFORM_IMPL;
+
+/// **Active Backend**
+///
+/// Returns the mechanism currently servicing the barriers.
+///
+/// ---
+///
+/// **当前后端**
+///
+/// 返回当前为屏障提供服务的机制。
+#[inline]
+pub fn active_backend() -> Backend {
+    sys::backend_impl()
+}
+
+/// **Barrier Statistics**
+///
+/// Returns a snapshot of the runtime detection results, e.g. for `println!("{:?}", stats())` in bug reports.
+///
+/// Builds with `-Zsanitizer=thread` report [`Backend::ThreadSanitizer`]: both barriers are real fences
+/// so TSan can see them, which differs from what a production build of the same target selects.
+/// With the `tsan-annotate` feature the production backend is kept and annotated for TSan instead.
+///
+/// ---
+///
+/// **屏障统计**
+///
+/// 返回运行时检测结果的快照，例如在问题报告中使用 `println!("{:?}", stats())`。
+///
+/// 使用 `-Zsanitizer=thread` 构建时报告 [`Backend::ThreadSanitizer`]：两种屏障均为真正的 fence 以便 TSan 识别，
+/// 这与同一目标的生产构建所选后端不同。启用 `tsan-annotate` 特性时则保留生产后端，并为 TSan 添加注解。
+pub fn stats() -> BarrierStats {
+    let backend = sys::backend_impl();
+    BarrierStats {
+        backend,
+        init_reason: sys::init_reason_impl(),
+        light: sys::light_fence_impl(),
+        heavy: backend.heavy_cost(),
+        registration_errno: sys::registration_errno_impl(),
+        validation: sys::validation_impl(),
+        sync_core: sys::sync_core_available_impl(),
+        topology: topology::cached(),
+        qemu_user: emulation::detect(),
+        slowest_heavy_ns: {
+            #[cfg(all(
+                feature = "std",
+                target_os = "linux",
+                not(feature = "loom"),
+                not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+            ))]
+            {
+                watchdog::slowest_ns()
+            }
+            #[cfg(not(all(
+                feature = "std",
+                target_os = "linux",
+                not(feature = "loom"),
+                not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+            )))]
+            {
+                None
+            }
+        },
+    }
+}
+
+/// **Runtime Telemetry**
+///
+/// One call for a metrics scrape: barrier counts (feature `telemetry`), the slowest recorded OS
+/// barrier (feature `latency`, or `heavy_barrier_with_deadline()`), whether barriers are degraded,
+/// and the [`barrier_generation()`]. Fields whose source is compiled out read as 0, so without
+/// those features this is a few plain loads.
+///
+/// The snapshot starts with a [`light_barrier()`], so it includes every barrier that completed
+/// before a heavy barrier the caller could already observe. The counters are read so that
+/// `fallback_count <= heavy_count <= generation` always holds; barriers racing with the call may
+/// be counted in some fields but not yet in later ones.
+///
+/// **Cost with `telemetry`**: every light barrier adds a `Relaxed` increment of one shared counter,
+/// which readers on different cores contend for. Enable it where the scrape is worth that, not on
+/// the hottest read paths.
+///
+/// ---
+///
+/// **运行时遥测**
+///
+/// 供指标采集的单次调用：屏障计数（`telemetry` 特性）、记录到的最慢 OS 屏障（`latency` 特性，或
+/// `heavy_barrier_with_deadline()`）、屏障是否降级，以及 [`barrier_generation()`]。来源被编译移除的字段读作 0，
+/// 因此未启用这些特性时只是几次普通加载。
+///
+/// 快照以一次 [`light_barrier()`] 开始，因此包含在调用方已可观察到的某次重型屏障之前完成的所有屏障。计数器的读取顺序
+/// 保证 `fallback_count <= heavy_count <= generation` 始终成立；与调用并发的屏障可能已计入某些字段，但尚未计入后读的字段。
+///
+/// **启用 `telemetry` 的开销**：每次轻型屏障增加一次对共享计数器的 `Relaxed` 递增，不同核心上的读者会争用它。
+/// 仅在值得为采集付出该代价的场景启用，不要用于最热的读路径。
+pub fn runtime_telemetry() -> RuntimeTelemetry {
+    light_barrier();
+    #[cfg(feature = "telemetry")]
+    let (heavy_count, light_count, fallback_count) = telemetry::counts();
+    #[cfg(not(feature = "telemetry"))]
+    let (heavy_count, light_count, fallback_count) = (0, 0, 0);
+
+    #[cfg(all(
+        feature = "std",
+        target_os = "linux",
+        not(feature = "loom"),
+        not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+    ))]
+    let max_latency_ns = {
+        let slowest = watchdog::slowest_ns().unwrap_or(0);
+        #[cfg(feature = "latency")]
+        let slowest = slowest.max(latency::max_ns());
+        slowest
+    };
+    #[cfg(not(all(
+        feature = "std",
+        target_os = "linux",
+        not(feature = "loom"),
+        not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+    )))]
+    let max_latency_ns = 0;
+
+    let reason = sys::init_reason_impl();
+    let degraded = !matches!(reason, InitReason::Pending | InitReason::Unsupported)
+        && (!sys::is_accelerated_impl() || fallback_count > 0);
+
+    RuntimeTelemetry {
+        heavy_count,
+        light_count,
+        fallback_count,
+        max_latency_ns,
+        degraded,
+        // Read last: every heavy barrier counted above bumped it first.
+        // 最后读取：上面计入的每次重型屏障都先递增了它。
+        #[cfg(target_has_atomic = "64")]
+        generation: barrier_generation(),
+        #[cfg(not(target_has_atomic = "64"))]
+        generation: 0,
+    }
+}
+
+/// **Write Stats**
+///
+/// Writes a one-line, human-readable summary of `stats` using only `core::fmt`, so `no_std`
+/// targets can log it (for example over a UART) without `alloc`. Uses the stable names from the
+/// `as_str()` methods; optional fields appear only when set:
+///
+/// ```text
+/// backend=linux-private-expedited accelerated=true reason=accelerated light=compiler heavy=ipi validation=passed cpus=8
+/// ```
+///
+/// For machine-readable output use [`BarrierStats::write_json`].
+///
+/// ---
+///
+/// **写出统计**
+///
+/// 仅使用 `core::fmt` 写出 `stats` 的单行可读摘要，使 `no_std` 目标无需 `alloc` 即可记录（例如通过 UART）。
+/// 使用 `as_str()` 方法的稳定名称；可选字段仅在有值时出现。机器可读输出请使用 [`BarrierStats::write_json`]。
+pub fn write_stats(stats: &BarrierStats, f: &mut impl core::fmt::Write) -> core::fmt::Result {
+    write!(
+        f,
+        "backend={} accelerated={} reason={} light={} heavy={} validation={}",
+        stats.backend.as_str(),
+        stats.backend.is_accelerated(),
+        stats.init_reason.as_str(),
+        stats.light.as_str(),
+        stats.heavy.as_str(),
+        stats.validation.as_str(),
+    )?;
+    if let Validation::Failed(errno) = stats.validation {
+        write!(f, " validation_errno={errno}")?;
+    }
+    if let Some(errno) = stats.registration_errno {
+        write!(f, " registration_errno={errno}")?;
+    }
+    if stats.sync_core {
+        f.write_str(" sync_core")?;
+    }
+    if let Some(topology) = stats.topology {
+        write!(f, " cpus={}", topology.cpus)?;
+    }
+    if stats.qemu_user {
+        f.write_str(" qemu_user")?;
+    }
+    if let Some(ns) = stats.slowest_heavy_ns {
+        write!(f, " slowest_heavy_ns={ns}")?;
+    }
+    Ok(())
+}
+
+/// **Register Current Thread**
+///
+/// Performs any per-thread setup the barriers need and returns the outcome.
+///
+/// Current backends register process-wide, so this is a no-op returning
+/// [`ThreadRegistration::NotRequired`]. It reserves the API surface for per-thread mechanisms
+/// (e.g. RSEQ-targeted barriers); runtimes spawning worker threads can call it at thread start today.
+/// Safe to call any number of times on the same thread.
+///
+/// ---
+///
+/// **注册当前线程**
+///
+/// 执行屏障所需的逐线程设置并返回结果。
+///
+/// 当前所有后端均为进程级注册，因此这是一个返回 [`ThreadRegistration::NotRequired`] 的空操作。
+/// 它为逐线程机制（例如基于 RSEQ 的定向屏障）预留了 API；创建工作线程的运行时现在就可以在线程启动时调用它。
+/// 同一线程可多次调用。
+#[inline]
+pub fn register_current_thread() -> ThreadRegistration {
+    ThreadRegistration::NotRequired
+}
@@ -0,0 +1,64 @@
+/// **Publish Macro**
+///
+/// Runs each store statement in order, then issues [`heavy_barrier()`](crate::heavy_barrier) once
+/// after all of them. Stores after the macro (e.g. a ready flag) are ordered after every store inside it.
+///
+/// ```
+/// use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering::Relaxed};
+/// use swmr_barrier::swmr_publish;
+///
+/// let (data, aux, ready) = (AtomicUsize::new(0), AtomicUsize::new(0), AtomicBool::new(false));
+///
+/// swmr_publish!(data.store(1, Relaxed); aux.store(2, Relaxed));
+/// // Expands to:
+/// // data.store(1, Relaxed);
+/// // aux.store(2, Relaxed);
+/// // swmr_barrier::heavy_barrier();
+/// ready.store(true, Relaxed);
+/// ```
+///
+/// ---
+///
+/// **发布宏**
+///
+/// 按顺序执行每条存储语句，并在全部执行后调用一次 [`heavy_barrier()`](crate::heavy_barrier)。
+/// 宏之后的存储（例如就绪标志）都排在宏内每条存储之后。
+#[macro_export]
+macro_rules! swmr_publish {
+    ($($store:stmt);+ $(;)?) => {{
+        $($store;)+
+        $crate::heavy_barrier();
+    }};
+}
+
+/// **Consume Macro**
+///
+/// Issues [`light_barrier()`](crate::light_barrier), then evaluates each load expression in order
+/// and returns their results as a tuple (a 1-tuple for a single load).
+///
+/// ```
+/// use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering::Relaxed};
+/// use swmr_barrier::swmr_consume;
+///
+/// let (data, aux, ready) = (AtomicUsize::new(1), AtomicUsize::new(2), AtomicBool::new(true));
+///
+/// if ready.load(Relaxed) {
+///     let (d, a) = swmr_consume!(data.load(Relaxed), aux.load(Relaxed));
+///     // Expands to:
+///     // { swmr_barrier::light_barrier(); (data.load(Relaxed), aux.load(Relaxed),) }
+///     assert_eq!((d, a), (1, 2));
+/// }
+/// ```
+///
+/// ---
+///
+/// **消费宏**
+///
+/// 调用 [`light_barrier()`](crate::light_barrier)，然后按顺序求值每个加载表达式，并以元组形式返回结果（单个加载时为一元组）。
+#[macro_export]
+macro_rules! swmr_consume {
+    ($($load:expr),+ $(,)?) => {{
+        $crate::light_barrier();
+        ($($load,)+)
+    }};
+}
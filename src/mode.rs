@@ -0,0 +1,118 @@
+//! **Backend Override** (Linux, feature `std`)
+//!
+//! Lets operators pin the membarrier command at runtime through the [`MODE_ENV_VAR`] environment
+//! variable, e.g. to work around a kernel bug in one command without recompiling. Detection reads
+//! it on every run (the pre-`main` constructor, [`init()`](crate::init), [`redetect()`](crate::redetect))
+//! and reports a value it cannot honor through the warning hook.
+//!
+//! ---
+//!
+//! **后端覆盖**（Linux，`std` 特性）
+//!
+//! 允许运维人员通过 [`MODE_ENV_VAR`] 环境变量在运行时固定 membarrier 命令，例如在不重新编译的情况下绕开某条命令的
+//! 内核缺陷。每次检测（`main` 之前的构造函数、[`init()`](crate::init)、[`redetect()`](crate::redetect)）都会读取它，
+//! 并通过警告钩子报告无法满足的取值。
+
+use std::string::String;
+use std::sync::{PoisonError, RwLock};
+
+/// Environment variable read by detection: `auto`, `private`, `shared` or `fallback`.
+/// 检测时读取的环境变量：`auto`、`private`、`shared` 或 `fallback`。
+pub const MODE_ENV_VAR: &str = "SWMR_BARRIER_MODE";
+
+/// **Barrier Mode**
+///
+/// The backend requested through [`MODE_ENV_VAR`]. Values are matched case-insensitively.
+///
+/// ---
+///
+/// **屏障模式**
+///
+/// 通过 [`MODE_ENV_VAR`] 请求的后端。取值匹配不区分大小写。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BarrierMode {
+    /// Normal detection: PRIVATE_EXPEDITED, then SHARED, then the fence fallback (`auto`, or unset).
+    /// 正常检测：依次尝试 PRIVATE_EXPEDITED、SHARED 与栅栏回退（`auto` 或未设置）。
+    #[default]
+    Auto,
+    /// Only `MEMBARRIER_CMD_PRIVATE_EXPEDITED` (`private`).
+    /// 仅使用 `MEMBARRIER_CMD_PRIVATE_EXPEDITED`（`private`）。
+    Private,
+    /// Only `MEMBARRIER_CMD_SHARED`, skipping registration (`shared`).
+    /// 仅使用 `MEMBARRIER_CMD_SHARED`，跳过注册（`shared`）。
+    Shared,
+    /// Never use membarrier; both sides fence (`fallback`).
+    /// 从不使用 membarrier；两侧都使用栅栏（`fallback`）。
+    Fallback,
+}
+
+impl BarrierMode {
+    /// Parses an environment value, ignoring case and surrounding whitespace.
+    /// 解析环境变量取值，忽略大小写与首尾空白。
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        [BarrierMode::Auto, BarrierMode::Private, BarrierMode::Shared, BarrierMode::Fallback]
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(value))
+    }
+
+    /// The environment value selecting this mode.
+    /// 选择该模式的环境变量取值。
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            BarrierMode::Auto => "auto",
+            BarrierMode::Private => "private",
+            BarrierMode::Shared => "shared",
+            BarrierMode::Fallback => "fallback",
+        }
+    }
+}
+
+/// **Mode Warning**
+///
+/// Why detection could not honor [`MODE_ENV_VAR`]. In both cases it continues as for
+/// [`BarrierMode::Auto`].
+///
+/// ---
+///
+/// **模式警告**
+///
+/// 检测无法满足 [`MODE_ENV_VAR`] 的原因。两种情况下检测都会按 [`BarrierMode::Auto`] 继续。
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ModeWarning {
+    /// The value is not one of the recognized modes.
+    /// 该取值不是可识别的模式。
+    Unrecognized(String),
+    /// The kernel does not advertise the requested command, or rejected it.
+    /// 内核未声明支持所请求的命令，或拒绝了它。
+    Unavailable(BarrierMode),
+}
+
+static WARNING_HOOK: RwLock<Option<fn(&ModeWarning)>> = RwLock::new(None);
+
+pub(crate) fn set_warning_hook(hook: Option<fn(&ModeWarning)>) {
+    *WARNING_HOOK.write().unwrap_or_else(PoisonError::into_inner) = hook;
+}
+
+/// Reports `warning` to the hook and, with `tracing`, as a `warn!` event.
+/// 将 `warning` 报告给钩子，并在启用 `tracing` 时作为 `warn!` 事件发出。
+pub(crate) fn warn(warning: ModeWarning) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(env = MODE_ENV_VAR, ?warning, "swmr-barrier: requested barrier mode not honored");
+    if let Some(hook) = *WARNING_HOOK.read().unwrap_or_else(PoisonError::into_inner) {
+        hook(&warning);
+    }
+}
+
+/// The mode requested by the environment; an unset or non-UTF-8 variable means `Auto`.
+/// 环境所请求的模式；变量未设置或非 UTF-8 时为 `Auto`。
+pub(crate) fn requested() -> BarrierMode {
+    let Ok(value) = std::env::var(MODE_ENV_VAR) else {
+        return BarrierMode::Auto;
+    };
+    BarrierMode::parse(&value).unwrap_or_else(|| {
+        warn(ModeWarning::Unrecognized(value));
+        BarrierMode::Auto
+    })
+}
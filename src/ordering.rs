@@ -0,0 +1,43 @@
+//! **Orderings for SWMR Call Sites**
+//!
+//! The [`Ordering`](core::sync::atomic::Ordering) variants, re-exported so a call site can write
+//! `use swmr_barrier::ordering::*;` instead of importing the enum.
+//!
+//! Loads and stores paired with the barriers should use [`Relaxed`]: the writer's
+//! [`heavy_barrier()`](crate::heavy_barrier) and the reader's [`light_barrier()`](crate::light_barrier)
+//! already order them, and on the accelerated path `SeqCst` turns the free reader side back into a
+//! full fence. [`Acquire`], [`Release`] and [`SeqCst`] remain for atomics that are not covered by a
+//! barrier.
+//!
+//! ```
+//! use std::sync::atomic::{AtomicBool, AtomicUsize};
+//! use swmr_barrier::ordering::Relaxed;
+//! use swmr_barrier::{heavy_barrier, light_barrier};
+//!
+//! static DATA: AtomicUsize = AtomicUsize::new(0);
+//! static READY: AtomicBool = AtomicBool::new(false);
+//!
+//! // Writer: write the data, order it with the heavy barrier, then publish.
+//! DATA.store(42, Relaxed);
+//! heavy_barrier();
+//! READY.store(true, Relaxed);
+//!
+//! // Reader: observe the flag, then pair it with the light barrier before reading the data.
+//! if READY.load(Relaxed) {
+//!     light_barrier();
+//!     assert_eq!(DATA.load(Relaxed), 42);
+//! }
+//! ```
+//!
+//! ---
+//!
+//! **SWMR 调用点的内存顺序**
+//!
+//! 重新导出 [`Ordering`](core::sync::atomic::Ordering) 的各个变体，调用点可以写
+//! `use swmr_barrier::ordering::*;` 而无需导入该枚举。
+//!
+//! 与屏障配对的加载和存储应使用 [`Relaxed`]：写者的 [`heavy_barrier()`](crate::heavy_barrier) 与读者的
+//! [`light_barrier()`](crate::light_barrier) 已对其排序，而在加速路径上 `SeqCst` 会把零开销的读者侧重新变成完整屏障。
+//! [`Acquire`]、[`Release`] 与 [`SeqCst`] 保留给不受屏障覆盖的原子变量。
+
+pub use core::sync::atomic::Ordering::{Acquire, Relaxed, Release, SeqCst};
@@ -0,0 +1,244 @@
+use crate::barrier::{AsymmetricBarrier, SystemBarrier};
+use crate::slot_ring::Unlock;
+use crate::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use crate::sync::{UnsafeCell, spin_loop};
+use core::fmt;
+use core::mem::MaybeUninit;
+
+/// **Overwrite Ring**
+///
+/// A latest-only single-writer multi-reader ring for samples readers may miss, such as telemetry.
+/// The writer never waits: [`push`](Self::push) overwrites the oldest slot. Readers keep a sequence
+/// cursor and call [`read_available`](Self::read_available); a reader that fell behind jumps to the
+/// oldest sample still held and is told how many it missed.
+///
+/// `N` slots hold the latest `N - 1` samples: the slot after the newest is the one the next push
+/// rewrites, so its sample is no longer guaranteed intact.
+///
+/// * **Reader**: One `light()` per batch, plus one per sample to check it was not overwritten
+///   while being copied.
+/// * **Writer**: One `heavy()` per [`push`](Self::push).
+///
+/// ---
+///
+/// **覆盖环**
+///
+/// 只保留最新数据的单写多读环，用于读者可以错过的样本，例如遥测。写者从不等待：[`push`](Self::push) 覆盖最旧的槽。
+/// 读者持有序号游标并调用 [`read_available`](Self::read_available)；落后的读者跳到仍保留的最旧样本，并得知错过了多少个。
+///
+/// `N` 个槽保留最新的 `N - 1` 个样本：最新样本之后的槽正是下一次推入要重写的槽，其样本不再保证完整。
+///
+/// * **读者**：每批一次 `light()`，另外每个样本一次，用于确认复制期间未被覆盖。
+/// * **写者**：每次 [`push`](Self::push) 一次 `heavy()`。
+pub struct OverwriteRing<T: Copy, const N: usize, B: AsymmetricBarrier = SystemBarrier> {
+    slots: [UnsafeCell<MaybeUninit<T>>; N],
+    // Number of samples pushed; sequences below it are published.
+    // 已推入的样本数；低于它的序号均已发布。
+    head: AtomicU64,
+    // Oldest sequence no push has started overwriting, stored before the `heavy()` that precedes
+    // the next slot write.
+    // 尚未被任何推入开始覆盖的最旧序号；在下一次槽写入之前的 `heavy()` 之前存储。
+    oldest: AtomicU64,
+    writing: AtomicBool,
+    barrier: B,
+}
+
+// SAFETY: Readers copy `T` out on their own threads (`T: Send`); a copy that raced with the writer
+// is discarded before it is used. `Send` is derived and needs only `T: Send`.
+// 安全性：读者在自身线程上复制出 `T`（`T: Send`）；与写者竞争的副本在使用前即被丢弃。`Send` 自动推导，只需 `T: Send`。
+unsafe impl<T: Copy + Send, const N: usize, B: AsymmetricBarrier + Sync> Sync for OverwriteRing<T, N, B> {}
+
+impl<T: Copy, const N: usize> OverwriteRing<T, N> {
+    /// Creates an empty ring.
+    /// 创建空环。
+    pub fn new() -> Self {
+        Self::with_barrier(SystemBarrier)
+    }
+}
+
+impl<T: Copy, const N: usize> Default for OverwriteRing<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy, const N: usize, B: AsymmetricBarrier> OverwriteRing<T, N, B> {
+    /// Creates an empty ring using a custom barrier.
+    /// 使用自定义屏障创建空环。
+    pub fn with_barrier(barrier: B) -> Self {
+        const { assert!(N >= 2, "an overwrite ring needs at least two slots") };
+        Self {
+            slots: core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit())),
+            head: AtomicU64::new(0),
+            oldest: AtomicU64::new(0),
+            writing: AtomicBool::new(false),
+            barrier,
+        }
+    }
+
+    /// Number of samples a reader can still catch up on: `N - 1`.
+    /// 读者仍可追赶的样本数：`N - 1`。
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N - 1
+    }
+
+    /// Number of samples pushed so far, i.e. the sequence the next push gets.
+    /// 迄今已推入的样本数，即下一次推入获得的序号。
+    #[inline]
+    pub fn pushed(&self) -> u64 {
+        let head = self.head.load(Ordering::Relaxed);
+        // Pairs with the writer's `heavy()` before it advances the head.
+        // 与写者推进头部前的 `heavy()` 配对。
+        self.barrier.light();
+        head
+    }
+
+    /// **Push**
+    ///
+    /// Stores `value` under the next sequence and publishes it, overwriting the oldest sample once
+    /// the ring is full. Concurrent writers are serialized.
+    ///
+    /// ---
+    ///
+    /// **推入**
+    ///
+    /// 以下一个序号存储 `value` 并发布；环已满时覆盖最旧的样本。并发写者会被串行化。
+    pub fn push(&self, value: T) {
+        while self
+            .writing
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            spin_loop();
+        }
+        // Release the writer flag even if `heavy()` panics. The sample is then not published, and the
+        // next push rewrites its slot under the same sequence.
+        // 即使 `heavy()` panic 也释放写者标志。此时样本未被发布，下一次推入会以相同序号重写其槽。
+        let _unlock = Unlock(&self.writing);
+
+        let seq = self.head.load(Ordering::Relaxed);
+        // SAFETY: We hold the writer flag. Readers may be copying this slot's previous sample; the
+        // `oldest` they check afterwards already excludes it (stored before the previous `heavy()`).
+        // 安全性：我们持有写者标志。读者可能正在复制该槽的上一个样本；他们随后检查的 `oldest` 已将其排除
+        // （在上一次 `heavy()` 之前存储）。
+        self.slots[Self::index(seq)].get_mut().with(|ptr| unsafe { (*ptr).write(value) });
+        // The next push rewrites the slot of `seq + 1 - (N - 1)`; retire it before this `heavy()`.
+        // 下一次推入会重写 `seq + 1 - (N - 1)` 的槽；在本次 `heavy()` 之前将其淘汰。
+        self.oldest.store((seq + 2).saturating_sub(N as u64), Ordering::Relaxed);
+        self.barrier.heavy();
+        self.head.store(seq + 1, Ordering::Relaxed);
+    }
+
+    /// **Read Available**
+    ///
+    /// Returns the samples published after `cursor` as `(sequence, value)` pairs, advancing
+    /// `cursor` past each one. If `cursor` fell behind the oldest sample still held (before or
+    /// during the iteration), it jumps forward and [`RingSamples::gap`] counts the skipped samples.
+    /// Start a reader with a cursor of `0`, or [`pushed`](Self::pushed) to skip the backlog.
+    ///
+    /// ---
+    ///
+    /// **读取可用样本**
+    ///
+    /// 以 `(序号, 值)` 对返回 `cursor` 之后发布的样本，并使 `cursor` 依次越过它们。若 `cursor` 落后于仍保留的最旧样本
+    /// （迭代之前或期间），它会向前跳跃，并由 [`RingSamples::gap`] 统计跳过的样本数。读者可从游标 `0` 开始，
+    /// 或从 [`pushed`](Self::pushed) 开始以跳过积压。
+    pub fn read_available<'a>(&'a self, cursor: &'a mut u64) -> RingSamples<'a, T, N, B> {
+        let end = self.head.load(Ordering::Relaxed);
+        // Pairs with the writer's `heavy()` before it advances the head.
+        // 与写者推进头部前的 `heavy()` 配对。
+        self.barrier.light();
+        let mut samples = RingSamples { ring: self, cursor, end, gap: 0 };
+        samples.skip_to(self.oldest.load(Ordering::Relaxed));
+        samples
+    }
+
+    #[inline]
+    fn index(seq: u64) -> usize {
+        (seq % N as u64) as usize
+    }
+}
+
+impl<T: Copy, const N: usize, B: AsymmetricBarrier> fmt::Debug for OverwriteRing<T, N, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OverwriteRing")
+            .field("pushed", &self.head.load(Ordering::Relaxed))
+            .field("capacity", &(N - 1))
+            .finish()
+    }
+}
+
+/// **Ring Samples**
+///
+/// Iterator returned by [`OverwriteRing::read_available`].
+///
+/// ---
+///
+/// **环样本**
+///
+/// 由 [`OverwriteRing::read_available`] 返回的迭代器。
+pub struct RingSamples<'a, T: Copy, const N: usize, B: AsymmetricBarrier = SystemBarrier> {
+    ring: &'a OverwriteRing<T, N, B>,
+    cursor: &'a mut u64,
+    end: u64,
+    gap: u64,
+}
+
+impl<T: Copy, const N: usize, B: AsymmetricBarrier> RingSamples<'_, T, N, B> {
+    /// Number of samples skipped so far because they were overwritten before this reader got to
+    /// them. Check it after iterating.
+    /// 迄今因在本读者读到之前已被覆盖而跳过的样本数。应在迭代结束后检查。
+    #[inline]
+    pub fn gap(&self) -> u64 {
+        self.gap
+    }
+
+    #[inline]
+    fn skip_to(&mut self, oldest: u64) {
+        if *self.cursor < oldest {
+            self.gap += oldest - *self.cursor;
+            *self.cursor = oldest;
+        }
+    }
+}
+
+impl<T: Copy, const N: usize, B: AsymmetricBarrier> Iterator for RingSamples<'_, T, N, B> {
+    type Item = (u64, T);
+
+    fn next(&mut self) -> Option<(u64, T)> {
+        while *self.cursor < self.end {
+            let seq = *self.cursor;
+            let ptr = self.ring.slots[OverwriteRing::<T, N, B>::index(seq)].get();
+            // SAFETY: `seq` is published, so the slot was written. The copy may race with a push
+            // rewriting it; it stays `MaybeUninit` until `oldest` below shows it did not.
+            // 安全性：`seq` 已发布，因此该槽已被写入。复制可能与重写该槽的推入竞争；在下方 `oldest` 证明未发生竞争之前，
+            // 它一直保持为 `MaybeUninit`。
+            let copy = unsafe { core::ptr::read_volatile(ptr.deref()) };
+            // Pairs with the `heavy()` after the writer retires a sequence: a copy that saw any byte
+            // of the overwrite also sees the retirement.
+            // 与写者淘汰序号后的 `heavy()` 配对：看到覆盖写入任一字节的复制也会看到该淘汰。
+            self.ring.barrier.light();
+            let oldest = self.ring.oldest.load(Ordering::Relaxed);
+            if seq < oldest {
+                self.skip_to(oldest);
+                continue;
+            }
+            *self.cursor = seq + 1;
+            // SAFETY: Not overwritten while copying, so this is the sample pushed as `seq`.
+            // 安全性：复制期间未被覆盖，因此这正是以 `seq` 推入的样本。
+            return Some((seq, unsafe { copy.assume_init() }));
+        }
+        None
+    }
+}
+
+impl<T: Copy, const N: usize, B: AsymmetricBarrier> fmt::Debug for RingSamples<'_, T, N, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RingSamples")
+            .field("cursor", &*self.cursor)
+            .field("end", &self.end)
+            .field("gap", &self.gap)
+            .finish()
+    }
+}
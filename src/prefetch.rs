@@ -0,0 +1,41 @@
+/// **Prefetch for Read**
+///
+/// Hints the CPU to start loading the cache line at `ptr` for an upcoming read: `prefetcht0` on
+/// x86/x86_64 (SSE), `prfm pldl1keep` on aarch64, and nothing elsewhere. A prefetch never faults,
+/// so any pointer is accepted, including null or dangling ones; it has no effect on ordering.
+///
+/// With the `prefetch` feature, the slot-ring readers ([`DoubleBuffer`](crate::DoubleBuffer),
+/// [`SwmrCell`](crate::SwmrCell)) call it on the active slot right after loading its index, so the
+/// copy's cache miss overlaps the pin and the light barrier.
+///
+/// ---
+///
+/// **读预取**
+///
+/// 提示 CPU 为即将发生的读取开始加载 `ptr` 所在的缓存行：x86/x86_64（SSE）上为 `prefetcht0`，aarch64 上为
+/// `prfm pldl1keep`，其他架构上不做任何事。预取从不触发故障，因此接受任何指针，包括空指针或悬垂指针；它不影响内存排序。
+///
+/// 启用 `prefetch` 特性时，槽环读者（[`DoubleBuffer`](crate::DoubleBuffer)、[`SwmrCell`](crate::SwmrCell)）在加载
+/// 活动槽索引后立即对该槽调用它，使拷贝的缓存未命中与固定操作及轻量屏障重叠。
+#[inline(always)]
+pub fn prefetch_read<T>(ptr: *const T) {
+    cfg_if::cfg_if! {
+        if #[cfg(target_arch = "x86_64")] {
+            // SAFETY: `prefetcht0` only hints the cache and never faults; SSE is baseline on x86_64.
+            // 安全性：`prefetcht0` 只是缓存提示，从不触发故障；SSE 是 x86_64 的基线特性。
+            unsafe { core::arch::x86_64::_mm_prefetch::<{ core::arch::x86_64::_MM_HINT_T0 }>(ptr.cast()) };
+        } else if #[cfg(all(target_arch = "x86", target_feature = "sse"))] {
+            // SAFETY: As above; SSE is enabled for this build.
+            // 安全性：同上；本次构建启用了 SSE。
+            unsafe { core::arch::x86::_mm_prefetch::<{ core::arch::x86::_MM_HINT_T0 }>(ptr.cast()) };
+        } else if #[cfg(target_arch = "aarch64")] {
+            // SAFETY: `prfm` only hints the cache and never faults.
+            // 安全性：`prfm` 只是缓存提示，从不触发故障。
+            unsafe {
+                core::arch::asm!("prfm pldl1keep, [{ptr}]", ptr = in(reg) ptr, options(nostack, readonly, preserves_flags));
+            }
+        } else {
+            let _ = ptr;
+        }
+    }
+}
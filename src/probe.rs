@@ -0,0 +1,46 @@
+//! **Single-Shot Ordering Probe** (feature `std`)
+//!
+//! One store-buffering round of the check that `integrity_ok()` (feature `verify`) runs
+//! continuously: the calling thread stores `X`, issues [`heavy_barrier()`](crate::heavy_barrier)
+//! and loads `Y`, while a scoped reader stores `Y`, issues [`light_barrier()`](crate::light_barrier)
+//! and loads `X`.
+//! `black_box` hides the atomics and the loaded values from the optimizer, so the round compiles
+//! to the real stores, barriers and loads.
+//!
+//! ---
+//!
+//! **单次顺序探测**（`std` 特性）
+//!
+//! `integrity_ok()`（`verify` 特性）持续运行的检查中的一轮存储缓冲测试：调用线程存储 `X`、执行
+//! [`heavy_barrier()`](crate::heavy_barrier) 并加载 `Y`；作用域内的读者线程存储 `Y`、执行
+//! [`light_barrier()`](crate::light_barrier) 并加载 `X`。`black_box` 向优化器隐藏原子变量与加载的值，
+//! 使这一轮编译为真实的存储、屏障与加载。
+
+use core::hint::{black_box, spin_loop};
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+
+pub(crate) fn ordering_once() -> bool {
+    let (x, y) = (AtomicUsize::new(0), AtomicUsize::new(0));
+    let ready = AtomicBool::new(false);
+    let (x, y) = (black_box(&x), black_box(&y));
+
+    thread::scope(|s| {
+        let reader = s.spawn(|| {
+            // Start together with the writer so the two halves overlap.
+            // 与写者同时开始，使两侧重叠。
+            ready.store(true, Ordering::Release);
+            y.store(black_box(1), Ordering::Relaxed);
+            crate::light_barrier();
+            black_box(x.load(Ordering::Relaxed))
+        });
+        while !ready.load(Ordering::Acquire) {
+            spin_loop();
+        }
+        x.store(black_box(1), Ordering::Relaxed);
+        crate::heavy_barrier();
+        let saw_y = black_box(y.load(Ordering::Relaxed));
+        let saw_x = reader.join().expect("swmr-barrier ordering probe reader panicked");
+        saw_x == 1 || saw_y == 1
+    })
+}
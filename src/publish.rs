@@ -0,0 +1,59 @@
+//! **Publish Stores**
+//!
+//! `heavy_barrier()` followed by a `Relaxed` store, in one call. The barrier already orders every
+//! earlier write before the store, so a `Release` store would only add cost. Readers pair the load
+//! of the published value with [`light_barrier()`](crate::light_barrier).
+//!
+//! Under the `loom` feature these take Loom's atomic types.
+//!
+//! ---
+//!
+//! **发布存储**
+//!
+//! 一次调用完成 `heavy_barrier()` 与随后的 `Relaxed` 存储。屏障已保证之前的所有写入先于该存储，
+//! 因此 `Release` 存储只会增加开销。读者在加载已发布的值后配对调用 [`light_barrier()`](crate::light_barrier)。
+//!
+//! 启用 `loom` 特性时，这些函数接受 Loom 的原子类型。
+
+#[cfg(target_has_atomic = "64")]
+use crate::sync::atomic::AtomicU64;
+use crate::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+
+/// Issues [`heavy_barrier()`](crate::heavy_barrier), then stores `value` with `Relaxed` ordering.
+/// 调用 [`heavy_barrier()`](crate::heavy_barrier)，然后以 `Relaxed` 顺序存储 `value`。
+#[cfg(target_has_atomic = "64")]
+#[inline]
+pub fn publish_store_u64(atomic: &AtomicU64, value: u64) {
+    crate::heavy_barrier();
+    atomic.store(value, Ordering::Relaxed);
+}
+
+/// Issues [`heavy_barrier()`](crate::heavy_barrier), then stores `value` with `Relaxed` ordering.
+/// 调用 [`heavy_barrier()`](crate::heavy_barrier)，然后以 `Relaxed` 顺序存储 `value`。
+#[inline]
+pub fn publish_store_usize(atomic: &AtomicUsize, value: usize) {
+    crate::heavy_barrier();
+    atomic.store(value, Ordering::Relaxed);
+}
+
+/// Issues [`heavy_barrier()`](crate::heavy_barrier), then stores `value` with `Relaxed` ordering.
+/// 调用 [`heavy_barrier()`](crate::heavy_barrier)，然后以 `Relaxed` 顺序存储 `value`。
+#[inline]
+pub fn publish_store_bool(atomic: &AtomicBool, value: bool) {
+    crate::heavy_barrier();
+    atomic.store(value, Ordering::Relaxed);
+}
+
+/// Issues [`heavy_barrier()`](crate::heavy_barrier), then stores `value` with `Relaxed` ordering.
+///
+/// The pointee's initialization is ordered before the store; reclaiming the previous pointer is
+/// still the caller's responsibility.
+///
+/// 调用 [`heavy_barrier()`](crate::heavy_barrier)，然后以 `Relaxed` 顺序存储 `value`。
+///
+/// 指向对象的初始化先于该存储；回收旧指针仍由调用方负责。
+#[inline]
+pub fn publish_store_ptr<T>(atomic: &AtomicPtr<T>, value: *mut T) {
+    crate::heavy_barrier();
+    atomic.store(value, Ordering::Relaxed);
+}
@@ -0,0 +1,328 @@
+use crate::barrier::{AsymmetricBarrier, BarrierKind, SystemBarrier};
+#[cfg(target_has_atomic = "64")]
+use crate::sync::atomic::AtomicU64;
+#[cfg(not(target_has_atomic = "64"))]
+use crate::sync::atomic::AtomicUsize;
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::sync::Backoff;
+use core::fmt;
+
+/// **RCU Domain**
+///
+/// A fixed table of `SLOTS` reader slots with read-copy-update grace periods. Readers register once,
+/// then enter read-side critical sections; the writer unpublishes data and calls
+/// [`synchronize`](Self::synchronize) to wait until no reader can still observe it.
+///
+/// * **Reader**: One store plus `light()` per [`read_lock`](RcuReader::read_lock), one store on unlock.
+/// * **Writer**: One `heavy()` per grace period, then a scan of all `SLOTS` slots.
+///
+/// `SLOTS` bounds the number of concurrently registered readers and lives inline, so the domain
+/// needs no allocation. More slots admit more readers but lengthen the writer's scan.
+///
+/// ---
+///
+/// **RCU 域**
+///
+/// 由 `SLOTS` 个读者槽组成的固定表，提供读-复制-更新 (RCU) 宽限期。读者注册一次后进入读侧临界区；
+/// 写者撤下数据后调用 [`synchronize`](Self::synchronize)，等待没有读者仍能观察到它。
+///
+/// * **读者**：每次 [`read_lock`](RcuReader::read_lock) 一次存储加一次 `light()`，解锁时一次存储。
+/// * **写者**：每个宽限期一次 `heavy()`，随后扫描全部 `SLOTS` 个槽。
+///
+/// `SLOTS` 限制同时注册的读者数量，并内联存储，因此该域无需分配内存。槽越多可容纳的读者越多，但写者扫描越长。
+pub struct RcuDomain<const SLOTS: usize = { crate::DEFAULT_READER_SLOTS }, B: AsymmetricBarrier = SystemBarrier> {
+    slots: [ReaderSlot; SLOTS],
+    barrier: B,
+}
+
+/// Reader sequence counter: 64 bits wherever the target has 64-bit atomics.
+/// 读者序号计数器：目标支持 64 位原子操作时为 64 位。
+#[cfg(target_has_atomic = "64")]
+type Seq = u64;
+#[cfg(target_has_atomic = "64")]
+type AtomicSeq = AtomicU64;
+#[cfg(not(target_has_atomic = "64"))]
+type Seq = usize;
+#[cfg(not(target_has_atomic = "64"))]
+type AtomicSeq = AtomicUsize;
+
+/// **Reader Slot**
+///
+/// One reader's state: `seq` is odd while the reader is inside a critical section.
+///
+/// `seq` advances with wrapping arithmetic. The width is a power of two, so parity survives the
+/// wrap, and [`synchronize`](RcuDomain::synchronize) only ever compares sequences for equality,
+/// never by order. A wrap therefore cannot end a grace period early; that would take exactly
+/// 2^(width-1) critical sections between two consecutive loads by the writer.
+///
+/// ---
+///
+/// **读者槽**
+///
+/// 单个读者的状态：读者处于临界区时 `seq` 为奇数。
+///
+/// `seq` 以回绕算术递增。其位宽为 2 的幂，因此回绕后奇偶性不变；且 [`synchronize`](RcuDomain::synchronize)
+/// 只比较序号是否相等，从不比较大小。因此回绕不会提前结束宽限期；那需要写者两次相邻加载之间恰好发生
+/// 2^(位宽-1) 次临界区。
+struct ReaderSlot {
+    in_use: AtomicBool,
+    seq: AtomicSeq,
+}
+
+impl<const SLOTS: usize> RcuDomain<SLOTS> {
+    /// Creates a domain with `SLOTS` free reader slots.
+    /// 创建具有 `SLOTS` 个空闲读者槽的域。
+    pub fn new() -> Self {
+        Self::with_barrier(SystemBarrier)
+    }
+}
+
+impl<const SLOTS: usize> RcuDomain<SLOTS, BarrierKind> {
+    /// Creates a domain using the selected [`BarrierKind`].
+    /// 使用所选的 [`BarrierKind`] 创建域。
+    pub fn with_kind(kind: BarrierKind) -> Self {
+        Self::with_barrier(kind)
+    }
+}
+
+impl<const SLOTS: usize> Default for RcuDomain<SLOTS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SLOTS: usize, B: AsymmetricBarrier> RcuDomain<SLOTS, B> {
+    /// Creates a domain using a custom barrier.
+    /// 使用自定义屏障创建域。
+    pub fn with_barrier(barrier: B) -> Self {
+        Self {
+            slots: core::array::from_fn(|_| ReaderSlot {
+                in_use: AtomicBool::new(false),
+                seq: AtomicSeq::new(0),
+            }),
+            barrier,
+        }
+    }
+
+    /// Number of reader slots.
+    /// 读者槽数量。
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        SLOTS
+    }
+
+    /// **Register Reader**
+    ///
+    /// Claims a free slot for the calling reader. The slot is released when the returned handle is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReaderTableFull`] if all `SLOTS` slots are taken.
+    ///
+    /// ---
+    ///
+    /// **注册读者**
+    ///
+    /// 为调用方读者占用一个空闲槽。返回的句柄被释放时归还该槽。
+    ///
+    /// 所有 `SLOTS` 个槽均被占用时返回 [`ReaderTableFull`]。
+    pub fn register_reader(&self) -> Result<RcuReader<'_, SLOTS, B>, ReaderTableFull> {
+        self.slots
+            .iter()
+            .position(|slot| {
+                slot.in_use
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            })
+            .map(|index| RcuReader { domain: self, index })
+            .ok_or(ReaderTableFull)
+    }
+
+    /// **Synchronize**
+    ///
+    /// Waits for a grace period: every critical section that began before this call has ended when it
+    /// returns. Unpublish data first, then call this before reclaiming or overwriting it.
+    ///
+    /// ---
+    ///
+    /// **同步**
+    ///
+    /// 等待一个宽限期：返回时，本次调用之前开始的所有临界区都已结束。应先撤下数据，再在回收或覆盖它之前调用此方法。
+    pub fn synchronize(&self) {
+        // Pairs with the reader's `light()` after entering: either we see the odd sequence, or the
+        // reader sees the unpublished state.
+        // 与读者进入后的 `light()` 配对：要么我们看到奇数序号，要么读者看到已撤下的状态。
+        self.barrier.heavy();
+
+        for slot in &self.slots {
+            let seq = slot.seq.load(Ordering::Acquire);
+            if seq & 1 == 1 {
+                // Any change means the critical section we observed has ended. Compare for equality
+                // only, so a wrapped counter is still a change.
+                // 任何变化都意味着我们观察到的临界区已结束。只比较是否相等，因此回绕后的计数器仍算作变化。
+                let mut backoff = Backoff::new();
+                while slot.seq.load(Ordering::Acquire) == seq {
+                    backoff.snooze();
+                }
+            }
+        }
+    }
+}
+
+/// **Wait for Grace Period**
+///
+/// The reclamation step of epoch-based schemes, as a free function: issues a `heavy()`, then spins
+/// with exponential backoff until every reader of `domain` has passed a quiescent point since that
+/// barrier. A reader that was outside a critical section is already quiescent; one that was inside
+/// must be seen to change its sequence, which it does only by leaving. When this returns, memory
+/// unpublished before the call can be freed: no reader can still reference it.
+///
+/// Never parks the thread, so it works in `no_std`; a reader that stays inside one critical
+/// section keeps the caller spinning. Same as [`RcuDomain::synchronize`].
+///
+/// ---
+///
+/// **等待宽限期**
+///
+/// 基于纪元的回收方案中的回收步骤，以自由函数形式提供：执行一次 `heavy()`，随后以指数退避自旋，直到 `domain` 的每个读者
+/// 自该屏障以来都经过了静止点。当时不在临界区内的读者已处于静止状态；当时在临界区内的读者必须被观察到序号发生变化，
+/// 而这只会在其离开时发生。返回后，调用之前撤下的内存即可释放：没有读者仍能引用它。
+///
+/// 从不挂起线程，因此可用于 `no_std`；停留在同一临界区内的读者会使调用方持续自旋。与 [`RcuDomain::synchronize`] 相同。
+pub fn wait_for_grace_period<const SLOTS: usize, B: AsymmetricBarrier>(domain: &RcuDomain<SLOTS, B>) {
+    domain.synchronize();
+}
+
+impl<const SLOTS: usize, B: AsymmetricBarrier> fmt::Debug for RcuDomain<SLOTS, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let registered = self
+            .slots
+            .iter()
+            .filter(|slot| slot.in_use.load(Ordering::Relaxed))
+            .count();
+        f.debug_struct("RcuDomain")
+            .field("capacity", &SLOTS)
+            .field("registered", &registered)
+            .finish()
+    }
+}
+
+/// **RCU Reader**
+///
+/// A registered reader slot, returned by [`register_reader`](RcuDomain::register_reader).
+/// Dropping it frees the slot.
+///
+/// ---
+///
+/// **RCU 读者**
+///
+/// 由 [`register_reader`](RcuDomain::register_reader) 返回的已注册读者槽。释放时归还该槽。
+pub struct RcuReader<'a, const SLOTS: usize = { crate::DEFAULT_READER_SLOTS }, B: AsymmetricBarrier = SystemBarrier> {
+    domain: &'a RcuDomain<SLOTS, B>,
+    index: usize,
+}
+
+impl<'a, const SLOTS: usize, B: AsymmetricBarrier> RcuReader<'a, SLOTS, B> {
+    /// **Read Lock**
+    ///
+    /// Enters a read-side critical section that lasts until the guard is dropped. Data observed
+    /// inside it is not reclaimed before the guard is dropped. Critical sections do not nest.
+    ///
+    /// ---
+    ///
+    /// **读锁**
+    ///
+    /// 进入读侧临界区，直到守卫释放为止。在其中观察到的数据在守卫释放前不会被回收。临界区不可嵌套。
+    #[inline]
+    pub fn read_lock(&mut self) -> RcuReadGuard<'_> {
+        let slot = &self.domain.slots[self.index];
+        // Only this reader writes its slot's sequence.
+        // 只有该读者会写入其槽的序号。
+        let seq = slot.seq.load(Ordering::Relaxed).wrapping_add(1);
+        slot.seq.store(seq, Ordering::Relaxed);
+        self.domain.barrier.light();
+        RcuReadGuard { slot, seq }
+    }
+
+    /// Index of the slot this reader occupies.
+    /// 该读者占用的槽索引。
+    #[inline]
+    pub fn slot(&self) -> usize {
+        self.index
+    }
+
+    /// Overwrites this reader's sequence counter, truncated to its width.
+    /// 覆盖该读者的序号计数器，按其位宽截断。
+    #[cfg(feature = "testing")]
+    pub(crate) fn seed_sequence(&mut self, seq: u64) {
+        self.domain.slots[self.index].seq.store(seq as Seq, Ordering::Relaxed);
+    }
+}
+
+impl<const SLOTS: usize, B: AsymmetricBarrier> Drop for RcuReader<'_, SLOTS, B> {
+    fn drop(&mut self) {
+        self.domain.slots[self.index].in_use.store(false, Ordering::Release);
+    }
+}
+
+impl<const SLOTS: usize, B: AsymmetricBarrier> fmt::Debug for RcuReader<'_, SLOTS, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RcuReader").field("slot", &self.index).finish()
+    }
+}
+
+/// **RCU Read Guard**
+///
+/// A read-side critical section, returned by [`read_lock`](RcuReader::read_lock).
+///
+/// The section also ends when the guard is dropped during unwinding. Its `Drop` is a single atomic
+/// store that cannot panic, so a reader that panics inside a critical section never stalls
+/// [`synchronize`](RcuDomain::synchronize).
+///
+/// ---
+///
+/// **RCU 读守卫**
+///
+/// 由 [`read_lock`](RcuReader::read_lock) 返回的读侧临界区。
+///
+/// 守卫在栈展开时被释放同样会结束临界区。其 `Drop` 只是一次不会 panic 的原子存储，因此在临界区内 panic 的读者
+/// 永远不会阻塞 [`synchronize`](RcuDomain::synchronize)。
+pub struct RcuReadGuard<'a> {
+    slot: &'a ReaderSlot,
+    seq: Seq,
+}
+
+impl Drop for RcuReadGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        // Release: our reads inside the critical section happen before the writer's reclamation.
+        // Release：临界区内的读取先于写者的回收。
+        self.slot.seq.store(self.seq.wrapping_add(1), Ordering::Release);
+    }
+}
+
+impl fmt::Debug for RcuReadGuard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RcuReadGuard").finish_non_exhaustive()
+    }
+}
+
+/// **Reader Table Full**
+///
+/// Returned by [`register_reader`](RcuDomain::register_reader) when every slot is taken.
+///
+/// ---
+///
+/// **读者表已满**
+///
+/// 所有槽均被占用时由 [`register_reader`](RcuDomain::register_reader) 返回。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReaderTableFull;
+
+impl fmt::Display for ReaderTableFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("reader table is full")
+    }
+}
+
+impl core::error::Error for ReaderTableFull {}
@@ -0,0 +1,122 @@
+//! **`Send`/`Sync` Audit**
+//!
+//! Compile-fail checks that the data structures do not become `Send` or `Sync` for element types
+//! that would make them unsound. Only built by `cargo test --doc`; the positive cases live in
+//! `tests/send_sync_test.rs`.
+//!
+//! | Type | `Send` | `Sync` |
+//! |------|--------|--------|
+//! | `DoubleBuffer<T, B>` | `T: Send` | `T: Send + Sync` |
+//! | `SnapshotGuard<T, B>` | `T: Sync` | `T: Sync` |
+//! | `SwmrCell<T, SLOTS, B>`, `ChecksummedCell<T, SLOTS, B>` | `T: Send` | `T: Copy + Send` |
+//! | `SwmrCellReader<T, SLOTS, B>`, `ResilientReader<T, SLOTS, B>` | `T: Copy + Send` | `T: Copy + Send` |
+//! | `AppendLog<T, CAP, B>` | `T: Send` | `T: Send + Sync` |
+//! | `OverwriteRing<T, N, B>` | `T: Send` | `T: Send` |
+//! | `FairSeqlock<T, B>`, `AtomicCell<T, B>` | `T: Send` | `T: Copy + Send` |
+//! | `SwmrArcCell<T, B>` (feature `std`) | `T: Send + Sync` | `T: Send + Sync` |
+//! | `SwmrWordCell`, `RcuDomain`, `RcuReader`, `DeferredBarrier`, `BarrierDomain`, `TrackedBarrier`, `WriterSequence`, `ReaderSequence` | always | always |
+//!
+//! `B` must additionally be `Send` / `Sync` respectively.
+//!
+//! ---
+//!
+//! **`Send`/`Sync` 审计**
+//!
+//! 编译失败检查：确保数据结构不会因使其不健全的元素类型而成为 `Send` 或 `Sync`。仅由 `cargo test --doc` 构建；
+//! 正向用例位于 `tests/send_sync_test.rs`。
+//!
+//! A non-`Send` value must not cross threads inside a cell.
+//! 非 `Send` 的值不得借助单元跨线程。
+//!
+//! ```compile_fail,E0277
+//! fn assert_send<T: Send>() {}
+//! assert_send::<swmr_barrier::SwmrCell<*const u8>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<swmr_barrier::SwmrCell<*const u8>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<swmr_barrier::OverwriteRing<*const u8, 4>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<swmr_barrier::FairSeqlock<*const u8>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_send<T: Send>() {}
+//! assert_send::<swmr_barrier::FairSeqlock<*const u8>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<swmr_barrier::AtomicCell<*const u8>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_send<T: Send>() {}
+//! assert_send::<swmr_barrier::DoubleBuffer<std::rc::Rc<u8>>>();
+//! ```
+//!
+//! Readers share `&T`, so a `Send` but non-`Sync` `T` must not make the buffer `Sync`.
+//! 读者共享 `&T`，因此 `Send` 但非 `Sync` 的 `T` 不得使缓冲区成为 `Sync`。
+//!
+//! ```compile_fail,E0277
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<swmr_barrier::DoubleBuffer<std::cell::Cell<u8>>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<swmr_barrier::AppendLog<std::cell::Cell<u8>, 4>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_send<T: Send>() {}
+//! assert_send::<swmr_barrier::SnapshotGuard<'static, std::cell::Cell<u8>>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_send<T: Send>() {}
+//! assert_send::<swmr_barrier::AppendLog<std::rc::Rc<u8>, 4>>();
+//! ```
+//!
+//! A barrier that is not `Sync` must not be shared through the structure.
+//! 非 `Sync` 的屏障不得通过数据结构被共享。
+//!
+//! ```compile_fail,E0277
+//! use std::cell::Cell;
+//! use swmr_barrier::{AsymmetricBarrier, SwmrWordCell};
+//!
+//! struct Counting(Cell<usize>);
+//! unsafe impl AsymmetricBarrier for Counting {
+//!     fn heavy(&self) { self.0.set(self.0.get() + 1) }
+//!     fn light(&self) {}
+//!     fn accelerated(&self) -> bool { false }
+//! }
+//!
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<SwmrWordCell<u64, Counting>>();
+//! ```
+#![cfg_attr(
+    feature = "std",
+    doc = r#"
+`SwmrArcCell` hands out `Arc<T>` clones to other threads, so `T` must be both `Send` and `Sync`.
+`SwmrArcCell` 会将 `Arc<T>` 的克隆交给其他线程，因此 `T` 必须同时是 `Send` 与 `Sync`。
+
+```compile_fail,E0277
+fn assert_sync<T: Sync>() {}
+assert_sync::<swmr_barrier::SwmrArcCell<std::cell::Cell<u8>>>();
+```
+
+```compile_fail,E0277
+fn assert_send<T: Send>() {}
+assert_send::<swmr_barrier::SwmrArcCell<std::cell::Cell<u8>>>();
+```
+"#
+)]
@@ -0,0 +1,401 @@
+use crate::barrier::{AsymmetricBarrier, SystemBarrier};
+use crate::slot_ring::Unlock;
+#[cfg(target_has_atomic = "64")]
+use crate::sync::atomic::AtomicU64;
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::sync::{UnsafeCell, spin_loop};
+use core::fmt;
+use core::mem::MaybeUninit;
+#[cfg(feature = "async")]
+use core::task::{Poll, Waker};
+#[cfg(feature = "async")]
+use std::sync::{Mutex, PoisonError};
+#[cfg(feature = "async")]
+use std::vec::Vec;
+
+// Upper bound on one fairness pause, in spin-loop iterations, so readers cannot stall the writer.
+// 单次公平暂停的上限（自旋次数），使读者无法让写者停滞。
+const PAUSE_SPINS: u32 = 1 << 14;
+
+/// Write sequence: 64 bits wherever the target has 64-bit atomics.
+/// 写入序号：目标支持 64 位原子操作时为 64 位。
+#[cfg(target_has_atomic = "64")]
+type Seq = u64;
+#[cfg(target_has_atomic = "64")]
+type AtomicSeq = AtomicU64;
+#[cfg(not(target_has_atomic = "64"))]
+type Seq = usize;
+#[cfg(not(target_has_atomic = "64"))]
+type AtomicSeq = AtomicUsize;
+
+/// Completed writes at `sequence`: each write moves it by two.
+/// 序号为 `sequence` 时已完成的写入次数：每次写入使其前进二。
+#[cfg(target_has_atomic = "64")]
+fn version_of(sequence: Seq) -> u64 {
+    sequence / 2
+}
+#[cfg(not(target_has_atomic = "64"))]
+fn version_of(sequence: Seq) -> u64 {
+    (sequence / 2) as u64
+}
+
+/// **Fair Seqlock**
+///
+/// A sequence lock over the asymmetric barriers: the writer brackets every write with `heavy()`
+/// and bumps a sequence before and after it, readers copy the value between two `light()`s and
+/// retry if the sequence moved. Unlike [`SwmrCell`](crate::SwmrCell) it keeps one copy of `T` and
+/// the writer never waits for readers, so a writer that publishes back to back can make a slow
+/// reader retry forever.
+///
+/// To prevent that, a [`read`](Self::read) that fails more than `patience` attempts in a row
+/// raises a shared counter. Before each write the writer checks it and, while it is non-zero,
+/// pauses for a bounded spin so the announced readers can finish a copy. The price is writer
+/// latency: each [`set`](Self::set) issued while a reader is starving may be delayed by up to one
+/// pause (about 2¹⁴ spin-loop hints). [`try_read`](Self::try_read) never announces itself.
+///
+/// * **Reader**: Two `light()`s per attempt.
+/// * **Writer**: Two `heavy()`s per [`set`](Self::set).
+///
+/// ---
+///
+/// **公平顺序锁**
+///
+/// 基于非对称屏障的顺序锁：写者在每次写入前后各执行一次 `heavy()` 并递增序号，读者在两次 `light()` 之间复制值，
+/// 若序号变化则重试。与 [`SwmrCell`](crate::SwmrCell) 不同，它只保存一份 `T`，写者从不等待读者，因此连续发布的写者
+/// 可能让慢速读者无限重试。
+///
+/// 为防止这种情况，连续失败超过 `patience` 次的 [`read`](Self::read) 会递增一个共享计数器。写者在每次写入前检查它，
+/// 非零期间进行有界自旋暂停，让已声明的读者完成一次复制。代价是写者延迟：读者饥饿期间发出的每次 [`set`](Self::set)
+/// 最多被延迟一次暂停（约 2¹⁴ 次自旋提示）。[`try_read`](Self::try_read) 从不声明自身。
+///
+/// * **读者**：每次尝试两次 `light()`。
+/// * **写者**：每次 [`set`](Self::set) 两次 `heavy()`。
+pub struct FairSeqlock<T: Copy, B: AsymmetricBarrier = SystemBarrier> {
+    value: UnsafeCell<T>,
+    // Odd while a write is in progress.
+    // 写入进行中时为奇数。
+    sequence: AtomicSeq,
+    // Readers that exceeded their patience and have not completed a read since.
+    // 超出耐心且此后尚未完成读取的读者数。
+    starving: AtomicUsize,
+    writing: AtomicBool,
+    patience: u32,
+    barrier: B,
+    // Tasks waiting in `changed`, woken after every write.
+    // 在 `changed` 中等待的任务，每次写入后唤醒。
+    #[cfg(feature = "async")]
+    wakers: Mutex<Vec<Waker>>,
+}
+
+// SAFETY: Readers copy `T` out on their own threads (`T: Send`); a copy that raced with the writer
+// is discarded before it is used. `Send` is derived and needs only `T: Send`.
+// 安全性：读者在自身线程上复制出 `T`（`T: Send`）；与写者竞争的副本在使用前即被丢弃。`Send` 自动推导，只需 `T: Send`。
+unsafe impl<T: Copy + Send, B: AsymmetricBarrier + Sync> Sync for FairSeqlock<T, B> {}
+
+impl<T: Copy> FairSeqlock<T> {
+    /// Creates a seqlock holding `initial`.
+    /// 创建持有 `initial` 的顺序锁。
+    pub fn new(initial: T) -> Self {
+        Self::with_barrier(initial, SystemBarrier)
+    }
+}
+
+impl<T: Copy + Default> Default for FairSeqlock<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Copy, B: AsymmetricBarrier> FairSeqlock<T, B> {
+    /// Failed attempts after which [`read`](Self::read) asks the writer to pause, unless changed
+    /// with [`with_patience`](Self::with_patience).
+    /// [`read`](Self::read) 在请求写者暂停之前的默认失败次数，可用 [`with_patience`](Self::with_patience) 修改。
+    pub const DEFAULT_PATIENCE: u32 = 16;
+
+    /// Creates a seqlock using a custom barrier, with [`DEFAULT_PATIENCE`](Self::DEFAULT_PATIENCE).
+    /// 使用自定义屏障创建顺序锁，耐心为 [`DEFAULT_PATIENCE`](Self::DEFAULT_PATIENCE)。
+    pub fn with_barrier(initial: T, barrier: B) -> Self {
+        Self {
+            value: UnsafeCell::new(initial),
+            sequence: AtomicSeq::new(0),
+            starving: AtomicUsize::new(0),
+            writing: AtomicBool::new(false),
+            patience: Self::DEFAULT_PATIENCE,
+            barrier,
+            #[cfg(feature = "async")]
+            wakers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sets how many failed attempts a [`read`](Self::read) makes before asking the writer to
+    /// pause. `0` announces after the first failure.
+    /// 设置 [`read`](Self::read) 在请求写者暂停之前的失败尝试次数。`0` 表示首次失败后即声明。
+    #[must_use]
+    pub fn with_patience(mut self, patience: u32) -> Self {
+        self.patience = patience;
+        self
+    }
+
+    /// **Read**
+    ///
+    /// Returns a consistent copy of the value, retrying while the writer is active. After
+    /// `patience` failed attempts the reader asks the writer to pause, so it completes even under a
+    /// writer that never stops publishing.
+    ///
+    /// ---
+    ///
+    /// **读取**
+    ///
+    /// 返回值的一致拷贝，写者活动期间会重试。失败 `patience` 次后读者请求写者暂停，因此即使写者从不停止发布，读取也能完成。
+    pub fn read(&self) -> T {
+        self.read_versioned().0
+    }
+
+    /// **Read If Changed**
+    ///
+    /// Returns the value if a write completed since `*last_seen` was recorded, updating it to the
+    /// value's [`version`](Self::version); otherwise `None`, at the cost of one atomic load. Start
+    /// from `0` to skip the initial value, or from `u64::MAX` to receive it too. The copy is made
+    /// like [`read`](Self::read), so it can ask the writer to pause.
+    ///
+    /// ---
+    ///
+    /// **变化时读取**
+    ///
+    /// 若自记录 `*last_seen` 以来有写入完成，则返回该值并将 `*last_seen` 更新为其 [`version`](Self::version)；否则返回
+    /// `None`，代价为一次原子加载。从 `0` 开始会跳过初始值，从 `u64::MAX` 开始则也会收到初始值。复制方式同
+    /// [`read`](Self::read)，因此可能请求写者暂停。
+    pub fn read_if_changed(&self, last_seen: &mut u64) -> Option<T> {
+        if self.version() == *last_seen {
+            return None;
+        }
+        let (value, sequence) = self.read_versioned();
+        *last_seen = version_of(sequence);
+        Some(value)
+    }
+
+    /// Number of completed writes: `0` for the initial value, then one more per [`set`](Self::set).
+    /// Wraps at `usize::MAX / 2` on targets without 64-bit atomics.
+    /// 已完成的写入次数：初始值为 `0`，此后每次 [`set`](Self::set) 加一。在不支持 64 位原子操作的目标上于
+    /// `usize::MAX / 2` 处回绕。
+    #[inline]
+    pub fn version(&self) -> u64 {
+        version_of(self.sequence.load(Ordering::Relaxed))
+    }
+
+    /// [`read`](Self::read), also returning the even sequence the copy was taken at.
+    /// 同 [`read`](Self::read)，并返回取得副本时的偶数序号。
+    fn read_versioned(&self) -> (T, Seq) {
+        let mut failures = 0u32;
+        let mut announced = false;
+        loop {
+            let before = self.sequence.load(Ordering::Relaxed);
+            if let Some(value) = self.attempt_at(before) {
+                if announced {
+                    self.starving.fetch_sub(1, Ordering::Relaxed);
+                }
+                return (value, before);
+            }
+            failures = failures.saturating_add(1);
+            if !announced && failures > self.patience {
+                self.starving.fetch_add(1, Ordering::Relaxed);
+                announced = true;
+            }
+            spin_loop();
+        }
+    }
+
+    /// Makes up to `attempts` reads, returning `None` if the writer interfered with each of them.
+    /// Never asks the writer to pause.
+    /// 最多尝试 `attempts` 次读取；若每次都受写者干扰则返回 `None`。从不请求写者暂停。
+    pub fn try_read(&self, attempts: u32) -> Option<T> {
+        for _ in 0..attempts {
+            if let Some(value) = self.attempt() {
+                return Some(value);
+            }
+            spin_loop();
+        }
+        None
+    }
+
+    /// Number of readers currently asking the writer to pause.
+    /// 当前请求写者暂停的读者数。
+    #[inline]
+    pub fn starving_readers(&self) -> usize {
+        self.starving.load(Ordering::Relaxed)
+    }
+
+    /// **Set**
+    ///
+    /// Writes `value`, first pausing (boundedly) while any reader is starving. Concurrent writers
+    /// are serialized.
+    ///
+    /// ---
+    ///
+    /// **设置**
+    ///
+    /// 写入 `value`；若有读者饥饿，先进行有界暂停。并发写者会被串行化。
+    pub fn set(&self, value: T) {
+        self.replace(value);
+    }
+
+    /// [`set`](Self::set), returning the value it overwrote. The old value is read while holding the
+    /// writer flag, so concurrent writers each get back a distinct value.
+    /// 同 [`set`](Self::set)，并返回被覆盖的值。旧值在持有写者标志期间读取，因此并发写者各自取回不同的值。
+    pub(crate) fn replace(&self, value: T) -> T {
+        while self
+            .writing
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            spin_loop();
+        }
+        // Release the writer flag even if `heavy()` panics.
+        // 即使 `heavy()` panic 也释放写者标志。
+        let _unlock = Unlock(&self.writing);
+
+        // The sequence is even here, so an announced reader can complete during the pause.
+        // 此时序号为偶数，因此已声明的读者可在暂停期间完成读取。
+        let mut spins = 0;
+        while self.starving.load(Ordering::Relaxed) != 0 && spins < PAUSE_SPINS {
+            spin_loop();
+            spins += 1;
+        }
+
+        let sequence = self.sequence.load(Ordering::Relaxed);
+        // Dropped before `_unlock`. Until the value is written, an unwind restores `sequence`: the
+        // value is untouched, so readers may keep copying it.
+        // 先于 `_unlock` 析构。值写入之前发生展开时恢复 `sequence`：值未被改动，读者可继续复制它。
+        let mut end = EndWrite {
+            sequence: &self.sequence,
+            end: sequence,
+            #[cfg(feature = "async")]
+            wakers: &self.wakers,
+        };
+        self.sequence.store(sequence.wrapping_add(1), Ordering::Relaxed);
+        // Pairs with the reader's second `light()`: a copy that saw any byte of this write also sees
+        // the odd sequence on its recheck.
+        // 与读者的第二次 `light()` 配对：看到本次写入任一字节的复制在复查时也会看到奇数序号。
+        self.barrier.heavy();
+        // SAFETY: We hold the writer flag, so no other write races the read of the old value. Readers
+        // may be copying concurrently; they discard any copy taken while the sequence is odd or has moved.
+        // 安全性：我们持有写者标志，因此没有其他写入与读取旧值竞争。读者可能正在并发复制；在序号为奇数或已变化期间
+        // 取得的副本会被丢弃。
+        let previous = self.value.get_mut().with(|ptr| unsafe { ptr.replace(value) });
+        end.end = sequence.wrapping_add(2);
+        // Pairs with the reader's first `light()`: a reader that starts from the new even sequence
+        // copies the whole write.
+        // 与读者的第一次 `light()` 配对：从新偶数序号开始的读者能复制到完整的写入。
+        self.barrier.heavy();
+        previous
+    }
+
+    /// Registers `waker` to be woken after the next write, unless an equivalent one already is.
+    /// 注册 `waker`，在下一次写入后唤醒；已注册等价的唤醒器时不重复注册。
+    #[cfg(feature = "async")]
+    fn register(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock().unwrap_or_else(PoisonError::into_inner);
+        if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+
+    #[inline]
+    fn attempt(&self) -> Option<T> {
+        self.attempt_at(self.sequence.load(Ordering::Relaxed))
+    }
+
+    /// One seqlock read starting from sequence `before`: `None` if a write was in progress or
+    /// completed during the copy.
+    /// 从序号 `before` 开始的一次顺序锁读取：若复制期间有写入进行或完成则返回 `None`。
+    #[inline]
+    fn attempt_at(&self, before: Seq) -> Option<T> {
+        if before % 2 == 1 {
+            return None;
+        }
+        self.barrier.light();
+        let ptr = self.value.get();
+        // SAFETY: The cell always holds an initialized `T`. The copy may race with a write; it stays
+        // `MaybeUninit` until the sequence recheck below shows it did not.
+        // 安全性：单元始终持有已初始化的 `T`。复制可能与写入竞争；在下方序号复查证明未发生竞争之前，它一直保持为 `MaybeUninit`。
+        let copy = unsafe { core::ptr::read_volatile((ptr.deref() as *const T).cast::<MaybeUninit<T>>()) };
+        self.barrier.light();
+        if self.sequence.load(Ordering::Relaxed) != before {
+            return None;
+        }
+        // SAFETY: No write overlapped the copy, so it is the value published as `before`.
+        // 安全性：没有写入与复制重叠，因此它正是以 `before` 发布的值。
+        Some(unsafe { copy.assume_init() })
+    }
+}
+
+/// **Changed**
+///
+/// Resolves to the value of `cell` once a write completes after `*last_seen`, through
+/// [`read_if_changed`](FairSeqlock::read_if_changed), so an async task can wait for the writer
+/// without blocking a runtime thread. While nothing changed the task registers its waker with the
+/// cell and sleeps; the next [`set`](FairSeqlock::set) wakes every registered task, so an idle
+/// wait costs nothing. Each write takes one uncontended lock to drain the wakers. A future dropped
+/// before resolving leaves its waker registered until the next write. Requires the `async` feature.
+///
+/// ---
+///
+/// **变化**
+///
+/// 通过 [`read_if_changed`](FairSeqlock::read_if_changed)，在 `*last_seen` 之后有写入完成时解析为 `cell` 的值，
+/// 使异步任务无需阻塞运行时线程即可等待写者。未发生变化时，任务向单元注册其唤醒器并休眠；下一次
+/// [`set`](FairSeqlock::set) 会唤醒所有已注册的任务，因此空闲等待没有开销。每次写入需获取一次无竞争的锁以取出唤醒器。
+/// 在解析前被丢弃的 future 会让其唤醒器保持注册直到下一次写入。需要启用 `async` 特性。
+#[cfg(feature = "async")]
+pub fn changed<'a, T: Copy, B: AsymmetricBarrier>(
+    cell: &'a FairSeqlock<T, B>,
+    last_seen: &'a mut u64,
+) -> impl Future<Output = T> + 'a {
+    core::future::poll_fn(move |cx| {
+        if let Some(value) = cell.read_if_changed(last_seen) {
+            return Poll::Ready(value);
+        }
+        // Checked again after registering: a write that ended before the registration was missed
+        // by its wake, and the lock makes its sequence visible here.
+        // 注册后再次检查：在注册之前结束的写入不会唤醒本任务，而锁保证此处能看到其序号。
+        cell.register(cx.waker());
+        match cell.read_if_changed(last_seen) {
+            Some(value) => Poll::Ready(value),
+            None => Poll::Pending,
+        }
+    })
+}
+
+/// Stores the even sequence that ends a write on drop, so a panicking `heavy()` cannot leave it odd
+/// and make every later read spin. A panic in the second `heavy()` ends the write without that
+/// barrier, so readers get only whatever ordering it provided before panicking. With `async`, then
+/// wakes the tasks waiting in [`changed`], outside the waker lock.
+/// 析构时存储结束写入的偶数序号，使 panic 的 `heavy()` 无法让序号停留在奇数而导致之后的每次读取自旋。第二次
+/// `heavy()` 中的 panic 会在缺少该屏障的情况下结束写入，读者只能获得该屏障在 panic 之前提供的顺序。启用 `async` 时，
+/// 随后在唤醒器锁之外唤醒在 [`changed`] 中等待的任务。
+struct EndWrite<'a> {
+    sequence: &'a AtomicSeq,
+    end: Seq,
+    #[cfg(feature = "async")]
+    wakers: &'a Mutex<Vec<Waker>>,
+}
+
+impl Drop for EndWrite<'_> {
+    fn drop(&mut self) {
+        self.sequence.store(self.end, Ordering::Relaxed);
+        #[cfg(feature = "async")]
+        {
+            let wakers = core::mem::take(&mut *self.wakers.lock().unwrap_or_else(PoisonError::into_inner));
+            wakers.into_iter().for_each(Waker::wake);
+        }
+    }
+}
+
+impl<T: Copy + fmt::Debug, B: AsymmetricBarrier> fmt::Debug for FairSeqlock<T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FairSeqlock")
+            .field("value", &self.read())
+            .field("starving", &self.starving_readers())
+            .finish()
+    }
+}
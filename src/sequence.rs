@@ -0,0 +1,207 @@
+use crate::barrier::{AsymmetricBarrier, SystemBarrier};
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WriterState {
+    // Nothing written since the last publish.
+    // 自上次发布以来没有写入。
+    Clean,
+    // Payload written, no heavy barrier since.
+    // 已写入负载，此后尚无重型屏障。
+    Written,
+    // Heavy barrier issued after the last payload write.
+    // 最后一次负载写入之后已执行重型屏障。
+    Fenced,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReaderState {
+    // Nothing observed yet.
+    // 尚未观察到任何发布。
+    Clean,
+    // Publish observed, no light barrier since.
+    // 已观察到发布，此后尚无轻量屏障。
+    Observed,
+    // Light barrier issued after the last observation.
+    // 最后一次观察之后已执行轻量屏障。
+    Fenced,
+}
+
+/// **Writer Sequence**
+///
+/// Runs the writer side of a hand-rolled publication through the steps it must follow:
+/// [`write`](Self::write) the payload, [`heavy`](Self::heavy), then [`publish`](Self::publish).
+/// In debug builds, publishing a payload that was written after (or without) the heavy barrier
+/// panics at the caller's `publish` line. Release builds skip the check entirely.
+///
+/// ---
+///
+/// **写者序列**
+///
+/// 按写者必须遵循的步骤执行手写发布的写侧：先 [`write`](Self::write) 负载，再 [`heavy`](Self::heavy)，最后
+/// [`publish`](Self::publish)。在调试构建中，若发布的负载写于重型屏障之后（或根本没有屏障），会在调用方的
+/// `publish` 行 panic。发布构建完全跳过该检查。
+pub struct WriterSequence<B: AsymmetricBarrier = SystemBarrier> {
+    barrier: B,
+    state: WriterState,
+}
+
+impl WriterSequence {
+    /// Creates a sequence using the process-wide barrier.
+    /// 使用进程级屏障创建序列。
+    pub const fn new() -> Self {
+        Self::with_barrier(SystemBarrier)
+    }
+}
+
+impl Default for WriterSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: AsymmetricBarrier> WriterSequence<B> {
+    /// Creates a sequence using a custom barrier.
+    /// 使用自定义屏障创建序列。
+    pub const fn with_barrier(barrier: B) -> Self {
+        Self { barrier, state: WriterState::Clean }
+    }
+
+    /// Runs `f`, which stores the payload.
+    /// 执行存储负载的 `f`。
+    #[inline]
+    pub fn write<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        self.state = WriterState::Written;
+        f()
+    }
+
+    /// Issues the heavy barrier, ordering every earlier payload write before the publish.
+    /// 执行重型屏障，使之前的所有负载写入先于发布。
+    #[inline]
+    pub fn heavy(&mut self) {
+        self.barrier.heavy();
+        if self.state == WriterState::Written {
+            self.state = WriterState::Fenced;
+        }
+    }
+
+    /// Runs `f`, which stores the value readers observe.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if a payload write has not been followed by [`heavy`](Self::heavy).
+    ///
+    /// ---
+    ///
+    /// 执行存储读者所观察值的 `f`。
+    ///
+    /// # Panics
+    ///
+    /// 在调试构建中，若某次负载写入之后没有调用 [`heavy`](Self::heavy)。
+    #[inline]
+    #[track_caller]
+    pub fn publish<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        debug_assert!(
+            self.state != WriterState::Written,
+            "swmr-barrier: publish after a payload write with no heavy barrier in between"
+        );
+        self.state = WriterState::Clean;
+        f()
+    }
+}
+
+impl<B: AsymmetricBarrier> fmt::Debug for WriterSequence<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriterSequence").field("state", &self.state).finish_non_exhaustive()
+    }
+}
+
+/// **Reader Sequence**
+///
+/// Runs the reader side of a hand-rolled publication through the steps it must follow:
+/// [`observe`](Self::observe) the published value, [`light`](Self::light), then
+/// [`read`](Self::read) the payload. In debug builds, reading the payload before the light barrier
+/// that follows the observation panics at the caller's `read` line. Release builds skip the check
+/// entirely.
+///
+/// ---
+///
+/// **读者序列**
+///
+/// 按读者必须遵循的步骤执行手写发布的读侧：先 [`observe`](Self::observe) 已发布的值，再 [`light`](Self::light)，
+/// 最后 [`read`](Self::read) 负载。在调试构建中，若在观察之后的轻量屏障之前读取负载，会在调用方的 `read` 行
+/// panic。发布构建完全跳过该检查。
+pub struct ReaderSequence<B: AsymmetricBarrier = SystemBarrier> {
+    barrier: B,
+    state: ReaderState,
+}
+
+impl ReaderSequence {
+    /// Creates a sequence using the process-wide barrier.
+    /// 使用进程级屏障创建序列。
+    pub const fn new() -> Self {
+        Self::with_barrier(SystemBarrier)
+    }
+}
+
+impl Default for ReaderSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: AsymmetricBarrier> ReaderSequence<B> {
+    /// Creates a sequence using a custom barrier.
+    /// 使用自定义屏障创建序列。
+    pub const fn with_barrier(barrier: B) -> Self {
+        Self { barrier, state: ReaderState::Clean }
+    }
+
+    /// Runs `f`, which loads the published value.
+    /// 执行加载已发布值的 `f`。
+    #[inline]
+    pub fn observe<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        self.state = ReaderState::Observed;
+        f()
+    }
+
+    /// Issues the light barrier, ordering the observation before every later payload read.
+    /// 执行轻量屏障，使观察先于之后的所有负载读取。
+    #[inline]
+    pub fn light(&mut self) {
+        self.barrier.light();
+        if self.state == ReaderState::Observed {
+            self.state = ReaderState::Fenced;
+        }
+    }
+
+    /// Runs `f`, which loads the payload.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if the last [`observe`](Self::observe) has not been followed by
+    /// [`light`](Self::light).
+    ///
+    /// ---
+    ///
+    /// 执行加载负载的 `f`。
+    ///
+    /// # Panics
+    ///
+    /// 在调试构建中，若最近一次 [`observe`](Self::observe) 之后没有调用 [`light`](Self::light)。
+    #[inline]
+    #[track_caller]
+    pub fn read<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        debug_assert!(
+            self.state == ReaderState::Fenced,
+            "swmr-barrier: payload read with no light barrier after observing the publish"
+        );
+        f()
+    }
+}
+
+impl<B: AsymmetricBarrier> fmt::Debug for ReaderSequence<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReaderSequence").field("state", &self.state).finish_non_exhaustive()
+    }
+}
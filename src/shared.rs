@@ -0,0 +1,80 @@
+//! **Shared Cell Handle** (feature `std`)
+//!
+//! A cloneable, `Arc`-backed handle to a [`SwmrCell`], so threads can each own a copy instead of
+//! wrapping the cell in `Arc` by hand.
+//!
+//! ---
+//!
+//! **共享单元句柄**（`std` 特性）
+//!
+//! 基于 `Arc` 的 [`SwmrCell`] 可克隆句柄，使各线程各自持有一份副本，无需手动用 `Arc` 包装单元。
+
+use crate::cell::SwmrCell;
+use core::fmt;
+use std::sync::Arc;
+
+/// **Shared SWMR Cell**
+///
+/// A [`SwmrCell`] behind an `Arc`: cloning the handle is cheap and every clone refers to the same
+/// cell. Hand one clone to the writer and one to each reader. Created with [`shared()`].
+///
+/// [`publish`](Self::publish) and [`load`](Self::load) are [`SwmrCell::set`] and
+/// [`SwmrCell::get`]; [`cell`](Self::cell) borrows the cell for the rest of its API.
+///
+/// ---
+///
+/// **共享 SWMR 单元**
+///
+/// 位于 `Arc` 之后的 [`SwmrCell`]：克隆句柄开销很小，所有克隆指向同一个单元。将一个克隆交给写者，其余交给各读者。
+/// 由 [`shared()`] 创建。
+///
+/// [`publish`](Self::publish) 与 [`load`](Self::load) 即 [`SwmrCell::set`] 与 [`SwmrCell::get`]；
+/// [`cell`](Self::cell) 借出单元以使用其余 API。
+pub struct SharedSwmrCell<T: Copy> {
+    cell: Arc<SwmrCell<T>>,
+}
+
+/// Creates a [`SharedSwmrCell`] holding `initial`.
+/// 创建持有 `initial` 的 [`SharedSwmrCell`]。
+pub fn shared<T: Copy>(initial: T) -> SharedSwmrCell<T> {
+    SharedSwmrCell {
+        cell: Arc::new(SwmrCell::new(initial)),
+    }
+}
+
+impl<T: Copy> SharedSwmrCell<T> {
+    /// Publishes `value` to every handle. Concurrent writers are serialized.
+    /// 向所有句柄发布 `value`。并发写者会被串行化。
+    #[inline]
+    pub fn publish(&self, value: T) {
+        self.cell.set(value);
+    }
+
+    /// Returns a copy of the current value.
+    /// 返回当前值的拷贝。
+    #[inline]
+    pub fn load(&self) -> T {
+        self.cell.get()
+    }
+
+    /// Borrows the underlying cell.
+    /// 借用底层单元。
+    #[inline]
+    pub fn cell(&self) -> &SwmrCell<T> {
+        &self.cell
+    }
+}
+
+impl<T: Copy> Clone for SharedSwmrCell<T> {
+    fn clone(&self) -> Self {
+        Self {
+            cell: Arc::clone(&self.cell),
+        }
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for SharedSwmrCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedSwmrCell").field(&self.load()).finish()
+    }
+}
@@ -0,0 +1,275 @@
+use crate::barrier::AsymmetricBarrier;
+use crate::cache_padded::CachePadded;
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering, fence};
+use crate::sync::{ConstPtr, UnsafeCell, spin_loop};
+#[cfg(not(feature = "loom"))]
+use core::mem::MaybeUninit;
+
+/// **Slot Ring**
+///
+/// `SLOTS` buffers of which one is active. Readers pin the active slot with a per-slot count; the
+/// writer fills the slot after the active one and advances the index on [`commit`](Self::commit),
+/// waiting only for readers still pinning that slot. Backs [`DoubleBuffer`](crate::DoubleBuffer)
+/// (`SLOTS = 2`) and [`SwmrCell`](crate::SwmrCell).
+///
+/// The slot the writer fills was last active `SLOTS - 1` commits ago, so more slots give slow
+/// readers longer to leave before the writer has to wait for them.
+///
+/// ---
+///
+/// **槽环**
+///
+/// 由 `SLOTS` 个缓冲区组成，其中一个为活动槽。读者以每槽计数固定活动槽；写者填充活动槽之后的槽并在
+/// [`commit`](Self::commit) 时推进索引，只等待仍固定该槽的读者。为 [`DoubleBuffer`](crate::DoubleBuffer)
+/// （`SLOTS = 2`）与 [`SwmrCell`](crate::SwmrCell) 提供实现。
+///
+/// 写者填充的槽上一次活动是在 `SLOTS - 1` 次提交之前，因此槽越多，慢速读者在写者必须等待之前就有越多时间离开。
+///
+/// Every buffer, every slot's bookkeeping and the active index sit on their own cache lines, so
+/// filling one slot or bumping its reader count never invalidates the line readers of another use.
+///
+/// 每个缓冲区、每个槽的簿记信息以及活动索引都位于各自的缓存行上，因此填充某个槽或递增其读者计数永远不会使
+/// 其他槽的读者所用的缓存行失效。
+///
+/// `repr(C)`, so [`SwmrCell`](crate::SwmrCell) has a layout that every build of the same version
+/// agrees on and can live in memory shared between processes.
+///
+/// `repr(C)`，使 [`SwmrCell`](crate::SwmrCell) 的布局在同一版本的每次构建中一致，可放入进程间共享的内存。
+#[repr(C)]
+pub(crate) struct SlotRing<T, const SLOTS: usize, B: AsymmetricBarrier> {
+    buffers: [CachePadded<UnsafeCell<T>>; SLOTS],
+    slots: [CachePadded<SlotState>; SLOTS],
+    active: CachePadded<AtomicUsize>,
+    writing: AtomicBool,
+    barrier: B,
+}
+
+/// Per-slot bookkeeping, kept on the slot's own cache line.
+/// 每个槽的簿记信息，位于该槽自己的缓存行上。
+#[repr(C)]
+struct SlotState {
+    readers: AtomicUsize,
+    // Commit count of the slot's contents, wrapping; the active slot's is the current version.
+    // 该槽内容对应的提交计数（回绕）；活动槽的计数即当前版本。
+    version: AtomicUsize,
+}
+
+// SAFETY: Readers share `&T` across threads (`T: Sync`); the writer moves values in and drops old
+// ones on its own thread (`T: Send`). Buffer access is serialized by the reader counts.
+// `Send` is derived and needs only `T: Send`.
+// 安全性：读者跨线程共享 `&T`（`T: Sync`）；写者移入新值并在自身线程释放旧值（`T: Send`）。
+// `Send` 自动推导，只需 `T: Send`。
+unsafe impl<T: Send + Sync, const SLOTS: usize, B: AsymmetricBarrier + Sync> Sync for SlotRing<T, SLOTS, B> {}
+
+impl<T: Clone, const SLOTS: usize, B: AsymmetricBarrier> SlotRing<T, SLOTS, B> {
+    /// Creates a ring with every slot holding `initial`.
+    /// 创建每个槽均持有 `initial` 的环。
+    pub(crate) fn new(initial: T, barrier: B) -> Self {
+        const { assert!(SLOTS >= 2, "a slot ring needs at least two slots") };
+        // Clone into all but the last slot, which takes `initial` itself.
+        // 克隆到除最后一个槽以外的所有槽，最后一个槽直接接收 `initial`。
+        let mut initial = Some(initial);
+        Self {
+            buffers: core::array::from_fn(|i| {
+                let value = if i + 1 == SLOTS { initial.take() } else { initial.clone() };
+                CachePadded::new(UnsafeCell::new(value.expect("the last slot is filled last")))
+            }),
+            slots: core::array::from_fn(|_| {
+                CachePadded::new(SlotState {
+                    readers: AtomicUsize::new(0),
+                    version: AtomicUsize::new(0),
+                })
+            }),
+            active: CachePadded::new(AtomicUsize::new(0)),
+            writing: AtomicBool::new(false),
+            barrier,
+        }
+    }
+}
+
+// Loom's atomics have no `const fn` constructors.
+// Loom 的原子类型没有 `const fn` 构造函数。
+#[cfg(not(feature = "loom"))]
+impl<T: Copy, const SLOTS: usize, B: AsymmetricBarrier> SlotRing<T, SLOTS, B> {
+    /// [`new`](Self::new) as a `const fn`, for `static`s. `T: Copy` lets every slot take a copy
+    /// without calling `Clone`.
+    ///
+    /// `const fn` 版本的 [`new`](Self::new)，用于 `static`。`T: Copy` 使每个槽无需调用 `Clone` 即可获得一份拷贝。
+    pub(crate) const fn new_const(initial: T, barrier: B) -> Self {
+        const { assert!(SLOTS >= 2, "a slot ring needs at least two slots") };
+        // `array::from_fn` is not `const`: fill the arrays slot by slot, then read them out whole.
+        // `array::from_fn` 不是 `const`：逐槽填充数组，再整体读出。
+        let mut buffers = [const { MaybeUninit::<CachePadded<UnsafeCell<T>>>::uninit() }; SLOTS];
+        let mut slots = [const { MaybeUninit::<CachePadded<SlotState>>::uninit() }; SLOTS];
+        let mut i = 0;
+        while i < SLOTS {
+            buffers[i] = MaybeUninit::new(CachePadded::new(UnsafeCell::new(initial)));
+            slots[i] = MaybeUninit::new(CachePadded::new(SlotState {
+                readers: AtomicUsize::new(0),
+                version: AtomicUsize::new(0),
+            }));
+            i += 1;
+        }
+        // SAFETY: Every element was written above, and `[MaybeUninit<X>; SLOTS]` has the layout of `[X; SLOTS]`.
+        // 安全性：上面已写入每个元素，且 `[MaybeUninit<X>; SLOTS]` 与 `[X; SLOTS]` 布局相同。
+        unsafe {
+            Self {
+                buffers: (&raw const buffers).cast::<[CachePadded<UnsafeCell<T>>; SLOTS]>().read(),
+                slots: (&raw const slots).cast::<[CachePadded<SlotState>; SLOTS]>().read(),
+                active: CachePadded::new(AtomicUsize::new(0)),
+                writing: AtomicBool::new(false),
+                barrier,
+            }
+        }
+    }
+}
+
+impl<T, const SLOTS: usize, B: AsymmetricBarrier> SlotRing<T, SLOTS, B> {
+    /// Pins the active slot and returns its index and buffer. The buffer is not written until
+    /// [`unpin`](Self::unpin) is called with the same index.
+    ///
+    /// 固定活动槽并返回其索引与缓冲区。以同一索引调用 [`unpin`](Self::unpin) 之前该缓冲区不会被写入。
+    #[inline]
+    pub(crate) fn pin(&self) -> (usize, ConstPtr<T>) {
+        let (index, ptr, _) = self.pin_with(|_| false);
+        (index, ptr)
+    }
+
+    /// [`pin`](Self::pin), also returning how many attempts found the slot already moved on.
+    /// Before each attempt `full_fence(retries_so_far)` chooses between `light()` and a
+    /// `fence(SeqCst)`, which pairs with `heavy()` on any backend.
+    ///
+    /// [`pin`](Self::pin)，并额外返回有多少次尝试发现槽已被移走。每次尝试前由 `full_fence(已重试次数)`
+    /// 在 `light()` 与 `fence(SeqCst)` 之间选择；后者在任何后端上都能与 `heavy()` 配对。
+    #[inline]
+    pub(crate) fn pin_with(&self, mut full_fence: impl FnMut(u32) -> bool) -> (usize, ConstPtr<T>, u32) {
+        let mut retries = 0u32;
+        loop {
+            let index = self.active.load(Ordering::Acquire);
+            // Start fetching the slot now, so the miss overlaps the pin and the barrier.
+            // 现在就开始取该槽，使缓存未命中与固定操作及屏障重叠。
+            #[cfg(feature = "prefetch")]
+            crate::prefetch_read(&*self.buffers[index]);
+            self.slots[index].readers.fetch_add(1, Ordering::Relaxed);
+
+            // Pairs with the writer's `heavy()` before it inspects the count: either the writer
+            // sees our increment, or we see that the slot is no longer active.
+            // 与写者检查计数前的 `heavy()` 配对：要么写者看到我们的递增，要么我们看到该槽已不再活动。
+            if full_fence(retries) {
+                fence(Ordering::SeqCst);
+            } else {
+                self.barrier.light();
+            }
+
+            // Acquire: the index may have moved away and back (ABA), in which case the slot now
+            // holds a newer commit whose contents we must synchronize with.
+            // Acquire：索引可能已移走又移回 (ABA)，此时该槽保存的是更新的提交，必须与其内容同步。
+            if self.active.load(Ordering::Acquire) == index {
+                return (index, self.buffers[index].get(), retries);
+            }
+            self.slots[index].readers.fetch_sub(1, Ordering::Release);
+            retries = retries.saturating_add(1);
+        }
+    }
+
+    /// Version of the slot pinned at `index`: the number of commits (wrapping) that produced its contents.
+    /// 固定于 `index` 的槽的版本：产生其内容的提交次数（回绕）。
+    #[inline]
+    pub(crate) fn pinned_version(&self, index: usize) -> usize {
+        // Stored before the `active` store that our `pin` acquired, and not rewritten while pinned.
+        // 在 `pin` 所获取的 `active` 存储之前写入，且在固定期间不会被改写。
+        self.slots[index].version.load(Ordering::Relaxed)
+    }
+
+    /// Current version without pinning. May lag a concurrent commit, or run one ahead of `active`
+    /// if the index just moved; either way a change shows up as a mismatch with an earlier version.
+    ///
+    /// 不固定槽即可读取的当前版本。可能落后于并发提交，或在索引刚移动时领先 `active` 一次；无论哪种情况，
+    /// 变化都会表现为与早先版本不一致。
+    #[inline]
+    pub(crate) fn version(&self) -> usize {
+        self.slots[self.active.load(Ordering::Acquire)].version.load(Ordering::Relaxed)
+    }
+
+    /// Releases a slot pinned by [`pin`](Self::pin). Every borrow of its buffer must have ended.
+    /// 释放由 [`pin`](Self::pin) 固定的槽。对其缓冲区的所有借用必须已经结束。
+    #[inline]
+    pub(crate) fn unpin(&self, index: usize) {
+        // Release: our reads of the buffer happen before the writer's next overwrite.
+        // Release：对缓冲区的读取先于写者的下一次覆盖。
+        self.slots[index].readers.fetch_sub(1, Ordering::Release);
+    }
+
+    /// Writes `value` into the slot after the active one and makes it active. Waits for readers
+    /// still pinning that slot. Concurrent writers are serialized.
+    ///
+    /// 将 `value` 写入活动槽之后的槽并将其设为活动槽。会等待仍固定该槽的读者。并发写者会被串行化。
+    #[inline]
+    pub(crate) fn commit(&self, value: T) {
+        self.commit_with(|_| value);
+    }
+
+    /// Like [`commit`](Self::commit), with the value computed by `f` from the active slot while
+    /// other writers are held off, so no commit lands between the read and the write.
+    ///
+    /// 同 [`commit`](Self::commit)，但值由 `f` 基于活动槽计算，期间其他写者被阻挡，因此读取与写入之间不会插入其他提交。
+    #[inline]
+    pub(crate) fn commit_with(&self, f: impl FnOnce(&T) -> T) {
+        self.try_commit_with(|current| Some(f(current)));
+    }
+
+    /// Like [`commit_with`](Self::commit_with), but commits nothing, not even the heavy barrier,
+    /// when `f` returns `None`. Returns whether it committed.
+    ///
+    /// 同 [`commit_with`](Self::commit_with)，但 `f` 返回 `None` 时不提交任何内容，连重型屏障也不执行。返回是否已提交。
+    pub(crate) fn try_commit_with(&self, f: impl FnOnce(&T) -> Option<T>) -> bool {
+        while self
+            .writing
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            spin_loop();
+        }
+        // Release the writer lock even if `f` panics.
+        // 即使 `f` panic 也释放写者锁。
+        let _unlock = Unlock(&self.writing);
+
+        let active = self.active.load(Ordering::Relaxed);
+        let value = {
+            let current = self.buffers[active].get();
+            // SAFETY: Only the writer writes buffers, and never the active one; readers only read it.
+            // 安全性：只有写者写入缓冲区，且从不写入活动槽；读者只读取它。
+            match f(unsafe { current.deref() }) {
+                Some(value) => value,
+                None => return false,
+            }
+        };
+        let next = (active + 1) % SLOTS;
+        let version = self.slots[active].version.load(Ordering::Relaxed).wrapping_add(1);
+
+        // Make the previous advance visible to every reader that may still enter `next`.
+        // 使上一次推进对所有仍可能进入 `next` 的读者可见。
+        self.barrier.heavy();
+        while self.slots[next].readers.load(Ordering::Acquire) != 0 {
+            spin_loop();
+        }
+
+        // SAFETY: No reader pins `next`, and new readers retry until it becomes active.
+        // 安全性：没有读者固定 `next`，新读者会重试直到其成为活动槽。
+        self.buffers[next].get_mut().with(|ptr| unsafe { *ptr = value });
+        self.slots[next].version.store(version, Ordering::Relaxed);
+
+        self.active.store(next, Ordering::Release);
+        true
+    }
+}
+
+/// Clears the writer flag on drop.
+/// 析构时清除写者标志。
+pub(crate) struct Unlock<'a>(pub(crate) &'a AtomicBool);
+
+impl Drop for Unlock<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
@@ -0,0 +1,514 @@
+use crate::topology::TopologyHint;
+use core::fmt;
+
+/// **Barrier Backend**
+///
+/// The mechanism currently servicing `heavy_barrier()` / `light_barrier()`.
+///
+/// ---
+///
+/// **屏障后端**
+///
+/// 当前为 `heavy_barrier()` / `light_barrier()` 提供服务的机制。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Backend {
+    /// Linux `MEMBARRIER_CMD_PRIVATE_EXPEDITED` (4.14+).
+    LinuxPrivateExpedited,
+    /// Linux `MEMBARRIER_CMD_SHARED` (4.3+).
+    LinuxShared,
+    /// Windows `FlushProcessWriteBuffers` (Vista+).
+    WindowsFlushProcessWriteBuffers,
+    /// A TLB shootdown forced by downgrading a helper page with `mprotect` (DragonFly BSD).
+    MprotectTlbShootdown,
+    /// `fence(SeqCst)` on both sides.
+    Fallback,
+    /// `compiler_fence` on both sides: the target cannot run threads (e.g. WASI without `atomics`).
+    SingleThreaded,
+    /// Loom model checking.
+    Loom,
+    /// `fence(SeqCst)` on both sides so ThreadSanitizer sees the synchronization (`-Zsanitizer=thread`).
+    /// Production builds of the same target may use an accelerated backend instead.
+    ThreadSanitizer,
+}
+
+impl Backend {
+    /// **Stable Name**
+    ///
+    /// A short kebab-case name for metrics labels and log grouping. The strings never change
+    /// between versions; new variants get new names.
+    ///
+    /// ---
+    ///
+    /// **稳定名称**
+    ///
+    /// 用于指标标签与日志分组的短 kebab-case 名称。这些字符串在各版本间保持不变；新变体使用新名称。
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Backend::LinuxPrivateExpedited => "linux-private-expedited",
+            Backend::LinuxShared => "linux-shared",
+            Backend::WindowsFlushProcessWriteBuffers => "windows-fpwb",
+            Backend::MprotectTlbShootdown => "mprotect",
+            Backend::Fallback => "fallback",
+            Backend::SingleThreaded => "single-threaded",
+            Backend::Loom => "loom",
+            Backend::ThreadSanitizer => "thread-sanitizer",
+        }
+    }
+
+    /// Whether this backend is OS-accelerated (see [`is_accelerated()`](crate::is_accelerated)).
+    /// 该后端是否为 OS 加速后端。
+    #[inline]
+    pub const fn is_accelerated(self) -> bool {
+        matches!(
+            self,
+            Backend::LinuxPrivateExpedited
+                | Backend::LinuxShared
+                | Backend::WindowsFlushProcessWriteBuffers
+                | Backend::MprotectTlbShootdown
+        )
+    }
+
+    /// What `light_barrier()` emits with this backend.
+    /// 该后端下 `light_barrier()` 产生的屏障。
+    #[inline]
+    pub const fn light_fence(self) -> FenceStrength {
+        match self {
+            Backend::LinuxPrivateExpedited
+            | Backend::LinuxShared
+            | Backend::WindowsFlushProcessWriteBuffers
+            | Backend::MprotectTlbShootdown
+            | Backend::SingleThreaded => FenceStrength::Compiler,
+            Backend::Fallback | Backend::Loom | Backend::ThreadSanitizer => FenceStrength::Full,
+        }
+    }
+
+    /// What `heavy_barrier()` costs with this backend.
+    /// 该后端下 `heavy_barrier()` 的开销类别。
+    #[inline]
+    pub const fn heavy_cost(self) -> CostClass {
+        match self {
+            Backend::LinuxPrivateExpedited
+            | Backend::LinuxShared
+            | Backend::WindowsFlushProcessWriteBuffers
+            | Backend::MprotectTlbShootdown => CostClass::Ipi,
+            Backend::Fallback | Backend::Loom | Backend::ThreadSanitizer => CostClass::Fence,
+            Backend::SingleThreaded => CostClass::Free,
+        }
+    }
+}
+
+/// **Initialization Reason**
+///
+/// Why detection selected the current backend.
+///
+/// ---
+///
+/// **初始化原因**
+///
+/// 检测选择当前后端的原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum InitReason {
+    /// Detection has not run yet.
+    /// 检测尚未运行。
+    Pending,
+    /// An OS-accelerated mechanism was selected.
+    /// 已选择 OS 加速机制。
+    Accelerated,
+    /// The capability query failed (e.g. `ENOSYS`, or `EPERM` from a seccomp filter).
+    /// 能力查询失败（例如 `ENOSYS`，或 seccomp 过滤器导致的 `EPERM`）。
+    QueryFailed,
+    /// The OS does not provide a usable mechanism (e.g. Linux < 4.3, Windows < Vista).
+    /// 操作系统不提供可用机制（例如 Linux < 4.3、Windows < Vista）。
+    NotSupported,
+    /// No accelerated backend exists for this platform or configuration.
+    /// 该平台或配置没有加速后端。
+    Unsupported,
+    /// The mechanism was advertised but rejected the validation barrier issued by `init()`.
+    /// 机制已声明支持，但拒绝了 `init()` 发起的验证屏障。
+    ValidationFailed,
+    /// A heavy barrier overran the deadline given to `heavy_barrier_with_deadline()`.
+    /// 某次重型屏障超出了传给 `heavy_barrier_with_deadline()` 的期限。
+    WatchdogTripped,
+    /// The kernel release is on the `strict` feature's denylist of known-broken `membarrier`s.
+    /// 内核发行版本位于 `strict` 特性的已知 `membarrier` 缺陷拒绝列表中。
+    KernelDenylisted,
+    /// [`shutdown()`](crate::shutdown) returned the process to the fence fallback.
+    /// [`shutdown()`](crate::shutdown) 已将进程切回栅栏回退路径。
+    Shutdown,
+    /// `SWMR_BARRIER_MODE=fallback` disabled membarrier although the kernel may support it.
+    /// `SWMR_BARRIER_MODE=fallback` 禁用了 membarrier，尽管内核可能支持它。
+    ModeOverride,
+}
+
+impl InitReason {
+    /// Stable kebab-case name, like [`Backend::as_str`].
+    /// 稳定的 kebab-case 名称，同 [`Backend::as_str`]。
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            InitReason::Pending => "pending",
+            InitReason::Accelerated => "accelerated",
+            InitReason::QueryFailed => "query-failed",
+            InitReason::NotSupported => "not-supported",
+            InitReason::Unsupported => "unsupported",
+            InitReason::ValidationFailed => "validation-failed",
+            InitReason::WatchdogTripped => "watchdog-tripped",
+            InitReason::KernelDenylisted => "kernel-denylisted",
+            InitReason::Shutdown => "shutdown",
+            InitReason::ModeOverride => "mode-override",
+        }
+    }
+
+    /// Compact encoding for storage in an atomic.
+    #[cfg_attr(any(feature = "loom", all(swmr_barrier_tsan, not(feature = "tsan-annotate")), not(target_os = "linux")), allow(dead_code))]
+    pub(crate) const fn to_u8(self) -> u8 {
+        self as u8
+    }
+
+    #[cfg_attr(any(feature = "loom", all(swmr_barrier_tsan, not(feature = "tsan-annotate")), not(target_os = "linux")), allow(dead_code))]
+    pub(crate) const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => InitReason::Accelerated,
+            2 => InitReason::QueryFailed,
+            3 => InitReason::NotSupported,
+            4 => InitReason::Unsupported,
+            5 => InitReason::ValidationFailed,
+            6 => InitReason::WatchdogTripped,
+            7 => InitReason::KernelDenylisted,
+            8 => InitReason::Shutdown,
+            9 => InitReason::ModeOverride,
+            _ => InitReason::Pending,
+        }
+    }
+}
+
+/// **Validation Outcome**
+///
+/// Result of the validation barrier that the explicit [`init()`](crate::init) issues after detection.
+///
+/// ---
+///
+/// **验证结果**
+///
+/// 显式 [`init()`](crate::init) 在检测后发起的验证屏障的结果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Validation {
+    /// No validation barrier has been issued (automatic detection only, or non-Linux platforms).
+    /// 尚未发起验证屏障（仅自动检测，或非 Linux 平台）。
+    NotRun,
+    /// The selected mechanism executed a real barrier successfully.
+    /// 所选机制成功执行了一次真实屏障。
+    Passed,
+    /// A validation barrier failed with this errno; the backend was downgraded.
+    /// 验证屏障以该 errno 失败；后端已降级。
+    Failed(i32),
+}
+
+impl Validation {
+    /// Stable kebab-case name, like [`Backend::as_str`]. The errno of `Failed` is not included.
+    /// 稳定的 kebab-case 名称，同 [`Backend::as_str`]。不包含 `Failed` 的 errno。
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Validation::NotRun => "not-run",
+            Validation::Passed => "passed",
+            Validation::Failed(_) => "failed",
+        }
+    }
+}
+
+/// **Fence Strength**
+///
+/// The kind of fence a barrier emits.
+///
+/// ---
+///
+/// **屏障强度**
+///
+/// 屏障实际产生的栅栏类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum FenceStrength {
+    /// `compiler_fence` only: no CPU instruction.
+    /// 仅 `compiler_fence`：不产生 CPU 指令。
+    Compiler,
+    /// A hardware `fence`.
+    /// 硬件 `fence`。
+    Full,
+}
+
+impl FenceStrength {
+    /// Stable kebab-case name, like [`Backend::as_str`].
+    /// 稳定的 kebab-case 名称，同 [`Backend::as_str`]。
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            FenceStrength::Compiler => "compiler",
+            FenceStrength::Full => "full",
+        }
+    }
+}
+
+/// **Cost Class**
+///
+/// Rough cost class of a barrier.
+///
+/// ---
+///
+/// **开销类别**
+///
+/// 屏障的大致开销类别。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum CostClass {
+    /// No CPU instruction (compiler fence only).
+    /// 不产生 CPU 指令（仅编译器屏障）。
+    Free,
+    /// A local hardware fence.
+    /// 本地硬件屏障。
+    Fence,
+    /// A system call broadcasting inter-processor interrupts.
+    /// 广播处理器间中断 (IPI) 的系统调用。
+    Ipi,
+}
+
+impl CostClass {
+    /// Stable kebab-case name, like [`Backend::as_str`].
+    /// 稳定的 kebab-case 名称，同 [`Backend::as_str`]。
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            CostClass::Free => "free",
+            CostClass::Fence => "fence",
+            CostClass::Ipi => "ipi",
+        }
+    }
+}
+
+/// **Barrier Statistics**
+///
+/// A snapshot of the crate's runtime detection results, returned by [`stats()`](crate::stats).
+///
+/// ---
+///
+/// **屏障统计**
+///
+/// crate 运行时检测结果的快照，由 [`stats()`](crate::stats) 返回。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct BarrierStats {
+    /// Active backend.
+    /// 当前后端。
+    pub backend: Backend,
+    /// Why the backend was selected.
+    /// 选择该后端的原因。
+    pub init_reason: InitReason,
+    /// What `light_barrier()` emits. Normally `backend.light_fence()`, but on Linux readers keep a
+    /// full fence until a barrier with the selected command has succeeded once.
+    /// `light_barrier()` 产生的屏障。通常为 `backend.light_fence()`，但在 Linux 上，所选命令的屏障成功执行一次之前读者保持完整栅栏。
+    pub light: FenceStrength,
+    /// What `heavy_barrier()` costs.
+    /// `heavy_barrier()` 的开销类别。
+    pub heavy: CostClass,
+    /// See [`registration_errno()`](crate::registration_errno).
+    pub registration_errno: Option<i32>,
+    /// Outcome of the validation barrier issued by [`init()`](crate::init).
+    /// [`init()`](crate::init) 发起的验证屏障的结果。
+    pub validation: Validation,
+    /// See [`sync_core_available()`](crate::sync_core_available).
+    pub sync_core: bool,
+    /// See [`cpu_topology_hint()`](crate::cpu_topology_hint); read once, on the first `stats()` call.
+    /// 参见 [`cpu_topology_hint()`](crate::cpu_topology_hint)；仅在首次调用 `stats()` 时读取一次。
+    pub topology: Option<TopologyHint>,
+    /// See [`running_under_qemu_user()`](crate::running_under_qemu_user).
+    pub qemu_user: bool,
+    /// Slowest `heavy_barrier_with_deadline()` call so far, in nanoseconds (`None` if never called
+    /// or unavailable).
+    /// 迄今最慢的一次 `heavy_barrier_with_deadline()` 调用耗时（纳秒；从未调用或不可用时为 `None`）。
+    pub slowest_heavy_ns: Option<u64>,
+}
+
+impl BarrierStats {
+    /// **Write as JSON**
+    ///
+    /// Writes the snapshot as one JSON object, for structured logs and startup diagnostics.
+    /// Works without `std` or `serde`. The output is what the `serde` feature's derive produces
+    /// through `serde_json`: field names as keys, unit variants as strings,
+    /// `Validation::Failed(e)` as `{"Failed":e}` and `None` as `null`.
+    ///
+    /// ---
+    ///
+    /// **以 JSON 写出**
+    ///
+    /// 将快照写为一个 JSON 对象，用于结构化日志与启动诊断。无需 `std` 或 `serde`。输出与 `serde` 特性的派生经
+    /// `serde_json` 产生的结果一致：字段名作为键，单元变体为字符串，`Validation::Failed(e)` 为 `{"Failed":e}`，
+    /// `None` 为 `null`。
+    pub fn write_json(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        write!(
+            out,
+            r#"{{"backend":"{:?}","init_reason":"{:?}","light":"{:?}","heavy":"{:?}","registration_errno":"#,
+            self.backend, self.init_reason, self.light, self.heavy
+        )?;
+        match self.registration_errno {
+            Some(errno) => write!(out, "{errno}")?,
+            None => out.write_str("null")?,
+        }
+        match self.validation {
+            Validation::Failed(errno) => write!(out, r#","validation":{{"Failed":{errno}}}"#)?,
+            validation => write!(out, r#","validation":"{validation:?}""#)?,
+        }
+        write!(out, r#","sync_core":{},"topology":"#, self.sync_core)?;
+        match self.topology {
+            Some(TopologyHint { cpus, packages, clusters, capacity_classes }) => write!(
+                out,
+                r#"{{"cpus":{cpus},"packages":{packages},"clusters":{clusters},"capacity_classes":{capacity_classes}}}"#
+            )?,
+            None => out.write_str("null")?,
+        }
+        write!(out, r#","qemu_user":{},"slowest_heavy_ns":"#, self.qemu_user)?;
+        match self.slowest_heavy_ns {
+            Some(ns) => write!(out, "{ns}}}"),
+            None => out.write_str("null}"),
+        }
+    }
+}
+
+/// **Runtime Telemetry**
+///
+/// Counters and health for a metrics scrape, returned by
+/// [`runtime_telemetry()`](crate::runtime_telemetry). The three counts need the `telemetry`
+/// feature and are 0 without it; `max_latency_ns` needs `latency` (or a
+/// `heavy_barrier_with_deadline()` call) and `generation` 64-bit atomics.
+///
+/// ---
+///
+/// **运行时遥测**
+///
+/// 供指标采集使用的计数器与健康状态，由 [`runtime_telemetry()`](crate::runtime_telemetry) 返回。三个计数需要
+/// `telemetry` 特性，未启用时为 0；`max_latency_ns` 需要 `latency`（或调用过 `heavy_barrier_with_deadline()`），
+/// `generation` 需要 64 位原子操作。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub struct RuntimeTelemetry {
+    /// Heavy barriers issued through the process-wide barrier.
+    /// 通过进程级屏障发起的重型屏障次数。
+    pub heavy_count: u64,
+    /// Light barriers issued through the process-wide barrier.
+    /// 通过进程级屏障发起的轻型屏障次数。
+    pub light_count: u64,
+    /// Heavy barriers that ordered memory with `fence(SeqCst)`, either because no OS barrier is
+    /// active or because the call degraded (see [`heavy_barrier_traced()`](crate::heavy_barrier_traced)).
+    /// 以 `fence(SeqCst)` 完成排序的重型屏障次数，原因可能是没有启用 OS 屏障，或单次调用发生降级
+    /// （见 [`heavy_barrier_traced()`](crate::heavy_barrier_traced)）。
+    pub fallback_count: u64,
+    /// Slowest OS barrier recorded so far, in nanoseconds (0 if none).
+    /// 迄今记录的最慢一次 OS 屏障耗时（纳秒；无记录时为 0）。
+    pub max_latency_ns: u64,
+    /// The platform has an accelerated backend, but barriers are on the fence fallback or at least
+    /// one heavy barrier fell back to a fence.
+    /// 平台具备加速后端，但屏障处于栅栏回退路径，或至少有一次重型屏障回退为栅栏。
+    pub degraded: bool,
+    /// See [`barrier_generation()`](crate::barrier_generation).
+    pub generation: u64,
+}
+
+/// **Platform Info**
+///
+/// What this binary was compiled to use, fixed by `cfg` before any runtime detection. See
+/// [`PLATFORM`](crate::PLATFORM). `can_accelerate` only means an OS mechanism is compiled in;
+/// [`stats()`](crate::stats) reports whether the running system provides it.
+///
+/// ---
+///
+/// **平台信息**
+///
+/// 本二进制在编译时选定可用的机制，由 `cfg` 决定，早于任何运行时检测。参见 [`PLATFORM`](crate::PLATFORM)。
+/// `can_accelerate` 只表示编译进了 OS 机制；运行中的系统是否提供该机制由 [`stats()`](crate::stats) 报告。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct PlatformInfo {
+    /// Whether an OS-accelerated barrier is compiled in (runtime may still fall back).
+    /// 是否编译进了 OS 加速屏障（运行时仍可能回退）。
+    pub can_accelerate: bool,
+    /// Target OS, as in `cfg(target_os)` (e.g. `"linux"`, `"windows"`, `"none"`).
+    /// 目标操作系统，与 `cfg(target_os)` 相同（例如 `"linux"`、`"windows"`、`"none"`）。
+    pub os: &'static str,
+    /// The mechanism behind `heavy_barrier()` when accelerated, or the fence used otherwise.
+    /// 加速时 `heavy_barrier()` 背后的机制，否则为所用的屏障。
+    pub mechanism: &'static str,
+}
+
+/// **Acceleration Matrix**
+///
+/// Which OS barrier variants are usable in this process, one flag each, as found by the last
+/// detection run. See [`acceleration_matrix()`](crate::acceleration_matrix). Flags for another
+/// platform's variants are always `false`, as is every flag after [`shutdown()`](crate::shutdown).
+///
+/// ---
+///
+/// **加速矩阵**
+///
+/// 本进程可用的各个 OS 屏障变体，每个变体一个标志，由最近一次检测得出。参见 [`acceleration_matrix()`](crate::acceleration_matrix)。
+/// 属于其他平台的变体标志始终为 `false`；[`shutdown()`](crate::shutdown) 之后所有标志也为 `false`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub struct AccelerationMatrix {
+    /// Linux: `MEMBARRIER_CMD_PRIVATE_EXPEDITED` is advertised and this process registered for it (4.14+).
+    /// Linux：内核声明支持 `MEMBARRIER_CMD_PRIVATE_EXPEDITED` 且本进程已完成注册 (4.14+)。
+    pub private_expedited: bool,
+    /// Linux: `MEMBARRIER_CMD_SHARED` is advertised (4.3+). Needs no registration.
+    /// Linux：内核声明支持 `MEMBARRIER_CMD_SHARED` (4.3+)。无需注册。
+    pub shared: bool,
+    /// Linux: `MEMBARRIER_CMD_GLOBAL` is advertised. It is `SHARED` under its 4.16 name, so it
+    /// always equals [`shared`](Self::shared).
+    /// Linux：内核声明支持 `MEMBARRIER_CMD_GLOBAL`。它就是 4.16 改名后的 `SHARED`，因此始终等于 [`shared`](Self::shared)。
+    pub global: bool,
+    /// Linux: `MEMBARRIER_CMD_GLOBAL_EXPEDITED` is advertised (4.16+). It only reaches processes
+    /// that registered with `MEMBARRIER_CMD_REGISTER_GLOBAL_EXPEDITED`, which this crate does not do.
+    /// Linux：内核声明支持 `MEMBARRIER_CMD_GLOBAL_EXPEDITED` (4.16+)。它只作用于以
+    /// `MEMBARRIER_CMD_REGISTER_GLOBAL_EXPEDITED` 注册过的进程，本 crate 不做此注册。
+    pub global_expedited: bool,
+    /// Same as [`sync_core_available()`](crate::sync_core_available): the Linux sync-core
+    /// membarrier, or `FlushInstructionCache` on Windows.
+    /// 与 [`sync_core_available()`](crate::sync_core_available) 相同：Linux 的 sync-core membarrier，或 Windows 的
+    /// `FlushInstructionCache`。
+    pub sync_core: bool,
+    /// Windows: `FlushProcessWriteBuffers` was resolved (Vista+).
+    /// Windows：已解析 `FlushProcessWriteBuffers` (Vista+)。
+    pub flush_process_write_buffers: bool,
+}
+
+impl AccelerationMatrix {
+    /// No variant usable.
+    /// 没有可用的变体。
+    pub(crate) const NONE: Self = Self {
+        private_expedited: false,
+        shared: false,
+        global: false,
+        global_expedited: false,
+        sync_core: false,
+        flush_process_write_buffers: false,
+    };
+}
+
+/// **Thread Registration Status**
+///
+/// Result of [`register_current_thread()`](crate::register_current_thread).
+///
+/// ---
+///
+/// **线程注册状态**
+///
+/// [`register_current_thread()`](crate::register_current_thread) 的返回结果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ThreadRegistration {
+    /// The active backend needs no per-thread setup (membarrier registration is process-wide).
+    /// 当前后端不需要逐线程设置（membarrier 注册是进程级的）。
+    NotRequired,
+}
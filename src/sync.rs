@@ -0,0 +1,115 @@
+//! Synchronization primitives used by the data structures.
+//!
+//! Under the `loom` feature these resolve to Loom's instrumented types so the
+//! structures can be model-checked; otherwise they are zero-cost `core` wrappers.
+//!
+//! 数据结构使用的同步原语。启用 `loom` 特性时解析为 Loom 的插桩类型以便模型检查，
+//! 否则为零开销的 `core` 包装。
+
+#[cfg(feature = "loom")]
+pub(crate) use loom::{
+    cell::{ConstPtr, UnsafeCell},
+    sync::atomic,
+};
+
+#[cfg(not(feature = "loom"))]
+pub(crate) mod atomic {
+    pub(crate) use core::sync::atomic::*;
+}
+
+/// Hint for spin-wait loops. Loom must be allowed to schedule another thread.
+/// 自旋等待提示。在 Loom 下必须允许调度其他线程。
+#[inline]
+pub(crate) fn spin_loop() {
+    #[cfg(feature = "loom")]
+    loom::thread::yield_now();
+    #[cfg(not(feature = "loom"))]
+    core::hint::spin_loop();
+}
+
+/// Exponential spin backoff for waits that may last a while: each [`snooze`](Self::snooze) spins
+/// twice as long as the last, up to `2^SPIN_LIMIT` hints. Never parks the thread, so it works in
+/// `no_std`. Under Loom every snooze is a single yield, to keep the model small.
+///
+/// 用于可能持续较久的等待的指数自旋退避：每次 [`snooze`](Self::snooze) 的自旋时长是上一次的两倍，
+/// 上限为 `2^SPIN_LIMIT` 次提示。从不挂起线程，因此可用于 `no_std`。在 Loom 下每次退避只让出一次，以保持模型规模较小。
+#[derive(Debug, Default)]
+pub(crate) struct Backoff {
+    step: u32,
+}
+
+impl Backoff {
+    const SPIN_LIMIT: u32 = 6;
+
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self { step: 0 }
+    }
+
+    #[inline]
+    pub(crate) fn snooze(&mut self) {
+        #[cfg(feature = "loom")]
+        spin_loop();
+        #[cfg(not(feature = "loom"))]
+        for _ in 0..1u32 << self.step {
+            spin_loop();
+        }
+        if self.step < Self::SPIN_LIMIT {
+            self.step += 1;
+        }
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+pub(crate) use self::cell::{ConstPtr, UnsafeCell};
+
+/// `core::cell::UnsafeCell` with Loom's tracked-pointer API.
+/// 提供 Loom 风格指针 API 的 `core::cell::UnsafeCell`。
+#[cfg(not(feature = "loom"))]
+mod cell {
+    #[derive(Debug)]
+    #[repr(transparent)]
+    pub(crate) struct UnsafeCell<T: ?Sized>(core::cell::UnsafeCell<T>);
+
+    impl<T> UnsafeCell<T> {
+        #[inline]
+        pub(crate) const fn new(value: T) -> Self {
+            Self(core::cell::UnsafeCell::new(value))
+        }
+    }
+
+    impl<T: ?Sized> UnsafeCell<T> {
+        #[inline]
+        pub(crate) fn get(&self) -> ConstPtr<T> {
+            ConstPtr(self.0.get())
+        }
+
+        #[inline]
+        pub(crate) fn get_mut(&self) -> MutPtr<T> {
+            MutPtr(self.0.get())
+        }
+    }
+
+    pub(crate) struct ConstPtr<T: ?Sized>(*const T);
+
+    impl<T: ?Sized> ConstPtr<T> {
+        /// # Safety
+        /// No mutable access may exist while the returned reference is alive.
+        #[inline]
+        pub(crate) unsafe fn deref(&self) -> &T {
+            unsafe { &*self.0 }
+        }
+    }
+
+    pub(crate) struct MutPtr<T: ?Sized>(*mut T);
+
+    impl<T: ?Sized> MutPtr<T> {
+        #[inline]
+        pub(crate) fn with<F, R>(&self, f: F) -> R
+        where
+            F: FnOnce(*mut T) -> R,
+        {
+            f(self.0)
+        }
+    }
+}
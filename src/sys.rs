@@ -1,9 +1,74 @@
 use cfg_if::cfg_if;
 
+use crate::barrier::FallbackOrdering;
+use crate::stats::{AccelerationMatrix, Backend, FenceStrength, InitReason, PlatformInfo, Validation};
+
+// ============================================================================
+// 0. Fallback Fence Ordering
+// 0. 回退屏障顺序
+// ============================================================================
+// The fence both sides issue when no OS barrier is active. Loom runs every model thread on the
+// thread that called `loom::model`, and tests run in parallel, so under Loom the setting is per
+// test thread; otherwise it is process-wide.
+//
+// 没有 OS 屏障时读写两端发出的屏障。Loom 在调用 `loom::model` 的线程上运行所有模型线程，且测试并行运行，
+// 因此在 Loom 下该设置按测试线程隔离；否则为进程级。
+#[cfg(not(feature = "loom"))]
+static FALLBACK_ACQ_REL: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
+#[cfg(feature = "loom")]
+std::thread_local! {
+    static FALLBACK_ACQ_REL: core::cell::Cell<bool> = const { core::cell::Cell::new(false) };
+}
+
+pub(crate) fn set_fallback_ordering_impl(ordering: FallbackOrdering) {
+    let acq_rel = ordering == FallbackOrdering::AcqRel;
+    #[cfg(not(feature = "loom"))]
+    FALLBACK_ACQ_REL.store(acq_rel, core::sync::atomic::Ordering::Relaxed);
+    #[cfg(feature = "loom")]
+    FALLBACK_ACQ_REL.with(|cell| cell.set(acq_rel));
+}
+
+#[inline]
+pub(crate) fn fallback_ordering_impl() -> FallbackOrdering {
+    #[cfg(not(feature = "loom"))]
+    let acq_rel = FALLBACK_ACQ_REL.load(core::sync::atomic::Ordering::Relaxed);
+    #[cfg(feature = "loom")]
+    let acq_rel = FALLBACK_ACQ_REL.with(core::cell::Cell::get);
+    if acq_rel { FallbackOrdering::AcqRel } else { FallbackOrdering::SeqCst }
+}
+
+// The ThreadSanitizer and WASI arms keep `fence(SeqCst)`: TSan should see the strongest
+// synchronization, and WebAssembly's only fence is sequentially consistent anyway.
+// ThreadSanitizer 与 WASI 分支保留 `fence(SeqCst)`：TSan 应看到最强的同步，而 WebAssembly 唯一的屏障本身就是顺序一致的。
+#[cfg_attr(
+    all(
+        not(feature = "loom"),
+        any(all(swmr_barrier_tsan, not(feature = "tsan-annotate")), target_os = "wasi")
+    ),
+    allow(dead_code)
+)]
+/// The fallback fence: `SeqCst` unless [`set_fallback_ordering`](crate::set_fallback_ordering) chose `AcqRel`.
+/// 回退屏障：除非 [`set_fallback_ordering`](crate::set_fallback_ordering) 选择了 `AcqRel`，否则为 `SeqCst`。
+#[inline]
+fn fallback_fence() {
+    use crate::sync::atomic::{fence, Ordering};
+    match fallback_ordering_impl() {
+        FallbackOrdering::SeqCst => fence(Ordering::SeqCst),
+        FallbackOrdering::AcqRel => fence(Ordering::AcqRel),
+    }
+}
+
 // ============================================================================
 // 1. Loom Simulation Implementation
 // 1. Loom 模拟实现
 // ============================================================================
+// First on purpose: `cfg_if!` takes the first matching arm, so with `loom` no OS arm is ever
+// compiled, and without it this arm never is. `lib.rs` also requires `--cfg loom` alongside the
+// feature, so the simulated fences cannot reach a production build through feature unification.
+//
+// 刻意放在首位：`cfg_if!` 选取第一个匹配的分支，因此启用 `loom` 时不会编译任何 OS 分支，未启用时也绝不会编译本分支。
+// `lib.rs` 还要求特性与 `--cfg loom` 同时存在，使模拟栅栏不会通过特性合并进入生产构建。
 cfg_if! {
     if #[cfg(feature = "loom")] {
         #[inline]
@@ -13,32 +78,223 @@ cfg_if! {
             //
             // Loom 无法模拟 "IPI 中断" 或 "Cache Coherency"，
             // 只能通过标准的 Atomic Fence 建立 Happens-Before 关系。
-            loom::sync::atomic::fence(loom::sync::atomic::Ordering::SeqCst);
+            fallback_fence();
         }
 
-        #[inline]
+        #[inline(always)]
         pub(crate) fn light_barrier_impl() {
             // Note: In Loom, if Heavy uses a fence, Light must also use a fence.
             // If we only use compiler_fence here, Loom will not see a synchronization relationship between the two threads.
             //
             // 注意：在 Loom 中，如果 Heavy 用了 fence，Light 必须也用 fence。
             // 如果这里只用 compiler_fence，Loom 会认为两条线程没有同步关系。
-            loom::sync::atomic::fence(loom::sync::atomic::Ordering::SeqCst);
+            fallback_fence();
         }
 
+        // Compile-time constant: callers' branches fold away.
+        // 编译期常量：调用方的分支会被折叠。
         #[inline]
-        pub(crate) fn is_accelerated_impl() -> bool {
+        pub(crate) const fn is_accelerated_impl() -> bool {
+            false
+        }
+
+        pub(crate) fn init_impl() -> bool {
+            false
+        }
+
+        // Nothing to detect on this arm.
+        // 本分支上无需检测任何内容。
+        pub(crate) fn redetect_impl() -> bool {
+            init_impl()
+        }
+
+        // Nothing to detect before `main`.
+        // `main` 之前无需检测任何内容。
+        #[cfg(swmr_barrier_ctor)]
+        pub(crate) fn auto_init_impl() {}
+
+        #[cfg(feature = "testing")]
+        pub(crate) fn reinitialize_impl() {}
+
+        // Nothing is allocated or registered on this arm.
+        // 本分支上没有任何分配或注册。
+        #[inline]
+        pub(crate) fn shutdown_impl() {}
+
+        #[inline]
+        pub(crate) fn registration_errno_impl() -> Option<i32> {
+            None
+        }
+
+        // Already async-signal-safe: no lock, allocation or lazy initialization.
+        // 本身已是异步信号安全的：没有锁、分配或延迟初始化。
+        pub(crate) use heavy_barrier_impl as heavy_barrier_signal_safe_impl;
+
+        // No barrier on this arm can degrade per call.
+        // 本分支上的屏障不会在单次调用中降级。
+        #[inline]
+        pub(crate) fn heavy_barrier_traced_impl() -> Backend {
+            heavy_barrier_impl();
+            backend_impl()
+        }
+
+        pub(crate) const PLATFORM_IMPL: PlatformInfo = PlatformInfo {
+            can_accelerate: false,
+            os: env!("SWMR_BARRIER_TARGET_OS"),
+            mechanism: "fence (loom)",
+        };
+
+        #[inline]
+        pub(crate) fn backend_impl() -> Backend {
+            Backend::Loom
+        }
+
+        #[inline]
+        pub(crate) fn light_fence_impl() -> FenceStrength {
+            backend_impl().light_fence()
+        }
+
+        #[inline]
+        pub(crate) fn init_reason_impl() -> InitReason {
+            InitReason::Unsupported
+        }
+
+        #[inline]
+        pub(crate) fn validation_impl() -> Validation {
+            Validation::NotRun
+        }
+
+        #[inline]
+        pub(crate) fn sync_core_available_impl() -> bool {
+            false
+        }
+
+        #[inline]
+        pub(crate) fn acceleration_matrix_impl() -> AccelerationMatrix {
+            AccelerationMatrix::NONE
+        }
+    }
+
+// ============================================================================
+// 2. ThreadSanitizer
+// 2. ThreadSanitizer
+// ============================================================================
+// Like Loom, TSan cannot see the IPI that makes a compiler-only light barrier sound, and reports
+// false races in downstream crates. Under `-Zsanitizer=thread` both sides emit a real fence so
+// TSan observes a synchronization point, trading fidelity to production codegen for clean reports.
+//
+// 与 Loom 一样，TSan 看不到使仅编译器屏障成立的 IPI，会在下游 crate 中报告虚假竞争。
+// 在 `-Zsanitizer=thread` 下读写两端都发出真正的 fence，使 TSan 能观察到同步点；
+// 以偏离生产代码生成为代价换取干净的报告。
+//
+// The `tsan-annotate` feature skips this arm and annotates the production barriers instead (see `tsan.rs`).
+// `tsan-annotate` 特性会跳过此分支，改为注解生产屏障（见 `tsan.rs`）。
+    else if #[cfg(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))] {
+        use core::sync::atomic::{fence, Ordering};
+
+        #[inline]
+        pub(crate) fn heavy_barrier_impl() {
+            fence(Ordering::SeqCst);
+        }
+
+        #[inline(always)]
+        pub(crate) fn light_barrier_impl() {
+            fence(Ordering::SeqCst);
+        }
+
+        // Compile-time constant: callers' branches fold away.
+        // 编译期常量：调用方的分支会被折叠。
+        #[inline]
+        pub(crate) const fn is_accelerated_impl() -> bool {
+            false
+        }
+
+        pub(crate) fn init_impl() -> bool {
+            false
+        }
+
+        // Nothing to detect on this arm.
+        // 本分支上无需检测任何内容。
+        pub(crate) fn redetect_impl() -> bool {
+            init_impl()
+        }
+
+        // Nothing to detect before `main`.
+        // `main` 之前无需检测任何内容。
+        #[cfg(swmr_barrier_ctor)]
+        pub(crate) fn auto_init_impl() {}
+
+        #[cfg(feature = "testing")]
+        pub(crate) fn reinitialize_impl() {}
+
+        // Nothing is allocated or registered on this arm.
+        // 本分支上没有任何分配或注册。
+        #[inline]
+        pub(crate) fn shutdown_impl() {}
+
+        #[inline]
+        pub(crate) fn registration_errno_impl() -> Option<i32> {
+            None
+        }
+
+        // Already async-signal-safe: no lock, allocation or lazy initialization.
+        // 本身已是异步信号安全的：没有锁、分配或延迟初始化。
+        pub(crate) use heavy_barrier_impl as heavy_barrier_signal_safe_impl;
+
+        // No barrier on this arm can degrade per call.
+        // 本分支上的屏障不会在单次调用中降级。
+        #[inline]
+        pub(crate) fn heavy_barrier_traced_impl() -> Backend {
+            heavy_barrier_impl();
+            backend_impl()
+        }
+
+        pub(crate) const PLATFORM_IMPL: PlatformInfo = PlatformInfo {
+            can_accelerate: false,
+            os: env!("SWMR_BARRIER_TARGET_OS"),
+            mechanism: "fence (ThreadSanitizer)",
+        };
+
+        #[inline]
+        pub(crate) fn backend_impl() -> Backend {
+            Backend::ThreadSanitizer
+        }
+
+        #[inline]
+        pub(crate) fn light_fence_impl() -> FenceStrength {
+            backend_impl().light_fence()
+        }
+
+        #[inline]
+        pub(crate) fn init_reason_impl() -> InitReason {
+            InitReason::Unsupported
+        }
+
+        #[inline]
+        pub(crate) fn validation_impl() -> Validation {
+            Validation::NotRun
+        }
+
+        #[inline]
+        pub(crate) fn sync_core_available_impl() -> bool {
             false
         }
+
+        #[inline]
+        pub(crate) fn acceleration_matrix_impl() -> AccelerationMatrix {
+            AccelerationMatrix::NONE
+        }
     }
 
 // ============================================================================
-// 2. Linux Real Implementation (Direct libc)
-// 2. Linux 真实实现 (直接使用 libc)
+// 3. Linux Real Implementation (Direct libc)
+// 3. Linux 真实实现 (直接使用 libc)
 // ============================================================================
     else if #[cfg(target_os = "linux")] {
-        use core::sync::atomic::{fence, compiler_fence, Ordering};
-        use libc::{syscall, c_int, c_long};
+        use core::sync::atomic::{fence, compiler_fence, AtomicBool, AtomicI32, AtomicU8, Ordering};
+        use libc::{syscall, c_int, c_long, c_uint};
+        #[cfg(feature = "std")]
+        use crate::error::BarrierError;
 
         // --------------------------------------------------------------------
         // Constants definition (from linux/membarrier.h)
@@ -50,96 +306,591 @@ cfg_if! {
 
         const MEMBARRIER_CMD_QUERY: c_int = 0;
         const MEMBARRIER_CMD_SHARED: c_int = 1;
+        const MEMBARRIER_CMD_GLOBAL_EXPEDITED: c_int = 2;
         const MEMBARRIER_CMD_PRIVATE_EXPEDITED: c_int = 8;
         const MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED: c_int = 16;
+        const MEMBARRIER_CMD_PRIVATE_EXPEDITED_SYNC_CORE: c_int = 32;
+        const MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED_SYNC_CORE: c_int = 64;
+        #[cfg(feature = "std")]
+        const MEMBARRIER_CMD_PRIVATE_EXPEDITED_RSEQ: c_int = 128;
+        #[cfg(feature = "std")]
+        const MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED_RSEQ: c_int = 256;
+        #[cfg(feature = "std")]
+        const MEMBARRIER_CMD_FLAG_CPU: c_uint = 1;
 
         // --------------------------------------------------------------------
         // State Management
         // --------------------------------------------------------------------
         // Store the membarrier command to use (0 = disabled/fallback, 1 = SHARED, 8 = PRIVATE_EXPEDITED)
         // 存储要使用的 membarrier 命令 (0 = 禁用/回退, 1 = SHARED, 8 = PRIVATE_EXPEDITED)
-        static mut MEMBARRIER_CMD: c_int = 0;
+        //
+        // Atomic because the explicit `init()` may update it after main has started.
+        // 使用原子变量，因为显式 `init()` 可能在 main 启动后更新它。
+        //
+        // Deliberately not cached per thread: `init()` can move it 0 -> 8 (late registration) or
+        // 8 -> 0 (failed validation), and a stale cache would pair `compiler_fence` on one side with
+        // `fence` on the other. A `Relaxed` load is already a plain load on every supported target.
+        // 刻意不做线程本地缓存：`init()` 可能将其从 0 改为 8（延迟注册）或从 8 改为 0（验证失败），
+        // 过期的缓存会使一侧使用 `compiler_fence` 而另一侧使用 `fence`。`Relaxed` 加载在所有支持的目标上已是普通加载。
+        static MEMBARRIER_CMD: AtomicI32 = AtomicI32::new(0);
+
+        // Whether `light_barrier()` may drop to `compiler_fence`; writers read `MEMBARRIER_CMD`.
+        // 是否允许 `light_barrier()` 降为 `compiler_fence`；写者读取 `MEMBARRIER_CMD`。
+        //
+        // Invariant: while a reader takes the cheap path, every concurrent heavy barrier is a
+        // membarrier. The other mismatch is harmless: a reader that still fences pairs with either a
+        // fence or a membarrier. So this is set only after a barrier with the new command has run
+        // (see `activate`), and cleared before the command drops back to 0.
+        // 不变式：读者走廉价路径期间，所有并发的重型屏障都是 membarrier。反向的不匹配无害：仍使用 fence 的读者与
+        // fence 或 membarrier 配对均可。因此只有在以新命令执行过一次屏障后才置位（见 `activate`），并在命令回落到
+        // 0 之前清除。
+        static READERS_ACCELERATED: AtomicBool = AtomicBool::new(false);
+
+        // errno of the last failed PRIVATE_EXPEDITED registration (0 = none)
+        // 最近一次 PRIVATE_EXPEDITED 注册失败的 errno (0 = 无)
+        static REGISTRATION_ERRNO: AtomicI32 = AtomicI32::new(0);
+
+        // Outcome of the last detection run (encoded `InitReason`)
+        // 最近一次检测的结果（编码后的 `InitReason`）
+        static INIT_REASON: AtomicU8 = AtomicU8::new(InitReason::Pending.to_u8());
+
+        // Outcome of the validation barrier issued by explicit `init()`
+        // (VALIDATION_NOT_RUN, VALIDATION_PASSED, or the errno of the last failed validation)
+        // 显式 `init()` 发起的验证屏障结果（VALIDATION_NOT_RUN、VALIDATION_PASSED，或最近一次验证失败的 errno）
+        const VALIDATION_NOT_RUN: i32 = -1;
+        const VALIDATION_PASSED: i32 = 0;
+        static VALIDATION: AtomicI32 = AtomicI32::new(VALIDATION_NOT_RUN);
+
+        // Whether PRIVATE_EXPEDITED_SYNC_CORE is advertised and registered (Linux 4.16+)
+        // PRIVATE_EXPEDITED_SYNC_CORE 是否已声明支持并完成注册 (Linux 4.16+)
+        static SYNC_CORE: AtomicBool = AtomicBool::new(false);
+
+        // Commands advertised by the last successful query (0 = none or not queried), and whether
+        // PRIVATE_EXPEDITED registration succeeded; reported by `acceleration_matrix()` only.
+        // 最近一次成功查询声明支持的命令 (0 = 无或尚未查询)，以及 PRIVATE_EXPEDITED 是否注册成功；仅供
+        // `acceleration_matrix()` 报告。
+        static QUERY_MASK: AtomicI32 = AtomicI32::new(0);
+        static PRIVATE_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+        // CPUs hosting readers; heavy barriers IPI only these while `TARGETED` is set (Linux 5.10+)
+        // 承载读者的 CPU；`TARGETED` 置位时重型屏障只向这些 CPU 发送 IPI (Linux 5.10+)
+        #[cfg(feature = "std")]
+        static READER_CPUS: std::sync::RwLock<std::vec::Vec<c_int>> = std::sync::RwLock::new(std::vec::Vec::new());
+        #[cfg(feature = "std")]
+        static TARGETED: AtomicBool = AtomicBool::new(false);
+        #[cfg(feature = "std")]
+        static RSEQ_REGISTERED: AtomicBool = AtomicBool::new(false);
 
         // --------------------------------------------------------------------
         // Initialization (runs before main)
         // 初始化 (在 main 之前运行)
         // --------------------------------------------------------------------
-        #[used]
-        #[unsafe(link_section = ".init_array")]
-        static __INIT: extern "C" fn() = linux_auto_init;
+        // Registered by `crate::init`.
+        // 由 `crate::init` 注册。
+        pub(crate) fn auto_init_impl() {
+            // The constructor must stay cheap: no retries here.
+            // 构造函数必须保持轻量：此处不重试。
+            detect(false);
+        }
 
-        extern "C" fn linux_auto_init() {
-            unsafe {
-                // Step 1: Check kernel support (Query)
-                // 第一步：检查内核支持 (查询)
-                let supported_mask = syscall(SYS_MEMBARRIER, MEMBARRIER_CMD_QUERY, 0, 0);
-                if supported_mask < 0 {
-                    return;
+        /// Issues a raw membarrier command.
+        /// 发起原始 membarrier 命令。
+        #[inline]
+        fn membarrier(cmd: c_int) -> c_long {
+            membarrier_with(cmd, 0, 0)
+        }
+
+        /// Issues a raw membarrier command with `flags` and `cpu_id` (Linux 5.10+).
+        /// 使用 `flags` 与 `cpu_id` 发起原始 membarrier 命令 (Linux 5.10+)。
+        #[inline]
+        fn membarrier_with(cmd: c_int, flags: c_uint, cpu_id: c_int) -> c_long {
+            #[cfg(feature = "testing")]
+            {
+                let injected = match cmd {
+                    MEMBARRIER_CMD_QUERY => crate::testing::take_query_failure(),
+                    MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED => crate::testing::take_registration_failure(),
+                    MEMBARRIER_CMD_PRIVATE_EXPEDITED | MEMBARRIER_CMD_SHARED => crate::testing::take_barrier_failure(),
+                    _ => None,
+                };
+                if let Some(errno) = injected {
+                    unsafe { *libc::__errno_location() = errno };
+                    return -1;
+                }
+            }
+            #[cfg(feature = "latency")]
+            let start = std::time::Instant::now();
+            #[cfg(all(feature = "testing", feature = "std"))]
+            if matches!(cmd, MEMBARRIER_CMD_PRIVATE_EXPEDITED | MEMBARRIER_CMD_SHARED)
+                && let Some(delay) = crate::testing::take_barrier_delay()
+            {
+                std::thread::sleep(delay);
+            }
+            #[cfg(all(feature = "testing", feature = "std"))]
+            if flags & MEMBARRIER_CMD_FLAG_CPU != 0 {
+                crate::testing::record_targeted_cpu(cpu_id as usize);
+            }
+            let ret = unsafe { syscall(SYS_MEMBARRIER, cmd, flags, cpu_id) };
+            #[cfg(feature = "latency")]
+            if ret == 0
+                && matches!(
+                    cmd,
+                    MEMBARRIER_CMD_PRIVATE_EXPEDITED
+                        | MEMBARRIER_CMD_SHARED
+                        | MEMBARRIER_CMD_PRIVATE_EXPEDITED_RSEQ
+                        | MEMBARRIER_CMD_PRIVATE_EXPEDITED_SYNC_CORE
+                )
+            {
+                crate::latency::record(start.elapsed());
+            }
+            ret
+        }
+
+        /// Barriers every CPU in `READER_CPUS`. Returns `false` if no mask is set or any call
+        /// failed, in which case the caller must broadcast instead.
+        ///
+        /// 对 `READER_CPUS` 中的每个 CPU 发起屏障。若未设置掩码或任一调用失败则返回 `false`，此时调用方必须改为广播。
+        #[cfg(feature = "std")]
+        #[inline]
+        fn targeted_barrier() -> bool {
+            if !TARGETED.load(Ordering::Relaxed) {
+                return false;
+            }
+            let cpus = READER_CPUS.read().unwrap_or_else(std::sync::PoisonError::into_inner);
+            !cpus.is_empty()
+                && cpus.iter().all(|&cpu| {
+                    membarrier_with(MEMBARRIER_CMD_PRIVATE_EXPEDITED_RSEQ, MEMBARRIER_CMD_FLAG_CPU, cpu) == 0
+                })
+        }
+
+        /// Restricts heavy barriers to `mask`, validating each CPU with one targeted barrier first.
+        /// An empty mask restores broadcasting.
+        ///
+        /// 将重型屏障限制在 `mask` 内，并先对每个 CPU 发起一次定向屏障进行验证。空掩码恢复广播。
+        #[cfg(feature = "std")]
+        pub(crate) fn set_reader_cpu_mask_impl(mask: &[usize]) -> Result<(), BarrierError> {
+            let mut cpus: std::vec::Vec<c_int> = mask
+                .iter()
+                .map(|&cpu| c_int::try_from(cpu).map_err(|_| BarrierError::SyscallFailed(libc::EINVAL)))
+                .collect::<Result<_, _>>()?;
+            cpus.sort_unstable();
+            cpus.dedup();
+            // The kernel silently skips CPUs it does not know, so reject them here.
+            // 内核会静默跳过未知的 CPU，因此在此处拒绝。
+            let configured = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) };
+            if configured > 0 && cpus.last().is_some_and(|&cpu| c_long::from(cpu) >= configured) {
+                return Err(BarrierError::SyscallFailed(libc::EINVAL));
+            }
+
+            if cpus.is_empty() {
+                TARGETED.store(false, Ordering::Relaxed);
+                READER_CPUS.write().unwrap_or_else(std::sync::PoisonError::into_inner).clear();
+                return Ok(());
+            }
+            if MEMBARRIER_CMD.load(Ordering::Relaxed) == 0 {
+                return Err(BarrierError::Unsupported);
+            }
+
+            if !RSEQ_REGISTERED.load(Ordering::Relaxed) {
+                let supported_mask = membarrier(MEMBARRIER_CMD_QUERY);
+                if supported_mask < 0 || (supported_mask as c_int & MEMBARRIER_CMD_PRIVATE_EXPEDITED_RSEQ) == 0 {
+                    return Err(BarrierError::KernelTooOld);
+                }
+                if membarrier(MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED_RSEQ) != 0 {
+                    return Err(BarrierError::RegistrationFailed(unsafe { *libc::__errno_location() }));
                 }
+                RSEQ_REGISTERED.store(true, Ordering::Relaxed);
+            }
+
+            for &cpu in &cpus {
+                if membarrier_with(MEMBARRIER_CMD_PRIVATE_EXPEDITED_RSEQ, MEMBARRIER_CMD_FLAG_CPU, cpu) != 0 {
+                    return Err(BarrierError::SyscallFailed(unsafe { *libc::__errno_location() }));
+                }
+            }
+
+            *READER_CPUS.write().unwrap_or_else(std::sync::PoisonError::into_inner) = cpus;
+            TARGETED.store(true, Ordering::Relaxed);
+            Ok(())
+        }
+
+        /// Registers for PRIVATE_EXPEDITED, returning the errno on failure.
+        /// 注册 PRIVATE_EXPEDITED，失败时返回 errno。
+        fn register_private_expedited() -> Result<(), c_int> {
+            if membarrier(MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED) == 0 {
+                Ok(())
+            } else {
+                Err(unsafe { *libc::__errno_location() })
+            }
+        }
+
+        /// Issues one barrier with `cmd` and records the outcome.
+        /// Hardened kernels may accept the query and registration yet reject every barrier.
+        ///
+        /// 使用 `cmd` 发起一次屏障并记录结果。
+        /// 加固内核可能接受查询和注册，却拒绝每一次屏障调用。
+        fn validate(cmd: c_int) -> bool {
+            if membarrier(cmd) == 0 {
+                // Keep an earlier failure visible (e.g. PRIVATE_EXPEDITED failed, SHARED passed).
+                // 保留之前的失败记录（例如 PRIVATE_EXPEDITED 失败而 SHARED 通过）。
+                let _ = VALIDATION.compare_exchange(VALIDATION_NOT_RUN, VALIDATION_PASSED, Ordering::Relaxed, Ordering::Relaxed);
+                true
+            } else {
+                VALIDATION.store(unsafe { *libc::__errno_location() }, Ordering::Relaxed);
+                false
+            }
+        }
+
+        /// Publishes `cmd` to writers, then to readers once a barrier with it has run.
+        ///
+        /// Readers may skip the fence only when no writer can still pair a plain `fence` with them.
+        /// The barrier runs a full barrier on every thread of the process, so a writer that loaded
+        /// the old command 0 has had its stores flushed before it returns, and every later heavy
+        /// barrier loads `cmd`. If it fails, readers keep fencing, which is safe with either writer path.
+        ///
+        /// 先向写者发布 `cmd`，在以其执行过一次屏障后再向读者发布。
+        ///
+        /// 只有当不再有写者可能以普通 `fence` 与读者配对时，读者才可跳过栅栏。该屏障会在进程的每个线程上执行完整屏障，
+        /// 因此加载到旧命令 0 的写者在其返回前已刷出存储，此后的每次重型屏障都会加载到 `cmd`。若屏障失败，读者继续使用
+        /// 栅栏，这与任一写者路径配对都是安全的。
+        fn activate(cmd: c_int) {
+            MEMBARRIER_CMD.store(cmd, Ordering::Relaxed);
+            if !READERS_ACCELERATED.load(Ordering::Relaxed) && membarrier(cmd) == 0 {
+                READERS_ACCELERATED.store(true, Ordering::Relaxed);
+            }
+        }
+
+        /// Stops using membarrier; returns whether it was in use. Readers return to fencing first, and
+        /// one last barrier with the old command orders readers still on the cheap path before writers
+        /// switch to fences. `init()` re-runs detection.
+        ///
+        /// 停止使用 membarrier；返回此前是否在使用。读者先恢复使用栅栏，再以旧命令执行最后一次屏障，在写者切换到
+        /// 栅栏之前对仍处于廉价路径上的读者排序。`init()` 会重新执行检测。
+        fn deactivate() -> bool {
+            let cmd = MEMBARRIER_CMD.load(Ordering::Relaxed);
+            if cmd == 0 {
+                return false;
+            }
+            READERS_ACCELERATED.store(false, Ordering::Relaxed);
+            if membarrier(cmd) != 0 {
+                fence(Ordering::SeqCst);
+            }
+            MEMBARRIER_CMD.store(0, Ordering::Relaxed);
+            true
+        }
+
+        /// Stops using membarrier after the watchdog tripped or a validation round failed, recording `reason`.
+        /// 在看门狗触发或验证轮次失败后停止使用 membarrier，并记录 `reason`。
+        #[cfg(feature = "std")]
+        pub(crate) fn disable_acceleration_impl(reason: InitReason) {
+            if deactivate() {
+                INIT_REASON.store(reason.to_u8(), Ordering::Relaxed);
+            }
+        }
+
+        /// Returns to the fence fallback and frees the reader CPU mask. The kernel keeps the process's
+        /// membarrier registration, which is harmless: nothing issues the command any more.
+        ///
+        /// 回到栅栏回退路径并释放读者 CPU 掩码。内核会保留进程的 membarrier 注册，这无害：之后不再有人发出该命令。
+        pub(crate) fn shutdown_impl() {
+            deactivate();
+            SYNC_CORE.store(false, Ordering::Relaxed);
+            QUERY_MASK.store(0, Ordering::Relaxed);
+            PRIVATE_REGISTERED.store(false, Ordering::Relaxed);
+            #[cfg(feature = "std")]
+            {
+                TARGETED.store(false, Ordering::Relaxed);
+                *READER_CPUS.write().unwrap_or_else(std::sync::PoisonError::into_inner) = std::vec::Vec::new();
+            }
+            INIT_REASON.store(InitReason::Shutdown.to_u8(), Ordering::Relaxed);
+        }
 
-                // Strategy 1: PRIVATE_EXPEDITED (Linux 4.14+)
-                // Best performance, requires registration.
-                // 策略 1: PRIVATE_EXPEDITED (Linux 4.14+)
-                // 性能最佳，需要注册。
-                if (supported_mask as c_int & MEMBARRIER_CMD_PRIVATE_EXPEDITED) != 0 {
-                    let res = syscall(SYS_MEMBARRIER, MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED, 0, 0);
-                    if res == 0 {
-                        MEMBARRIER_CMD = MEMBARRIER_CMD_PRIVATE_EXPEDITED;
-                        return;
+        /// Strategy 1 of `detect`: registers for PRIVATE_EXPEDITED (retrying once if `explicit`),
+        /// validates it if `explicit`, and activates it. Returns whether it is now active.
+        /// `detect` 的策略 1：注册 PRIVATE_EXPEDITED（`explicit` 时重试一次），在 `explicit` 时验证并启用它。
+        /// 返回其现在是否已启用。
+        fn try_private_expedited(supported_mask: c_int, explicit: bool) -> bool {
+            if (supported_mask & MEMBARRIER_CMD_PRIVATE_EXPEDITED) == 0 {
+                return false;
+            }
+            let mut res = register_private_expedited();
+            if explicit && res.is_err() {
+                unsafe { libc::sched_yield() };
+                res = register_private_expedited();
+            }
+            match res {
+                Ok(()) => {
+                    REGISTRATION_ERRNO.store(0, Ordering::Relaxed);
+                    PRIVATE_REGISTERED.store(true, Ordering::Relaxed);
+                    if !explicit || validate(MEMBARRIER_CMD_PRIVATE_EXPEDITED) {
+                        activate(MEMBARRIER_CMD_PRIVATE_EXPEDITED);
+                        INIT_REASON.store(InitReason::Accelerated.to_u8(), Ordering::Relaxed);
+                        return true;
                     }
                 }
+                Err(errno) => REGISTRATION_ERRNO.store(errno, Ordering::Relaxed),
+            }
+            false
+        }
+
+        /// Runs kernel detection and publishes the selected command.
+        /// `explicit` (explicit `init()` only) allows one extra registration attempt after yielding
+        /// and validates the selected command with a real barrier before trusting it.
+        ///
+        /// 执行内核检测并发布选定的命令。
+        /// `explicit`（仅限显式 `init()`）允许在让出 CPU 后再尝试一次注册，并在信任所选命令前用一次真实屏障进行验证。
+        fn detect(explicit: bool) {
+            VALIDATION.store(VALIDATION_NOT_RUN, Ordering::Relaxed);
+
+            // Step 0 (`strict`): refuse kernels whose membarrier is known to be broken.
+            // 第零步（`strict`）：拒绝 membarrier 已知有缺陷的内核。
+            #[cfg(feature = "strict")]
+            if let Some(version) = crate::kernel::detect()
+                && crate::kernel::is_denylisted(version)
+            {
+                deactivate();
+                INIT_REASON.store(InitReason::KernelDenylisted.to_u8(), Ordering::Relaxed);
+                // Never from the pre-main constructor: unwinding out of it aborts.
+                // 绝不在 main 之前的构造函数中 panic：从中展开会导致中止。
+                if cfg!(feature = "strict-panic") && explicit {
+                    panic!("swmr-barrier: Linux {version} has a known-broken membarrier");
+                }
+                return;
+            }
+
+            // `SWMR_BARRIER_MODE`: `fallback` skips the kernel entirely; `private` / `shared` narrow the
+            // strategies below, and an unavailable one is reported before trying the others.
+            // `SWMR_BARRIER_MODE`：`fallback` 完全绕过内核；`private` / `shared` 限定下面的策略，所请求的策略不可用时
+            // 先报告警告，再尝试其他策略。
+            #[cfg(feature = "std")]
+            let mode = crate::mode::requested();
+            #[cfg(feature = "std")]
+            if mode == crate::mode::BarrierMode::Fallback {
+                deactivate();
+                INIT_REASON.store(InitReason::ModeOverride.to_u8(), Ordering::Relaxed);
+                return;
+            }
+            #[cfg(feature = "std")]
+            let unavailable = |requested| {
+                if mode == requested {
+                    crate::mode::warn(crate::mode::ModeWarning::Unavailable(requested));
+                }
+            };
+            #[cfg(feature = "std")]
+            let try_private_first = mode != crate::mode::BarrierMode::Shared;
+            #[cfg(not(feature = "std"))]
+            let try_private_first = true;
 
-                // Strategy 2: SHARED (Linux 4.3+)
-                // Fallback for older kernels. Slower than PRIVATE_EXPEDITED but still asymmetric (good for readers).
-                // 策略 2: SHARED (Linux 4.3+)
-                // 旧内核的回退方案。比 PRIVATE_EXPEDITED 慢，但在读侧依然是非对称的（对读者友好）。
-                if (supported_mask as c_int & MEMBARRIER_CMD_SHARED) != 0 {
-                    MEMBARRIER_CMD = MEMBARRIER_CMD_SHARED;
+            // Step 1: Check kernel support (Query)
+            // 第一步：检查内核支持 (查询)
+            // A seccomp filter installed by another crate's constructor before ours ran makes this
+            // fail with `EPERM`, leaving the fallback; the application can call `init()` before
+            // installing its own filter. A command already active was registered before the filter
+            // went in, and a failed query says nothing about it, so it is kept.
+            // 其他 crate 的构造函数若先于本 crate 安装 seccomp 过滤器，此处会以 `EPERM` 失败并保持回退；应用可在安装自己的
+            // 过滤器之前调用 `init()`。已启用的命令是在过滤器生效之前注册的，查询失败并不说明它不可用，因此予以保留。
+            let supported_mask = membarrier(MEMBARRIER_CMD_QUERY);
+            if supported_mask < 0 {
+                if MEMBARRIER_CMD.load(Ordering::Relaxed) == 0 {
+                    INIT_REASON.store(InitReason::QueryFailed.to_u8(), Ordering::Relaxed);
+                }
+                return;
+            }
+
+            QUERY_MASK.store(supported_mask as c_int, Ordering::Relaxed);
+
+            // Sync-core only matters to callers that modify code; registering it is independent
+            // of which barrier command is selected below.
+            // Sync-core 仅对修改代码的调用方有意义；其注册与下面选择哪条屏障命令无关。
+            if (supported_mask as c_int & MEMBARRIER_CMD_PRIVATE_EXPEDITED_SYNC_CORE) != 0
+                && membarrier(MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED_SYNC_CORE) == 0
+            {
+                SYNC_CORE.store(true, Ordering::Relaxed);
+            }
+
+            // Strategy 1: PRIVATE_EXPEDITED (Linux 4.14+)
+            // Best performance, requires registration. Registration can fail transiently
+            // (e.g. ENOMEM under memory pressure or cgroup limits) even when advertised.
+            // 策略 1: PRIVATE_EXPEDITED (Linux 4.14+)
+            // 性能最佳，需要注册。即使内核声明支持，注册也可能暂时失败（例如内存压力或 cgroup 限制导致 ENOMEM）。
+            if try_private_first && try_private_expedited(supported_mask as c_int, explicit) {
+                return;
+            }
+            #[cfg(feature = "std")]
+            unavailable(crate::mode::BarrierMode::Private);
+
+            // Strategy 2: SHARED (Linux 4.3+)
+            // Fallback for older kernels. Slower than PRIVATE_EXPEDITED but still asymmetric (good for readers).
+            // 策略 2: SHARED (Linux 4.3+)
+            // 旧内核的回退方案。比 PRIVATE_EXPEDITED 慢，但在读侧依然是非对称的（对读者友好）。
+            if (supported_mask as c_int & MEMBARRIER_CMD_SHARED) != 0
+                && (!explicit || validate(MEMBARRIER_CMD_SHARED))
+            {
+                activate(MEMBARRIER_CMD_SHARED);
+                INIT_REASON.store(InitReason::Accelerated.to_u8(), Ordering::Relaxed);
+                return;
+            }
+
+            // `SWMR_BARRIER_MODE=shared` on a kernel without SHARED: PRIVATE_EXPEDITED was skipped above.
+            // 内核不支持 SHARED 时的 `SWMR_BARRIER_MODE=shared`：上面跳过了 PRIVATE_EXPEDITED。
+            if !try_private_first {
+                #[cfg(feature = "std")]
+                unavailable(crate::mode::BarrierMode::Shared);
+                if try_private_expedited(supported_mask as c_int, explicit) {
                     return;
                 }
             }
+
+            if VALIDATION.load(Ordering::Relaxed) > 0 {
+                // Advertised but rejected: fall back instead of failing every heavy barrier.
+                // Readers return to fencing before writers do.
+                // 已声明支持却被拒绝：回退，而不是让每次重型屏障都失败。读者先于写者恢复使用栅栏。
+                READERS_ACCELERATED.store(false, Ordering::Relaxed);
+                MEMBARRIER_CMD.store(0, Ordering::Relaxed);
+                INIT_REASON.store(InitReason::ValidationFailed.to_u8(), Ordering::Relaxed);
+                return;
+            }
+
+            INIT_REASON.store(InitReason::NotSupported.to_u8(), Ordering::Relaxed);
+        }
+
+        /// Explicit initialization: retries detection unless PRIVATE_EXPEDITED is already active and validated.
+        /// 显式初始化：除非 PRIVATE_EXPEDITED 已启用且已验证，否则重新执行检测。
+        pub(crate) fn init_impl() -> bool {
+            if MEMBARRIER_CMD.load(Ordering::Relaxed) != MEMBARRIER_CMD_PRIVATE_EXPEDITED
+                || VALIDATION.load(Ordering::Relaxed) != VALIDATION_PASSED
+            {
+                detect(true);
+            }
+            is_accelerated_impl()
+        }
+
+        /// Like `init_impl`, but re-runs detection even when PRIVATE_EXPEDITED is active and validated.
+        /// 同 `init_impl`，但即使 PRIVATE_EXPEDITED 已启用且已验证也会重新执行检测。
+        pub(crate) fn redetect_impl() -> bool {
+            detect(true);
+            is_accelerated_impl()
+        }
+
+        /// Switches writers to `MEMBARRIER_CMD_SHARED` if the kernel advertises it, whatever detection
+        /// chose (test support). Returns whether SHARED is now the active command.
+        /// 只要内核声明支持，就将写者切换到 `MEMBARRIER_CMD_SHARED`，无论检测选择了什么（测试支持）。返回 SHARED
+        /// 现在是否为当前命令。
+        #[cfg(feature = "testing")]
+        pub(crate) fn force_shared_impl() -> bool {
+            let supported_mask = membarrier(MEMBARRIER_CMD_QUERY);
+            if supported_mask < 0 || (supported_mask as c_int & MEMBARRIER_CMD_SHARED) == 0 {
+                return false;
+            }
+            activate(MEMBARRIER_CMD_SHARED);
+            INIT_REASON.store(InitReason::Accelerated.to_u8(), Ordering::Relaxed);
+            true
+        }
+
+        /// Restores the pre-`main` state and re-runs detection from scratch (test support).
+        /// 恢复 `main` 之前的状态并从头重新执行检测（测试支持）。
+        #[cfg(feature = "testing")]
+        pub(crate) fn reinitialize_impl() {
+            READERS_ACCELERATED.store(false, Ordering::Relaxed);
+            MEMBARRIER_CMD.store(0, Ordering::Relaxed);
+            REGISTRATION_ERRNO.store(0, Ordering::Relaxed);
+            INIT_REASON.store(InitReason::Pending.to_u8(), Ordering::Relaxed);
+            SYNC_CORE.store(false, Ordering::Relaxed);
+            QUERY_MASK.store(0, Ordering::Relaxed);
+            PRIVATE_REGISTERED.store(false, Ordering::Relaxed);
+            #[cfg(feature = "std")]
+            {
+                TARGETED.store(false, Ordering::Relaxed);
+                READER_CPUS.write().unwrap_or_else(std::sync::PoisonError::into_inner).clear();
+            }
+            detect(false);
+        }
+
+        #[inline]
+        pub(crate) fn registration_errno_impl() -> Option<i32> {
+            match REGISTRATION_ERRNO.load(Ordering::Relaxed) {
+                0 => None,
+                errno => Some(errno),
+            }
         }
 
         // --------------------------------------------------------------------
         // Barrier Implementations
         // --------------------------------------------------------------------
 
+        /// The heavy barrier, returning the mechanism that ordered memory on this call.
+        /// 重型屏障，并返回本次调用中实际完成内存排序的机制。
         #[inline]
-        pub(crate) fn heavy_barrier_impl() {
-            let cmd = unsafe { MEMBARRIER_CMD };
+        pub(crate) fn heavy_barrier_traced_impl() -> Backend {
+            let cmd = MEMBARRIER_CMD.load(Ordering::Relaxed);
 
             // Check if we are in accelerated mode
             // 检查是否处于加速模式
             if cmd != 0 {
-                unsafe {
-                    // Trigger the IPI barrier (PRIVATE_EXPEDITED or SHARED)
-                    // 触发 IPI 屏障 (PRIVATE_EXPEDITED 或 SHARED)
-                    let ret = syscall(SYS_MEMBARRIER, cmd, 0, 0);
-
-                    // Safety net
-                    // 安全网
-                    if ret != 0 {
-                        fence(Ordering::SeqCst);
-                    }
+                // Only the reader CPUs, if a mask is set and every targeted call succeeds
+                // 若设置了掩码且每次定向调用都成功，则只针对读者 CPU
+                #[cfg(feature = "std")]
+                if targeted_barrier() {
+                    compiler_fence(Ordering::SeqCst);
+                    return backend_for(cmd);
                 }
-                // Prevent compiler reordering locally
-                // 防止本地编译器重排
+
+                // Trigger the IPI barrier (PRIVATE_EXPEDITED or SHARED)
+                // 触发 IPI 屏障 (PRIVATE_EXPEDITED 或 SHARED)
+                let ret = membarrier(cmd);
+
+                // Safety net
+                // 安全网
+                let backend = if ret != 0 {
+                    fence(Ordering::SeqCst);
+                    Backend::Fallback
+                } else {
+                    backend_for(cmd)
+                };
+                // A compiler fence is enough locally: membarrier runs `smp_mb()` on this CPU and
+                // returns only after every reader CPU executed a full barrier, so the hardware
+                // ordering is already done; this only keeps the compiler from moving accesses across
+                // the call. Modeled by `test_membarrier_local_ordering` in `tests/loom_test.rs`.
+                // 本地只需编译器屏障：membarrier 在本 CPU 上执行 `smp_mb()`，并在每个读者 CPU 都执行完全屏障后才返回，
+                // 硬件排序已经完成；这里只是防止编译器将访问移过该调用。由 `tests/loom_test.rs` 中的
+                // `test_membarrier_local_ordering` 建模验证。
                 compiler_fence(Ordering::SeqCst);
+                backend
             } else {
                 // Fallback: Standard heavy fence
                 // 回退：标准全屏障
-                fence(Ordering::SeqCst);
+                fallback_fence();
+                Backend::Fallback
             }
         }
 
+        /// `heavy_barrier_traced_impl` without the CPU mask (a lock), the testing hooks or anything else
+        /// that is not async-signal-safe: one atomic load plus `syscall`. Always broadcasts, which
+        /// covers any mask. Restores `errno` so the interrupted code never sees it change.
+        ///
+        /// 去掉 CPU 掩码（需要加锁）、测试钩子及其他非异步信号安全操作的 `heavy_barrier_traced_impl`：
+        /// 仅一次原子加载加 `syscall`。始终广播，覆盖任何掩码。会恢复 `errno`，使被中断的代码看不到其变化。
         #[inline]
+        pub(crate) fn heavy_barrier_signal_safe_impl() {
+            let cmd = MEMBARRIER_CMD.load(Ordering::Relaxed);
+            if cmd != 0 {
+                let errno = unsafe { *libc::__errno_location() };
+                if unsafe { syscall(SYS_MEMBARRIER, cmd, 0, 0) } != 0 {
+                    fence(Ordering::SeqCst);
+                    unsafe { *libc::__errno_location() = errno };
+                }
+                compiler_fence(Ordering::SeqCst);
+            } else {
+                fallback_fence();
+            }
+        }
+
+        #[inline(always)]
         pub(crate) fn light_barrier_impl() {
-            // CRITICAL: Match the heavy_barrier strategy.
-            // 关键：必须与 heavy_barrier 策略匹配。
-            if unsafe { MEMBARRIER_CMD } != 0 {
+            // CRITICAL: Only skip the fence while every heavy barrier is a membarrier (see `READERS_ACCELERATED`).
+            // 关键：只有当所有重型屏障都是 membarrier 时才跳过栅栏（见 `READERS_ACCELERATED`）。
+            if READERS_ACCELERATED.load(Ordering::Relaxed) {
                 compiler_fence(Ordering::SeqCst);
             } else {
-                fence(Ordering::SeqCst);
+                fallback_fence();
             }
         }
 
@@ -147,39 +898,106 @@ cfg_if! {
         /// 返回是否正在使用 OS 加速屏障（membarrier）。
         #[inline]
         pub(crate) fn is_accelerated_impl() -> bool {
-            unsafe { MEMBARRIER_CMD != 0 }
+            MEMBARRIER_CMD.load(Ordering::Relaxed) != 0
+        }
+
+        pub(crate) const PLATFORM_IMPL: PlatformInfo = PlatformInfo {
+            can_accelerate: true,
+            os: env!("SWMR_BARRIER_TARGET_OS"),
+            mechanism: "membarrier",
+        };
+
+        #[inline]
+        fn backend_for(cmd: c_int) -> Backend {
+            match cmd {
+                MEMBARRIER_CMD_PRIVATE_EXPEDITED => Backend::LinuxPrivateExpedited,
+                MEMBARRIER_CMD_SHARED => Backend::LinuxShared,
+                _ => Backend::Fallback,
+            }
+        }
+
+        #[inline]
+        pub(crate) fn backend_impl() -> Backend {
+            backend_for(MEMBARRIER_CMD.load(Ordering::Relaxed))
+        }
+
+        /// `Full` until the selected command has been confirmed for readers (see `activate`).
+        /// 在所选命令对读者确认之前为 `Full`（见 `activate`）。
+        #[inline]
+        pub(crate) fn light_fence_impl() -> FenceStrength {
+            if READERS_ACCELERATED.load(Ordering::Relaxed) { FenceStrength::Compiler } else { FenceStrength::Full }
+        }
+
+        #[inline]
+        pub(crate) fn init_reason_impl() -> InitReason {
+            InitReason::from_u8(INIT_REASON.load(Ordering::Relaxed))
+        }
+
+        #[inline]
+        pub(crate) fn validation_impl() -> Validation {
+            match VALIDATION.load(Ordering::Relaxed) {
+                VALIDATION_NOT_RUN => Validation::NotRun,
+                VALIDATION_PASSED => Validation::Passed,
+                errno => Validation::Failed(errno),
+            }
+        }
+
+        #[inline]
+        pub(crate) fn sync_core_available_impl() -> bool {
+            SYNC_CORE.load(Ordering::Relaxed)
+        }
+
+        pub(crate) fn acceleration_matrix_impl() -> AccelerationMatrix {
+            let mask = QUERY_MASK.load(Ordering::Relaxed);
+            AccelerationMatrix {
+                private_expedited: PRIVATE_REGISTERED.load(Ordering::Relaxed),
+                shared: mask & MEMBARRIER_CMD_SHARED != 0,
+                global: mask & MEMBARRIER_CMD_SHARED != 0,
+                global_expedited: mask & MEMBARRIER_CMD_GLOBAL_EXPEDITED != 0,
+                sync_core: SYNC_CORE.load(Ordering::Relaxed),
+                ..AccelerationMatrix::NONE
+            }
         }
     }
 
 // ============================================================================
-// 3. Windows Implementation
-// 3. Windows 实现
+// 4. Windows Implementation
+// 4. Windows 实现
 // ============================================================================
     else if #[cfg(target_os = "windows")] {
         use windows_sys::Win32::System::LibraryLoader::{GetModuleHandleA, GetProcAddress};
-        use core::sync::atomic::{compiler_fence, fence, Ordering};
+        use windows_sys::Win32::System::Threading::GetCurrentProcess;
+        use crate::error::BarrierError;
+        use core::sync::atomic::{compiler_fence, AtomicPtr, Ordering};
         use core::ffi::c_void;
 
         // --------------------------------------------------------------------
         // State Management
         // --------------------------------------------------------------------
-        static mut IS_ACCELERATED: bool = false;
-        static mut MB_FN_PTR: *mut c_void = core::ptr::null_mut();
+        // `FlushProcessWriteBuffers`, or null when not accelerated. A single word cannot be
+        // observed half-set, so no reader ever sees "accelerated" without the pointer.
+        // Relaxed suffices: the pointer targets code in kernel32, which is loaded before any load.
+        // `FlushProcessWriteBuffers`，未加速时为空。单个字不会被观察到一半写入，
+        // 因此读者不会在没有指针的情况下看到“已加速”。Relaxed 足够：指针指向 kernel32 中的代码，它在任何加载之前已加载。
+        static MB_FN_PTR: AtomicPtr<c_void> = AtomicPtr::new(core::ptr::null_mut());
+
+        // `FlushInstructionCache`, resolved the same way; only the sync-core barrier uses it.
+        // `FlushInstructionCache`，以同样方式解析；只有 sync-core 屏障使用它。
+        static ICACHE_FN_PTR: AtomicPtr<c_void> = AtomicPtr::new(core::ptr::null_mut());
 
         // Function signature for FlushProcessWriteBuffers
         type FnFlushProcessWriteBuffers = unsafe extern "system" fn();
 
+        // Function signature for FlushInstructionCache (HANDLE, LPCVOID, SIZE_T) -> BOOL
+        type FnFlushInstructionCache = unsafe extern "system" fn(*mut c_void, *const c_void, usize) -> i32;
+
         // --------------------------------------------------------------------
         // Initialization (runs before main)
         // 初始化 (在 main 之前运行)
         // --------------------------------------------------------------------
-        // On Windows MSVC, .CRT$XCU is the section for C++ dynamic initializers.
-        // Rust uses this for its own pre-main code.
-        #[used]
-        #[unsafe(link_section = ".CRT$XCU")]
-        static __INIT: extern "C" fn() = windows_auto_init;
-
-        extern "C" fn windows_auto_init() {
+        // Registered by `crate::init` in `.CRT$XCU`.
+        // 由 `crate::init` 注册到 `.CRT$XCU`。
+        pub(crate) fn auto_init_impl() {
             unsafe {
                 // 1. Get readable handle to Kernel32.dll (already loaded)
                 let h_kernel32 = GetModuleHandleA(b"kernel32.dll\0".as_ptr());
@@ -190,72 +1008,763 @@ cfg_if! {
                 // 2. Try to find FlushProcessWriteBuffers
                 // It is available on Vista / Server 2008 and later.
                 if let Some(func_ptr) = GetProcAddress(h_kernel32, b"FlushProcessWriteBuffers\0".as_ptr()) {
-                    // Store the function pointer
+                    // Store the function pointer, which also enables acceleration
                     // Transmute the FARPROC to *mut c_void for storage
-                    MB_FN_PTR = func_ptr as *mut c_void;
+                    MB_FN_PTR.store(func_ptr as *mut c_void, Ordering::Relaxed);
+                }
+
+                // 3. FlushInstructionCache, for heavy_barrier_sync_core()
+                if let Some(func_ptr) = GetProcAddress(h_kernel32, b"FlushInstructionCache\0".as_ptr()) {
+                    ICACHE_FN_PTR.store(func_ptr as *mut c_void, Ordering::Relaxed);
+                }
+            }
+        }
 
-                    // Enable acceleration
-                    IS_ACCELERATED = true;
+        /// `FlushProcessWriteBuffers` followed by `FlushInstructionCache` over the whole process.
+        /// `FlushProcessWriteBuffers` 之后对整个进程执行 `FlushInstructionCache`。
+        pub(crate) fn heavy_barrier_sync_core_impl() -> Result<(), BarrierError> {
+            let icache = ICACHE_FN_PTR.load(Ordering::Relaxed);
+            if !is_accelerated_impl() || icache.is_null() {
+                return Err(BarrierError::Unsupported);
+            }
+            heavy_barrier_impl();
+            unsafe {
+                let func: FnFlushInstructionCache = core::mem::transmute(icache);
+                // A null base with size 0 flushes the entire instruction cache of the process.
+                // 基址为空且大小为 0 时刷新进程的整个指令缓存。
+                if func(GetCurrentProcess(), core::ptr::null(), 0) == 0 {
+                    return Err(BarrierError::SyscallFailed(windows_sys::Win32::Foundation::GetLastError() as i32));
                 }
             }
+            compiler_fence(Ordering::SeqCst);
+            Ok(())
         }
 
         #[inline]
         pub(crate) fn heavy_barrier_impl() {
             // Check if we have the accelerated function
-            if unsafe { IS_ACCELERATED } {
+            let ptr = MB_FN_PTR.load(Ordering::Relaxed);
+            if !ptr.is_null() {
                 unsafe {
-                    let ptr = MB_FN_PTR;
-                    if !ptr.is_null() {
-                        let func: FnFlushProcessWriteBuffers = core::mem::transmute(ptr);
-                        func();
-                    }
+                    let func: FnFlushProcessWriteBuffers = core::mem::transmute(ptr);
+                    func();
                 }
+                // Like membarrier, the call returns once every processor flushed its write queue, so
+                // only the compiler still needs fencing.
+                // 与 membarrier 相同，该调用在每个处理器都刷新写队列后才返回，因此只剩编译器需要屏障。
                 compiler_fence(Ordering::SeqCst);
             } else {
                 // Fallback for XP / Server 2003 or if detection failed
-                fence(Ordering::SeqCst);
+                fallback_fence();
             }
         }
 
-        #[inline]
+        #[inline(always)]
         pub(crate) fn light_barrier_impl() {
-            if unsafe { IS_ACCELERATED } {
+            if is_accelerated_impl() {
                 compiler_fence(Ordering::SeqCst);
             } else {
-                fence(Ordering::SeqCst);
+                fallback_fence();
             }
         }
 
         /// Returns whether OS-accelerated barriers are in use.
         #[inline]
         pub(crate) fn is_accelerated_impl() -> bool {
-            unsafe { IS_ACCELERATED }
+            !MB_FN_PTR.load(Ordering::Relaxed).is_null()
+        }
+
+        /// FlushProcessWriteBuffers availability cannot change at runtime, so there is nothing to retry.
+        /// FlushProcessWriteBuffers 的可用性在运行时不会变化，因此无需重试。
+        pub(crate) fn init_impl() -> bool {
+            is_accelerated_impl()
+        }
+
+        // `FlushProcessWriteBuffers` does not depend on process privileges.
+        // `FlushProcessWriteBuffers` 与进程权限无关。
+        pub(crate) fn redetect_impl() -> bool {
+            init_impl()
+        }
+
+        #[cfg(feature = "testing")]
+        pub(crate) fn reinitialize_impl() {}
+
+        // Nothing is allocated or registered on this arm.
+        // 本分支上没有任何分配或注册。
+        #[inline]
+        pub(crate) fn shutdown_impl() {}
+
+        #[inline]
+        pub(crate) fn registration_errno_impl() -> Option<i32> {
+            None
+        }
+
+        // Already async-signal-safe: no lock, allocation or lazy initialization.
+        // 本身已是异步信号安全的：没有锁、分配或延迟初始化。
+        pub(crate) use heavy_barrier_impl as heavy_barrier_signal_safe_impl;
+
+        // No barrier on this arm can degrade per call.
+        // 本分支上的屏障不会在单次调用中降级。
+        #[inline]
+        pub(crate) fn heavy_barrier_traced_impl() -> Backend {
+            heavy_barrier_impl();
+            backend_impl()
+        }
+
+        pub(crate) const PLATFORM_IMPL: PlatformInfo = PlatformInfo {
+            can_accelerate: true,
+            os: env!("SWMR_BARRIER_TARGET_OS"),
+            mechanism: "FlushProcessWriteBuffers",
+        };
+
+        #[inline]
+        pub(crate) fn backend_impl() -> Backend {
+            if is_accelerated_impl() {
+                Backend::WindowsFlushProcessWriteBuffers
+            } else {
+                Backend::Fallback
+            }
+        }
+
+        #[inline]
+        pub(crate) fn light_fence_impl() -> FenceStrength {
+            backend_impl().light_fence()
+        }
+
+        #[inline]
+        pub(crate) fn init_reason_impl() -> InitReason {
+            if is_accelerated_impl() {
+                InitReason::Accelerated
+            } else {
+                InitReason::NotSupported
+            }
+        }
+
+        #[inline]
+        pub(crate) fn validation_impl() -> Validation {
+            Validation::NotRun
+        }
+
+        #[inline]
+        pub(crate) fn sync_core_available_impl() -> bool {
+            is_accelerated_impl() && !ICACHE_FN_PTR.load(Ordering::Relaxed).is_null()
+        }
+
+        #[inline]
+        pub(crate) fn acceleration_matrix_impl() -> AccelerationMatrix {
+            AccelerationMatrix {
+                flush_process_write_buffers: is_accelerated_impl(),
+                sync_core: sync_core_available_impl(),
+                ..AccelerationMatrix::NONE
+            }
         }
     }
 
 // ============================================================================
-// 4. Other Platforms / Fallback
-// 4. 其他平台 / Fallback
+// 5. DragonFly BSD Implementation
+// 5. DragonFly BSD 实现
 // ============================================================================
-    else {
-        use core::sync::atomic::{fence, Ordering};
+// DragonFly has no membarrier, but downgrading the protection of a mapped page makes the kernel
+// invalidate that page's TLB entries on every CPU the process is active on, by IPI. Taking the
+// interrupt serializes each of those CPUs, which is the same guarantee `FlushProcessWriteBuffers`
+// gives (and how it is emulated elsewhere). The heavy barrier dirties a locked helper page and
+// flips it to read-only and back; the light barrier drops to a compiler fence.
+//
+// DragonFly 没有 membarrier，但降低已映射页的保护会使内核通过 IPI 在本进程活跃的每个 CPU 上使该页的 TLB 项失效。
+// 处理中断会串行化这些 CPU，这与 `FlushProcessWriteBuffers` 提供的保证相同（其他平台上也以此方式模拟它）。
+// 重型屏障写脏一个锁定的辅助页，将其翻转为只读再翻转回来；轻型屏障降为编译器屏障。
+    else if #[cfg(target_os = "dragonfly")] {
+        use core::sync::atomic::{fence, compiler_fence, AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+        use core::ffi::c_void;
+        use libc::{mlock, mmap, mprotect, munmap, MAP_ANON, MAP_FAILED, MAP_PRIVATE, PROT_READ, PROT_WRITE};
+
+        // --------------------------------------------------------------------
+        // State Management
+        // --------------------------------------------------------------------
+        // The helper page, or null when not accelerated. Set once by the constructor before any
+        // thread exists, so, as on Windows, one word decides both sides and they always agree.
+        // 辅助页，未加速时为空。由构造函数在任何线程存在之前设置一次，因此与 Windows 相同，由一个字同时决定两端，二者始终一致。
+        static PAGE: AtomicPtr<c_void> = AtomicPtr::new(core::ptr::null_mut());
+
+        // Page size, recorded with `PAGE`.
+        // 页大小，与 `PAGE` 一同记录。
+        static PAGE_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+        // Held while the helper page is flipped: a concurrent flip could leave it read-only under
+        // another barrier's store.
+        // 翻转辅助页期间持有：并发翻转可能在另一次屏障写入时使其处于只读状态。
+        static FLIPPING: AtomicBool = AtomicBool::new(false);
+
+        // A second locked page for signal handlers that find `FLIPPING` held, possibly by the very
+        // code they interrupted. Set with `PAGE`, so it is non-null whenever `PAGE` is.
+        // 供发现 `FLIPPING` 被持有（可能正被其中断的代码持有）的信号处理函数使用的第二个锁定页。与 `PAGE` 一同设置，
+        // 因此 `PAGE` 非空时它也非空。
+        static SIGNAL_PAGE: AtomicPtr<c_void> = AtomicPtr::new(core::ptr::null_mut());
+
+        // Held while `SIGNAL_PAGE` is flipped.
+        // 翻转 `SIGNAL_PAGE` 期间持有。
+        static SIGNAL_FLIPPING: AtomicBool = AtomicBool::new(false);
+
+        /// Dirties `page`, then makes it read-only and writable again. Returns `false` if either
+        /// `mprotect` failed. The store keeps the kernel from skipping the shootdown for a clean page.
+        ///
+        /// 写脏 `page`，然后将其设为只读再恢复可写。任一 `mprotect` 失败时返回 `false`。写入可防止内核对干净页跳过 TLB 击落。
+        fn flip(page: *mut c_void, size: usize) -> bool {
+            unsafe {
+                // SAFETY: `page` is a private writable mapping of at least one word, and no one
+                // else flips it while we do.
+                // 安全性：`page` 是至少一个字大小的私有可写映射，且在此期间没有其他人翻转它。
+                (*page.cast::<AtomicUsize>()).fetch_add(1, Ordering::SeqCst);
+                mprotect(page, size, PROT_READ) == 0 && mprotect(page, size, PROT_READ | PROT_WRITE) == 0
+            }
+        }
+
+        /// Maps a private writable page, locks it and flips it once, or returns null. Locked, so the
+        /// page is never swapped out and its protection change always reaches the TLBs. The trial
+        /// flip proves `mprotect` works before readers depend on it.
+        ///
+        /// 映射一个私有可写页，锁定并试翻转一次，失败时返回空。锁定后该页永不换出，其保护变更总能到达 TLB。
+        /// 试翻转在读者依赖它之前证明 `mprotect` 可用。
+        fn map_locked_page(size: usize) -> *mut c_void {
+            let page = unsafe { mmap(core::ptr::null_mut(), size, PROT_READ | PROT_WRITE, MAP_PRIVATE | MAP_ANON, -1, 0) };
+            if page == MAP_FAILED {
+                return core::ptr::null_mut();
+            }
+            if unsafe { mlock(page, size) } != 0 || !flip(page, size) {
+                unsafe { munmap(page, size) };
+                return core::ptr::null_mut();
+            }
+            page
+        }
+
+        // --------------------------------------------------------------------
+        // Initialization (runs before main)
+        // 初始化 (在 main 之前运行)
+        // --------------------------------------------------------------------
+        // Registered by `crate::init` in `.init_array`. Any failure leaves `PAGE` null, so both
+        // sides keep `fence(SeqCst)`.
+        // 由 `crate::init` 注册到 `.init_array`。任何失败都会使 `PAGE` 保持为空，两端继续使用 `fence(SeqCst)`。
+        pub(crate) fn auto_init_impl() {
+            let size = match unsafe { libc::sysconf(libc::_SC_PAGESIZE) } {
+                size if size > 0 => size as usize,
+                _ => return,
+            };
+            // Both pages or neither: the signal path must never have to map memory.
+            // 两页都成功或都不使用：信号路径绝不能需要映射内存。
+            let page = map_locked_page(size);
+            if page.is_null() {
+                return;
+            }
+            let signal_page = map_locked_page(size);
+            if signal_page.is_null() {
+                unsafe { munmap(page, size) };
+                return;
+            }
+            PAGE_SIZE.store(size, Ordering::Relaxed);
+            SIGNAL_PAGE.store(signal_page, Ordering::Relaxed);
+            PAGE.store(page, Ordering::Relaxed);
+        }
+
+        /// The heavy barrier, returning the mechanism that ordered memory on this call.
+        /// 重型屏障，并返回本次调用中实际完成内存排序的机制。
+        #[inline]
+        pub(crate) fn heavy_barrier_traced_impl() -> Backend {
+            let page = PAGE.load(Ordering::Relaxed);
+            if page.is_null() {
+                fallback_fence();
+                return Backend::Fallback;
+            }
+            while FLIPPING
+                .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                core::hint::spin_loop();
+            }
+            let flipped = flip(page, PAGE_SIZE.load(Ordering::Relaxed));
+            FLIPPING.store(false, Ordering::Release);
+
+            // Safety net, as for a failed membarrier: the fence orders this side, and readers on
+            // the cheap path are exposed only for this one call.
+            // 安全网，与 membarrier 失败时相同：栅栏为本侧排序，廉价路径上的读者仅在这一次调用中暴露。
+            let backend = if flipped {
+                Backend::MprotectTlbShootdown
+            } else {
+                fence(Ordering::SeqCst);
+                Backend::Fallback
+            };
+            // The shootdown returns only after every CPU took the IPI, so only the compiler still
+            // needs fencing.
+            // TLB 击落在每个 CPU 都处理完 IPI 后才返回，因此只剩编译器需要屏障。
+            compiler_fence(Ordering::SeqCst);
+            backend
+        }
+
+        /// Flips `page` if `lock` is free, without waiting. `None` if another flip holds it.
+        /// 若 `lock` 空闲则翻转 `page`，不等待。另一次翻转持有它时返回 `None`。
+        fn try_flip(lock: &AtomicBool, page: *mut c_void, size: usize) -> Option<bool> {
+            lock.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).ok()?;
+            let flipped = flip(page, size);
+            lock.store(false, Ordering::Release);
+            Some(flipped)
+        }
+
+        /// `heavy_barrier_traced_impl` without the spin lock, which a handler interrupting a flip on its
+        /// own thread would wait on forever. Flips the pre-mapped signal page instead when the
+        /// helper page is busy, so only `mprotect` runs in the handler. If both are busy (a handler
+        /// nested in a handler, or two threads' handlers at once) it falls back to the safety-net
+        /// fence, like a failed flip. Restores `errno`.
+        ///
+        /// 去掉自旋锁的 `heavy_barrier_impl`：中断本线程翻转的信号处理函数会永远等待该锁。辅助页忙时改为翻转预先映射的
+        /// 信号页，因此处理函数中只运行 `mprotect`。两页都忙时（处理函数嵌套于处理函数，或两个线程的处理函数同时运行）
+        /// 与翻转失败时一样退回安全网栅栏。会恢复 `errno`。
+        pub(crate) fn heavy_barrier_signal_safe_impl() {
+            let page = PAGE.load(Ordering::Relaxed);
+            if page.is_null() {
+                fallback_fence();
+                return;
+            }
+            let size = PAGE_SIZE.load(Ordering::Relaxed);
+            let errno = unsafe { *libc::__errno_location() };
+            let flipped = try_flip(&FLIPPING, page, size)
+                .or_else(|| try_flip(&SIGNAL_FLIPPING, SIGNAL_PAGE.load(Ordering::Relaxed), size))
+                .unwrap_or(false);
+            if !flipped {
+                fence(Ordering::SeqCst);
+            }
+            unsafe { *libc::__errno_location() = errno };
+            compiler_fence(Ordering::SeqCst);
+        }
+
+        #[inline(always)]
+        pub(crate) fn light_barrier_impl() {
+            if is_accelerated_impl() {
+                compiler_fence(Ordering::SeqCst);
+            } else {
+                fallback_fence();
+            }
+        }
+
+        /// Returns whether OS-accelerated barriers are in use.
+        #[inline]
+        pub(crate) fn is_accelerated_impl() -> bool {
+            !PAGE.load(Ordering::Relaxed).is_null()
+        }
+
+        /// The helper page is set up once, before `main`: switching readers to the cheap path
+        /// later would race writers still fencing, so there is nothing to retry.
+        /// 辅助页只在 `main` 之前设置一次：之后再将读者切换到廉价路径会与仍在使用栅栏的写者竞争，因此无需重试。
+        pub(crate) fn init_impl() -> bool {
+            is_accelerated_impl()
+        }
+
+        // Same reason as `init_impl`: even if `RLIMIT_MEMLOCK` was raised since, the page cannot be
+        // set up safely once threads exist.
+        // 与 `init_impl` 同理：即使此后提高了 `RLIMIT_MEMLOCK`，线程存在后也无法安全地设置辅助页。
+        pub(crate) fn redetect_impl() -> bool {
+            init_impl()
+        }
+
+        #[cfg(feature = "testing")]
+        pub(crate) fn reinitialize_impl() {}
+
+        // The helper page stays mapped: readers may still be on the cheap path.
+        // 辅助页保持映射：读者可能仍在廉价路径上。
+        #[inline]
+        pub(crate) fn shutdown_impl() {}
+
+        #[inline]
+        pub(crate) fn registration_errno_impl() -> Option<i32> {
+            None
+        }
+
+        pub(crate) const PLATFORM_IMPL: PlatformInfo = PlatformInfo {
+            can_accelerate: true,
+            os: env!("SWMR_BARRIER_TARGET_OS"),
+            mechanism: "mprotect",
+        };
+
+        #[inline]
+        pub(crate) fn backend_impl() -> Backend {
+            if is_accelerated_impl() {
+                Backend::MprotectTlbShootdown
+            } else {
+                Backend::Fallback
+            }
+        }
+
+        #[inline]
+        pub(crate) fn light_fence_impl() -> FenceStrength {
+            backend_impl().light_fence()
+        }
+
+        #[inline]
+        pub(crate) fn init_reason_impl() -> InitReason {
+            if is_accelerated_impl() {
+                InitReason::Accelerated
+            } else {
+                InitReason::NotSupported
+            }
+        }
+
+        #[inline]
+        pub(crate) fn validation_impl() -> Validation {
+            Validation::NotRun
+        }
+
+        #[inline]
+        pub(crate) fn sync_core_available_impl() -> bool {
+            false
+        }
+
+        #[inline]
+        pub(crate) fn acceleration_matrix_impl() -> AccelerationMatrix {
+            AccelerationMatrix::NONE
+        }
+    }
+
+// ============================================================================
+// 6. WASI
+// 6. WASI
+// ============================================================================
+// WASI has no OS barrier. With the `atomics` target feature (wasi-threads) memory is
+// shared between threads and both sides need a real `atomic.fence`. Without it the
+// module is single-threaded, so preventing compiler reordering is sufficient.
+//
+// WASI 没有 OS 屏障。启用 `atomics` 目标特性 (wasi-threads) 时内存在线程间共享，
+// 读写两端都需要真正的 `atomic.fence`；否则模块是单线程的，只需阻止编译器重排。
+    else if #[cfg(target_os = "wasi")] {
+        use core::sync::atomic::Ordering;
+        #[cfg(target_feature = "atomics")]
+        use core::sync::atomic::fence;
+        #[cfg(not(target_feature = "atomics"))]
+        use core::sync::atomic::compiler_fence as fence;
 
         #[inline]
         pub(crate) fn heavy_barrier_impl() {
             fence(Ordering::SeqCst);
         }
 
+        #[inline(always)]
+        pub(crate) fn light_barrier_impl() {
+            fence(Ordering::SeqCst);
+        }
+
+        // Compile-time constant: callers' branches fold away.
+        // 编译期常量：调用方的分支会被折叠。
         #[inline]
+        pub(crate) const fn is_accelerated_impl() -> bool {
+            false
+        }
+
+        pub(crate) fn init_impl() -> bool {
+            false
+        }
+
+        // Nothing to detect on this arm.
+        // 本分支上无需检测任何内容。
+        pub(crate) fn redetect_impl() -> bool {
+            init_impl()
+        }
+
+        #[cfg(feature = "testing")]
+        pub(crate) fn reinitialize_impl() {}
+
+        // Nothing is allocated or registered on this arm.
+        // 本分支上没有任何分配或注册。
+        #[inline]
+        pub(crate) fn shutdown_impl() {}
+
+        #[inline]
+        pub(crate) fn registration_errno_impl() -> Option<i32> {
+            None
+        }
+
+        // Already async-signal-safe: no lock, allocation or lazy initialization.
+        // 本身已是异步信号安全的：没有锁、分配或延迟初始化。
+        pub(crate) use heavy_barrier_impl as heavy_barrier_signal_safe_impl;
+
+        // No barrier on this arm can degrade per call.
+        // 本分支上的屏障不会在单次调用中降级。
+        #[inline]
+        pub(crate) fn heavy_barrier_traced_impl() -> Backend {
+            heavy_barrier_impl();
+            backend_impl()
+        }
+
+        pub(crate) const PLATFORM_IMPL: PlatformInfo = PlatformInfo {
+            can_accelerate: false,
+            os: env!("SWMR_BARRIER_TARGET_OS"),
+            mechanism: if cfg!(target_feature = "atomics") { "fence" } else { "compiler fence" },
+        };
+
+        #[inline]
+        pub(crate) fn backend_impl() -> Backend {
+            if cfg!(target_feature = "atomics") {
+                Backend::Fallback
+            } else {
+                Backend::SingleThreaded
+            }
+        }
+
+        #[inline]
+        pub(crate) fn light_fence_impl() -> FenceStrength {
+            backend_impl().light_fence()
+        }
+
+        #[inline]
+        pub(crate) fn init_reason_impl() -> InitReason {
+            InitReason::Unsupported
+        }
+
+        #[inline]
+        pub(crate) fn validation_impl() -> Validation {
+            Validation::NotRun
+        }
+
+        #[inline]
+        pub(crate) fn sync_core_available_impl() -> bool {
+            false
+        }
+
+        #[inline]
+        pub(crate) fn acceleration_matrix_impl() -> AccelerationMatrix {
+            AccelerationMatrix::NONE
+        }
+    }
+
+// ============================================================================
+// 7. Real-Time Operating Systems
+// 7. 实时操作系统
+// ============================================================================
+// VxWorks, Horizon (Nintendo 3DS / Switch), NuttX, RTEMS and ESP-IDF export no asymmetric barrier:
+// tasks share one address space and are ordered only by the CPU's own barrier instruction. On
+// every architecture these targets support, LLVM lowers `fence(SeqCst)` to that instruction
+// (`mfence`, `dmb ish` / `mcr p15` DMB on ARMv6K, `sync` on PowerPC, `fence rw,rw` on RISC-V,
+// `memw` on Xtensa), so both sides fencing is correct on SMP parts and merely redundant on
+// single-core ones. A BSP that offers a cross-core barrier (e.g. an IPI broadcast) plugs in at
+// `rtos_heavy_barrier`; `light_barrier_impl` may then drop to `compiler_fence`, and
+// `is_accelerated_impl`, `PLATFORM_IMPL` and `backend_impl` must change with it.
+//
+// VxWorks、Horizon（任天堂 3DS / Switch）、NuttX、RTEMS 与 ESP-IDF 不提供非对称屏障：任务共享同一地址空间，
+// 仅由 CPU 自身的屏障指令排序。在这些目标支持的所有架构上，LLVM 都将 `fence(SeqCst)` 降级为该指令（`mfence`、
+// `dmb ish` / ARMv6K 上的 `mcr p15` DMB、PowerPC 上的 `sync`、RISC-V 上的 `fence rw,rw`、Xtensa 上的 `memw`），
+// 因此读写两端都使用栅栏在 SMP 芯片上是正确的，在单核芯片上只是冗余。若 BSP 提供跨核屏障（例如 IPI 广播），
+// 可接入 `rtos_heavy_barrier`；届时 `light_barrier_impl` 可降为 `compiler_fence`，`is_accelerated_impl`、
+// `PLATFORM_IMPL` 与 `backend_impl` 也必须随之修改。
+    else if #[cfg(any(
+        target_os = "vxworks",
+        target_os = "horizon",
+        target_os = "nuttx",
+        target_os = "rtems",
+        target_os = "espidf"
+    ))] {
+        /// Writer-side barrier of the RTOS; the extension point for a BSP-provided barrier.
+        /// RTOS 的写侧屏障；BSP 提供的屏障的接入点。
+        #[inline]
+        fn rtos_heavy_barrier() {
+            fallback_fence();
+        }
+
+        #[inline]
+        pub(crate) fn heavy_barrier_impl() {
+            rtos_heavy_barrier();
+        }
+
+        #[inline(always)]
+        pub(crate) fn light_barrier_impl() {
+            // Pairs with a plain fence until `rtos_heavy_barrier` is a real asymmetric barrier.
+            // 在 `rtos_heavy_barrier` 成为真正的非对称屏障之前，与普通栅栏配对。
+            fallback_fence();
+        }
+
+        // Compile-time constant: callers' branches fold away.
+        // 编译期常量：调用方的分支会被折叠。
+        #[inline]
+        pub(crate) const fn is_accelerated_impl() -> bool {
+            false
+        }
+
+        pub(crate) fn init_impl() -> bool {
+            false
+        }
+
+        // Nothing to detect on this arm.
+        // 本分支上无需检测任何内容。
+        pub(crate) fn redetect_impl() -> bool {
+            init_impl()
+        }
+
+        #[cfg(feature = "testing")]
+        pub(crate) fn reinitialize_impl() {}
+
+        // Nothing is allocated or registered on this arm.
+        // 本分支上没有任何分配或注册。
+        #[inline]
+        pub(crate) fn shutdown_impl() {}
+
+        #[inline]
+        pub(crate) fn registration_errno_impl() -> Option<i32> {
+            None
+        }
+
+        // Already async-signal-safe: no lock, allocation or lazy initialization.
+        // 本身已是异步信号安全的：没有锁、分配或延迟初始化。
+        pub(crate) use heavy_barrier_impl as heavy_barrier_signal_safe_impl;
+
+        // No barrier on this arm can degrade per call.
+        // 本分支上的屏障不会在单次调用中降级。
+        #[inline]
+        pub(crate) fn heavy_barrier_traced_impl() -> Backend {
+            heavy_barrier_impl();
+            backend_impl()
+        }
+
+        pub(crate) const PLATFORM_IMPL: PlatformInfo = PlatformInfo {
+            can_accelerate: false,
+            os: env!("SWMR_BARRIER_TARGET_OS"),
+            mechanism: "fence (RTOS)",
+        };
+
+        #[inline]
+        pub(crate) fn backend_impl() -> Backend {
+            Backend::Fallback
+        }
+
+        #[inline]
+        pub(crate) fn light_fence_impl() -> FenceStrength {
+            backend_impl().light_fence()
+        }
+
+        #[inline]
+        pub(crate) fn init_reason_impl() -> InitReason {
+            InitReason::Unsupported
+        }
+
+        #[inline]
+        pub(crate) fn validation_impl() -> Validation {
+            Validation::NotRun
+        }
+
+        #[inline]
+        pub(crate) fn sync_core_available_impl() -> bool {
+            false
+        }
+
+        #[inline]
+        pub(crate) fn acceleration_matrix_impl() -> AccelerationMatrix {
+            AccelerationMatrix::NONE
+        }
+    }
+
+// ============================================================================
+// 8. Other Platforms / Fallback
+// 8. 其他平台 / Fallback
+// ============================================================================
+// SPARC (Solaris / illumos): user code normally runs under TSO, where only #StoreLoad needs a
+// barrier, but the V9 architecture also allows PSO/RMO, where all four orderings can be relaxed.
+// LLVM lowers every `fence` on sparc64 to `membar #StoreLoad | #StoreStore | #LoadStore | #LoadLoad`,
+// so `fence(SeqCst)` already is the full membar on both sides. Neither OS offers an asymmetric
+// barrier, so the reader cannot drop to a compiler fence. (Inline `membar` would need SPARC
+// `asm!`, which is not yet stable.)
+//
+// SPARC (Solaris / illumos)：用户代码通常运行在 TSO 下，只有 #StoreLoad 需要屏障；但 V9 架构也允许
+// PSO/RMO，四种顺序都可能被放宽。LLVM 在 sparc64 上将所有 `fence` 降级为
+// `membar #StoreLoad | #StoreStore | #LoadStore | #LoadLoad`，因此 `fence(SeqCst)` 在读写两端本身就是完整的 membar。
+// 两个系统都不提供非对称屏障，读者无法退化为编译器屏障。（内联 `membar` 需要 SPARC `asm!`，目前尚未稳定。）
+    else {
+        #[inline]
+        pub(crate) fn heavy_barrier_impl() {
+            fallback_fence();
+        }
+
+        #[inline(always)]
         pub(crate) fn light_barrier_impl() {
             // No OS acceleration, both Reader and Writer must use heavy barriers.
             // 没有 OS 加速，读写两端都必须是重屏障
-            fence(Ordering::SeqCst);
+            fallback_fence();
         }
 
+        // Compile-time constant: callers' branches fold away.
+        // 编译期常量：调用方的分支会被折叠。
         #[inline]
-        pub(crate) fn is_accelerated_impl() -> bool {
+        pub(crate) const fn is_accelerated_impl() -> bool {
+            false
+        }
+
+        pub(crate) fn init_impl() -> bool {
             false
         }
+
+        // Nothing to detect on this arm.
+        // 本分支上无需检测任何内容。
+        pub(crate) fn redetect_impl() -> bool {
+            init_impl()
+        }
+
+        // Nothing to detect before `main`.
+        // `main` 之前无需检测任何内容。
+        #[cfg(swmr_barrier_ctor)]
+        pub(crate) fn auto_init_impl() {}
+
+        #[cfg(feature = "testing")]
+        pub(crate) fn reinitialize_impl() {}
+
+        // Nothing is allocated or registered on this arm.
+        // 本分支上没有任何分配或注册。
+        #[inline]
+        pub(crate) fn shutdown_impl() {}
+
+        #[inline]
+        pub(crate) fn registration_errno_impl() -> Option<i32> {
+            None
+        }
+
+        // Already async-signal-safe: no lock, allocation or lazy initialization.
+        // 本身已是异步信号安全的：没有锁、分配或延迟初始化。
+        pub(crate) use heavy_barrier_impl as heavy_barrier_signal_safe_impl;
+
+        // No barrier on this arm can degrade per call.
+        // 本分支上的屏障不会在单次调用中降级。
+        #[inline]
+        pub(crate) fn heavy_barrier_traced_impl() -> Backend {
+            heavy_barrier_impl();
+            backend_impl()
+        }
+
+        pub(crate) const PLATFORM_IMPL: PlatformInfo = PlatformInfo {
+            can_accelerate: false,
+            os: env!("SWMR_BARRIER_TARGET_OS"),
+            mechanism: "fence",
+        };
+
+        #[inline]
+        pub(crate) fn backend_impl() -> Backend {
+            Backend::Fallback
+        }
+
+        #[inline]
+        pub(crate) fn light_fence_impl() -> FenceStrength {
+            backend_impl().light_fence()
+        }
+
+        #[inline]
+        pub(crate) fn init_reason_impl() -> InitReason {
+            InitReason::Unsupported
+        }
+
+        #[inline]
+        pub(crate) fn validation_impl() -> Validation {
+            Validation::NotRun
+        }
+
+        #[inline]
+        pub(crate) fn sync_core_available_impl() -> bool {
+            false
+        }
+
+        #[inline]
+        pub(crate) fn acceleration_matrix_impl() -> AccelerationMatrix {
+            AccelerationMatrix::NONE
+        }
     }
 }
@@ -0,0 +1,54 @@
+//! **Runtime Telemetry Counters** (feature `telemetry`)
+//!
+//! Process-wide counts of heavy barriers, light barriers and heavy barriers that ended on a fence,
+//! read together by [`runtime_telemetry()`](crate::runtime_telemetry). The heavy and fallback
+//! counters are bumped with `Release` and read with `Acquire` in the opposite order, so a snapshot
+//! never reports more fallbacks than heavy barriers, nor more heavy barriers than the generation.
+//!
+//! ---
+//!
+//! **运行时遥测计数器**（`telemetry` 特性）
+//!
+//! 进程级的重型屏障、轻型屏障以及以栅栏结束的重型屏障计数，由 [`runtime_telemetry()`](crate::runtime_telemetry)
+//! 一并读取。重型与回退计数器以 `Release` 递增、以相反顺序用 `Acquire` 读取，因此快照报告的回退次数绝不多于重型屏障，
+//! 重型屏障也绝不多于屏障代数。
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+static HEAVY: AtomicU64 = AtomicU64::new(0);
+static LIGHT: AtomicU64 = AtomicU64::new(0);
+static FALLBACK: AtomicU64 = AtomicU64::new(0);
+
+/// Counts one heavy barrier; called after the generation bump.
+/// 计数一次重型屏障；在屏障代数递增之后调用。
+#[inline]
+pub(crate) fn record_heavy() {
+    HEAVY.fetch_add(1, Ordering::Release);
+}
+
+/// Counts one heavy barrier that ordered memory with a fence; called after [`record_heavy`].
+/// 计数一次以栅栏完成排序的重型屏障；在 [`record_heavy`] 之后调用。
+#[inline]
+pub(crate) fn record_fallback() {
+    FALLBACK.fetch_add(1, Ordering::Release);
+}
+
+#[inline(always)]
+pub(crate) fn record_light() {
+    LIGHT.fetch_add(1, Ordering::Relaxed);
+}
+
+/// `(heavy, light, fallback)`, read fallback first.
+/// `(heavy, light, fallback)`，先读取回退计数。
+pub(crate) fn counts() -> (u64, u64, u64) {
+    let fallback = FALLBACK.load(Ordering::Acquire);
+    let heavy = HEAVY.load(Ordering::Acquire);
+    (heavy, LIGHT.load(Ordering::Relaxed), fallback)
+}
+
+#[cfg(feature = "testing")]
+pub(crate) fn reset() {
+    for counter in [&HEAVY, &LIGHT, &FALLBACK] {
+        counter.store(0, Ordering::Relaxed);
+    }
+}
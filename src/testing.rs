@@ -0,0 +1,305 @@
+//! **Test Support** (feature `testing`)
+//!
+//! Fault-injection hooks for exercising degradation paths that real kernels rarely take.
+//! Not intended for production builds.
+//!
+//! ---
+//!
+//! **测试支持**（`testing` 特性）
+//!
+//! 用于触发真实内核中很少出现的降级路径的故障注入钩子。不应用于生产构建。
+
+use crate::{AsymmetricBarrier, RcuReader};
+use core::sync::atomic::{AtomicI32, AtomicU8, AtomicU32, AtomicUsize, Ordering};
+
+static REGISTRATION_FAILURES: AtomicUsize = AtomicUsize::new(0);
+static REGISTRATION_ERRNO: AtomicI32 = AtomicI32::new(0);
+static BARRIER_FAILURES: AtomicUsize = AtomicUsize::new(0);
+static BARRIER_ERRNO: AtomicI32 = AtomicI32::new(0);
+static QUERY_FAILURES: AtomicUsize = AtomicUsize::new(0);
+static QUERY_ERRNO: AtomicI32 = AtomicI32::new(0);
+
+/// Makes the next `count` membarrier registration attempts fail with `errno`.
+///
+/// 使接下来 `count` 次 membarrier 注册以 `errno` 失败。
+pub fn inject_registration_failure(errno: i32, count: usize) {
+    REGISTRATION_ERRNO.store(errno, Ordering::Relaxed);
+    REGISTRATION_FAILURES.store(count, Ordering::Relaxed);
+}
+
+/// Makes the next `count` membarrier barrier commands (PRIVATE_EXPEDITED or SHARED) fail with `errno`.
+///
+/// 使接下来 `count` 次 membarrier 屏障命令（PRIVATE_EXPEDITED 或 SHARED）以 `errno` 失败。
+pub fn inject_barrier_failure(errno: i32, count: usize) {
+    BARRIER_ERRNO.store(errno, Ordering::Relaxed);
+    BARRIER_FAILURES.store(count, Ordering::Relaxed);
+}
+
+/// Makes the next `count` membarrier capability queries fail with `errno`, like a seccomp filter
+/// installed before the crate's constructor ran.
+///
+/// 使接下来 `count` 次 membarrier 能力查询以 `errno` 失败，模拟在 crate 构造函数运行之前安装的 seccomp 过滤器。
+pub fn inject_query_failure(errno: i32, count: usize) {
+    QUERY_ERRNO.store(errno, Ordering::Relaxed);
+    QUERY_FAILURES.store(count, Ordering::Relaxed);
+}
+
+#[cfg(feature = "std")]
+static BARRIER_DELAY_NS: core::sync::atomic::AtomicU64 = core::sync::atomic::AtomicU64::new(0);
+#[cfg(feature = "std")]
+static BARRIER_DELAYS: AtomicUsize = AtomicUsize::new(0);
+
+/// Makes the next `count` membarrier barrier commands sleep for `delay` before running, like a
+/// syscall stuck on a CPU in a bad state.
+///
+/// 使接下来 `count` 次 membarrier 屏障命令在执行前休眠 `delay`，模拟卡在异常 CPU 上的系统调用。
+#[cfg(feature = "std")]
+pub fn inject_barrier_delay(delay: std::time::Duration, count: usize) {
+    BARRIER_DELAY_NS.store(delay.as_nanos().try_into().unwrap_or(u64::MAX), Ordering::Relaxed);
+    BARRIER_DELAYS.store(count, Ordering::Relaxed);
+}
+
+#[cfg(feature = "std")]
+static TORN_WRITE_NS: core::sync::atomic::AtomicU64 = core::sync::atomic::AtomicU64::new(0);
+#[cfg(feature = "std")]
+static TORN_WRITES: AtomicUsize = AtomicUsize::new(0);
+
+/// Makes the next `count` [`ChecksummedCell::set`](crate::ChecksummedCell::set) calls first publish
+/// the new checksum over the old value, as a torn write would leave it, and hold that for `delay`
+/// before publishing the real value.
+///
+/// 使接下来 `count` 次 [`ChecksummedCell::set`](crate::ChecksummedCell::set) 调用先发布旧值配新校验和（如同撕裂写入留下的状态），
+/// 保持 `delay` 后再发布真实值。
+#[cfg(feature = "std")]
+pub fn inject_torn_write(delay: std::time::Duration, count: usize) {
+    TORN_WRITE_NS.store(delay.as_nanos().try_into().unwrap_or(u64::MAX), Ordering::Relaxed);
+    TORN_WRITES.store(count, Ordering::Relaxed);
+}
+
+// Fake kernel release: KERNEL_NONE (use `uname`), KERNEL_UNPARSEABLE, or KERNEL_SET (below).
+// 伪造的内核发行版本：KERNEL_NONE（使用 `uname`）、KERNEL_UNPARSEABLE 或 KERNEL_SET（见下）。
+const KERNEL_NONE: u8 = 0;
+const KERNEL_UNPARSEABLE: u8 = 1;
+const KERNEL_SET: u8 = 2;
+static KERNEL_STATE: AtomicU8 = AtomicU8::new(KERNEL_NONE);
+static KERNEL_PARTS: [AtomicU32; 3] = [AtomicU32::new(0), AtomicU32::new(0), AtomicU32::new(0)];
+
+/// Makes [`kernel_version()`](crate::kernel_version) and the `strict` denylist check see `release`
+/// (e.g. `"4.14.3-generic"`) instead of `uname`. `None` removes the override. Takes effect at the
+/// next detection, e.g. [`reinitialize()`] or [`init()`](crate::init).
+///
+/// 使 [`kernel_version()`](crate::kernel_version) 与 `strict` 拒绝列表检查看到 `release`（例如
+/// `"4.14.3-generic"`）而非 `uname` 的结果。`None` 移除覆盖。在下一次检测时生效，例如 [`reinitialize()`] 或
+/// [`init()`](crate::init)。
+pub fn set_kernel_release(release: Option<&str>) {
+    let state = match release.map(crate::KernelVersion::parse) {
+        None => KERNEL_NONE,
+        Some(None) => KERNEL_UNPARSEABLE,
+        Some(Some(version)) => {
+            for (part, value) in KERNEL_PARTS.iter().zip([version.major, version.minor, version.patch]) {
+                part.store(value, Ordering::Relaxed);
+            }
+            KERNEL_SET
+        }
+    };
+    KERNEL_STATE.store(state, Ordering::Relaxed);
+}
+
+/// Clears all pending injected failures and the kernel release override.
+///
+/// 清除所有待触发的注入故障及内核发行版本覆盖。
+pub fn clear_injected_failures() {
+    KERNEL_STATE.store(KERNEL_NONE, Ordering::Relaxed);
+    REGISTRATION_FAILURES.store(0, Ordering::Relaxed);
+    BARRIER_FAILURES.store(0, Ordering::Relaxed);
+    QUERY_FAILURES.store(0, Ordering::Relaxed);
+    #[cfg(feature = "std")]
+    {
+        BARRIER_DELAYS.store(0, Ordering::Relaxed);
+        TORN_WRITES.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Re-runs the automatic (pre-`main`) detection path from a clean state, honoring injected failures.
+///
+/// 从干净状态重新执行自动（`main` 之前）检测路径，注入的故障会生效。
+pub fn reinitialize() {
+    crate::sys::reinitialize_impl();
+}
+
+/// **Force SHARED**
+///
+/// Makes heavy barriers issue `MEMBARRIER_CMD_SHARED` instead of the command detection selected
+/// (normally PRIVATE_EXPEDITED), so tests can cover the older kernels' path on a current one.
+/// Returns `false`, changing nothing, if the kernel does not advertise SHARED. Readers keep the
+/// compiler-fence path; [`reset_for_test`] restores detection's choice.
+///
+/// ---
+///
+/// **强制 SHARED**
+///
+/// 使重型屏障发出 `MEMBARRIER_CMD_SHARED`，而非检测选定的命令（通常为 PRIVATE_EXPEDITED），以便在新内核上测试旧内核的路径。
+/// 若内核未声明支持 SHARED，则不做任何改变并返回 `false`。读者保持编译器屏障路径；[`reset_for_test`] 恢复检测的选择。
+#[cfg(all(target_os = "linux", not(feature = "loom"), not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))))]
+pub fn force_shared_command() -> bool {
+    crate::sys::force_shared_impl()
+}
+
+/// Whether the crate's pre-`main` constructor ran. Always `false` on targets without one (WASI,
+/// RTOS, bare metal).
+///
+/// crate 的 `main` 之前构造函数是否已运行。在没有构造函数的目标（WASI、RTOS、裸机）上始终为 `false`。
+pub fn constructor_ran() -> bool {
+    crate::init::ran()
+}
+
+/// **Reset for Test**
+///
+/// Clears all injected failures and restores every global barrier static to its post-init default
+/// by re-running detection. Call it at the start of any test that mutates global barrier state, so
+/// the outcome does not depend on which tests ran before it in the same binary.
+///
+/// ---
+///
+/// **测试重置**
+///
+/// 清除所有注入的故障，并通过重新执行检测将所有全局屏障状态恢复为初始化后的默认值。
+/// 任何会修改全局屏障状态的测试都应在开始时调用它，使结果不依赖于同一二进制中先前运行的测试。
+pub fn reset_for_test() {
+    clear_injected_failures();
+    #[cfg(all(
+        feature = "std",
+        target_os = "linux",
+        not(feature = "loom"),
+        not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+    ))]
+    crate::mode::set_warning_hook(None);
+    #[cfg(feature = "debug-checks")]
+    crate::debug_checks::set_hook(None);
+    reinitialize();
+    #[cfg(all(
+        feature = "std",
+        target_os = "linux",
+        not(feature = "loom"),
+        not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+    ))]
+    crate::watchdog::reset();
+    #[cfg(all(
+        feature = "latency",
+        target_os = "linux",
+        not(feature = "loom"),
+        not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+    ))]
+    crate::latency::reset();
+    #[cfg(feature = "telemetry")]
+    crate::telemetry::reset();
+}
+
+/// **Seed Reader Sequence**
+///
+/// Sets `reader`'s sequence counter to `seq`, e.g. just below `u64::MAX` to exercise wrap-around.
+/// `seq` must be even (outside a critical section); the `&mut` borrow guarantees no guard is open.
+///
+/// ---
+///
+/// **设置读者序号**
+///
+/// 将 `reader` 的序号计数器设为 `seq`，例如设为略小于 `u64::MAX` 的值以测试回绕。
+/// `seq` 必须为偶数（位于临界区之外）；`&mut` 借用保证没有未释放的守卫。
+pub fn seed_reader_sequence<const SLOTS: usize, B: AsymmetricBarrier>(reader: &mut RcuReader<'_, SLOTS, B>, seq: u64) {
+    assert!(seq & 1 == 0, "seeded sequence must be even");
+    reader.seed_sequence(seq);
+}
+
+/// Parses a directory laid out like `/sys/devices/system/cpu`, as [`cpu_topology_hint()`](crate::cpu_topology_hint) does.
+///
+/// 按 [`cpu_topology_hint()`](crate::cpu_topology_hint) 的方式解析布局与 `/sys/devices/system/cpu` 相同的目录。
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub fn read_cpu_topology(root: &std::path::Path) -> Option<crate::TopologyHint> {
+    crate::topology::read_sysfs(root)
+}
+
+/// Applies the qemu-user check of [`running_under_qemu_user()`](crate::running_under_qemu_user) to
+/// the given environment variable names and `/proc/self/maps` contents.
+///
+/// 对给定的环境变量名与 `/proc/self/maps` 内容应用 [`running_under_qemu_user()`](crate::running_under_qemu_user) 的 qemu-user 检测。
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub fn detect_qemu_user(env_names: &[&str], maps: &str) -> bool {
+    crate::emulation::detect_in(env_names.iter().copied(), maps)
+}
+
+/// CPUs targeted by per-CPU heavy barriers since the last call, in issue order.
+///
+/// 自上次调用以来按发起顺序记录的、被逐 CPU 重型屏障定向的 CPU。
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub fn take_targeted_cpus() -> std::vec::Vec<usize> {
+    core::mem::take(&mut *TARGETED_CPUS.lock().unwrap_or_else(std::sync::PoisonError::into_inner))
+}
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+static TARGETED_CPUS: std::sync::Mutex<std::vec::Vec<usize>> = std::sync::Mutex::new(std::vec::Vec::new());
+
+/// Records one per-CPU barrier for [`take_targeted_cpus`].
+#[cfg(all(feature = "std", target_os = "linux"))]
+#[cfg_attr(any(feature = "loom", all(swmr_barrier_tsan, not(feature = "tsan-annotate"))), allow(dead_code))]
+pub(crate) fn record_targeted_cpu(cpu: usize) {
+    TARGETED_CPUS.lock().unwrap_or_else(std::sync::PoisonError::into_inner).push(cpu);
+}
+
+/// Consumes one injected registration failure, if any.
+#[cfg_attr(any(feature = "loom", all(swmr_barrier_tsan, not(feature = "tsan-annotate")), not(target_os = "linux")), allow(dead_code))]
+pub(crate) fn take_registration_failure() -> Option<i32> {
+    REGISTRATION_FAILURES
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+        .ok()
+        .map(|_| REGISTRATION_ERRNO.load(Ordering::Relaxed))
+}
+
+/// Consumes one injected torn write, if any.
+#[cfg(feature = "std")]
+pub(crate) fn take_torn_write() -> Option<std::time::Duration> {
+    TORN_WRITES
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+        .ok()
+        .map(|_| std::time::Duration::from_nanos(TORN_WRITE_NS.load(Ordering::Relaxed)))
+}
+
+/// Consumes one injected barrier delay, if any.
+#[cfg(feature = "std")]
+#[cfg_attr(any(feature = "loom", all(swmr_barrier_tsan, not(feature = "tsan-annotate")), not(target_os = "linux")), allow(dead_code))]
+pub(crate) fn take_barrier_delay() -> Option<std::time::Duration> {
+    BARRIER_DELAYS
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+        .ok()
+        .map(|_| std::time::Duration::from_nanos(BARRIER_DELAY_NS.load(Ordering::Relaxed)))
+}
+
+/// The kernel release set by [`set_kernel_release`], if any.
+pub(crate) fn kernel_release_override() -> Option<Option<crate::KernelVersion>> {
+    match KERNEL_STATE.load(Ordering::Relaxed) {
+        KERNEL_NONE => None,
+        KERNEL_UNPARSEABLE => Some(None),
+        _ => {
+            let [major, minor, patch] = [0, 1, 2].map(|i| KERNEL_PARTS[i].load(Ordering::Relaxed));
+            Some(Some(crate::KernelVersion::new(major, minor, patch)))
+        }
+    }
+}
+
+/// Consumes one injected query failure, if any.
+#[cfg_attr(any(feature = "loom", all(swmr_barrier_tsan, not(feature = "tsan-annotate")), not(target_os = "linux")), allow(dead_code))]
+pub(crate) fn take_query_failure() -> Option<i32> {
+    QUERY_FAILURES
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+        .ok()
+        .map(|_| QUERY_ERRNO.load(Ordering::Relaxed))
+}
+
+/// Consumes one injected barrier failure, if any.
+#[cfg_attr(any(feature = "loom", all(swmr_barrier_tsan, not(feature = "tsan-annotate")), not(target_os = "linux")), allow(dead_code))]
+pub(crate) fn take_barrier_failure() -> Option<i32> {
+    BARRIER_FAILURES
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+        .ok()
+        .map(|_| BARRIER_ERRNO.load(Ordering::Relaxed))
+}
@@ -0,0 +1,138 @@
+//! **CPU Topology Hint**
+//!
+//! A coarse view of the CPU layout that heavy-barrier IPIs must reach. Crossing packages or
+//! clusters, and heterogeneous (big.LITTLE) cores, make `heavy_barrier()` latency more variable.
+//! Read from Linux sysfs with the `std` feature; `None` everywhere else.
+//!
+//! ---
+//!
+//! **CPU 拓扑提示**
+//!
+//! 重型屏障的 IPI 需要到达的 CPU 布局的粗略视图。跨越封装或簇，以及异构 (big.LITTLE) 核心，
+//! 都会使 `heavy_barrier()` 的延迟波动更大。启用 `std` 特性时从 Linux sysfs 读取；其他情况下为 `None`。
+
+/// **Topology Hint**
+///
+/// Counts of distinct CPUs, packages, clusters and capacity classes among the online CPUs,
+/// returned by [`cpu_topology_hint()`](crate::cpu_topology_hint).
+///
+/// ---
+///
+/// **拓扑提示**
+///
+/// 在线 CPU 中不同 CPU、封装、簇与算力等级的数量，由 [`cpu_topology_hint()`](crate::cpu_topology_hint) 返回。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct TopologyHint {
+    /// Online CPUs with a readable topology.
+    /// 拓扑可读的在线 CPU 数量。
+    pub cpus: usize,
+    /// Distinct `physical_package_id` values (sockets).
+    /// 不同 `physical_package_id` 的数量（插槽）。
+    pub packages: usize,
+    /// Distinct `cluster_id` values; equals `packages` on kernels without `cluster_id` (< 5.16).
+    /// 不同 `cluster_id` 的数量；在没有 `cluster_id` 的内核 (< 5.16) 上等于 `packages`。
+    pub clusters: usize,
+    /// Distinct `cpu_capacity` values; more than one means big.LITTLE-style cores.
+    /// 不同 `cpu_capacity` 的数量；大于 1 表示 big.LITTLE 式的异构核心。
+    pub capacity_classes: usize,
+}
+
+impl TopologyHint {
+    /// Whether the CPUs differ in capacity (e.g. ARM big.LITTLE).
+    /// CPU 算力是否不同（例如 ARM big.LITTLE）。
+    #[inline]
+    pub const fn is_heterogeneous(&self) -> bool {
+        self.capacity_classes > 1
+    }
+}
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+mod sysfs {
+    use super::TopologyHint;
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::Path;
+
+    /// Reads one integer attribute; missing or malformed files yield `None`.
+    /// 读取一个整数属性；文件缺失或格式错误时返回 `None`。
+    fn read_id(path: &Path) -> Option<i64> {
+        fs::read_to_string(path).ok()?.trim().parse().ok()
+    }
+
+    /// Parses `root` laid out like `/sys/devices/system/cpu`. CPUs whose `topology` directory
+    /// is unreadable (e.g. offline) are skipped; `None` if no CPU could be read.
+    ///
+    /// 解析布局与 `/sys/devices/system/cpu` 相同的 `root`。跳过 `topology` 目录不可读的 CPU（例如离线）；
+    /// 若没有任何 CPU 可读则返回 `None`。
+    pub(crate) fn read(root: &Path) -> Option<TopologyHint> {
+        let mut cpus = 0;
+        let mut packages = BTreeSet::new();
+        let mut clusters = BTreeSet::new();
+        let mut capacities = BTreeSet::new();
+
+        for entry in fs::read_dir(root).ok()?.flatten() {
+            let name = entry.file_name();
+            let Some(index) = name.to_str().and_then(|n| n.strip_prefix("cpu")) else {
+                continue;
+            };
+            if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                continue;
+            }
+
+            let cpu = entry.path();
+            let topology = cpu.join("topology");
+            let Some(package) = read_id(&topology.join("physical_package_id")) else {
+                continue;
+            };
+            // `cluster_id` is absent before Linux 5.16 and -1 where firmware does not describe one.
+            // `cluster_id` 在 Linux 5.16 之前不存在，固件未描述时为 -1。
+            let cluster = read_id(&topology.join("cluster_id")).filter(|&id| id >= 0);
+
+            cpus += 1;
+            packages.insert(package);
+            clusters.insert((package, cluster));
+            capacities.insert(read_id(&cpu.join("cpu_capacity")));
+        }
+
+        (cpus > 0).then_some(TopologyHint {
+            cpus,
+            packages: packages.len(),
+            clusters: clusters.len(),
+            capacity_classes: capacities.len(),
+        })
+    }
+}
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub(crate) use sysfs::read as read_sysfs;
+
+/// Reads the topology of this machine.
+/// 读取本机拓扑。
+pub(crate) fn detect() -> Option<TopologyHint> {
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    {
+        read_sysfs(std::path::Path::new("/sys/devices/system/cpu"))
+    }
+    #[cfg(not(all(feature = "std", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// [`detect`] on first use, then the same answer: [`stats()`](crate::stats) is called on hot paths
+/// such as [`try_init()`](crate::try_init), which must not walk sysfs each time.
+/// 首次使用时执行 [`detect`]，此后返回相同结果：[`stats()`](crate::stats) 会在 [`try_init()`](crate::try_init)
+/// 等热路径上调用，不能每次都遍历 sysfs。
+pub(crate) fn cached() -> Option<TopologyHint> {
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    {
+        static HINT: std::sync::OnceLock<Option<TopologyHint>> = std::sync::OnceLock::new();
+        *HINT.get_or_init(detect)
+    }
+    #[cfg(not(all(feature = "std", target_os = "linux")))]
+    {
+        None
+    }
+}
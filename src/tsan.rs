@@ -0,0 +1,38 @@
+//! ThreadSanitizer annotations for the accelerated path (feature `tsan-annotate`).
+//!
+//! Compiled only under `-Zsanitizer=thread`, which links the TSan runtime that defines these symbols.
+//! Every heavy barrier releases and every light barrier acquires the same address, so TSan records
+//! a happens-before edge from the writer to all later readers. This is coarser than the real
+//! guarantee (readers synchronize even if they did not observe the write) and can hide races
+//! between barrier users that do not publish through the barrier pair.
+//!
+//! ThreadSanitizer 对加速路径的注解（`tsan-annotate` 特性）。
+//!
+//! 仅在 `-Zsanitizer=thread` 下编译，此时会链接定义这些符号的 TSan 运行时。每次重型屏障对同一地址 release，
+//! 每次轻型屏障对其 acquire，从而让 TSan 记录从写者到之后所有读者的 happens-before 边。这比真实保证更粗
+//! （即使读者没有观察到写入也会同步），可能掩盖未通过屏障对发布数据的竞争。
+
+use core::ffi::c_void;
+
+unsafe extern "C" {
+    fn __tsan_acquire(addr: *mut c_void);
+    fn __tsan_release(addr: *mut c_void);
+}
+
+/// Address token shared by all barrier annotations.
+/// 所有屏障注解共享的地址标记。
+static SYNC: u8 = 0;
+
+#[inline]
+pub(crate) fn release() {
+    // SAFETY: The TSan runtime is linked whenever this module is compiled; it only records the address.
+    // 安全性：编译此模块时必然链接了 TSan 运行时；它只记录该地址。
+    unsafe { __tsan_release(&raw const SYNC as *mut c_void) }
+}
+
+#[inline]
+pub(crate) fn acquire() {
+    // SAFETY: As in `release`.
+    // 安全性：同 `release`。
+    unsafe { __tsan_acquire(&raw const SYNC as *mut c_void) }
+}
@@ -0,0 +1,74 @@
+//! **Multi-Round Validation** (Linux, feature `std`)
+//!
+//! The store-buffering check of the integrity monitor, run a fixed number of times at startup: the
+//! calling thread stores `X`, issues a heavy barrier and loads `Y`, while a scoped reader stores
+//! `Y`, issues [`light_barrier()`](crate::light_barrier) and loads `X`. A round fails if neither
+//! load sees the other thread's store, or if the heavy barrier degraded to a fence because the
+//! syscall failed. Any failed round returns the process to the fence fallback.
+//!
+//! ---
+//!
+//! **多轮验证**（Linux，`std` 特性）
+//!
+//! 完整性监视器的存储缓冲检查，在启动时运行固定次数：调用线程存储 `X`、执行重型屏障并加载 `Y`；作用域内的读者线程
+//! 存储 `Y`、执行 [`light_barrier()`](crate::light_barrier) 并加载 `X`。若两次加载都未看到对方的存储，或重型屏障因
+//! 系统调用失败而降级为栅栏，则该轮失败。任一轮失败都会使进程回到栅栏回退路径。
+
+use crate::stats::InitReason;
+use core::hint::spin_loop;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Waits until `counter` reaches `target`, spinning briefly and then yielding.
+/// 等待 `counter` 达到 `target`，先短暂自旋，之后让出 CPU。
+fn wait_for(counter: &AtomicUsize, target: usize) {
+    let mut spins = 0u32;
+    while counter.load(Ordering::Acquire) < target {
+        if spins < 1_000 {
+            spins += 1;
+            spin_loop();
+        } else {
+            thread::yield_now();
+        }
+    }
+}
+
+/// Runs `rounds` checks against the active backend; `true` if every one passed.
+/// 对当前后端运行 `rounds` 轮检查；全部通过时返回 `true`。
+fn rounds_pass(rounds: usize) -> bool {
+    let (x, y) = (AtomicUsize::new(0), AtomicUsize::new(0));
+    // Round the writer has started and round the reader has finished.
+    // 写者已开始的轮次与读者已完成的轮次。
+    let (started, finished) = (AtomicUsize::new(0), AtomicUsize::new(0));
+    let reader_saw_x = AtomicUsize::new(0);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            for round in 1..=rounds {
+                wait_for(&started, round);
+                y.store(round, Ordering::Relaxed);
+                crate::light_barrier();
+                reader_saw_x.store(x.load(Ordering::Relaxed), Ordering::Relaxed);
+                finished.store(round, Ordering::Release);
+            }
+        });
+
+        let mut passed = true;
+        for round in 1..=rounds {
+            started.store(round, Ordering::Release);
+            x.store(round, Ordering::Relaxed);
+            let accelerated = crate::heavy_barrier_traced().is_accelerated();
+            let saw_y = y.load(Ordering::Relaxed);
+            wait_for(&finished, round);
+            passed &= accelerated && (saw_y == round || reader_saw_x.load(Ordering::Relaxed) == round);
+        }
+        passed
+    })
+}
+
+pub(crate) fn init_with_validation(rounds: usize) -> InitReason {
+    if crate::init() && !rounds_pass(rounds) {
+        crate::sys::disable_acceleration_impl(InitReason::ValidationFailed);
+    }
+    crate::sys::init_reason_impl()
+}
@@ -0,0 +1,103 @@
+//! **Integrity Monitor** (feature `verify`)
+//!
+//! A writer/reader canary pair that keeps running the store-buffering check the barriers exist
+//! for. Each round, the writer stores `X`, issues [`heavy_barrier()`](crate::heavy_barrier) and
+//! loads `Y`. The reader stores `Y`, issues [`light_barrier()`](crate::light_barrier) and loads `X`.
+//! If both loads miss the other thread's store, the barriers failed, and the monitor is poisoned
+//! for good.
+//!
+//! ---
+//!
+//! **完整性监视器**（`verify` 特性）
+//!
+//! 一对写者/读者金丝雀线程，持续运行屏障所要保证的存储缓冲检查。每一轮中，写者存储 `X`、执行
+//! [`heavy_barrier()`](crate::heavy_barrier) 并加载 `Y`；读者存储 `Y`、执行 [`light_barrier()`](crate::light_barrier)
+//! 并加载 `X`。若两次加载都未看到对方的存储，说明屏障失效，监视器将被永久标记为中毒。
+
+use core::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Once;
+use std::thread;
+use std::time::Duration;
+
+/// Pause between rounds; bounds the monitor to about a thousand heavy barriers per second.
+/// 轮次之间的间隔；将监视器限制为每秒约一千次重型屏障。
+const INTERVAL: Duration = Duration::from_millis(1);
+
+static X: AtomicUsize = AtomicUsize::new(0);
+static Y: AtomicUsize = AtomicUsize::new(0);
+static READER_SAW_X: AtomicUsize = AtomicUsize::new(0);
+static START: AtomicUsize = AtomicUsize::new(0);
+static END: AtomicUsize = AtomicUsize::new(0);
+static POISONED: AtomicBool = AtomicBool::new(false);
+static ROUNDS: AtomicU64 = AtomicU64::new(0);
+static SPAWN: Once = Once::new();
+
+/// Arrives at a two-thread rendezvous and waits until `count` reaches `target`.
+/// 到达双线程会合点，并等待 `count` 达到 `target`。
+fn rendezvous(count: &AtomicUsize, target: usize) {
+    count.fetch_add(1, Ordering::AcqRel);
+    let mut spins = 0u32;
+    while count.load(Ordering::Acquire) < target {
+        // Spin briefly so both halves of the round overlap, then stop burning the CPU.
+        // 先短暂自旋使本轮两侧重叠，之后不再空耗 CPU。
+        if spins < 1_000 {
+            spins += 1;
+            core::hint::spin_loop();
+        } else {
+            thread::yield_now();
+        }
+    }
+}
+
+fn writer() {
+    for round in 1usize.. {
+        rendezvous(&START, 2 * round);
+        X.store(1, Ordering::Relaxed);
+        crate::heavy_barrier();
+        let saw_y = Y.load(Ordering::Relaxed);
+        rendezvous(&END, 2 * round);
+
+        if saw_y == 0 && READER_SAW_X.load(Ordering::Relaxed) == 0 {
+            POISONED.store(true, Ordering::Relaxed);
+        }
+        // The reader touches neither until the next `START` rendezvous, which orders these resets.
+        // 读者在下一次 `START` 会合之前不会访问二者，该会合保证了这些重置的顺序。
+        X.store(0, Ordering::Relaxed);
+        Y.store(0, Ordering::Relaxed);
+        ROUNDS.fetch_add(1, Ordering::Relaxed);
+        thread::sleep(INTERVAL);
+    }
+}
+
+fn reader() {
+    for round in 1usize.. {
+        rendezvous(&START, 2 * round);
+        Y.store(1, Ordering::Relaxed);
+        crate::light_barrier();
+        READER_SAW_X.store(X.load(Ordering::Relaxed), Ordering::Relaxed);
+        rendezvous(&END, 2 * round);
+        thread::sleep(INTERVAL);
+    }
+}
+
+/// Spawns the canary pair on first use.
+/// 首次使用时创建金丝雀线程对。
+fn start() {
+    SPAWN.call_once(|| {
+        for (name, body) in [("swmr-canary-writer", writer as fn()), ("swmr-canary-reader", reader)] {
+            thread::Builder::new()
+                .name(name.into())
+                .spawn(body)
+                .expect("failed to spawn a swmr-barrier canary thread");
+        }
+    });
+}
+
+pub(crate) fn integrity_ok() -> bool {
+    start();
+    !POISONED.load(Ordering::Relaxed)
+}
+
+pub(crate) fn integrity_rounds() -> u64 {
+    ROUNDS.load(Ordering::Relaxed)
+}
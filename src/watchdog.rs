@@ -0,0 +1,59 @@
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+// Slowest deadline-checked heavy barrier so far (0 = none yet).
+// 迄今最慢的一次带期限检查的重型屏障 (0 = 尚无)。
+static SLOWEST_NS: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn heavy_barrier_with_deadline(max_ns: u64) -> Result<(), BarrierTimeout> {
+    let start = Instant::now();
+    crate::heavy_barrier();
+    let elapsed_ns = start.elapsed().as_nanos().try_into().unwrap_or(u64::MAX);
+    // Never 0, so a recorded call is distinguishable from none.
+    // 从不为 0，以便区分已记录的调用与尚无调用。
+    SLOWEST_NS.fetch_max(elapsed_ns.max(1), Ordering::Relaxed);
+
+    if elapsed_ns <= max_ns {
+        return Ok(());
+    }
+    crate::sys::disable_acceleration_impl(crate::InitReason::WatchdogTripped);
+    Err(BarrierTimeout { elapsed_ns })
+}
+
+pub(crate) fn slowest_ns() -> Option<u64> {
+    match SLOWEST_NS.load(Ordering::Relaxed) {
+        0 => None,
+        ns => Some(ns),
+    }
+}
+
+#[cfg(feature = "testing")]
+pub(crate) fn reset() {
+    SLOWEST_NS.store(0, Ordering::Relaxed);
+}
+
+/// **Barrier Timeout**
+///
+/// Returned by [`heavy_barrier_with_deadline`](crate::heavy_barrier_with_deadline) when the
+/// barrier took longer than the deadline. The barrier itself has completed.
+///
+/// ---
+///
+/// **屏障超时**
+///
+/// 屏障耗时超过期限时由 [`heavy_barrier_with_deadline`](crate::heavy_barrier_with_deadline) 返回。屏障本身已完成。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierTimeout {
+    /// How long the barrier took, in nanoseconds.
+    /// 屏障耗时（纳秒）。
+    pub elapsed_ns: u64,
+}
+
+impl fmt::Display for BarrierTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "heavy barrier took {} ns, past its deadline", self.elapsed_ns)
+    }
+}
+
+impl core::error::Error for BarrierTimeout {}
@@ -0,0 +1,142 @@
+//! Tests for `AppendLog` and `LogCursor`.
+
+#![cfg(not(feature = "loom"))]
+
+mod common;
+
+use common::{CountingBarrier, PanickingBarrier};
+use std::sync::Arc;
+use std::thread;
+use swmr_barrier::AppendLog;
+
+/// A push whose barrier panics publishes nothing and does not wedge later pushes.
+#[test]
+fn test_panicking_barrier_does_not_wedge_push() {
+    let log = AppendLog::<u32, 4, _>::with_barrier(PanickingBarrier::on_heavy(1));
+    assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| log.push(1))).is_err());
+    assert!(log.is_empty());
+
+    log.push(2).unwrap();
+    assert_eq!(log.get(0), Some(2));
+}
+
+#[test]
+fn test_push_until_full() {
+    let log = AppendLog::<String, 2>::new();
+    assert!(log.is_empty());
+    assert_eq!(log.push("a".into()), Ok(()));
+    assert_eq!(log.push("b".into()), Ok(()));
+    assert_eq!(log.push("c".into()), Err("c".into()));
+
+    assert_eq!(log.len(), 2);
+    assert_eq!(log.get(1).as_deref(), Some("b"));
+    assert_eq!(log.get(2), None);
+}
+
+/// A cursor drains a published batch with one light barrier, plus one to learn it is caught up.
+#[test]
+fn test_cursor_one_light_barrier_per_batch() {
+    let barrier = CountingBarrier::new();
+    let log = AppendLog::<u32, 16, _>::with_barrier(&barrier);
+    for i in 0..10 {
+        log.push(i).unwrap();
+    }
+
+    let mut cursor = log.cursor();
+    let drained: Vec<_> = cursor.by_ref().collect();
+    assert_eq!(drained, (0..10).collect::<Vec<_>>());
+    assert_eq!(barrier.light_count(), 2);
+
+    log.push(10).unwrap();
+    assert_eq!(cursor.next(), Some(10));
+    assert_eq!(cursor.position(), 11);
+}
+
+/// Each cursor caches the published length of its own log: draining a full log and then reading an
+/// empty one must not read the empty log's uninitialized entries.
+#[test]
+fn test_cursors_are_tied_to_their_log() {
+    let full = AppendLog::<String, 4>::new();
+    for entry in ["a", "b", "c", "d"] {
+        full.push(entry.into()).unwrap();
+    }
+    let empty = AppendLog::<String, 4>::new();
+
+    let mut from_full = full.cursor();
+    assert_eq!(from_full.next().as_deref(), Some("a"));
+    let mut from_empty = empty.cursor();
+    assert_eq!(from_empty.next(), None);
+    assert_eq!(from_full.collect::<Vec<_>>(), ["b", "c", "d"]);
+
+    empty.push("e".into()).unwrap();
+    assert_eq!(from_empty.next().as_deref(), Some("e"));
+}
+
+/// One writer appends while a cursor-based reader consumes every entry exactly once, in order.
+#[test]
+fn test_cursor_consumes_all_entries_in_order() {
+    const ENTRIES: usize = 4_096;
+
+    let log = Arc::new(AppendLog::<Box<usize>, ENTRIES>::new());
+
+    let reader = {
+        let log = log.clone();
+        thread::spawn(move || {
+            let mut cursor = log.cursor();
+            let mut expected = 0;
+            while expected < ENTRIES {
+                match cursor.next() {
+                    Some(value) => {
+                        assert_eq!(*value, expected, "entries out of order");
+                        expected += 1;
+                    }
+                    None => thread::yield_now(),
+                }
+            }
+            assert_eq!(cursor.next(), None);
+        })
+    };
+
+    for i in 0..ENTRIES {
+        log.push(Box::new(i)).unwrap();
+    }
+    reader.join().unwrap();
+}
+
+/// A reader that stopped at entry K reports `N - K` once the writer has pushed N entries, and never
+/// more than the entries published so far while the writer is still running.
+#[test]
+fn test_staleness_tracks_writer() {
+    const ENTRIES: usize = 1_024;
+    const READ: usize = 100;
+
+    let log = Arc::new(AppendLog::<usize, ENTRIES>::new());
+    for i in 0..READ {
+        log.push(i).unwrap();
+    }
+    let mut cursor = log.cursor();
+    cursor.by_ref().for_each(drop);
+    assert_eq!(log.staleness(cursor.position()), 0);
+
+    let writer = {
+        let log = log.clone();
+        thread::spawn(move || {
+            for i in READ..ENTRIES {
+                log.push(i).unwrap();
+            }
+        })
+    };
+
+    let mut last = 0;
+    while last < ENTRIES - READ {
+        let staleness = log.staleness(cursor.position());
+        assert!(staleness >= last, "staleness went backwards: {staleness} < {last}");
+        assert!(staleness <= ENTRIES - READ);
+        last = staleness;
+        thread::yield_now();
+    }
+    writer.join().unwrap();
+
+    assert_eq!(log.staleness(cursor.position()), ENTRIES - READ);
+    assert_eq!(log.staleness(ENTRIES + 1), 0);
+}
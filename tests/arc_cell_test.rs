@@ -0,0 +1,85 @@
+//! Tests for `SwmrArcCell`.
+//!
+//! Run with: `cargo test --features std --test arc_cell_test`
+
+#![cfg(all(feature = "std", not(feature = "loom")))]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use swmr_barrier::SwmrArcCell;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts live instances and poisons itself on drop, so a read after free shows up as a wrong
+/// value even without a sanitizer.
+struct Tracked {
+    value: usize,
+    check: usize,
+}
+
+impl Tracked {
+    fn new(value: usize) -> Arc<Self> {
+        LIVE.fetch_add(1, Ordering::Relaxed);
+        Arc::new(Self { value, check: !value })
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.check = self.value;
+        LIVE.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn test_load_and_store() {
+    let cell = SwmrArcCell::from_pointee(1u32);
+    assert_eq!(*cell.load(), 1);
+    let held = cell.load();
+    cell.store(Arc::new(2));
+    assert_eq!(*cell.load(), 2);
+    assert_eq!(*held, 1, "a loaded Arc keeps its value after a store");
+    assert_eq!(format!("{cell:?}"), "SwmrArcCell(2)");
+    assert_eq!(*SwmrArcCell::<u32>::default().load(), 0);
+}
+
+/// A writer swaps `Arc`s as fast as it can while readers load and hold them. Every loaded value is
+/// intact and never older than one seen before, and once the cell is gone nothing leaks.
+#[test]
+fn test_no_use_after_free_under_swaps() {
+    const READERS: usize = 4;
+    const STORES: usize = 20_000;
+
+    {
+        let cell = SwmrArcCell::new(Tracked::new(0));
+        let done = AtomicBool::new(false);
+        thread::scope(|s| {
+            for _ in 0..READERS {
+                s.spawn(|| {
+                    let mut last = 0;
+                    let mut held = Vec::new();
+                    while !done.load(Ordering::Relaxed) {
+                        let current = cell.load();
+                        assert_eq!(current.check, !current.value, "loaded a freed value");
+                        assert!(current.value >= last, "load went backwards");
+                        last = current.value;
+                        // Keep a few alive across stores, then check them again.
+                        held.push(current);
+                        if held.len() == 16 {
+                            assert!(held.iter().all(|t| t.check == !t.value), "a held Arc was freed");
+                            held.clear();
+                        }
+                    }
+                });
+            }
+
+            for i in 1..=STORES {
+                cell.store(Tracked::new(i));
+            }
+            done.store(true, Ordering::Relaxed);
+        });
+        assert_eq!(cell.load().value, STORES);
+    }
+    assert_eq!(LIVE.load(Ordering::Relaxed), 0, "an Arc leaked");
+}
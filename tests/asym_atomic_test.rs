@@ -0,0 +1,44 @@
+//! Tests for `AsymAtomic`.
+
+#![cfg(not(feature = "loom"))]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use swmr_barrier::AsymAtomic;
+
+#[test]
+fn test_publish_consume_and_relaxed_access() {
+    let atomic = AsymAtomic::new(1u32);
+    assert_eq!(atomic.consume(), 1);
+    atomic.publish(2);
+    assert_eq!(atomic.consume(), 2);
+    atomic.store_relaxed(3);
+    assert_eq!(atomic.load_relaxed(), 3);
+    assert_eq!(format!("{atomic:?}"), "AsymAtomic(3)");
+    assert!(!AsymAtomic::<bool>::default().consume());
+}
+
+/// A reader that consumes version `n` also sees the payload written before `publish(n)`.
+#[test]
+fn test_consume_sees_payload() {
+    const UPDATES: u64 = 10_000;
+
+    let payload = AtomicU64::new(0);
+    let version = AsymAtomic::new(0u64);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            let mut last = 0;
+            while last < UPDATES {
+                let seen = version.consume();
+                assert!(payload.load(Ordering::Relaxed) >= seen, "saw version {seen} before its payload");
+                last = seen;
+            }
+        });
+
+        for i in 1..=UPDATES {
+            payload.store(i, Ordering::Relaxed);
+            version.publish(i);
+        }
+    });
+}
@@ -0,0 +1,74 @@
+//! Tests for the 128-bit `SwmrWordCell` path (feature `atomic128`), checked against the general
+//! slot-ring `SwmrCell` for the same observable behavior.
+
+#![cfg(all(feature = "atomic128", not(feature = "loom")))]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use swmr_barrier::{SwmrCell, SwmrWordCell};
+
+const VALUES: [u128; 4] = [1, u128::MAX, 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210, 0];
+
+#[test]
+fn test_paths_match_sequentially() {
+    let general = SwmrCell::new(7u128);
+    let word = SwmrWordCell::new(7u128);
+    assert_eq!(general.get(), word.get());
+    for value in VALUES {
+        general.set(value);
+        word.set(value);
+        assert_eq!(general.get(), word.get());
+    }
+
+    let signed = SwmrWordCell::new(i128::MIN);
+    assert_eq!(signed.get(), i128::MIN);
+    signed.set(-1);
+    assert_eq!(signed.get(), -1);
+}
+
+#[test]
+fn test_pair_round_trips() {
+    let general = SwmrCell::new([0u64; 2]);
+    let word = SwmrWordCell::new([0u64; 2]);
+    for pair in [[1, 2], [u64::MAX, 0], [0, u64::MAX], [0xdead_beef, 7]] {
+        general.set(pair);
+        word.set(pair);
+        assert_eq!(word.get(), pair);
+        assert_eq!(general.get(), word.get());
+    }
+    assert_eq!(format!("{word:?}"), format!("SwmrWordCell {{ value: {:?} }}", [0xdead_beef_u64, 7]));
+}
+
+/// Reads `[i, !i]` pairs until `done`, checking each is whole and none goes backwards.
+fn check_reader(get: impl Fn() -> [u64; 2], done: &AtomicBool) {
+    let mut last = 0;
+    while !done.load(Ordering::Relaxed) {
+        let [low, high] = get();
+        assert_eq!(low, !high, "torn read");
+        assert!(low >= last, "read went backwards: {low} < {last}");
+        last = low;
+    }
+}
+
+/// Both halves are written together, so a reader of either path never sees one half from one
+/// publish and the other half from another, and never goes backwards.
+#[test]
+fn test_paths_never_tear() {
+    const UPDATES: u64 = 20_000;
+
+    let general = SwmrCell::new([0, !0]);
+    let word = SwmrWordCell::new([0, !0]);
+    let done = AtomicBool::new(false);
+
+    thread::scope(|s| {
+        s.spawn(|| check_reader(|| general.get(), &done));
+        s.spawn(|| check_reader(|| word.get(), &done));
+
+        for i in 1..=UPDATES {
+            general.set([i, !i]);
+            word.set([i, !i]);
+        }
+        done.store(true, Ordering::Relaxed);
+    });
+    assert_eq!(general.get(), word.get());
+}
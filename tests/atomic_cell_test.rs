@@ -0,0 +1,121 @@
+//! Tests for `AtomicCell`.
+//!
+//! With the `crossbeam` feature, the same operations also run against
+//! `crossbeam_utils::atomic::AtomicCell` and must return the same values.
+//!
+//! Run with: `cargo test --features crossbeam --test atomic_cell_test`
+
+#![cfg(not(feature = "loom"))]
+
+use std::mem::{align_of, size_of};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use swmr_barrier::AtomicCell;
+
+/// Seqlock state stored next to the value on 64-bit targets: the 64-bit sequence, the
+/// starving-reader count, the patience and the writer flag, padded to 8 bytes.
+#[cfg(all(target_pointer_width = "64", not(feature = "async")))]
+const STATE: usize = 24;
+/// With `async`, the waker list follows.
+#[cfg(all(target_pointer_width = "64", feature = "async"))]
+const STATE: usize = 24 + size_of::<std::sync::Mutex<Vec<std::task::Waker>>>();
+
+// The value is stored inline, once, after the state; only a value of at most 3 bytes fits in the
+// state's padding. The sequence sets the minimum alignment.
+#[cfg(target_pointer_width = "64")]
+const _: () = {
+    assert!(size_of::<AtomicCell<()>>() == STATE);
+    assert!(size_of::<AtomicCell<u8>>() == STATE);
+    assert!(size_of::<AtomicCell<u64>>() == size_of::<u64>() + STATE);
+    assert!(size_of::<AtomicCell<[u64; 8]>>() == size_of::<[u64; 8]>() + STATE);
+    assert!(size_of::<AtomicCell<u128>>() == (size_of::<u128>() + STATE).next_multiple_of(align_of::<u128>()));
+    assert!(align_of::<AtomicCell<u8>>() == align_of::<u64>());
+    assert!(align_of::<AtomicCell<u128>>() == align_of::<u128>());
+};
+
+// crossbeam's cell is `#[repr(transparent)]` over the value; this one is not.
+#[cfg(all(feature = "crossbeam", target_pointer_width = "64"))]
+const _: () = {
+    use crossbeam_utils::atomic::AtomicCell as CrossbeamCell;
+    assert!(size_of::<CrossbeamCell<u64>>() == size_of::<u64>());
+    assert!(size_of::<AtomicCell<u64>>() == size_of::<CrossbeamCell<u64>>() + STATE);
+};
+
+/// Runs the supported operations on a cell of `u32` starting at 7, returning every result.
+macro_rules! exercise {
+    ($cell:expr) => {{
+        let cell = $cell;
+        let mut seen = Vec::new();
+        seen.push(cell.load());
+        cell.store(8);
+        seen.push(cell.load());
+        seen.push(cell.swap(9));
+        seen.push(cell.take());
+        seen.push(cell.load());
+        cell.store(10);
+        seen.push(cell.into_inner());
+        seen
+    }};
+}
+
+#[test]
+fn test_operations() {
+    assert_eq!(exercise!(AtomicCell::new(7u32)), [7, 8, 8, 9, 0, 10]);
+
+    let cell = AtomicCell::from((1u8, 'a'));
+    assert_eq!(format!("{cell:?}"), "AtomicCell { value: (1, 'a') }");
+    assert_eq!(AtomicCell::<u64>::default().load(), 0);
+}
+
+#[cfg(feature = "crossbeam")]
+#[test]
+fn test_matches_crossbeam() {
+    use crossbeam_utils::atomic::AtomicCell as CrossbeamCell;
+
+    assert_eq!(exercise!(AtomicCell::new(7u32)), exercise!(CrossbeamCell::new(7u32)));
+    assert_eq!(
+        format!("{:?}", AtomicCell::new([1u16, 2])),
+        format!("{:?}", CrossbeamCell::new([1u16, 2]))
+    );
+}
+
+/// Concurrent swaps serialize: every stored value comes back exactly once.
+#[test]
+fn test_concurrent_swaps_return_each_value_once() {
+    const PER_THREAD: u64 = 2_000;
+    let cell = AtomicCell::new(0u64);
+
+    let mut returned: Vec<u64> = thread::scope(|s| {
+        let swappers: Vec<_> = (0..2u64)
+            .map(|t| {
+                let cell = &cell;
+                s.spawn(move || (1..=PER_THREAD).map(|i| cell.swap(t * PER_THREAD + i)).collect::<Vec<_>>())
+            })
+            .collect();
+        swappers.into_iter().flat_map(|h| h.join().unwrap()).collect()
+    });
+    returned.push(cell.into_inner());
+    returned.sort_unstable();
+    assert_eq!(returned, (0..=2 * PER_THREAD).collect::<Vec<_>>());
+}
+
+/// Readers never see a torn multi-word value while the writer stores.
+#[test]
+fn test_load_is_never_torn() {
+    let cell = AtomicCell::new([0u64; 16]);
+    let done = AtomicBool::new(false);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            for version in 1..=5_000 {
+                cell.store([version; 16]);
+            }
+            done.store(true, Ordering::Relaxed);
+        });
+
+        while !done.load(Ordering::Relaxed) {
+            let value = cell.load();
+            assert!(value.iter().all(|&word| word == value[0]), "torn load: {value:?}");
+        }
+    });
+}
@@ -0,0 +1,177 @@
+//! Tests for the `AsymmetricBarrier` abstraction.
+//!
+//! Verifies that generic SWMR code can be driven by a test double and that
+//! `SystemBarrier` mirrors the crate's free functions.
+
+#![cfg(not(feature = "loom"))]
+
+mod common;
+
+use common::CountingBarrier;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use swmr_barrier::{AsymmetricBarrier, SystemBarrier};
+
+/// Downstream-style generic publish routine.
+fn publish<B: AsymmetricBarrier>(barrier: &B, data: &AtomicUsize, flag: &AtomicUsize, value: usize) {
+    data.store(value, Ordering::Relaxed);
+    barrier.heavy();
+    flag.store(1, Ordering::Relaxed);
+}
+
+/// Downstream-style generic consume routine.
+fn consume<B: AsymmetricBarrier>(barrier: &B, data: &AtomicUsize, flag: &AtomicUsize) -> Option<usize> {
+    let f = flag.load(Ordering::Relaxed);
+    barrier.light();
+    let d = data.load(Ordering::Relaxed);
+    (f == 1).then_some(d)
+}
+
+/// A mock injected into generic code observes every barrier call.
+#[test]
+fn test_mock_barrier_is_invoked() {
+    let barrier = CountingBarrier::default();
+    let data = AtomicUsize::new(0);
+    let flag = AtomicUsize::new(0);
+
+    assert_eq!(consume(&barrier, &data, &flag), None);
+    publish(&barrier, &data, &flag, 42);
+    assert_eq!(consume(&barrier, &data, &flag), Some(42));
+
+    assert_eq!(barrier.heavy_count(), 1);
+    assert_eq!(barrier.light_count(), 2);
+    assert!(!barrier.accelerated());
+}
+
+/// `SystemBarrier` reports the same acceleration status as the free function.
+#[test]
+fn test_system_barrier_matches_free_functions() {
+    assert_eq!(SystemBarrier.accelerated(), swmr_barrier::is_accelerated());
+}
+
+/// `SystemBarrier` preserves the store-heavy / load-light invariant across threads.
+#[test]
+fn test_system_barrier_ordering() {
+    for _ in 0..10_000 {
+        let data = Arc::new(AtomicUsize::new(0));
+        let flag = Arc::new(AtomicUsize::new(0));
+
+        let (data_w, flag_w) = (data.clone(), flag.clone());
+        let writer = thread::spawn(move || publish(&SystemBarrier, &data_w, &flag_w, 1));
+
+        let reader = thread::spawn(move || {
+            if let Some(d) = consume(&SystemBarrier, &data, &flag) {
+                assert_eq!(d, 1, "SystemBarrier violation: saw flag=1 but data=0");
+            }
+        });
+
+        writer.join().unwrap();
+        reader.join().unwrap();
+    }
+}
+
+/// `BarrierKind` defaults to the crate's asymmetric barriers; the fence kind is never accelerated.
+#[test]
+fn test_barrier_kind() {
+    use swmr_barrier::BarrierKind;
+
+    assert_eq!(BarrierKind::default(), BarrierKind::Asymmetric);
+    assert_eq!(BarrierKind::Asymmetric.accelerated(), swmr_barrier::is_accelerated());
+    assert!(!BarrierKind::SymmetricFence.accelerated());
+}
+
+/// Domains count their own heavy barriers, including those issued by structures holding a
+/// reference, while reporting the same acceleration status as the system barrier.
+#[test]
+fn test_barrier_domains_keep_separate_epochs() {
+    use swmr_barrier::{BarrierDomain, DoubleBuffer, RcuDomain};
+
+    static NETWORK: BarrierDomain = BarrierDomain::new();
+    let storage = BarrierDomain::new();
+
+    let routes = DoubleBuffer::with_barrier(0u32, &NETWORK);
+    routes.commit(1);
+    routes.commit(2);
+    let rcu = RcuDomain::<4, _>::with_barrier(&storage);
+    rcu.synchronize();
+
+    assert_eq!(*routes.borrow_snapshot(), 2);
+    assert_eq!(NETWORK.epoch(), 2);
+    assert!(storage.epoch() >= 1);
+    assert_eq!(NETWORK.accelerated(), swmr_barrier::is_accelerated());
+
+    // A domain is still a valid barrier across threads.
+    let domain = Arc::new(BarrierDomain::new());
+    for _ in 0..1_000 {
+        let data = Arc::new(AtomicUsize::new(0));
+        let flag = Arc::new(AtomicUsize::new(0));
+
+        let (domain_w, data_w, flag_w) = (domain.clone(), data.clone(), flag.clone());
+        let writer = thread::spawn(move || publish(&*domain_w, &data_w, &flag_w, 1));
+        let domain_r = domain.clone();
+        let reader = thread::spawn(move || {
+            if let Some(d) = consume(&*domain_r, &data, &flag) {
+                assert_eq!(d, 1, "BarrierDomain violation: saw flag=1 but data=0");
+            }
+        });
+
+        writer.join().unwrap();
+        reader.join().unwrap();
+    }
+    assert_eq!(domain.epoch(), 1_000);
+}
+
+/// With no registered reader the wrapped barrier's `heavy()` is skipped; registering brings it
+/// back until the guard drops. `light()` always forwards.
+#[test]
+fn test_tracked_barrier_skips_heavy_without_readers() {
+    use swmr_barrier::TrackedBarrier;
+
+    let inner = CountingBarrier::default();
+    let tracked = TrackedBarrier::with_barrier(&inner);
+
+    tracked.heavy();
+    assert_eq!(inner.heavy_count(), 0);
+
+    {
+        let _first = tracked.register();
+        let _second = tracked.register();
+        assert_eq!(tracked.readers(), 2);
+        tracked.heavy();
+        tracked.light();
+        assert_eq!(inner.heavy_count(), 1);
+        assert_eq!(inner.light_count(), 1);
+    }
+    assert_eq!(tracked.readers(), 0);
+    tracked.heavy();
+    assert_eq!(inner.heavy_count(), 1);
+    assert!(format!("{tracked:?}").contains("readers: 0"));
+}
+
+/// Readers registering while the writer publishes, some of them after it saw no readers and
+/// skipped the system barrier, still see complete data.
+#[test]
+fn test_tracked_barrier_ordering() {
+    use swmr_barrier::TrackedBarrier;
+
+    let tracked = Arc::new(TrackedBarrier::new());
+    for _ in 0..10_000 {
+        let data = Arc::new(AtomicUsize::new(0));
+        let flag = Arc::new(AtomicUsize::new(0));
+
+        let (tracked_w, data_w, flag_w) = (tracked.clone(), data.clone(), flag.clone());
+        let writer = thread::spawn(move || publish(&*tracked_w, &data_w, &flag_w, 1));
+        let tracked_r = tracked.clone();
+        let reader = thread::spawn(move || {
+            let _registration = tracked_r.register();
+            if let Some(d) = consume(&*tracked_r, &data, &flag) {
+                assert_eq!(d, 1, "TrackedBarrier violation: saw flag=1 but data=0");
+            }
+        });
+
+        writer.join().unwrap();
+        reader.join().unwrap();
+    }
+    assert_eq!(tracked.readers(), 0);
+}
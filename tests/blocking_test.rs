@@ -0,0 +1,76 @@
+//! Tests for `SwmrCell::subscribe_blocking` and `PublishSignal`.
+//!
+//! Run with: `cargo test --features std --test blocking_test`
+
+#![cfg(all(feature = "std", not(feature = "loom")))]
+
+use std::thread;
+use std::time::Duration;
+use swmr_barrier::{PublishSignal, SwmrCell};
+
+/// A reader blocked in `wait` stays asleep through plain `set`s and wakes on the notifying
+/// publish, reading its value.
+#[test]
+fn test_blocked_reader_wakes_on_publish() {
+    let cell = SwmrCell::<[u64; 4]>::new([0; 4]);
+    let signal = PublishSignal::new();
+
+    thread::scope(|s| {
+        let mut subscriber = cell.subscribe_blocking(&signal);
+        let reader = s.spawn(move || subscriber.wait());
+
+        thread::sleep(Duration::from_millis(50));
+        cell.set([1; 4]);
+        thread::sleep(Duration::from_millis(50));
+        assert!(!reader.is_finished(), "reader woke without a notifying publish");
+
+        cell.set_and_notify([2; 4], &signal);
+        assert_eq!(reader.join().unwrap(), [2; 4]);
+    });
+}
+
+/// A publish made while the subscriber was not waiting is picked up by the next wait at once.
+#[test]
+fn test_publish_before_wait_not_lost() {
+    let cell = SwmrCell::new(0u64);
+    let signal = PublishSignal::new();
+    let mut subscriber = cell.subscribe_blocking(&signal);
+
+    assert_eq!(subscriber.wait_timeout(Duration::from_millis(10)), None);
+    cell.set_and_notify(7, &signal);
+    cell.set_and_notify(8, &signal);
+    assert_eq!(subscriber.wait(), 8);
+    assert_eq!(subscriber.wait_timeout(Duration::from_millis(10)), None);
+}
+
+/// Subscribers racing the writer never see the value go backwards and end on the last publish.
+/// A wake can return a value already seen: `get` may run between a later `set` and its notify.
+#[test]
+fn test_subscribers_follow_writer() {
+    const PUBLISHES: u64 = 200;
+    let cell = SwmrCell::new(0u64);
+    let signal = PublishSignal::new();
+
+    thread::scope(|s| {
+        let readers: Vec<_> = (0..3)
+            .map(|_| {
+                let mut subscriber = cell.subscribe_blocking(&signal);
+                s.spawn(move || {
+                    let mut last = 0;
+                    while last < PUBLISHES {
+                        let value = subscriber.wait();
+                        assert!(value >= last, "value went from {last} to {value}");
+                        last = value;
+                    }
+                })
+            })
+            .collect();
+
+        for value in 1..=PUBLISHES {
+            cell.set_and_notify(value, &signal);
+        }
+        for reader in readers {
+            reader.join().unwrap();
+        }
+    });
+}
@@ -0,0 +1,53 @@
+//! Layout tests for `CachePadded` and the padded slot rings.
+
+#![cfg(not(feature = "loom"))]
+
+use std::sync::atomic::AtomicU64;
+use swmr_barrier::{CACHE_LINE, CachePadded, DoubleBuffer, SwmrCell};
+
+/// Index of the `CACHE_LINE`-byte line holding `ptr`.
+fn line_of<T>(ptr: *const T) -> usize {
+    ptr as usize / CACHE_LINE
+}
+
+// The crate's own fallback: 128 bytes on the listed architectures, 64 elsewhere.
+#[cfg(not(feature = "crossbeam"))]
+const _: () = {
+    assert!(CACHE_LINE == 64 || CACHE_LINE == 128);
+    assert!(CACHE_LINE == 128 || !cfg!(any(target_arch = "x86_64", target_arch = "aarch64", target_arch = "powerpc64")));
+};
+
+// Static layout checks: the build fails if any of these stop holding.
+const _: () = {
+    assert!(align_of::<CachePadded<u8>>() == CACHE_LINE);
+    assert!(size_of::<CachePadded<u8>>() == CACHE_LINE);
+    assert!(size_of::<CachePadded<[u8; 65]>>() == if CACHE_LINE >= 65 { CACHE_LINE } else { 65usize.next_multiple_of(CACHE_LINE) });
+
+    // The two payload slots, their bookkeeping and the active index each take at least a full line.
+    // 两个负载槽、它们的簿记信息与活动索引各自至少占据一整行。
+    assert!(align_of::<SwmrCell<u8>>() == CACHE_LINE);
+    assert!(size_of::<SwmrCell<u8>>() >= 5 * CACHE_LINE);
+    assert!(size_of::<SwmrCell<u8, 3>>() >= 7 * CACHE_LINE);
+    assert!(size_of::<DoubleBuffer<u8>>() >= 5 * CACHE_LINE);
+};
+
+/// Neighbouring padded values never share a cache line, whatever their size.
+#[test]
+fn test_padded_values_on_distinct_lines() {
+    let pair = [CachePadded::new(AtomicU64::new(0)), CachePadded::new(AtomicU64::new(0))];
+    assert_ne!(line_of(&*pair[0]), line_of(&*pair[1]));
+    assert!((&*pair[1] as *const AtomicU64 as usize) - (&*pair[0] as *const AtomicU64 as usize) >= CACHE_LINE);
+
+    let mut padded = CachePadded::from(7u32);
+    *padded += 1;
+    assert_eq!(padded.into_inner(), 8);
+}
+
+/// With `crossbeam`, the exported type and the slot rings use crossbeam's padding.
+#[cfg(feature = "crossbeam")]
+#[test]
+fn test_crossbeam_padding_in_use() {
+    assert!(std::any::type_name::<CachePadded<u8>>().starts_with("crossbeam_utils::"));
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(CACHE_LINE, 128);
+}
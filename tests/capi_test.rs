@@ -0,0 +1,80 @@
+//! Tests for the `capi` feature: the symbols link under their C names.
+//!
+//! Run with: `cargo test --features capi --test capi_test` (add `std` for the cell handle tests)
+
+#![cfg(all(feature = "capi", not(feature = "loom")))]
+
+// Declared exactly as in include/swmr_barrier.h, resolved by the linker rather than by path.
+unsafe extern "C" {
+    fn swmr_heavy_barrier();
+    fn swmr_light_barrier();
+    fn swmr_is_accelerated() -> bool;
+}
+
+#[cfg(feature = "std")]
+#[repr(C)]
+struct SwmrCellU64 {
+    _opaque: [u8; 0],
+}
+
+#[cfg(feature = "std")]
+unsafe extern "C" {
+    fn swmr_cell_new(initial: u64) -> *mut SwmrCellU64;
+    fn swmr_cell_publish(cell: *mut SwmrCellU64, value: u64);
+    fn swmr_cell_load(cell: *const SwmrCellU64) -> u64;
+    fn swmr_cell_free(cell: *mut SwmrCellU64);
+}
+
+#[test]
+fn test_symbols_link_and_match_rust_api() {
+    unsafe {
+        swmr_heavy_barrier();
+        swmr_light_barrier();
+        assert_eq!(swmr_is_accelerated(), swmr_barrier::is_accelerated());
+    }
+}
+
+/// The opaque handle goes through its whole lifecycle through the C symbols alone.
+#[cfg(feature = "std")]
+#[test]
+fn test_cell_handle_lifecycle() {
+    unsafe {
+        let cell = swmr_cell_new(7);
+        assert!(!cell.is_null());
+        assert_eq!(swmr_cell_load(cell), 7);
+        swmr_cell_publish(cell, u64::MAX);
+        assert_eq!(swmr_cell_load(cell), u64::MAX);
+        swmr_cell_free(cell);
+        swmr_cell_free(std::ptr::null_mut());
+    }
+}
+
+/// Readers on other threads only ever load published values, and end on the last one.
+#[cfg(feature = "std")]
+#[test]
+fn test_cell_handle_concurrent_readers() {
+    const LAST: u64 = 10_000;
+
+    struct Handle(*mut SwmrCellU64);
+    // SAFETY: The cell is `Sync`; the handle is freed only after every reader is joined.
+    unsafe impl Send for Handle {}
+    unsafe impl Sync for Handle {}
+
+    let handle = &Handle(unsafe { swmr_cell_new(0) });
+    std::thread::scope(|s| {
+        for _ in 0..3 {
+            s.spawn(move || {
+                let mut last = 0;
+                while last != LAST {
+                    let value = unsafe { swmr_cell_load(handle.0) };
+                    assert!(value >= last, "went backwards: {value} after {last}");
+                    last = value;
+                }
+            });
+        }
+        for value in 1..=LAST {
+            unsafe { swmr_cell_publish(handle.0, value) };
+        }
+    });
+    unsafe { swmr_cell_free(handle.0) };
+}
@@ -0,0 +1,104 @@
+//! Tests for the `changed()` async adapter over `FairSeqlock`.
+//!
+//! Run with: `cargo test --features async --test changed_test`
+
+#![cfg(all(feature = "async", not(feature = "loom")))]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Duration;
+use swmr_barrier::{FairSeqlock, changed};
+
+/// An await resolves once a writer on another thread publishes.
+#[tokio::test]
+async fn test_changed_resolves_on_set() {
+    let lock = Arc::new(FairSeqlock::new(1u32));
+    let mut seen = lock.version();
+    let writer = {
+        let lock = lock.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            lock.set(2);
+        })
+    };
+    assert_eq!(changed(&lock, &mut seen).await, 2);
+    assert_eq!(seen, 1);
+    writer.join().unwrap();
+}
+
+/// On a single-threaded runtime the writer task only runs if the waiting future yields, so this
+/// resolves only because `changed` returns `Pending` instead of spinning.
+#[tokio::test(flavor = "current_thread")]
+async fn test_changed_yields_to_writer_task() {
+    let lock = Arc::new(FairSeqlock::new(1u32));
+    let writer = tokio::spawn({
+        let lock = lock.clone();
+        async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            lock.set(2);
+        }
+    });
+    let mut seen = 0;
+    assert_eq!(changed(&lock, &mut seen).await, 2);
+    writer.await.unwrap();
+}
+
+/// Counts how often the wrapped future is polled.
+struct CountPolls<'a, F> {
+    future: Pin<&'a mut F>,
+    polls: &'a AtomicUsize,
+}
+
+impl<F: Future> Future for CountPolls<'_, F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        self.polls.fetch_add(1, Ordering::Relaxed);
+        self.future.as_mut().poll(cx)
+    }
+}
+
+/// An idle wait sleeps until the write instead of waking itself: a busy-polling future would be
+/// polled thousands of times during the writer's delay.
+#[tokio::test(flavor = "current_thread")]
+async fn test_changed_sleeps_until_set() {
+    let lock = Arc::new(FairSeqlock::new(1u32));
+    let writer = {
+        let lock = lock.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            lock.set(2);
+        })
+    };
+    let mut seen = lock.version();
+    let polls = AtomicUsize::new(0);
+    let future = std::pin::pin!(changed(&lock, &mut seen));
+    assert_eq!(CountPolls { future, polls: &polls }.await, 2);
+    assert!(polls.load(Ordering::Relaxed) <= 3, "polled {} times", polls.load(Ordering::Relaxed));
+    writer.join().unwrap();
+}
+
+/// Every task waiting on the same seqlock is woken by one write, and the registrations are
+/// consumed by it.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_changed_wakes_every_waiter() {
+    let lock = Arc::new(FairSeqlock::new(0u32));
+    let waiters: Vec<_> = (0..4)
+        .map(|_| {
+            let lock = lock.clone();
+            tokio::spawn(async move {
+                let mut seen = 0;
+                changed(&lock, &mut seen).await
+            })
+        })
+        .collect();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    lock.set(7);
+    for waiter in waiters {
+        assert_eq!(waiter.await.unwrap(), 7);
+    }
+}
@@ -0,0 +1,89 @@
+//! Tests for `ChecksummedCell`.
+//!
+//! The torn-write test needs the `testing` hooks:
+//! `cargo test --features testing,std --test checksummed_test`
+
+#![cfg(not(feature = "loom"))]
+
+use std::sync::Mutex;
+use std::thread;
+use swmr_barrier::ChecksummedCell;
+
+// The torn-write hook is process-wide; keep other writers out while it is armed.
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn test_get_set() {
+    let _guard = LOCK.lock().unwrap();
+    let cell = ChecksummedCell::new((1u32, [2u8; 3]));
+    assert_eq!(cell.get(), (1, [2; 3]));
+    assert_eq!(cell.try_get(), Some((1, [2; 3])));
+    cell.set((4, [5; 3]));
+    assert_eq!(cell.get(), (4, [5; 3]));
+    assert_eq!(format!("{cell:?}"), "ChecksummedCell { value: (4, [5, 5, 5]) }");
+}
+
+/// Under a working barrier every copy matches its checksum.
+#[test]
+fn test_concurrent_reads_match() {
+    let _guard = LOCK.lock().unwrap();
+    const SETS: u64 = 100_000;
+    let cell = ChecksummedCell::new([0u64; 4]);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            for i in 1..=SETS {
+                cell.set([i; 4]);
+            }
+        });
+        s.spawn(|| {
+            let mut last = 0;
+            while last < SETS {
+                let value = cell.try_get().expect("checksum mismatch under a working barrier");
+                assert!(value.iter().all(|&v| v == value[0]), "torn read: {value:?}");
+                last = value[0];
+            }
+        });
+    });
+}
+
+/// A torn write is detected: `try_get` reports it and `get` retries until the real value lands,
+/// never returning the torn one.
+#[cfg(all(feature = "testing", feature = "std"))]
+#[test]
+fn test_torn_write_detected_and_retried() {
+    use std::time::Duration;
+    use swmr_barrier::testing;
+
+    let _guard = LOCK.lock().unwrap();
+    let cell = ChecksummedCell::new(1u64);
+    let mut detected = false;
+
+    testing::inject_torn_write(Duration::from_millis(50), 1);
+    thread::scope(|s| {
+        s.spawn(|| cell.set(2));
+        loop {
+            match cell.try_get() {
+                None => detected = true,
+                Some(2) => break,
+                Some(value) => assert_eq!(value, 1),
+            }
+        }
+    });
+    assert!(detected, "the torn write was never observed");
+    assert_eq!(cell.get(), 2);
+
+    // `get` rides out the torn window instead of returning it.
+    // `get` 会等过撕裂窗口，而不是返回撕裂的值。
+    testing::inject_torn_write(Duration::from_millis(20), 1);
+    thread::scope(|s| {
+        s.spawn(|| cell.set(3));
+        loop {
+            match cell.get() {
+                3 => break,
+                value => assert_eq!(value, 2),
+            }
+        }
+    });
+    testing::clear_injected_failures();
+}
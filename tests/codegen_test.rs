@@ -0,0 +1,88 @@
+//! Codegen check: `light_barrier()` compiles to no fence instruction on the accelerated path
+//! (x86_64 Linux).
+//!
+//! Builds the crate in release mode with `--emit asm` and inspects `swmr_light_barrier`, the
+//! `capi` export that consists solely of `light_barrier()`.
+
+#![cfg(all(target_os = "linux", target_arch = "x86_64", not(feature = "loom"), not(swmr_barrier_tsan)))]
+
+use std::path::Path;
+use std::process::Command;
+
+/// Compiles the library to assembly and returns the body of `symbol`, one instruction or label per line.
+fn function_asm(symbol: &str) -> Vec<String> {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("codegen");
+    let status = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+        .args(["rustc", "--release", "--lib", "--features", "capi", "--manifest-path"])
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .args(["--", "--emit", "asm"])
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "release build with --emit asm failed");
+
+    let deps = target_dir.join("release").join("deps");
+    let asm = std::fs::read_dir(&deps)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| {
+            path.extension().is_some_and(|ext| ext == "s")
+                && path.file_name().unwrap().to_string_lossy().starts_with("swmr_barrier-")
+        })
+        .expect("no assembly emitted");
+    let asm = std::fs::read_to_string(asm).unwrap();
+
+    asm.lines()
+        .skip_while(|line| *line != format!("{symbol}:"))
+        .skip(1)
+        .take_while(|line| !line.contains(".cfi_endproc"))
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('.') || line.ends_with(':'))
+        .filter(|line| !line.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
+
+/// The instructions from `start` up to and including the first `ret`.
+fn block_from(body: &[String], start: usize) -> Vec<&str> {
+    let mut block: Vec<&str> = body[start..].iter().map(String::as_str).filter(|line| !line.ends_with(':')).collect();
+    let end = block.iter().position(|insn| insn.starts_with("ret")).expect("block does not return");
+    block.truncate(end + 1);
+    block
+}
+
+/// `light_barrier()` is fully inlined, and when the backend is accelerated it reaches `ret` after
+/// the backend load and branch without executing a fence or locked instruction.
+#[test]
+fn test_light_barrier_accelerated_path_has_no_fence() {
+    let body = function_asm("swmr_light_barrier");
+    assert!(!body.is_empty(), "swmr_light_barrier not found in the assembly");
+    assert!(
+        !body.iter().any(|insn| insn.starts_with("call")),
+        "light_barrier() was not inlined: {body:#?}"
+    );
+
+    // The first branch tests the membarrier command; find the block taken when it is non-zero.
+    // 第一个分支检测 membarrier 命令；找出其非零时执行的代码块。
+    let (branch, insn) = body
+        .iter()
+        .enumerate()
+        .find(|(_, insn)| insn.starts_with("jne") || insn.starts_with("je"))
+        .expect("no branch on the backend");
+    let target = insn.split_whitespace().last().unwrap();
+    let accelerated = if insn.starts_with("jne") {
+        let label = body.iter().position(|line| *line == format!("{target}:")).expect("branch target");
+        block_from(&body, label)
+    } else {
+        block_from(&body, branch + 1)
+    };
+
+    for insn in &accelerated {
+        assert!(
+            !(insn.starts_with("mfence") || insn.starts_with("lock") || insn.starts_with("xchg")),
+            "fence on the accelerated path: {accelerated:?}"
+        );
+    }
+    assert_eq!(accelerated.len(), 1, "accelerated path should be a bare return: {body:#?}");
+}
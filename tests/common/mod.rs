@@ -0,0 +1,88 @@
+//! Barrier test doubles shared by the integration tests.
+//!
+//! Each test binary includes this module with `mod common;` and uses only some of it.
+
+#![allow(dead_code)]
+
+use std::sync::atomic::{AtomicUsize, Ordering, fence};
+use swmr_barrier::AsymmetricBarrier;
+
+/// Counts `heavy()` and `light()` calls and upgrades both sides to `fence(SeqCst)`, so it stays a
+/// valid barrier when shared across threads. Pass it by reference (`&B` is a barrier too) to keep
+/// reading the counts after handing it to a structure.
+#[derive(Default)]
+pub struct CountingBarrier {
+    heavy: AtomicUsize,
+    light: AtomicUsize,
+}
+
+impl CountingBarrier {
+    /// A barrier with both counts at zero; `const` so it can back a `static`.
+    pub const fn new() -> Self {
+        Self {
+            heavy: AtomicUsize::new(0),
+            light: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn heavy_count(&self) -> usize {
+        self.heavy.load(Ordering::Relaxed)
+    }
+
+    pub fn light_count(&self) -> usize {
+        self.light.load(Ordering::Relaxed)
+    }
+}
+
+// SAFETY: Both sides issue `fence(SeqCst)`. Two sequentially consistent fences order every
+// store-buffering and message-passing pair on their own, whatever the counters do.
+unsafe impl AsymmetricBarrier for CountingBarrier {
+    fn heavy(&self) {
+        self.heavy.fetch_add(1, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+    }
+
+    fn light(&self) {
+        self.light.fetch_add(1, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+    }
+
+    fn accelerated(&self) -> bool {
+        false
+    }
+}
+
+/// Panics from its `panic_at`-th `heavy()` call (1-based) and from no other; every call that
+/// returns is a `fence(SeqCst)`, as is `light()`.
+pub struct PanickingBarrier {
+    heavies: AtomicUsize,
+    panic_at: usize,
+}
+
+impl PanickingBarrier {
+    pub const fn on_heavy(panic_at: usize) -> Self {
+        Self {
+            heavies: AtomicUsize::new(0),
+            panic_at,
+        }
+    }
+}
+
+// SAFETY: As for `CountingBarrier`: every `heavy()` that returns and every `light()` is a
+// `fence(SeqCst)`. A `heavy()` that panics returns nothing for a caller to rely on.
+unsafe impl AsymmetricBarrier for PanickingBarrier {
+    fn heavy(&self) {
+        if self.heavies.fetch_add(1, Ordering::Relaxed) + 1 == self.panic_at {
+            panic!("injected barrier panic");
+        }
+        fence(Ordering::SeqCst);
+    }
+
+    fn light(&self) {
+        fence(Ordering::SeqCst);
+    }
+
+    fn accelerated(&self) -> bool {
+        false
+    }
+}
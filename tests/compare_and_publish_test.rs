@@ -0,0 +1,28 @@
+//! Tests for `SwmrCell::compare_and_publish`.
+//!
+//! `barrier_generation()` counts heavy barriers process-wide, so this binary holds a single test:
+//! no other test can issue a barrier while it compares the counter.
+
+#![cfg(all(not(feature = "loom"), target_has_atomic = "64"))]
+
+use swmr_barrier::{SwmrCell, barrier_generation};
+
+/// An update whose `expected` matches publishes with one heavy barrier; a mismatch publishes
+/// nothing and issues no barrier at all, so polling readers see no new version.
+#[test]
+fn test_compare_and_publish_elides_unchanged_value() {
+    let cell = SwmrCell::<[u64; 4]>::new([1; 4]);
+    let mut reader = cell.reader();
+    assert_eq!(reader.read_if_newer(), Some([1; 4]));
+
+    let before = barrier_generation();
+    assert!(!cell.compare_and_publish([0; 4], [2; 4]));
+    assert_eq!(barrier_generation(), before, "a skipped publish issued a heavy barrier");
+    assert_eq!(cell.get(), [1; 4]);
+    assert_eq!(reader.read_if_newer(), None);
+
+    assert!(cell.compare_and_publish([1; 4], [2; 4]));
+    assert_eq!(barrier_generation(), before + 1);
+    assert_eq!(cell.get(), [2; 4]);
+    assert_eq!(reader.read_if_newer(), Some([2; 4]));
+}
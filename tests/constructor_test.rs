@@ -0,0 +1,38 @@
+//! Tests that the pre-`main` constructor registered by `src/init.rs` runs on every object format
+//! it supports (ELF `.init_array`, PE `.CRT$XCU`, Mach-O `__mod_init_func`).
+//!
+//! Run with: `cargo test --features testing --test constructor_test`
+
+#![cfg(feature = "testing")]
+
+use swmr_barrier::testing;
+
+/// Targets whose object format has a constructor section.
+const HAS_CONSTRUCTOR: bool = cfg!(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "dragonfly",
+    target_os = "illumos",
+    target_os = "solaris",
+    target_os = "haiku",
+    target_os = "fuchsia",
+    target_os = "hurd",
+    target_os = "windows",
+    target_vendor = "apple",
+));
+
+/// The test harness's `main` only starts after constructors, so the flag is already set here.
+#[test]
+fn test_constructor_ran_before_main() {
+    assert_eq!(testing::constructor_ran(), HAS_CONSTRUCTOR);
+}
+
+/// On Linux the constructor runs detection: its outcome is recorded before any test code.
+#[cfg(all(target_os = "linux", not(feature = "loom"), not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))))]
+#[test]
+fn test_linux_detection_ran_before_main() {
+    assert_ne!(swmr_barrier::stats().init_reason, swmr_barrier::InitReason::Pending);
+}
@@ -0,0 +1,115 @@
+//! Stress test: the seqlock invariant under CPU hotplug (Linux).
+//!
+//! Offlining and onlining CPUs while membarrier IPIs are in flight has exposed kernel bugs before.
+//! This test runs the heavy/light seqlock workload while toggling one CPU through
+//! `/sys/devices/system/cpu/cpuN/online`. It needs root and a hotpluggable CPU, and skips with a
+//! message otherwise (non-root, read-only sysfs in a container, or a single-CPU machine).
+//!
+//! Run with: `sudo -E cargo test --features std --test cpu_hotplug_test -- --nocapture`
+
+#![cfg(all(target_os = "linux", feature = "std", not(feature = "loom")))]
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use swmr_barrier::{heavy_barrier, light_barrier};
+
+const ITERATIONS: usize = 200_000;
+
+/// The highest-numbered CPU that is online and can be taken offline.
+fn hotpluggable_cpu() -> Option<PathBuf> {
+    let mut cpus: Vec<(usize, PathBuf)> = fs::read_dir("/sys/devices/system/cpu")
+        .ok()?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let id = entry.file_name().to_str()?.strip_prefix("cpu")?.parse().ok()?;
+            let online = entry.path().join("online");
+            (fs::read_to_string(&online).ok()?.trim() == "1").then_some((id, online))
+        })
+        .collect();
+    cpus.sort();
+    cpus.pop().map(|(_, online)| online)
+}
+
+/// Brings the CPU back online when the test ends, even if it panics.
+struct Online(PathBuf);
+
+impl Drop for Online {
+    fn drop(&mut self) {
+        if let Err(err) = fs::write(&self.0, "1") {
+            eprintln!("failed to bring {} back online: {err}", self.0.display());
+        }
+    }
+}
+
+/// Data stored before the heavy barrier is visible to a reader that saw the version stored after
+/// it, while one CPU keeps going offline and online.
+#[test]
+fn test_seqlock_under_cpu_hotplug() {
+    if unsafe { libc::geteuid() } != 0 {
+        eprintln!("skipping: CPU hotplug needs root");
+        return;
+    }
+    let Some(online) = hotpluggable_cpu() else {
+        eprintln!("skipping: no hotpluggable CPU is online");
+        return;
+    };
+    if let Err(err) = fs::write(&online, "0") {
+        eprintln!("skipping: cannot offline {}: {err}", online.display());
+        return;
+    }
+    let online = Online(online);
+
+    let version = AtomicUsize::new(0);
+    let data = AtomicUsize::new(0);
+    let done = AtomicBool::new(false);
+    let toggles = AtomicUsize::new(1);
+    let readers = thread::available_parallelism().map_or(2, |n| n.get()).max(2);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            // Threads on the CPU being offlined are migrated mid-loop, which is the point.
+            // 位于被下线 CPU 上的线程会在循环中途被迁移，这正是测试的目的。
+            let mut up = false;
+            while !done.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+                match fs::write(&online.0, if up { "0" } else { "1" }) {
+                    Ok(()) => {
+                        up = !up;
+                        toggles.fetch_add(1, Ordering::Relaxed);
+                    }
+                    // The kernel may refuse briefly while a previous transition settles.
+                    // 上一次切换尚未完成时，内核可能会暂时拒绝。
+                    Err(err) if err.raw_os_error() == Some(libc::EBUSY) => {}
+                    Err(err) => panic!("toggling {} failed: {err}", online.0.display()),
+                }
+            }
+        });
+
+        for _ in 0..readers {
+            s.spawn(|| {
+                while !done.load(Ordering::Relaxed) {
+                    let v = version.load(Ordering::Relaxed);
+                    light_barrier();
+                    let d = data.load(Ordering::Relaxed);
+                    assert!(d >= v, "seqlock violation under hotplug: version={v} but data={d}");
+                }
+            });
+        }
+
+        for i in 1..=ITERATIONS {
+            data.store(i, Ordering::Relaxed);
+            heavy_barrier();
+            version.store(i, Ordering::Relaxed);
+        }
+        done.store(true, Ordering::Relaxed);
+    });
+
+    println!(
+        "{ITERATIONS} publishes across {} hotplug transitions of {}",
+        toggles.load(Ordering::Relaxed),
+        online.0.display()
+    );
+}
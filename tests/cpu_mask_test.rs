@@ -0,0 +1,93 @@
+//! Tests for `set_reader_cpu_mask()` (Linux 5.10+).
+//!
+//! These tests change process-global barrier state, so they live in their own binary and are
+//! serialized with a lock.
+//!
+//! Run with: `cargo test --features testing,std --test cpu_mask_test`
+
+#![cfg(all(
+    target_os = "linux",
+    feature = "std",
+    feature = "testing",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+
+use std::sync::Mutex;
+use swmr_barrier::{BarrierError, testing};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+const EINVAL: i32 = 22;
+
+/// Sets `mask`, or returns `false` when this kernel cannot target CPUs.
+fn try_set(mask: &[usize]) -> bool {
+    // SAFETY: No data in this binary is published through the barriers; they only feed the log of
+    // targeted CPUs, so unpinned light barriers race with nothing.
+    match unsafe { swmr_barrier::set_reader_cpu_mask(mask) } {
+        Ok(()) => true,
+        Err(BarrierError::KernelTooOld | BarrierError::Unsupported) => false,
+        Err(err) => panic!("unexpected error: {err}"),
+    }
+}
+
+/// Heavy barriers target exactly the masked CPUs, and an empty mask restores broadcasting.
+#[test]
+fn test_mask_is_applied_to_heavy_barriers() {
+    let _guard = LOCK.lock().unwrap();
+    testing::reset_for_test();
+
+    if !try_set(&[0, 0]) {
+        return;
+    }
+    testing::take_targeted_cpus();
+
+    swmr_barrier::heavy_barrier();
+    swmr_barrier::heavy_barrier();
+    assert_eq!(testing::take_targeted_cpus(), [0, 0], "one targeted call per masked CPU per barrier");
+
+    // `light_barrier()` never issues a system call.
+    swmr_barrier::light_barrier();
+    assert!(testing::take_targeted_cpus().is_empty());
+
+    // SAFETY: An empty mask is always sound.
+    unsafe { swmr_barrier::set_reader_cpu_mask(&[]) }.unwrap();
+    swmr_barrier::heavy_barrier();
+    assert!(testing::take_targeted_cpus().is_empty());
+}
+
+/// A CPU that does not exist is rejected and leaves the previous mask in place.
+#[test]
+fn test_invalid_cpu_keeps_previous_mask() {
+    let _guard = LOCK.lock().unwrap();
+    testing::reset_for_test();
+
+    if !try_set(&[0]) {
+        return;
+    }
+    // SAFETY: As in `try_set`; the call fails and keeps the previous mask anyway.
+    let rejected = unsafe { swmr_barrier::set_reader_cpu_mask(&[0, 1 << 20]) };
+    assert_eq!(rejected, Err(BarrierError::SyscallFailed(EINVAL)));
+    testing::take_targeted_cpus();
+
+    swmr_barrier::heavy_barrier();
+    assert_eq!(testing::take_targeted_cpus(), [0]);
+
+    testing::reset_for_test();
+}
+
+/// `reset_for_test()` drops the mask.
+#[test]
+fn test_reset_clears_mask() {
+    let _guard = LOCK.lock().unwrap();
+    testing::reset_for_test();
+
+    if !try_set(&[0]) {
+        return;
+    }
+    testing::reset_for_test();
+    testing::take_targeted_cpus();
+
+    swmr_barrier::heavy_barrier();
+    assert!(testing::take_targeted_cpus().is_empty());
+}
@@ -0,0 +1,69 @@
+//! Tests for the `debug-checks` multi-writer reports on the heavy barrier.
+//!
+//! The last caller is process-global, so these tests live in their own binary and are serialized
+//! with a lock. The check only exists in debug builds.
+//!
+//! Run with: `cargo test --features debug-checks --test debug_checks_test`
+
+#![cfg(all(feature = "debug-checks", debug_assertions))]
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use swmr_barrier::{MultiWriterReport, heavy_barrier, multi_writer_reports, set_multi_writer_hook};
+
+static LOCK: Mutex<()> = Mutex::new(());
+static HOOK_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn count(report: &MultiWriterReport) {
+    assert_ne!(report.previous_thread, report.thread);
+    assert!(report.gap < Duration::from_millis(100), "{report:?}");
+    HOOK_CALLS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Longer than the 100 ms window, so the next caller starts fresh.
+const QUIET: Duration = Duration::from_millis(150);
+
+/// One thread issuing barriers back to back is a single writer.
+#[test]
+fn test_single_writer_passes() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    thread::sleep(QUIET);
+    let before = multi_writer_reports();
+    thread::spawn(|| {
+        for _ in 0..100 {
+            heavy_barrier();
+        }
+    })
+    .join()
+    .unwrap();
+    assert_eq!(multi_writer_reports(), before);
+}
+
+/// A second thread issuing a heavy barrier right after the first is reported to the hook, without
+/// panicking.
+#[test]
+fn test_second_writer_reported() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    thread::sleep(QUIET);
+    set_multi_writer_hook(Some(count));
+    let (before, calls) = (multi_writer_reports(), HOOK_CALLS.load(Ordering::Relaxed));
+    thread::spawn(heavy_barrier).join().unwrap();
+    thread::spawn(heavy_barrier).join().expect("second writer panicked");
+    set_multi_writer_hook(None);
+    assert_eq!(multi_writer_reports(), before + 1);
+    assert_eq!(HOOK_CALLS.load(Ordering::Relaxed), calls + 1);
+}
+
+/// Writers that take turns with a quiet gap longer than the window are not flagged.
+#[test]
+fn test_hand_off_after_window_passes() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    thread::sleep(QUIET);
+    let before = multi_writer_reports();
+    thread::spawn(heavy_barrier).join().unwrap();
+    thread::sleep(QUIET);
+    thread::spawn(heavy_barrier).join().unwrap();
+    assert_eq!(multi_writer_reports(), before);
+}
@@ -0,0 +1,62 @@
+//! Tests for the `debug-seq` light-barrier sequence check.
+//!
+//! Run with: `cargo test --features debug-seq --test debug_seq_test`
+
+#![cfg(feature = "debug-seq")]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use swmr_barrier::{barrier_generation, heavy_barrier, last_seen_sequence, light_barrier};
+
+/// A thread that has not issued a light barrier has seen nothing.
+#[test]
+fn test_fresh_thread_sees_zero() {
+    thread::spawn(|| assert_eq!(last_seen_sequence(), 0)).join().unwrap();
+}
+
+/// A light barrier after a heavy barrier on the same thread sees at least that barrier.
+#[test]
+fn test_light_barrier_sees_own_heavy_barrier() {
+    heavy_barrier();
+    let issued = barrier_generation();
+    light_barrier();
+    assert!(last_seen_sequence() >= issued);
+}
+
+/// With one writer issuing heavy barriers, every reader records a non-decreasing sequence.
+#[test]
+fn test_sequences_monotonic_per_thread() {
+    const READERS: usize = 4;
+    const READS: usize = 20_000;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let writer = {
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                heavy_barrier();
+            }
+        })
+    };
+
+    let readers: Vec<_> = (0..READERS)
+        .map(|_| {
+            thread::spawn(|| {
+                let mut seen = Vec::with_capacity(READS);
+                for _ in 0..READS {
+                    light_barrier();
+                    seen.push(last_seen_sequence());
+                }
+                seen
+            })
+        })
+        .collect();
+
+    for reader in readers {
+        let seen = reader.join().unwrap();
+        assert!(seen.windows(2).all(|w| w[0] <= w[1]), "sequence regressed on a reader thread");
+    }
+    stop.store(true, Ordering::Relaxed);
+    writer.join().unwrap();
+}
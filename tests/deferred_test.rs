@@ -0,0 +1,68 @@
+//! Tests for `DeferredBarrier` and `heavy_barrier_deferred()`.
+
+#![cfg(not(feature = "loom"))]
+
+mod common;
+
+use common::CountingBarrier;
+use swmr_barrier::DeferredBarrier;
+
+/// Requests made before a `service()` call share one heavy barrier.
+#[test]
+fn test_service_batches_requests() {
+    let barrier = CountingBarrier::new();
+    let deferred = DeferredBarrier::with_barrier(&barrier);
+
+    assert!(!deferred.service(), "nothing was pending");
+    let tickets: Vec<_> = (0..3).map(|_| deferred.request()).collect();
+    assert!(tickets.iter().all(|&t| !deferred.is_complete(t)));
+    assert_eq!(barrier.heavy_count(), 0, "request() must not issue the barrier");
+
+    assert!(deferred.service());
+    assert!(tickets.iter().all(|&t| deferred.is_complete(t)));
+    assert_eq!(barrier.heavy_count(), 1);
+
+    let later = deferred.request();
+    assert!(!deferred.is_complete(later));
+    assert!(deferred.service());
+    assert!(!deferred.service());
+    assert_eq!(barrier.heavy_count(), 2);
+}
+
+/// The background barrier thread serves tickets, and data published after completion is visible
+/// to a reader pairing with `light_barrier()`.
+#[test]
+#[cfg(feature = "std")]
+fn test_global_deferred_barrier_publishes() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    const ROUNDS: usize = 200;
+
+    let data = Arc::new(AtomicUsize::new(0));
+    let ready = Arc::new(AtomicBool::new(false));
+    let reader = {
+        let (data, ready) = (data.clone(), ready.clone());
+        thread::spawn(move || {
+            while !ready.load(Ordering::Relaxed) {
+                thread::yield_now();
+            }
+            swmr_barrier::light_barrier();
+            assert_eq!(data.load(Ordering::Relaxed), ROUNDS);
+        })
+    };
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    for round in 1..=ROUNDS {
+        data.store(round, Ordering::Relaxed);
+        let ticket = swmr_barrier::heavy_barrier_deferred();
+        while !swmr_barrier::heavy_barrier_completed(ticket) {
+            assert!(Instant::now() < deadline, "deferred barrier never completed");
+            thread::yield_now();
+        }
+    }
+    ready.store(true, Ordering::Relaxed);
+    reader.join().unwrap();
+}
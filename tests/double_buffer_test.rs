@@ -0,0 +1,92 @@
+//! Tests for `DoubleBuffer` snapshot borrowing.
+
+#![cfg(not(feature = "loom"))]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use swmr_barrier::{BarrierKind, DoubleBuffer};
+
+#[test]
+fn test_borrow_snapshot_sees_latest_commit() {
+    let buffer = DoubleBuffer::new(vec![0u32; 4]);
+    assert_eq!(*buffer.borrow_snapshot(), [0, 0, 0, 0]);
+
+    buffer.commit(vec![1; 4]);
+    assert_eq!(*buffer.borrow_snapshot(), [1, 1, 1, 1]);
+
+    buffer.commit(vec![2; 8]);
+    assert_eq!(buffer.read(), vec![2; 8]);
+}
+
+/// A guard keeps its buffer intact across one commit: the writer fills the other buffer.
+#[test]
+fn test_guard_survives_one_commit() {
+    let buffer = DoubleBuffer::new(String::from("a"));
+    let guard = buffer.borrow_snapshot();
+    buffer.commit(String::from("b"));
+    assert_eq!(*guard, "a");
+    drop(guard);
+    assert_eq!(*buffer.borrow_snapshot(), "b");
+}
+
+/// Readers never observe a partially written payload while the writer commits concurrently.
+fn check_concurrent_snapshots(kind: BarrierKind) {
+    const READERS: usize = 4;
+    const COMMITS: u64 = 2_000;
+
+    let buffer = Arc::new(DoubleBuffer::with_kind([0u64; 64], kind));
+    let done = Arc::new(AtomicBool::new(false));
+
+    let readers: Vec<_> = (0..READERS)
+        .map(|_| {
+            let buffer = buffer.clone();
+            let done = done.clone();
+            thread::spawn(move || {
+                let mut last = 0;
+                while !done.load(Ordering::Relaxed) {
+                    let guard = buffer.borrow_snapshot();
+                    let first = guard[0];
+                    assert!(guard.iter().all(|&v| v == first), "torn snapshot: {:?}", &guard[..]);
+                    assert!(first >= last, "snapshot went backwards: {} < {}", first, last);
+                    last = first;
+                }
+            })
+        })
+        .collect();
+
+    for i in 1..=COMMITS {
+        buffer.commit([i; 64]);
+    }
+    done.store(true, Ordering::Relaxed);
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    assert_eq!(buffer.read(), [COMMITS; 64]);
+}
+
+#[test]
+fn test_concurrent_snapshots_asymmetric() {
+    check_concurrent_snapshots(BarrierKind::Asymmetric);
+}
+
+#[test]
+fn test_concurrent_snapshots_symmetric_fence() {
+    check_concurrent_snapshots(BarrierKind::SymmetricFence);
+}
+
+static BUFFER: DoubleBuffer<[u32; 4]> = DoubleBuffer::new_const([1; 4]);
+
+/// A double buffer built by `new_const` works as a `static`, and `Default` starts from `T::default()`.
+#[test]
+fn test_new_const_and_default() {
+    assert_eq!(BUFFER.read(), [1; 4]);
+    thread::scope(|s| {
+        s.spawn(|| BUFFER.commit([2; 4]));
+    });
+    assert_eq!(*BUFFER.borrow_snapshot(), [2; 4]);
+
+    let buffer = DoubleBuffer::<Vec<u32>>::default();
+    assert!(buffer.read().is_empty());
+}
@@ -0,0 +1,54 @@
+//! Tests for `running_under_qemu_user()` and the checks behind it.
+
+#![cfg(not(feature = "loom"))]
+
+/// `stats()` carries the same answer, and this test is not run under qemu-user.
+#[test]
+fn test_live_detection() {
+    let detected = swmr_barrier::running_under_qemu_user();
+    assert_eq!(swmr_barrier::stats().qemu_user, detected);
+
+    // Cross-compiled CI runs set `QEMU_LD_PREFIX`; everywhere else this must not false-positive.
+    if std::env::var_os("QEMU_LD_PREFIX").is_none() {
+        assert!(!detected, "false positive on native hardware");
+    }
+}
+
+#[cfg(all(feature = "testing", feature = "std", target_os = "linux"))]
+mod parser {
+    use swmr_barrier::testing::detect_qemu_user;
+
+    const NATIVE_MAPS: &str = "\
+55d0c0a00000-55d0c0a21000 r-xp 00000000 08:01 1048602                    /usr/bin/cat
+7f3b2c000000-7f3b2c1c6000 r-xp 00028000 08:01 1050011                    /usr/lib/x86_64-linux-gnu/libc.so.6
+7ffd4a1f0000-7ffd4a211000 rw-p 00000000 00:00 0                          [stack]
+7ffd4a3e1000-7ffd4a3e3000 r-xp 00000000 00:00 0                          [vdso]
+";
+
+    #[test]
+    fn test_native_process() {
+        assert!(!detect_qemu_user(&["PATH", "HOME", "CARGO_MANIFEST_DIR"], NATIVE_MAPS));
+        assert!(!detect_qemu_user(&[], ""));
+    }
+
+    #[test]
+    fn test_qemu_env_var() {
+        assert!(detect_qemu_user(&["PATH", "QEMU_LD_PREFIX"], NATIVE_MAPS));
+        assert!(detect_qemu_user(&["QEMU_CPU"], ""));
+        // Only the prefix counts, not variables merely mentioning qemu.
+        // 只认前缀，不认仅提及 qemu 的变量。
+        assert!(!detect_qemu_user(&["MY_QEMU_PATH", "qemu_cpu"], ""));
+    }
+
+    #[test]
+    fn test_qemu_binary_mapped() {
+        let maps = format!(
+            "{NATIVE_MAPS}4000000000-4000800000 r-xp 00000000 08:01 2097153                    /usr/bin/qemu-aarch64-static\n"
+        );
+        assert!(detect_qemu_user(&[], &maps));
+        // A data file under a qemu directory is not the emulator itself.
+        // qemu 目录下的数据文件并非模拟器本身。
+        let data = "7f00-7f01 r--p 00000000 08:01 42                    /usr/share/qemu/keymaps/en-us\n";
+        assert!(!detect_qemu_user(&[], data));
+    }
+}
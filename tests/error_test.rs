@@ -0,0 +1,42 @@
+//! Tests for `BarrierError` and `try_init()`.
+
+#![cfg(not(feature = "loom"))]
+
+use std::error::Error;
+use swmr_barrier::{BarrierError, ReaderTableFull};
+
+/// Every variant renders a message and is usable as `dyn Error`.
+#[test]
+fn test_display_and_error_trait() {
+    let errors = [
+        BarrierError::KernelTooOld,
+        BarrierError::PermissionDenied,
+        BarrierError::QueryFailed,
+        BarrierError::RegistrationFailed(12),
+        BarrierError::SyscallFailed(22),
+        BarrierError::Unsupported,
+    ];
+    for error in errors {
+        let boxed: Box<dyn Error> = Box::new(error);
+        assert!(!boxed.to_string().is_empty());
+    }
+    assert_eq!(BarrierError::RegistrationFailed(12).to_string(), "barrier registration failed (errno 12)");
+
+    let boxed: Box<dyn Error> = Box::new(ReaderTableFull);
+    assert_eq!(boxed.to_string(), "reader table is full");
+}
+
+/// `try_init()` agrees with `init()` and composes with `?`.
+#[test]
+fn test_try_init_matches_init() {
+    fn setup() -> Result<swmr_barrier::Backend, Box<dyn Error>> {
+        Ok(swmr_barrier::try_init()?)
+    }
+
+    let result = setup();
+    assert_eq!(result.is_ok(), swmr_barrier::init());
+    if let Ok(backend) = result {
+        assert_eq!(backend, swmr_barrier::active_backend());
+        assert!(backend.is_accelerated());
+    }
+}
@@ -0,0 +1,55 @@
+//! Checks that feature combinations that cannot work under `loom`, and `loom` without
+//! `--cfg loom`, fail with a clear `compile_error!` instead of confusing downstream errors.
+//!
+//! Each case runs `cargo check` on the crate in a scratch target directory, like a
+//! `trybuild` compile-fail test.
+
+#![cfg(all(not(feature = "loom"), not(swmr_barrier_tsan)))]
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// `cfg_loom` sets `RUSTFLAGS="--cfg loom"`, which every `loom` build needs.
+fn check(features: &str, cfg_loom: bool) -> Output {
+    let rustflags = if cfg_loom { "--cfg loom" } else { "" };
+    Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+        .env("RUSTFLAGS", rustflags)
+        .args(["check", "--lib", "--features", features, "--manifest-path"])
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(Path::new(env!("CARGO_TARGET_TMPDIR")).join(if cfg_loom { "feature_guard_loom" } else { "feature_guard" }))
+        .output()
+        .expect("failed to run cargo")
+}
+
+fn assert_rejected(features: &str, cfg_loom: bool, message: &str) {
+    let output = check(features, cfg_loom);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "`{features}` should not compile");
+    assert!(stderr.contains(message), "`{features}` failed without the guard message:\n{stderr}");
+}
+
+#[test]
+fn test_loom_rejects_thread_spawning_features() {
+    assert_rejected("loom,capi", true, "features `loom` and `capi` are mutually exclusive");
+    assert_rejected("loom,verify", true, "features `loom` and `verify` are mutually exclusive");
+}
+
+/// The canary writer of `verify` would flood the `debug-checks` multi-writer reports.
+#[test]
+fn test_verify_rejects_debug_checks() {
+    assert_rejected("verify,debug-checks", false, "features `verify` and `debug-checks` are mutually exclusive");
+}
+
+/// The guards do not fire for the combinations `loom` supports.
+#[test]
+fn test_loom_accepts_compatible_features() {
+    let output = check("loom,std,testing,tracing,strict", true);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+/// The feature alone, as feature unification would enable it, is rejected without `--cfg loom`.
+#[test]
+fn test_loom_requires_cfg() {
+    assert_rejected("loom", false, "must be paired with `RUSTFLAGS=\"--cfg loom\"`");
+}
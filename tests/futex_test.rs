@@ -0,0 +1,75 @@
+//! Tests for the futex wake-up bridge (`std`, Linux).
+//!
+//! Run with: `cargo test --features std --test futex_test`
+
+#![cfg(all(feature = "std", target_os = "linux", not(feature = "loom")))]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::thread;
+use std::time::Duration;
+use swmr_barrier::{publish_and_wake, wait_for_publish};
+
+/// A reader blocked in `wait_for_publish` wakes up and observes the published value.
+#[test]
+fn test_sleeping_reader_wakes() {
+    let flag = Arc::new(AtomicU32::new(0));
+    let futex = Arc::new(AtomicU32::new(0));
+
+    let reader = {
+        let (flag, futex) = (flag.clone(), futex.clone());
+        thread::spawn(move || {
+            let seq = wait_for_publish(&futex, 0);
+            (seq, flag.load(Ordering::Relaxed))
+        })
+    };
+
+    // Give the reader time to block in FUTEX_WAIT.
+    thread::sleep(Duration::from_millis(50));
+    publish_and_wake(&flag, 42, &futex);
+
+    assert_eq!(reader.join().unwrap(), (1, 42));
+}
+
+/// A reader arriving after the publish does not block.
+#[test]
+fn test_late_reader_does_not_block() {
+    let flag = AtomicU32::new(0);
+    let futex = AtomicU32::new(0);
+
+    publish_and_wake(&flag, 7, &futex);
+    assert_eq!(wait_for_publish(&futex, 0), 1);
+    assert_eq!(flag.load(Ordering::Relaxed), 7);
+}
+
+/// Readers chaining on the returned sequence see every publish in order.
+#[test]
+fn test_sequence_of_publishes() {
+    const PUBLISHES: u32 = 100;
+
+    let flag = Arc::new(AtomicU32::new(0));
+    let futex = Arc::new(AtomicU32::new(0));
+
+    let readers: Vec<_> = (0..2)
+        .map(|_| {
+            let (flag, futex) = (flag.clone(), futex.clone());
+            thread::spawn(move || {
+                let mut seq = 0;
+                let mut last = 0;
+                while last < PUBLISHES {
+                    seq = wait_for_publish(&futex, seq);
+                    let value = flag.load(Ordering::Relaxed);
+                    assert!(value >= seq, "woke for publish {} but saw value {}", seq, value);
+                    last = value;
+                }
+            })
+        })
+        .collect();
+
+    for value in 1..=PUBLISHES {
+        publish_and_wake(&flag, value, &futex);
+    }
+    for reader in readers {
+        reader.join().unwrap();
+    }
+}
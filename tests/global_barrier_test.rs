@@ -0,0 +1,50 @@
+//! Tests for `set_global_barrier` (feature `global-barrier`).
+//!
+//! The override is process-global, so these tests live in their own binary and are serialized
+//! with a lock.
+//!
+//! Run with: `cargo test --features global-barrier --test global_barrier_test`
+
+#![cfg(all(feature = "global-barrier", not(feature = "loom")))]
+
+mod common;
+
+use common::CountingBarrier;
+use std::sync::Mutex;
+use swmr_barrier::{SwmrCell, clear_global_barrier, heavy_barrier, light_barrier, set_global_barrier};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+static COUNTING: CountingBarrier = CountingBarrier::new();
+
+fn counts() -> (usize, usize) {
+    (COUNTING.heavy_count(), COUNTING.light_count())
+}
+
+/// The free functions, and `SystemBarrier` structures through them, route to the override until
+/// it is cleared.
+#[test]
+fn test_free_functions_route_through_override() {
+    let _guard = LOCK.lock().unwrap();
+    // SAFETY: The lock serializes the tests; every barrier user below runs on this thread.
+    unsafe { set_global_barrier(&COUNTING) };
+    let start = counts();
+
+    heavy_barrier();
+    light_barrier();
+    assert_eq!(counts(), (start.0 + 1, start.1 + 1));
+
+    let cell = SwmrCell::new(1u32);
+    cell.set(2);
+    assert_eq!(cell.get(), 2);
+    let routed = counts();
+    assert!(routed.0 > start.0 + 1 && routed.1 > start.1 + 1, "SwmrCell bypassed the override");
+
+    // SAFETY: As above.
+    unsafe { clear_global_barrier() };
+    heavy_barrier();
+    light_barrier();
+    cell.set(3);
+    assert_eq!(cell.get(), 3);
+    assert_eq!(counts(), routed);
+}
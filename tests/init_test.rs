@@ -0,0 +1,296 @@
+//! Tests for explicit initialization and registration failure handling (Linux).
+//!
+//! These tests mutate process-global barrier state through the `testing` hooks,
+//! so they live in their own binary and are serialized with a lock.
+//!
+//! Run with: `cargo test --features testing --test init_test`
+
+#![cfg(all(target_os = "linux", feature = "testing", not(feature = "loom"), not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))))]
+
+use std::sync::Mutex;
+use swmr_barrier::{Backend, BarrierError, InitReason, Validation, testing};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+const ENOMEM: i32 = 12;
+
+/// A registration failure (e.g. ENOMEM under cgroup limits) degrades to SHARED:
+/// the errno is exposed and acceleration stays enabled.
+#[test]
+fn test_registration_failure_degrades_to_shared() {
+    let _guard = LOCK.lock().unwrap();
+
+    testing::inject_registration_failure(ENOMEM, 1);
+    testing::reinitialize();
+
+    assert_eq!(swmr_barrier::registration_errno(), Some(ENOMEM));
+    assert!(
+        swmr_barrier::is_accelerated(),
+        "SHARED should remain available after a failed PRIVATE_EXPEDITED registration"
+    );
+    assert_eq!(swmr_barrier::active_backend(), Backend::LinuxShared);
+    let matrix = swmr_barrier::acceleration_matrix();
+    assert!(!matrix.private_expedited && matrix.shared && matrix.global);
+
+    // Barriers keep working in the degraded mode.
+    swmr_barrier::heavy_barrier();
+    swmr_barrier::light_barrier();
+
+    testing::clear_injected_failures();
+    testing::reinitialize();
+    assert_eq!(swmr_barrier::registration_errno(), None);
+    assert!(swmr_barrier::acceleration_matrix().private_expedited);
+}
+
+/// The explicit `init()` retries registration once, recovering from a transient failure.
+#[test]
+fn test_init_retries_registration() {
+    let _guard = LOCK.lock().unwrap();
+
+    testing::inject_registration_failure(ENOMEM, 1);
+    testing::reinitialize();
+    assert_eq!(swmr_barrier::registration_errno(), Some(ENOMEM));
+
+    // One more transient failure: the retry inside init() must succeed.
+    testing::inject_registration_failure(ENOMEM, 1);
+    assert!(swmr_barrier::init());
+    assert_eq!(swmr_barrier::registration_errno(), None);
+    assert_eq!(swmr_barrier::active_backend(), Backend::LinuxPrivateExpedited);
+
+    testing::clear_injected_failures();
+}
+
+/// A persistent failure survives the retry but still leaves SHARED enabled.
+#[test]
+fn test_init_persistent_failure_keeps_shared() {
+    let _guard = LOCK.lock().unwrap();
+
+    testing::inject_registration_failure(ENOMEM, 3);
+    testing::reinitialize();
+    assert!(swmr_barrier::init());
+    assert_eq!(swmr_barrier::registration_errno(), Some(ENOMEM));
+
+    testing::clear_injected_failures();
+    assert!(swmr_barrier::init());
+    assert_eq!(swmr_barrier::registration_errno(), None);
+}
+
+const EPERM: i32 = 1;
+const EINVAL: i32 = 22;
+
+/// `init()` validates the selected command with a real barrier.
+#[test]
+fn test_init_validation_passes() {
+    let _guard = LOCK.lock().unwrap();
+
+    testing::clear_injected_failures();
+    testing::reinitialize();
+    assert_eq!(swmr_barrier::stats().validation, Validation::NotRun);
+
+    assert!(swmr_barrier::init());
+    let stats = swmr_barrier::stats();
+    assert_eq!(stats.validation, Validation::Passed);
+    assert_eq!(stats.backend, Backend::LinuxPrivateExpedited);
+}
+
+/// A kernel that accepts registration but rejects PRIVATE_EXPEDITED barriers is downgraded to SHARED.
+#[test]
+fn test_init_validation_failure_downgrades_to_shared() {
+    let _guard = LOCK.lock().unwrap();
+
+    testing::reinitialize();
+    testing::inject_barrier_failure(EPERM, 1);
+    assert!(swmr_barrier::init());
+
+    let stats = swmr_barrier::stats();
+    assert_eq!(stats.backend, Backend::LinuxShared);
+    assert_eq!(stats.validation, Validation::Failed(EPERM));
+    assert_eq!(stats.init_reason, InitReason::Accelerated);
+
+    testing::clear_injected_failures();
+    assert!(swmr_barrier::init());
+    assert_eq!(swmr_barrier::active_backend(), Backend::LinuxPrivateExpedited);
+    assert_eq!(swmr_barrier::stats().validation, Validation::Passed);
+}
+
+/// When every barrier command is rejected, `init()` falls back to `fence(SeqCst)`.
+#[test]
+fn test_init_validation_failure_falls_back() {
+    let _guard = LOCK.lock().unwrap();
+
+    testing::reinitialize();
+    testing::inject_barrier_failure(EPERM, 2);
+    assert!(!swmr_barrier::init());
+
+    let stats = swmr_barrier::stats();
+    assert_eq!(stats.backend, Backend::Fallback);
+    assert_eq!(stats.init_reason, InitReason::ValidationFailed);
+    assert_eq!(stats.validation, Validation::Failed(EPERM));
+
+    // Barriers keep working in fallback mode.
+    swmr_barrier::heavy_barrier();
+    swmr_barrier::light_barrier();
+
+    testing::clear_injected_failures();
+    assert!(swmr_barrier::init());
+    assert_eq!(swmr_barrier::active_backend(), Backend::LinuxPrivateExpedited);
+}
+
+/// `try_init()` reports why validation forced the fallback, and the backend once it succeeds.
+#[test]
+fn test_try_init_reports_validation_errno() {
+    let _guard = LOCK.lock().unwrap();
+
+    testing::reinitialize();
+    testing::inject_barrier_failure(EPERM, 2);
+    assert_eq!(swmr_barrier::try_init(), Err(BarrierError::PermissionDenied));
+
+    testing::reinitialize();
+    testing::inject_barrier_failure(EINVAL, 2);
+    assert_eq!(swmr_barrier::try_init(), Err(BarrierError::SyscallFailed(EINVAL)));
+
+    testing::clear_injected_failures();
+    assert_eq!(swmr_barrier::try_init(), Ok(Backend::LinuxPrivateExpedited));
+}
+
+/// `reset_for_test()` undoes injected failures and the state left behind by a degraded `init()`.
+#[test]
+fn test_reset_for_test_restores_clean_state() {
+    let _guard = LOCK.lock().unwrap();
+
+    testing::reset_for_test();
+    let clean = swmr_barrier::stats();
+    assert_eq!(clean.validation, Validation::NotRun);
+    assert_eq!(clean.registration_errno, None);
+
+    // Leave the process degraded, with failures still pending.
+    testing::inject_registration_failure(ENOMEM, 10);
+    testing::inject_barrier_failure(EPERM, 10);
+    assert!(!swmr_barrier::init());
+    assert_eq!(swmr_barrier::active_backend(), Backend::Fallback);
+
+    testing::reset_for_test();
+    assert_eq!(swmr_barrier::stats(), clean);
+
+    // No failure is left pending.
+    assert!(swmr_barrier::init());
+    assert_eq!(swmr_barrier::stats().validation, Validation::Passed);
+}
+
+/// Readers keep fencing until a barrier with the selected command has run, so a writer that still
+/// loaded the fallback command never pairs its fence with a compiler-only reader. The reverse
+/// mismatch (reader fence, writer membarrier) is safe and is what this window allows.
+#[test]
+fn test_readers_accelerate_only_after_confirming_barrier() {
+    use swmr_barrier::FenceStrength;
+
+    let _guard = LOCK.lock().unwrap();
+
+    // Registration succeeds, but the confirming barrier is rejected.
+    testing::inject_barrier_failure(EPERM, 1);
+    testing::reinitialize();
+    let stats = swmr_barrier::stats();
+    assert_eq!(stats.backend, Backend::LinuxPrivateExpedited);
+    assert_eq!(stats.light, FenceStrength::Full, "readers must not skip the fence before confirmation");
+
+    // Mismatched pair: reader fence, writer membarrier.
+    swmr_barrier::heavy_barrier();
+    swmr_barrier::light_barrier();
+
+    testing::clear_injected_failures();
+    assert!(swmr_barrier::init());
+    assert_eq!(swmr_barrier::stats().light, FenceStrength::Compiler);
+}
+
+/// A failed membarrier degrades only the call it hit: `heavy_barrier_traced()` reports the fence
+/// it fell back to, while the configured backend stays accelerated.
+#[test]
+fn test_heavy_barrier_traced_reports_per_call_degradation() {
+    let _guard = LOCK.lock().unwrap();
+    testing::reset_for_test();
+    let configured = swmr_barrier::active_backend();
+    if !configured.is_accelerated() {
+        return;
+    }
+
+    assert_eq!(swmr_barrier::heavy_barrier_traced(), configured);
+
+    testing::inject_barrier_failure(EPERM, 1);
+    assert_eq!(swmr_barrier::heavy_barrier_traced(), Backend::Fallback);
+    assert_eq!(swmr_barrier::active_backend(), configured);
+
+    assert_eq!(swmr_barrier::heavy_barrier_traced(), configured);
+    testing::reset_for_test();
+}
+
+/// A query blocked before the constructor ran (e.g. another crate's seccomp filter) leaves the
+/// fence fallback on both sides; a later `init()`, called before the application locks down,
+/// enables acceleration.
+#[test]
+fn test_blocked_query_falls_back_until_init() {
+    let _guard = LOCK.lock().unwrap();
+
+    testing::reset_for_test();
+    testing::inject_query_failure(EPERM, 1);
+    testing::reinitialize();
+
+    let stats = swmr_barrier::stats();
+    assert!(!swmr_barrier::is_accelerated());
+    assert_eq!(stats.backend, Backend::Fallback);
+    assert_eq!(stats.init_reason, InitReason::QueryFailed);
+    assert_eq!(stats.light, swmr_barrier::FenceStrength::Full);
+    swmr_barrier::heavy_barrier();
+    swmr_barrier::light_barrier();
+
+    assert!(swmr_barrier::init());
+    assert_eq!(swmr_barrier::active_backend(), Backend::LinuxPrivateExpedited);
+    assert_eq!(swmr_barrier::stats().init_reason, InitReason::Accelerated);
+    testing::reset_for_test();
+}
+
+/// A filter installed after the constructor registered: the explicit `init()`'s query fails, but
+/// the already active command is kept and still reported as accelerated.
+#[test]
+fn test_blocked_query_keeps_active_command() {
+    let _guard = LOCK.lock().unwrap();
+
+    testing::reset_for_test();
+    let configured = swmr_barrier::active_backend();
+    testing::inject_query_failure(EPERM, 1);
+    assert_eq!(swmr_barrier::init(), configured.is_accelerated());
+    assert_eq!(swmr_barrier::active_backend(), configured);
+    if configured.is_accelerated() {
+        assert_eq!(swmr_barrier::stats().init_reason, InitReason::Accelerated);
+    }
+    testing::reset_for_test();
+}
+
+/// `init()` leaves a validated PRIVATE_EXPEDITED alone, but `redetect()` re-runs detection and
+/// picks up a (simulated) loss of privileges, then their return.
+#[test]
+fn test_redetect_picks_up_capability_change() {
+    let _guard = LOCK.lock().unwrap();
+
+    testing::reset_for_test();
+    assert!(swmr_barrier::init());
+    assert_eq!(swmr_barrier::active_backend(), Backend::LinuxPrivateExpedited);
+
+    // The process lost the right to issue membarrier.
+    testing::inject_barrier_failure(EPERM, 2);
+    assert!(swmr_barrier::init(), "init() should not re-run detection once validated");
+    assert!(!swmr_barrier::redetect());
+    let stats = swmr_barrier::stats();
+    assert_eq!(stats.backend, Backend::Fallback);
+    assert_eq!(stats.init_reason, InitReason::ValidationFailed);
+
+    // Barriers keep working across the switch.
+    swmr_barrier::heavy_barrier();
+    swmr_barrier::light_barrier();
+
+    // Privileges restored.
+    testing::clear_injected_failures();
+    assert!(swmr_barrier::redetect());
+    assert_eq!(swmr_barrier::active_backend(), Backend::LinuxPrivateExpedited);
+    assert_eq!(swmr_barrier::stats().validation, Validation::Passed);
+    testing::reset_for_test();
+}
@@ -292,7 +292,7 @@ fn test_ping_pong() {
 /// 此测试确保在 Linux 内核 4.3+ 上，库成功注册并使用
 /// MEMBARRIER_CMD_PRIVATE_EXPEDITED (4.14+) 或 MEMBARRIER_CMD_SHARED (4.3+) 实现零开销读取屏障。
 #[test]
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))))]
 fn test_linux_membarrier_acceleration_enabled() {
     assert!(
         swmr_barrier::is_accelerated(),
@@ -318,3 +318,50 @@ fn test_windows_acceleration_enabled() {
     );
     println!("Windows acceleration is enabled (IS_ACCELERATED = true)");
 }
+
+/// Windows-specific test: the sync-core variant resolves `FlushInstructionCache` and succeeds.
+///
+/// Windows 专用测试：sync-core 变体解析到 `FlushInstructionCache` 并执行成功。
+#[test]
+#[cfg(target_os = "windows")]
+fn test_windows_sync_core_available() {
+    assert!(swmr_barrier::sync_core_available(), "FlushInstructionCache was not found");
+    let before = swmr_barrier::barrier_generation();
+    swmr_barrier::heavy_barrier_sync_core().expect("sync-core barrier failed");
+    assert!(swmr_barrier::barrier_generation() > before);
+}
+
+/// Every heavy barrier, from any thread, strictly increases the generation.
+#[test]
+#[cfg(target_has_atomic = "64")]
+fn test_barrier_generation_increases() {
+    use swmr_barrier::{barrier_generation, heavy_barrier_async_signal_safe};
+
+    let mut last = barrier_generation();
+    for _ in 0..100 {
+        heavy_barrier();
+        let now = barrier_generation();
+        assert!(now > last, "generation did not increase: {now} after {last}");
+        last = now;
+    }
+    heavy_barrier_async_signal_safe();
+    assert!(barrier_generation() > last);
+
+    // A reader that observed a publish after `light_barrier()` also observes the generation bump.
+    // 在 `light_barrier()` 之后观察到发布的读者也能观察到代数的递增。
+    let published = Arc::new(AtomicUsize::new(0));
+    let before = barrier_generation();
+    let reader = {
+        let published = published.clone();
+        thread::spawn(move || {
+            while published.load(Ordering::Relaxed) == 0 {
+                std::hint::spin_loop();
+            }
+            light_barrier();
+            barrier_generation()
+        })
+    };
+    heavy_barrier();
+    published.store(1, Ordering::Relaxed);
+    assert!(reader.join().unwrap() > before);
+}
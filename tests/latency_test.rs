@@ -0,0 +1,69 @@
+//! Tests for the membarrier latency histogram (Linux).
+//!
+//! These tests reset process-global counters through the `testing` hooks,
+//! so they live in their own binary and are serialized with a lock.
+//!
+//! Run with: `cargo test --features testing,latency --test latency_test`
+
+#![cfg(all(
+    target_os = "linux",
+    feature = "testing",
+    feature = "latency",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+
+use std::sync::Mutex;
+use std::time::Duration;
+use swmr_barrier::{LATENCY_BUCKETS, barrier_latency_histogram, heavy_barrier, max_barrier_latency_ns, testing};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Each accelerated heavy barrier lands in exactly one bucket.
+#[test]
+fn test_barriers_populate_histogram() {
+    let _guard = LOCK.lock().unwrap();
+    testing::reset_for_test();
+    assert_eq!(barrier_latency_histogram(), [0; LATENCY_BUCKETS]);
+    assert_eq!(max_barrier_latency_ns(), 0);
+
+    for _ in 0..10 {
+        heavy_barrier();
+    }
+    let recorded: u64 = barrier_latency_histogram().iter().sum();
+    if swmr_barrier::is_accelerated() {
+        assert_eq!(recorded, 10);
+        assert!(max_barrier_latency_ns() > 0);
+    } else {
+        // The fence fallback never reaches the syscall.
+        // 栅栏回退从不进入系统调用。
+        assert_eq!(recorded, 0);
+    }
+}
+
+/// A slow membarrier raises the maximum and lands in a bucket covering its duration.
+#[test]
+fn test_slow_barrier_recorded() {
+    let _guard = LOCK.lock().unwrap();
+    testing::reset_for_test();
+    if !swmr_barrier::is_accelerated() {
+        return;
+    }
+
+    testing::inject_barrier_delay(Duration::from_millis(5), 1);
+    heavy_barrier();
+    let max = max_barrier_latency_ns();
+    assert!(max >= 5_000_000, "max = {max} ns");
+
+    // 5 ms lies in [2^22, 2^23) ns, bucket 13, unless preemption pushed it further.
+    // 5 毫秒位于 [2^22, 2^23) 纳秒，即桶 13，除非抢占使其落入更高的桶。
+    let histogram = barrier_latency_histogram();
+    assert_eq!(histogram.iter().sum::<u64>(), 1);
+    assert_eq!(histogram[..13].iter().sum::<u64>(), 0, "{histogram:?}");
+
+    // A fast barrier afterwards leaves the maximum alone.
+    // 之后的快速屏障不改变最大值。
+    heavy_barrier();
+    assert_eq!(max_barrier_latency_ns(), max);
+    assert_eq!(barrier_latency_histogram().iter().sum::<u64>(), 2);
+}
@@ -47,3 +47,825 @@ fn test_heavy_light_barrier_ordering() {
         });
     });
 }
+
+/// Two independent (data, version) pairs, each published behind its own heavy barrier, as two
+/// separate SWMR structures would. A reader that sees the second version after one light barrier
+/// must also see the first pair: the barriers are process-wide, so the ordering composes.
+#[test]
+#[cfg(feature = "loom")]
+fn test_independent_heavy_barriers_compose() {
+    loom::model(|| {
+        let a = Arc::new((AtomicUsize::new(0), AtomicUsize::new(0)));
+        let b = Arc::new((AtomicUsize::new(0), AtomicUsize::new(0)));
+
+        let (wa, wb) = (a.clone(), b.clone());
+        thread::spawn(move || {
+            // Structure A publishes.
+            wa.0.store(1, Ordering::Relaxed);
+            heavy_barrier();
+            wa.1.store(1, Ordering::Relaxed);
+            // Structure B publishes, with its own barrier.
+            wb.0.store(1, Ordering::Relaxed);
+            heavy_barrier();
+            wb.1.store(1, Ordering::Relaxed);
+        });
+
+        let version_b = b.1.load(Ordering::Relaxed);
+        light_barrier();
+        let (data_a, version_a, data_b) = (a.0.load(Ordering::Relaxed), a.1.load(Ordering::Relaxed), b.0.load(Ordering::Relaxed));
+        if version_b == 1 {
+            assert_eq!(data_b, 1, "Violation: saw B's version without B's data");
+            assert_eq!(version_a, 1, "Violation: saw B published but not A, published earlier");
+            assert_eq!(data_a, 1, "Violation: saw B published but not A's data");
+        }
+    });
+}
+
+/// Guards never observe a torn or overwritten buffer: loom's `UnsafeCell` tracking fails the
+/// model if the writer writes into a buffer that a live guard still references.
+#[test]
+#[cfg(feature = "loom")]
+fn test_double_buffer_snapshot_guard() {
+    use swmr_barrier::DoubleBuffer;
+
+    loom::model(|| {
+        let buffer = Arc::new(DoubleBuffer::new((0usize, 0usize)));
+
+        let writer = buffer.clone();
+        let w = thread::spawn(move || {
+            writer.commit((1, 1));
+            writer.commit((2, 2));
+        });
+
+        let guard = buffer.borrow_snapshot();
+        let (a, b) = *guard;
+        assert_eq!(a, b, "Violation: guard observed a partially committed value");
+        assert!(a <= 2);
+        // The value must stay stable for the guard's lifetime.
+        assert_eq!(*guard, (a, b));
+        drop(guard);
+
+        w.join().unwrap();
+        assert_eq!(*buffer.borrow_snapshot(), (2, 2));
+    });
+}
+
+/// A three-slot cell never hands out a torn value, including when the writer wraps around to the
+/// slot the reader started from.
+#[test]
+#[cfg(feature = "loom")]
+fn test_swmr_cell_three_slots() {
+    use swmr_barrier::SwmrCell;
+
+    loom::model(|| {
+        let cell = Arc::new(SwmrCell::<(usize, usize), 3>::with_slots((0, 0)));
+
+        let reader = cell.clone();
+        let r = thread::spawn(move || {
+            let (a, b) = reader.get();
+            assert_eq!(a, b, "Violation: reader observed a torn value");
+            assert!(a <= 3);
+        });
+
+        for i in 1..=3 {
+            cell.set((i, i));
+        }
+
+        r.join().unwrap();
+        assert_eq!(cell.get(), (3, 3));
+    });
+}
+
+/// A reader concurrent with two `update`s sees them in order and never a torn value.
+#[test]
+#[cfg(feature = "loom")]
+fn test_swmr_cell_update() {
+    use swmr_barrier::SwmrCell;
+
+    loom::model(|| {
+        let cell = Arc::new(SwmrCell::new((0usize, 0usize)));
+
+        let reader = cell.clone();
+        let r = thread::spawn(move || {
+            let (a, b) = reader.get();
+            let (c, d) = reader.get();
+            assert_eq!((a, c), (b, d), "Violation: reader observed a torn value");
+            assert!(a <= c && c <= 2);
+        });
+
+        cell.update(|(a, b)| (a + 1, b + 1));
+        cell.update(|(a, b)| (a + 1, b + 1));
+
+        r.join().unwrap();
+        assert_eq!(cell.get(), (2, 2));
+    });
+}
+
+/// `read_if_newer` returns each published value at most once and never a stale one after a newer.
+#[test]
+#[cfg(feature = "loom")]
+fn test_swmr_cell_reader_read_if_newer() {
+    use swmr_barrier::SwmrCell;
+
+    loom::model(|| {
+        let cell = Arc::new(SwmrCell::new(0usize));
+
+        let reader = cell.clone();
+        let r = thread::spawn(move || {
+            let mut reader = reader.reader();
+            let first = reader.read_if_newer().expect("the first poll always returns the value");
+            match reader.read_if_newer() {
+                Some(second) => assert!(first == 0 && second == 1, "Violation: {first} then {second}"),
+                None => assert!(first <= 1),
+            }
+        });
+
+        cell.set(1);
+        r.join().unwrap();
+    });
+}
+
+/// The writer never overwrites data that a reader inside a critical section may still read.
+#[test]
+#[cfg(feature = "loom")]
+fn test_rcu_synchronize() {
+    use loom::cell::UnsafeCell;
+    use loom::sync::atomic::AtomicBool;
+    use swmr_barrier::RcuDomain;
+
+    struct Data(UnsafeCell<usize>);
+    // SAFETY: Access is serialized by the RCU protocol under test.
+    unsafe impl Sync for Data {}
+
+    loom::model(|| {
+        let domain = Arc::new(RcuDomain::<1>::new());
+        let published = Arc::new(AtomicBool::new(true));
+        let data = Arc::new(Data(UnsafeCell::new(1usize)));
+
+        let (domain1, published1, data1) = (domain.clone(), published.clone(), data.clone());
+        let writer = thread::spawn(move || {
+            published1.store(false, Ordering::Relaxed);
+            domain1.synchronize();
+            data1.0.with_mut(|ptr| unsafe { *ptr = 0 });
+        });
+
+        let mut reader = domain.register_reader().unwrap();
+        {
+            let _guard = reader.read_lock();
+            if published.load(Ordering::Relaxed) {
+                let value = data.0.with(|ptr| unsafe { *ptr });
+                assert_eq!(value, 1, "Violation: read reclaimed data inside a critical section");
+            }
+        }
+        drop(reader);
+
+        writer.join().unwrap();
+    });
+}
+
+/// Once `wait_for_grace_period` returns, the unpublished node can be reclaimed: no reader that
+/// loaded the old pointer is still dereferencing it.
+#[test]
+#[cfg(feature = "loom")]
+fn test_rcu_wait_for_grace_period_reclaim() {
+    use loom::cell::UnsafeCell;
+    use loom::sync::atomic::AtomicPtr;
+    use swmr_barrier::{RcuDomain, wait_for_grace_period};
+
+    const FREED: usize = usize::MAX;
+
+    struct Node(UnsafeCell<usize>);
+    // SAFETY: Access is serialized by the RCU protocol under test.
+    unsafe impl Sync for Node {}
+
+    loom::model(|| {
+        let domain = Arc::new(RcuDomain::<1>::new());
+        let nodes = Arc::new([Node(UnsafeCell::new(1)), Node(UnsafeCell::new(2))]);
+        let current = Arc::new(AtomicPtr::new(&nodes[0] as *const Node as *mut Node));
+
+        let (domain1, nodes1, current1) = (domain.clone(), nodes.clone(), current.clone());
+        let writer = thread::spawn(move || {
+            let old = current1.swap(&nodes1[1] as *const Node as *mut Node, Ordering::Relaxed);
+            wait_for_grace_period(&*domain1);
+            // "Free" the old node by poisoning it; loom flags any read still in flight.
+            // SAFETY: The grace period has ended, so no reader holds `old`.
+            unsafe { (*old).0.with_mut(|ptr| *ptr = FREED) };
+        });
+
+        let mut reader = domain.register_reader().unwrap();
+        {
+            let _guard = reader.read_lock();
+            let node = current.load(Ordering::Relaxed);
+            // SAFETY: `node` is not reclaimed while the critical section lasts.
+            let value = unsafe { (*node).0.with(|ptr| *ptr) };
+            assert_ne!(value, FREED, "Violation: read a node reclaimed inside a critical section");
+        }
+        drop(reader);
+
+        writer.join().unwrap();
+    });
+}
+
+/// `AsymAtomic<T>`: a reader whose `consume()` returns the published value sees the payload
+/// written before `publish()`, for every supported type.
+macro_rules! asym_atomic_loom_tests {
+    ($($name:ident: $ty:ty = $initial:expr => $published:expr;)*) => {$(
+        #[test]
+        #[cfg(feature = "loom")]
+        fn $name() {
+            use swmr_barrier::AsymAtomic;
+
+            loom::model(|| {
+                let payload = Arc::new(AtomicUsize::new(0));
+                let atomic = Arc::new(AsymAtomic::<$ty>::new($initial));
+
+                let (payload1, atomic1) = (payload.clone(), atomic.clone());
+                thread::spawn(move || {
+                    payload1.store(1, Ordering::Relaxed);
+                    atomic1.publish($published);
+                });
+
+                if atomic.consume() == $published {
+                    assert_eq!(
+                        payload.load(Ordering::Relaxed),
+                        1,
+                        concat!("Violation: consumed ", stringify!($ty), " but stale payload")
+                    );
+                }
+            });
+        }
+    )*};
+}
+
+asym_atomic_loom_tests! {
+    test_asym_atomic_u8: u8 = 0 => u8::MAX;
+    test_asym_atomic_u16: u16 = 0 => u16::MAX;
+    test_asym_atomic_u32: u32 = 0 => u32::MAX;
+    test_asym_atomic_u64: u64 = 0 => u64::MAX;
+    test_asym_atomic_usize: usize = 0 => usize::MAX;
+    test_asym_atomic_bool: bool = false => true;
+    test_asym_atomic_ptr: *mut u8 = std::ptr::null_mut() => std::ptr::dangling_mut::<u8>();
+}
+
+/// The publish/consume macros place the barriers so a reader that sees the flag sees every field.
+#[test]
+#[cfg(feature = "loom")]
+fn test_publish_consume_macros() {
+    use swmr_barrier::{swmr_consume, swmr_publish};
+
+    loom::model(|| {
+        let data = Arc::new((AtomicUsize::new(0), AtomicUsize::new(0)));
+        let ready = Arc::new(AtomicUsize::new(0));
+
+        let (data1, ready1) = (data.clone(), ready.clone());
+        thread::spawn(move || {
+            swmr_publish!(data1.0.store(1, Ordering::Relaxed); data1.1.store(2, Ordering::Relaxed));
+            ready1.store(1, Ordering::Relaxed);
+        });
+
+        if ready.load(Ordering::Relaxed) == 1 {
+            let (a, b) = swmr_consume!(data.0.load(Ordering::Relaxed), data.1.load(Ordering::Relaxed));
+            assert_eq!((a, b), (1, 2), "Violation: saw ready flag but stale fields");
+        }
+    });
+}
+
+/// `publish_store_*`: store payload -> publish flag. A reader that sees the flag and issues
+/// `light_barrier()` must see the payload.
+#[test]
+#[cfg(feature = "loom")]
+fn test_publish_store_u64() {
+    use loom::sync::atomic::AtomicU64;
+    use swmr_barrier::publish_store_u64;
+
+    loom::model(|| {
+        let payload = Arc::new(AtomicUsize::new(0));
+        let flag = Arc::new(AtomicU64::new(0));
+
+        let (payload1, flag1) = (payload.clone(), flag.clone());
+        thread::spawn(move || {
+            payload1.store(1, Ordering::Relaxed);
+            publish_store_u64(&flag1, u64::MAX);
+        });
+
+        if flag.load(Ordering::Relaxed) == u64::MAX {
+            light_barrier();
+            assert_eq!(payload.load(Ordering::Relaxed), 1, "Violation: saw u64 flag but stale payload");
+        }
+    });
+}
+
+#[test]
+#[cfg(feature = "loom")]
+fn test_publish_store_usize() {
+    use loom::sync::atomic::AtomicUsize;
+    use swmr_barrier::publish_store_usize;
+
+    loom::model(|| {
+        let payload = Arc::new(AtomicUsize::new(0));
+        let flag = Arc::new(AtomicUsize::new(0));
+
+        let (payload1, flag1) = (payload.clone(), flag.clone());
+        thread::spawn(move || {
+            payload1.store(1, Ordering::Relaxed);
+            publish_store_usize(&flag1, 2);
+        });
+
+        if flag.load(Ordering::Relaxed) == 2 {
+            light_barrier();
+            assert_eq!(payload.load(Ordering::Relaxed), 1, "Violation: saw usize flag but stale payload");
+        }
+    });
+}
+
+#[test]
+#[cfg(feature = "loom")]
+fn test_publish_store_bool() {
+    use loom::sync::atomic::AtomicBool;
+    use swmr_barrier::publish_store_bool;
+
+    loom::model(|| {
+        let payload = Arc::new(AtomicUsize::new(0));
+        let flag = Arc::new(AtomicBool::new(false));
+
+        let (payload1, flag1) = (payload.clone(), flag.clone());
+        thread::spawn(move || {
+            payload1.store(1, Ordering::Relaxed);
+            publish_store_bool(&flag1, true);
+        });
+
+        if flag.load(Ordering::Relaxed) {
+            light_barrier();
+            assert_eq!(payload.load(Ordering::Relaxed), 1, "Violation: saw bool flag but stale payload");
+        }
+    });
+}
+
+#[test]
+#[cfg(feature = "loom")]
+fn test_publish_store_ptr() {
+    use loom::sync::atomic::AtomicPtr;
+    use swmr_barrier::publish_store_ptr;
+
+    loom::model(|| {
+        let payload = Arc::new(AtomicUsize::new(0));
+        let target = Arc::new(0u8);
+        let flag = Arc::new(AtomicPtr::new(std::ptr::null_mut::<u8>()));
+
+        let (payload1, flag1, target1) = (payload.clone(), flag.clone(), target.clone());
+        thread::spawn(move || {
+            payload1.store(1, Ordering::Relaxed);
+            publish_store_ptr(&flag1, Arc::as_ptr(&target1) as *mut u8);
+        });
+
+        if !flag.load(Ordering::Relaxed).is_null() {
+            light_barrier();
+            assert_eq!(payload.load(Ordering::Relaxed), 1, "Violation: saw ptr flag but stale payload");
+        }
+    });
+}
+
+/// A cursor only reads entries the writer has finished: loom's `UnsafeCell` tracking fails the
+/// model if a read overlaps the write of the same entry.
+#[test]
+#[cfg(feature = "loom")]
+fn test_append_log_cursor() {
+    use swmr_barrier::AppendLog;
+
+    loom::model(|| {
+        let log = Arc::new(AppendLog::<usize, 2>::new());
+
+        // The reader runs on a spawned thread so loom also explores it racing ahead of the writer.
+        let reader = {
+            let log = log.clone();
+            thread::spawn(move || {
+                let seen: Vec<_> = log.cursor().collect();
+                assert_eq!(seen[..], [10, 20][..seen.len()], "Violation: cursor returned wrong entries");
+            })
+        };
+
+        log.push(10).unwrap();
+        log.push(20).unwrap();
+        reader.join().unwrap();
+    });
+}
+
+/// Data written before a deferred request, and published once the ticket completes, is visible
+/// to a reader after `light_barrier()`; the heavy barrier runs on a third thread.
+#[test]
+#[cfg(feature = "loom")]
+fn test_deferred_barrier() {
+    use swmr_barrier::DeferredBarrier;
+
+    loom::model(|| {
+        let deferred = Arc::new(DeferredBarrier::new());
+        let data = Arc::new(AtomicUsize::new(0));
+        let ready = Arc::new(AtomicUsize::new(0));
+
+        let server = {
+            let deferred = deferred.clone();
+            thread::spawn(move || {
+                while !deferred.service() {
+                    thread::yield_now();
+                }
+            })
+        };
+
+        let reader = {
+            let (data, ready) = (data.clone(), ready.clone());
+            thread::spawn(move || {
+                if ready.load(Ordering::Relaxed) == 1 {
+                    light_barrier();
+                    assert_eq!(data.load(Ordering::Relaxed), 1, "Violation: saw ready flag but stale data");
+                }
+            })
+        };
+
+        data.store(1, Ordering::Relaxed);
+        let ticket = deferred.request();
+        while !deferred.is_complete(ticket) {
+            thread::yield_now();
+        }
+        ready.store(1, Ordering::Relaxed);
+
+        server.join().unwrap();
+        reader.join().unwrap();
+    });
+}
+
+/// Three stores, each with its own deferred request, are covered by a single `service()`. A reader
+/// that sees the commit marker sees all three; one that does not may see any subset.
+#[test]
+#[cfg(feature = "loom")]
+fn test_deferred_barrier_coalescing() {
+    use swmr_barrier::DeferredBarrier;
+
+    loom::model(|| {
+        let deferred = Arc::new(DeferredBarrier::new());
+        let data: Arc<[AtomicUsize; 3]> = Arc::new(core::array::from_fn(|_| AtomicUsize::new(0)));
+        let committed = Arc::new(AtomicUsize::new(0));
+
+        let readers: Vec<_> = (0..2)
+            .map(|_| {
+                let (data, committed) = (data.clone(), committed.clone());
+                thread::spawn(move || {
+                    let marker = committed.load(Ordering::Relaxed);
+                    light_barrier();
+                    let seen = data.each_ref().map(|d| d.load(Ordering::Relaxed));
+                    if marker == 1 {
+                        assert_eq!(seen, [1, 1, 1], "Violation: saw commit marker but not every store");
+                    }
+                })
+            })
+            .collect();
+
+        let tickets = data.each_ref().map(|d| {
+            d.store(1, Ordering::Relaxed);
+            deferred.request()
+        });
+        assert!(deferred.service(), "the batch should be pending");
+        assert!(!deferred.service(), "one service should cover the whole batch");
+        assert!(tickets.iter().all(|&t| deferred.is_complete(t)));
+        committed.store(1, Ordering::Relaxed);
+
+        for reader in readers {
+            reader.join().unwrap();
+        }
+    });
+}
+
+/// Runs the store-buffering pattern (store, barrier, load on both sides) under `ordering` and
+/// reports whether any execution let both loads miss the other side's store.
+#[cfg(feature = "loom")]
+fn store_buffering_both_missed(ordering: swmr_barrier::FallbackOrdering) -> bool {
+    use std::sync::atomic::AtomicBool;
+
+    // The setting is per test thread under Loom, so this does not leak into other tests.
+    // SAFETY: Only the raw atomics below are shared, and reporting the missed loads is the point.
+    unsafe { swmr_barrier::set_fallback_ordering(ordering) };
+    let missed = Arc::new(AtomicBool::new(false));
+    let seen = missed.clone();
+    loom::model(move || {
+        let x = Arc::new(AtomicUsize::new(0));
+        let y = Arc::new(AtomicUsize::new(0));
+
+        let (x1, y1) = (x.clone(), y.clone());
+        let writer = thread::spawn(move || {
+            x1.store(1, Ordering::Relaxed);
+            heavy_barrier();
+            y1.load(Ordering::Relaxed)
+        });
+
+        y.store(1, Ordering::Relaxed);
+        light_barrier();
+        let r2 = x.load(Ordering::Relaxed);
+        let r1 = writer.join().unwrap();
+        if r1 == 0 && r2 == 0 {
+            seen.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+    });
+    // SAFETY: `SeqCst` is always sound.
+    unsafe { swmr_barrier::set_fallback_ordering(swmr_barrier::FallbackOrdering::SeqCst) };
+    missed.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// `SeqCst` fallback fences preserve store-buffering; `AcqRel` ones do not, as documented on
+/// `set_fallback_ordering`.
+#[test]
+#[cfg(feature = "loom")]
+fn test_fallback_ordering_store_buffering() {
+    use swmr_barrier::FallbackOrdering;
+
+    assert!(!store_buffering_both_missed(FallbackOrdering::SeqCst));
+    assert!(
+        store_buffering_both_missed(FallbackOrdering::AcqRel),
+        "AcqRel fences should allow both loads to miss"
+    );
+}
+
+/// `AcqRel` fallback fences still preserve message passing (store data, heavy, store flag / load
+/// flag, light, load data).
+#[test]
+#[cfg(feature = "loom")]
+fn test_fallback_acq_rel_message_passing() {
+    use swmr_barrier::FallbackOrdering;
+
+    // SAFETY: The model below is message passing only.
+    unsafe { swmr_barrier::set_fallback_ordering(FallbackOrdering::AcqRel) };
+    loom::model(|| {
+        assert_eq!(swmr_barrier::fallback_ordering(), FallbackOrdering::AcqRel);
+        let data = Arc::new(AtomicUsize::new(0));
+        let flag = Arc::new(AtomicUsize::new(0));
+
+        let (d1, f1) = (data.clone(), flag.clone());
+        let writer = thread::spawn(move || {
+            // Loom threads run on the test thread, so they share its setting.
+            assert_eq!(swmr_barrier::fallback_ordering(), FallbackOrdering::AcqRel);
+            d1.store(1, Ordering::Relaxed);
+            heavy_barrier();
+            f1.store(1, Ordering::Relaxed);
+        });
+
+        if flag.load(Ordering::Relaxed) == 1 {
+            light_barrier();
+            assert_eq!(data.load(Ordering::Relaxed), 1, "Violation: saw the flag but not the data");
+        }
+        writer.join().unwrap();
+    });
+    // SAFETY: `SeqCst` is always sound.
+    unsafe { swmr_barrier::set_fallback_ordering(FallbackOrdering::SeqCst) };
+}
+
+/// Runs store-buffering with a writer `fence(SeqCst)`, the part of a membarrier Loom can model,
+/// against a reader that fences or not, and reports whether both loads could miss.
+#[cfg(feature = "loom")]
+fn init_race_both_missed(reader_fences: bool) -> bool {
+    use loom::sync::atomic::fence;
+    use std::sync::atomic::AtomicBool;
+
+    let missed = Arc::new(AtomicBool::new(false));
+    let seen = missed.clone();
+    loom::model(move || {
+        let x = Arc::new(AtomicUsize::new(0));
+        let y = Arc::new(AtomicUsize::new(0));
+
+        let (x1, y1) = (x.clone(), y.clone());
+        let writer = thread::spawn(move || {
+            x1.store(1, Ordering::Relaxed);
+            fence(Ordering::SeqCst);
+            y1.load(Ordering::Relaxed)
+        });
+
+        y.store(1, Ordering::Relaxed);
+        if reader_fences {
+            fence(Ordering::SeqCst);
+        }
+        let r2 = x.load(Ordering::Relaxed);
+        let r1 = writer.join().unwrap();
+        if r1 == 0 && r2 == 0 {
+            seen.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+    });
+    missed.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// The init race on Linux: a reader that still fences is safe against a writer that already uses
+/// membarrier, but a reader that skips the fence is not safe against a writer that still fences.
+/// This is why readers only accelerate after a confirming barrier.
+#[test]
+#[cfg(feature = "loom")]
+fn test_init_race_mismatch() {
+    assert!(!init_race_both_missed(true), "reader fence + writer barrier must order both sides");
+    assert!(
+        init_race_both_missed(false),
+        "a compiler-only reader against a fencing writer should allow both loads to miss"
+    );
+}
+
+/// Inner barrier for the tracked-barrier model: counts `heavy()` calls and gives `light()` no
+/// ordering at all, so only the tracker's own fences can order a skipped publish.
+#[cfg(feature = "loom")]
+struct CountingFence {
+    heavy: Arc<AtomicUsize>,
+}
+
+// SAFETY: Test-only; the model asserts nothing once `heavy()` ran, where `light()` is too weak.
+// 安全性：仅用于测试；`heavy()` 执行后模型不做断言，因为此时 `light()` 太弱。
+#[cfg(feature = "loom")]
+unsafe impl swmr_barrier::AsymmetricBarrier for CountingFence {
+    fn heavy(&self) {
+        self.heavy.fetch_add(1, Ordering::Relaxed);
+        loom::sync::atomic::fence(Ordering::SeqCst);
+    }
+
+    fn light(&self) {}
+
+    fn accelerated(&self) -> bool {
+        false
+    }
+}
+
+/// A writer that skips the inner heavy barrier because no reader was registered still publishes
+/// to a reader that registers concurrently: the paired `SeqCst` fences order the data store before
+/// every load after `register()` returns.
+#[test]
+#[cfg(feature = "loom")]
+fn test_tracked_barrier_skip() {
+    use swmr_barrier::{AsymmetricBarrier, TrackedBarrier};
+
+    loom::model(|| {
+        let heavy_calls = Arc::new(AtomicUsize::new(0));
+        let tracked = Arc::new(TrackedBarrier::with_barrier(CountingFence { heavy: heavy_calls.clone() }));
+        let data = Arc::new(AtomicUsize::new(0));
+        let ready = Arc::new(AtomicUsize::new(0));
+
+        let reader = {
+            let (tracked, data, ready) = (tracked.clone(), data.clone(), ready.clone());
+            thread::spawn(move || {
+                let _registration = tracked.register();
+                let flag = ready.load(Ordering::Relaxed);
+                tracked.light();
+                (flag, data.load(Ordering::Relaxed))
+            })
+        };
+
+        data.store(1, Ordering::Relaxed);
+        tracked.heavy();
+        let skipped = heavy_calls.load(Ordering::Relaxed) == 0;
+        ready.store(1, Ordering::Relaxed);
+
+        // The reader may also have finished and unregistered before the publish, so only a reader
+        // that saw the flag is checked.
+        // 读者也可能在发布之前就已结束并注销，因此只检查看到标志的读者。
+        let (flag, seen) = reader.join().unwrap();
+        if skipped && flag == 1 {
+            assert_eq!(seen, 1, "Violation: saw ready flag but stale data after a skipped heavy barrier");
+        }
+    });
+}
+
+/// Samples a reader gets from an `OverwriteRing` belong to their sequence, and retired samples are
+/// reported as a gap. Two pushes into two slots retire the first without rewriting it; Loom would
+/// flag an actual lap as a race on the slot, which the ring only detects after copying.
+#[test]
+#[cfg(feature = "loom")]
+fn test_overwrite_ring() {
+    use swmr_barrier::OverwriteRing;
+
+    loom::model(|| {
+        let ring = Arc::new(OverwriteRing::<usize, 2>::new());
+
+        let reader = {
+            let ring = ring.clone();
+            thread::spawn(move || {
+                let mut cursor = 0;
+                let mut samples = ring.read_available(&mut cursor);
+                let mut seen = 0;
+                for (seq, value) in samples.by_ref() {
+                    assert_eq!(value as u64, seq + 100, "Violation: sample does not match its sequence");
+                    seen += 1;
+                }
+                let gap = samples.gap();
+                assert_eq!(seen + gap, cursor, "Violation: cursor advanced past unreported samples");
+            })
+        };
+
+        ring.push(100);
+        ring.push(101);
+        reader.join().unwrap();
+    });
+}
+
+/// A model of one `membarrier` call, for checking the heavy path's local ordering. The kernel runs
+/// `smp_mb()` on the caller, interrupts every CPU running a reader so it executes a full barrier,
+/// and waits for those interrupts to complete before returning. The crate adds only
+/// `compiler_fence(SeqCst)` after the call, which Loom models as nothing.
+#[cfg(feature = "loom")]
+struct MembarrierModel {
+    requested: AtomicUsize,
+    acked: AtomicUsize,
+    exited: AtomicUsize,
+}
+
+#[cfg(feature = "loom")]
+impl MembarrierModel {
+    fn new() -> Self {
+        Self { requested: AtomicUsize::new(0), acked: AtomicUsize::new(0), exited: AtomicUsize::new(0) }
+    }
+
+    /// The writer's syscall. `wait` is false for the negative control: return without waiting
+    /// for the interrupts, leaving nothing but the caller's own fence.
+    fn heavy(&self, wait: bool) {
+        loom::sync::atomic::fence(Ordering::SeqCst);
+        self.requested.store(1, Ordering::Relaxed);
+        while wait && self.acked.load(Ordering::Acquire) == 0 && self.exited.load(Ordering::Acquire) == 0 {
+            thread::yield_now();
+        }
+    }
+
+    /// An interrupt point on the reader, placed between each of its accesses.
+    fn interrupt_point(&self) {
+        if self.requested.load(Ordering::Relaxed) == 1 && self.acked.load(Ordering::Relaxed) == 0 {
+            loom::sync::atomic::fence(Ordering::SeqCst);
+            self.acked.store(1, Ordering::Release);
+        }
+    }
+
+    /// The reader thread ends; a thread that no longer runs needs no interrupt.
+    fn exit(&self) {
+        self.interrupt_point();
+        self.exited.store(1, Ordering::Release);
+    }
+}
+
+/// Runs message passing and store buffering over [`MembarrierModel`] with a compiler-only reader
+/// and no local writer fence after the call, and reports whether either pattern was violated.
+#[cfg(feature = "loom")]
+fn membarrier_local_ordering_violated(wait: bool) -> bool {
+    use std::sync::atomic::AtomicBool;
+
+    let violated = Arc::new(AtomicBool::new(false));
+    let seen = violated.clone();
+    loom::model(move || {
+        let barrier = Arc::new(MembarrierModel::new());
+        let (data, flag) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+
+        let reader = {
+            let (barrier, data, flag) = (barrier.clone(), data.clone(), flag.clone());
+            thread::spawn(move || {
+                barrier.interrupt_point();
+                let f = flag.load(Ordering::Relaxed);
+                barrier.interrupt_point();
+                let d = data.load(Ordering::Relaxed);
+                barrier.exit();
+                (f, d)
+            })
+        };
+
+        data.store(1, Ordering::Relaxed);
+        barrier.heavy(wait);
+        flag.store(1, Ordering::Relaxed);
+        if reader.join().unwrap() == (1, 0) {
+            seen.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+    });
+    let seen = violated.clone();
+    loom::model(move || {
+        let barrier = Arc::new(MembarrierModel::new());
+        let (x, y) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+
+        let reader = {
+            let (barrier, x, y) = (barrier.clone(), x.clone(), y.clone());
+            thread::spawn(move || {
+                barrier.interrupt_point();
+                y.store(1, Ordering::Relaxed);
+                barrier.interrupt_point();
+                let r2 = x.load(Ordering::Relaxed);
+                barrier.exit();
+                r2
+            })
+        };
+
+        x.store(1, Ordering::Relaxed);
+        barrier.heavy(wait);
+        let r1 = y.load(Ordering::Relaxed);
+        if r1 == 0 && reader.join().unwrap() == 0 {
+            seen.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+    });
+    violated.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Regression test for the heavy path's local ordering: `compiler_fence(SeqCst)` after a
+/// successful membarrier suffices, because the call returns only after the remote barriers
+/// completed, and those already order the writer's accesses on both sides of it. A writer that did
+/// not wait for them would need more, which the negative control shows the model catches.
+#[test]
+#[cfg(feature = "loom")]
+fn test_membarrier_local_ordering() {
+    assert!(!membarrier_local_ordering_violated(true), "a completed membarrier must order both patterns");
+    assert!(
+        membarrier_local_ordering_violated(false),
+        "a membarrier that does not wait for its interrupts should be caught by the model"
+    );
+}
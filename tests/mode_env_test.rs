@@ -0,0 +1,138 @@
+//! Tests for the `SWMR_BARRIER_MODE` backend override (Linux).
+//!
+//! These tests mutate the process environment and global barrier state,
+//! so they live in their own binary and are serialized with a lock.
+//!
+//! Run with: `cargo test --features testing,std --test mode_env_test`
+
+#![cfg(all(
+    target_os = "linux",
+    feature = "testing",
+    feature = "std",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+
+use std::sync::Mutex;
+use swmr_barrier::{Backend, BarrierMode, InitReason, MODE_ENV_VAR, ModeWarning, testing};
+
+static LOCK: Mutex<()> = Mutex::new(());
+static WARNINGS: Mutex<Vec<ModeWarning>> = Mutex::new(Vec::new());
+
+fn record(warning: &ModeWarning) {
+    WARNINGS.lock().unwrap().push(warning.clone());
+}
+
+/// Sets `SWMR_BARRIER_MODE` (or removes it with `None`) and re-runs detection from scratch.
+fn detect_with(mode: Option<&str>) {
+    // SAFETY: Every test that touches the environment holds `LOCK`, and nothing else reads it.
+    unsafe {
+        match mode {
+            Some(mode) => std::env::set_var(MODE_ENV_VAR, mode),
+            None => std::env::remove_var(MODE_ENV_VAR),
+        }
+    }
+    testing::reset_for_test();
+    WARNINGS.lock().unwrap().clear();
+    swmr_barrier::set_mode_warning_hook(Some(record));
+    swmr_barrier::redetect();
+}
+
+/// Whether this kernel provides membarrier at all (nothing to override otherwise).
+fn kernel_supported() -> bool {
+    detect_with(None);
+    if !swmr_barrier::is_accelerated() {
+        eprintln!("membarrier unavailable; skipping");
+        return false;
+    }
+    true
+}
+
+#[test]
+fn test_parse_modes() {
+    assert_eq!(BarrierMode::parse("auto"), Some(BarrierMode::Auto));
+    assert_eq!(BarrierMode::parse(" Private\n"), Some(BarrierMode::Private));
+    assert_eq!(BarrierMode::parse("SHARED"), Some(BarrierMode::Shared));
+    assert_eq!(BarrierMode::parse("fallback"), Some(BarrierMode::Fallback));
+    assert_eq!(BarrierMode::parse("expedited"), None);
+    for mode in [BarrierMode::Auto, BarrierMode::Private, BarrierMode::Shared, BarrierMode::Fallback] {
+        assert_eq!(BarrierMode::parse(mode.as_str()), Some(mode));
+    }
+}
+
+#[test]
+fn test_fallback_disables_acceleration() {
+    let _guard = LOCK.lock().unwrap();
+    if !kernel_supported() {
+        return;
+    }
+
+    detect_with(Some("fallback"));
+    assert!(!swmr_barrier::is_accelerated());
+    assert_eq!(swmr_barrier::active_backend(), Backend::Fallback);
+    assert_eq!(swmr_barrier::stats().init_reason, InitReason::ModeOverride);
+    assert!(WARNINGS.lock().unwrap().is_empty());
+    // Barriers stay correct on the fence path.
+    swmr_barrier::heavy_barrier();
+    swmr_barrier::light_barrier();
+
+    // The pre-`main` path honors it too.
+    testing::reinitialize();
+    assert!(!swmr_barrier::is_accelerated());
+
+    detect_with(None);
+    assert!(swmr_barrier::is_accelerated());
+}
+
+#[test]
+fn test_shared_pins_shared_command() {
+    let _guard = LOCK.lock().unwrap();
+    if !kernel_supported() {
+        return;
+    }
+
+    detect_with(Some("shared"));
+    let warnings = WARNINGS.lock().unwrap().clone();
+    if warnings.is_empty() {
+        assert_eq!(swmr_barrier::active_backend(), Backend::LinuxShared);
+    } else {
+        // SHARED is not advertised (e.g. `nohz_full` kernels); detection fell back to `auto`.
+        assert_eq!(warnings, [ModeWarning::Unavailable(BarrierMode::Shared)]);
+        assert!(swmr_barrier::is_accelerated());
+    }
+    detect_with(None);
+}
+
+#[test]
+fn test_unavailable_private_warns_and_continues() {
+    let _guard = LOCK.lock().unwrap();
+    if !kernel_supported() {
+        return;
+    }
+
+    const ENOMEM: i32 = 12;
+    // SAFETY: See `detect_with`.
+    unsafe { std::env::set_var(MODE_ENV_VAR, "private") };
+    testing::reset_for_test();
+    WARNINGS.lock().unwrap().clear();
+    swmr_barrier::set_mode_warning_hook(Some(record));
+    testing::inject_registration_failure(ENOMEM, 2);
+    swmr_barrier::redetect();
+
+    assert_eq!(*WARNINGS.lock().unwrap(), [ModeWarning::Unavailable(BarrierMode::Private)]);
+    assert_ne!(swmr_barrier::active_backend(), Backend::LinuxPrivateExpedited);
+    detect_with(None);
+}
+
+#[test]
+fn test_unrecognized_value_warns() {
+    let _guard = LOCK.lock().unwrap();
+    if !kernel_supported() {
+        return;
+    }
+
+    detect_with(Some("turbo"));
+    assert_eq!(*WARNINGS.lock().unwrap(), [ModeWarning::Unrecognized("turbo".into())]);
+    assert!(swmr_barrier::is_accelerated());
+    detect_with(None);
+}
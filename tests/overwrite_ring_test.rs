@@ -0,0 +1,110 @@
+//! Tests for `OverwriteRing` and `RingSamples`.
+
+#![cfg(not(feature = "loom"))]
+
+mod common;
+
+use common::PanickingBarrier;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use swmr_barrier::OverwriteRing;
+
+#[test]
+fn test_reader_sees_pushes_in_order() {
+    let ring = OverwriteRing::<u32, 4>::new();
+    assert_eq!(ring.capacity(), 3);
+    let mut cursor = 0;
+    assert_eq!(ring.read_available(&mut cursor).count(), 0);
+
+    ring.push(10);
+    ring.push(11);
+    let mut samples = ring.read_available(&mut cursor);
+    assert_eq!(samples.by_ref().collect::<Vec<_>>(), [(0, 10), (1, 11)]);
+    assert_eq!(samples.gap(), 0);
+    assert_eq!(cursor, 2);
+
+    ring.push(12);
+    assert_eq!(ring.read_available(&mut cursor).collect::<Vec<_>>(), [(2, 12)]);
+    assert_eq!(ring.pushed(), 3);
+}
+
+/// A reader that fell more than the capacity behind is told how many samples it missed and
+/// resumes from the oldest one still held.
+#[test]
+fn test_slow_reader_gets_gap() {
+    let ring = OverwriteRing::<u32, 4>::new();
+    let mut cursor = 0;
+    ring.push(0);
+    assert_eq!(ring.read_available(&mut cursor).collect::<Vec<_>>(), [(0, 0)]);
+
+    for value in 1..10 {
+        ring.push(value);
+    }
+    let mut samples = ring.read_available(&mut cursor);
+    assert_eq!(samples.gap(), 6);
+    assert_eq!(samples.by_ref().collect::<Vec<_>>(), [(7, 7), (8, 8), (9, 9)]);
+    assert_eq!(samples.gap(), 6);
+    assert_eq!(cursor, 10);
+
+    // Caught up again: no gap.
+    // 再次追上：没有缺口。
+    ring.push(10);
+    let mut samples = ring.read_available(&mut cursor);
+    assert_eq!(samples.next(), Some((10, 10)));
+    assert_eq!(samples.gap(), 0);
+}
+
+/// A reader starting from `pushed()` skips the backlog without reporting it as a gap.
+#[test]
+fn test_start_from_pushed() {
+    let ring = OverwriteRing::<u32, 8>::new();
+    for value in 0..5 {
+        ring.push(value);
+    }
+    let mut cursor = ring.pushed();
+    ring.push(5);
+    let mut samples = ring.read_available(&mut cursor);
+    assert_eq!(samples.by_ref().collect::<Vec<_>>(), [(5, 5)]);
+    assert_eq!(samples.gap(), 0);
+}
+
+/// A writer lapping a reader: every sample the reader gets is intact and belongs to its sequence,
+/// and the sequences plus the reported gaps account for every push.
+#[test]
+fn test_concurrent_overwrite() {
+    const PUSHES: u64 = 200_000;
+    let ring = OverwriteRing::<[u64; 4], 8>::new();
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            for seq in 0..PUSHES {
+                ring.push([seq; 4]);
+            }
+        });
+        s.spawn(|| {
+            let mut cursor = 0;
+            let (mut seen, mut skipped) = (0, 0);
+            while cursor < PUSHES {
+                let mut samples = ring.read_available(&mut cursor);
+                for (seq, value) in samples.by_ref() {
+                    assert_eq!(value, [seq; 4], "torn or misplaced sample");
+                    seen += 1;
+                }
+                skipped += samples.gap();
+            }
+            assert_eq!(seen + skipped, PUSHES);
+        });
+    });
+}
+
+/// A push whose barrier panics publishes nothing and does not wedge later pushes.
+#[test]
+fn test_panicking_barrier_does_not_wedge_push() {
+    let ring = OverwriteRing::<u32, 4, _>::with_barrier(PanickingBarrier::on_heavy(1));
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| ring.push(1))).is_err());
+    assert_eq!(ring.pushed(), 0);
+
+    ring.push(2);
+    let mut cursor = 0;
+    assert_eq!(ring.read_available(&mut cursor).collect::<Vec<_>>(), [(0, 2)]);
+}
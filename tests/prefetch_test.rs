@@ -0,0 +1,51 @@
+//! Tests for `prefetch_read` and the `prefetch` feature.
+
+#![cfg(not(feature = "loom"))]
+
+use std::thread;
+use swmr_barrier::{DoubleBuffer, SwmrCell, prefetch_read};
+
+/// A prefetch never faults and has no visible effect, on every target: valid, null, dangling,
+/// unaligned and freed pointers are all accepted.
+#[test]
+fn test_prefetch_any_pointer() {
+    let value = [7u64; 16];
+    prefetch_read(&value);
+    prefetch_read(value.as_ptr().wrapping_add(1000));
+    prefetch_read(core::ptr::null::<u64>());
+    prefetch_read(core::ptr::dangling::<[u8; 4096]>());
+    prefetch_read(value.as_ptr().cast::<u8>().wrapping_add(3).cast::<u64>());
+    let freed = Box::into_raw(Box::new(1u32));
+    drop(unsafe { Box::from_raw(freed) });
+    prefetch_read(freed);
+    assert_eq!(value, [7; 16]);
+}
+
+/// The slot-ring readers stay correct with the prefetch in their pin path (run with and without
+/// `--features prefetch`).
+#[test]
+fn test_readers_with_prefetch() {
+    const SETS: u64 = 50_000;
+    let cell = SwmrCell::new([0u64; 8]);
+    let buffer = DoubleBuffer::new([0u64; 8]);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            for i in 1..=SETS {
+                cell.set([i; 8]);
+                buffer.commit([i; 8]);
+            }
+        });
+        s.spawn(|| {
+            let (mut last_cell, mut last_buffer) = (0, 0);
+            while last_cell < SETS || last_buffer < SETS {
+                let value = cell.get();
+                assert!(value.iter().all(|&v| v == value[0]) && value[0] >= last_cell);
+                last_cell = value[0];
+                let value = buffer.read();
+                assert!(value.iter().all(|&v| v == value[0]) && value[0] >= last_buffer);
+                last_buffer = value[0];
+            }
+        });
+    });
+}
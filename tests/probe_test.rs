@@ -0,0 +1,15 @@
+//! Tests for `assert_ordering_once`.
+//!
+//! Run with: `cargo test --features std --test probe_test`
+
+#![cfg(all(feature = "std", not(feature = "loom")))]
+
+use swmr_barrier::assert_ordering_once;
+
+/// The barriers hold on every supported platform, so no round may report a violation.
+#[test]
+fn test_ordering_holds() {
+    for _ in 0..200 {
+        assert!(assert_ordering_once(), "store-buffering round saw neither store");
+    }
+}
@@ -0,0 +1,186 @@
+//! Tests for `RcuDomain` reader registration and grace periods.
+
+#![cfg(not(feature = "loom"))]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use swmr_barrier::{BarrierKind, DEFAULT_READER_SLOTS, RcuDomain, ReaderTableFull};
+
+#[test]
+fn test_default_capacity() {
+    let domain: RcuDomain = RcuDomain::new();
+    assert_eq!(domain.capacity(), DEFAULT_READER_SLOTS);
+}
+
+/// A table can be filled exactly to capacity.
+#[test]
+fn test_register_exactly_full() {
+    let domain = RcuDomain::<4>::new();
+    let readers: Vec<_> = (0..4).map(|_| domain.register_reader().unwrap()).collect();
+
+    let mut slots: Vec<_> = readers.iter().map(|r| r.slot()).collect();
+    slots.sort_unstable();
+    assert_eq!(slots, [0, 1, 2, 3]);
+}
+
+/// Registering one reader beyond capacity fails, and succeeds again once a slot is freed.
+#[test]
+fn test_register_beyond_capacity() {
+    let domain = RcuDomain::<4>::new();
+    let mut readers: Vec<_> = (0..4).map(|_| domain.register_reader().unwrap()).collect();
+
+    assert_eq!(domain.register_reader().unwrap_err(), ReaderTableFull);
+
+    let freed = readers.remove(1).slot();
+    assert_eq!(domain.register_reader().unwrap().slot(), freed);
+}
+
+/// `synchronize()` does not return while a critical section that began before it is still open.
+#[test]
+fn test_synchronize_waits_for_reader() {
+    let domain = Arc::new(RcuDomain::<2>::new());
+    let entered = Arc::new(AtomicBool::new(false));
+    let released = Arc::new(AtomicBool::new(false));
+
+    let reader = {
+        let domain = domain.clone();
+        let entered = entered.clone();
+        let released = released.clone();
+        thread::spawn(move || {
+            let mut reader = domain.register_reader().unwrap();
+            let guard = reader.read_lock();
+            entered.store(true, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(50));
+            released.store(true, Ordering::SeqCst);
+            drop(guard);
+        })
+    };
+
+    while !entered.load(Ordering::SeqCst) {
+        thread::yield_now();
+    }
+    domain.synchronize();
+    assert!(released.load(Ordering::SeqCst), "grace period ended before the reader unlocked");
+    reader.join().unwrap();
+}
+
+/// A reader that panics inside a critical section still ends it and frees its slot during
+/// unwinding, so a concurrent `synchronize()` returns.
+#[test]
+fn test_panicking_reader_does_not_stall_synchronize() {
+    let domain = Arc::new(RcuDomain::<1>::new());
+    let entered = Arc::new(AtomicBool::new(false));
+    let synchronizing = Arc::new(AtomicBool::new(false));
+
+    let reader = {
+        let (domain, entered, synchronizing) = (domain.clone(), entered.clone(), synchronizing.clone());
+        thread::spawn(move || {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                let mut reader = domain.register_reader().unwrap();
+                let _guard = reader.read_lock();
+                entered.store(true, Ordering::SeqCst);
+                while !synchronizing.load(Ordering::SeqCst) {
+                    thread::yield_now();
+                }
+                thread::sleep(Duration::from_millis(20));
+                panic!("reader panicked inside its critical section");
+            }))
+        })
+    };
+
+    while !entered.load(Ordering::SeqCst) {
+        thread::yield_now();
+    }
+    synchronizing.store(true, Ordering::SeqCst);
+    domain.synchronize();
+
+    assert!(reader.join().unwrap().is_err(), "the reader should have panicked");
+    // The only slot was released on unwind.
+    assert!(domain.register_reader().is_ok());
+}
+
+/// Readers never observe a value after the writer has retired it.
+fn check_readers_never_see_retired_value(kind: BarrierKind) {
+    const READERS: usize = 4;
+    const UPDATES: usize = 2_000;
+    const RETIRED: usize = usize::MAX;
+
+    let domain = Arc::new(RcuDomain::<READERS, _>::with_kind(kind));
+    // Two cells alternate as "current"; the writer poisons the old one after a grace period.
+    let cells = Arc::new([AtomicUsize::new(0), AtomicUsize::new(RETIRED)]);
+    let current = Arc::new(AtomicUsize::new(0));
+    let done = Arc::new(AtomicBool::new(false));
+
+    let readers: Vec<_> = (0..READERS)
+        .map(|_| {
+            let (domain, cells, current, done) = (domain.clone(), cells.clone(), current.clone(), done.clone());
+            thread::spawn(move || {
+                let mut reader = domain.register_reader().unwrap();
+                while !done.load(Ordering::Relaxed) {
+                    let _guard = reader.read_lock();
+                    let index = current.load(Ordering::Acquire);
+                    let first = cells[index].load(Ordering::Relaxed);
+                    assert_ne!(first, RETIRED, "observed a retired cell");
+                    assert_eq!(cells[index].load(Ordering::Relaxed), first, "cell changed inside a critical section");
+                }
+            })
+        })
+        .collect();
+
+    for i in 1..=UPDATES {
+        let old = current.load(Ordering::Relaxed);
+        let new = old ^ 1;
+        cells[new].store(i, Ordering::Relaxed);
+        current.store(new, Ordering::Release);
+        domain.synchronize();
+        cells[old].store(RETIRED, Ordering::Relaxed);
+    }
+    done.store(true, Ordering::Relaxed);
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+}
+
+#[test]
+fn test_readers_never_see_retired_value_asymmetric() {
+    check_readers_never_see_retired_value(BarrierKind::Asymmetric);
+}
+
+#[test]
+fn test_readers_never_see_retired_value_symmetric_fence() {
+    check_readers_never_see_retired_value(BarrierKind::SymmetricFence);
+}
+
+/// Grace periods still wait for a reader whose sequence counter wraps past `u64::MAX`.
+#[test]
+#[cfg(feature = "testing")]
+fn test_synchronize_across_sequence_wrap() {
+    use swmr_barrier::testing;
+
+    let domain = Arc::new(RcuDomain::<1>::new());
+    let mut reader = domain.register_reader().unwrap();
+    testing::seed_reader_sequence(&mut reader, u64::MAX - 3);
+
+    // Sequences MAX-2 (lock), MAX-1, MAX (lock), 0 (wrapped), 1 (lock): each lock is odd and must be waited for.
+    for _ in 0..3 {
+        let guard = reader.read_lock();
+        let finished = Arc::new(AtomicBool::new(false));
+        let writer = {
+            let domain = domain.clone();
+            let finished = finished.clone();
+            thread::spawn(move || {
+                domain.synchronize();
+                finished.store(true, Ordering::SeqCst);
+            })
+        };
+
+        thread::sleep(Duration::from_millis(20));
+        assert!(!finished.load(Ordering::SeqCst), "grace period ended inside a critical section");
+        drop(guard);
+        writer.join().unwrap();
+        assert!(finished.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,48 @@
+//! Tests for per-thread registration (`register_current_thread`).
+
+#![cfg(not(feature = "loom"))]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use swmr_barrier::{ThreadRegistration, heavy_barrier, light_barrier, register_current_thread};
+
+/// Registration is idempotent: repeated calls on one thread report the same status.
+#[test]
+fn test_register_is_idempotent() {
+    let first = register_current_thread();
+    for _ in 0..10 {
+        assert_eq!(register_current_thread(), first);
+    }
+    assert_eq!(first, ThreadRegistration::NotRequired);
+}
+
+/// Worker threads registering at start still observe correct barrier ordering.
+#[test]
+fn test_registered_threads_ordering() {
+    for _ in 0..1_000 {
+        let x = Arc::new(AtomicUsize::new(0));
+        let y = Arc::new(AtomicUsize::new(0));
+
+        let (x_w, y_w) = (x.clone(), y.clone());
+        let writer = thread::spawn(move || {
+            register_current_thread();
+            x_w.store(1, Ordering::Relaxed);
+            heavy_barrier();
+            y_w.store(1, Ordering::Relaxed);
+        });
+
+        let reader = thread::spawn(move || {
+            register_current_thread();
+            let r_y = y.load(Ordering::Relaxed);
+            light_barrier();
+            let r_x = x.load(Ordering::Relaxed);
+            if r_y == 1 {
+                assert_eq!(r_x, 1, "Barrier violation after registration: saw Y=1 but X=0");
+            }
+        });
+
+        writer.join().unwrap();
+        reader.join().unwrap();
+    }
+}
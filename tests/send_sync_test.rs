@@ -0,0 +1,96 @@
+//! Positive `Send`/`Sync` checks; the compile-fail counterparts are doctests in `src/send_sync.rs`.
+
+#![cfg(not(feature = "loom"))]
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use swmr_barrier::{
+    AppendLog, AsymAtomic, AtomicCell, ChecksummedCell, DeferredBarrier, DoubleBuffer, FairSeqlock, OverwriteRing, RcuDomain, RcuReadGuard,
+    RcuReader, SnapshotGuard, SwmrCell, ReaderSequence, ResilientReader, SwmrCellReader, SwmrWordCell, TrackedBarrier, WriterSequence,
+};
+
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+
+/// `Copy + Send` but not `Sync`: only a cell that hands out copies may be shared.
+#[derive(Clone, Copy)]
+struct SendOnly(#[allow(dead_code)] u32, PhantomData<Cell<()>>);
+
+#[test]
+fn test_swmr_cell_needs_only_send() {
+    assert_send::<SwmrCell<SendOnly>>();
+    assert_sync::<SwmrCell<SendOnly>>();
+    assert_sync::<SwmrCell<[u64; 4]>>();
+    assert_send::<ChecksummedCell<[u64; 4]>>();
+    assert_sync::<ChecksummedCell<[u64; 4]>>();
+    assert_send::<SwmrCellReader<'static, SendOnly>>();
+    assert_sync::<SwmrCellReader<'static, SendOnly>>();
+    assert_send::<ResilientReader<'static, SendOnly>>();
+    assert_sync::<ResilientReader<'static, SendOnly>>();
+    assert_send::<OverwriteRing<SendOnly, 4>>();
+    assert_sync::<OverwriteRing<SendOnly, 4>>();
+    assert_send::<FairSeqlock<SendOnly>>();
+    assert_sync::<FairSeqlock<SendOnly>>();
+    assert_send::<AtomicCell<SendOnly>>();
+    assert_sync::<AtomicCell<SendOnly>>();
+}
+
+#[test]
+fn test_buffers_with_sync_elements() {
+    assert_send::<DoubleBuffer<String>>();
+    assert_sync::<DoubleBuffer<String>>();
+    assert_send::<SnapshotGuard<'static, String>>();
+    assert_sync::<SnapshotGuard<'static, String>>();
+    assert_send::<AppendLog<String, 8>>();
+    assert_sync::<AppendLog<String, 8>>();
+
+    // Send-only elements can still be moved with the structure.
+    // 仅 Send 的元素仍可随数据结构一起移动。
+    assert_send::<DoubleBuffer<Cell<u8>>>();
+    assert_send::<AppendLog<Cell<u8>, 8>>();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_arc_cell_needs_send_and_sync() {
+    use std::sync::Mutex;
+    use swmr_barrier::SwmrArcCell;
+
+    assert_send::<SwmrArcCell<String>>();
+    assert_sync::<SwmrArcCell<String>>();
+    assert_send::<SwmrArcCell<Mutex<Cell<u8>>>>();
+    assert_sync::<SwmrArcCell<Mutex<Cell<u8>>>>();
+}
+
+#[test]
+fn test_atomic_only_structures() {
+    assert_send::<SwmrWordCell<u64>>();
+    assert_sync::<SwmrWordCell<u64>>();
+    assert_sync::<SwmrWordCell<*mut Cell<u8>>>();
+    assert_send::<AsymAtomic<u64>>();
+    assert_sync::<AsymAtomic<u64>>();
+    assert_sync::<AsymAtomic<*mut Cell<u8>>>();
+    assert_send::<RcuDomain>();
+    assert_sync::<RcuDomain>();
+    assert_send::<RcuReader<'static>>();
+    assert_send::<RcuReadGuard<'static>>();
+    assert_send::<DeferredBarrier>();
+    assert_sync::<DeferredBarrier>();
+    assert_send::<WriterSequence>();
+    assert_sync::<WriterSequence>();
+    assert_send::<ReaderSequence>();
+    assert_sync::<ReaderSequence>();
+    assert_send::<TrackedBarrier>();
+    assert_sync::<TrackedBarrier>();
+}
+
+#[test]
+fn test_snapshot_guard_crosses_threads() {
+    let buffer = DoubleBuffer::new(String::from("swmr"));
+    let guard = buffer.borrow_snapshot();
+    std::thread::scope(|s| {
+        s.spawn(move || assert_eq!(&*guard, "swmr"));
+    });
+    buffer.commit(String::from("next"));
+    assert_eq!(buffer.read(), "next");
+}
@@ -0,0 +1,90 @@
+//! Tests for `FairSeqlock`.
+
+#![cfg(not(feature = "loom"))]
+
+mod common;
+
+use common::PanickingBarrier;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use swmr_barrier::FairSeqlock;
+
+#[test]
+fn test_read_and_set() {
+    let lock = FairSeqlock::new(1u32);
+    assert_eq!(lock.read(), 1);
+    assert_eq!(lock.try_read(1), Some(1));
+    assert_eq!(lock.try_read(0), None);
+
+    lock.set(2);
+    assert_eq!(lock.read(), 2);
+    assert_eq!(lock.starving_readers(), 0);
+    assert_eq!(format!("{lock:?}"), "FairSeqlock { value: 2, starving: 0 }");
+}
+
+/// A large payload keeps the reader's copy slow, so a writer publishing back to back interrupts
+/// nearly every attempt. The reader still completes each read, every copy is whole, and it stops
+/// asking for pauses afterwards.
+#[test]
+fn test_reader_completes_under_hot_writer() {
+    const WORDS: usize = 4096;
+    let lock = FairSeqlock::new([0u64; WORDS]).with_patience(2);
+    let done = AtomicBool::new(false);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            let mut version = 0;
+            while !done.load(Ordering::Relaxed) {
+                version += 1;
+                lock.set([version; WORDS]);
+            }
+        });
+
+        let mut last = 0;
+        for _ in 0..50 {
+            let copy = lock.read();
+            assert!(copy.iter().all(|&word| word == copy[0]), "torn read");
+            assert!(copy[0] >= last, "read went backwards");
+            last = copy[0];
+        }
+        done.store(true, Ordering::Relaxed);
+    });
+    assert_eq!(lock.starving_readers(), 0);
+}
+
+/// A barrier that panics in either half of a write leaves the lock usable: the writer flag is
+/// released and the sequence is even again, so later reads and writes do not hang.
+#[test]
+fn test_panicking_barrier_does_not_wedge_lock() {
+    for panic_at in [1, 2] {
+        let lock = FairSeqlock::with_barrier(1u32, PanickingBarrier::on_heavy(panic_at));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| lock.set(2)));
+        assert!(result.is_err());
+
+        // The first `heavy()` runs before the write, the second after it.
+        let expected = if panic_at == 1 { 1 } else { 2 };
+        assert_eq!(lock.try_read(1), Some(expected));
+        lock.set(3);
+        assert_eq!(lock.read(), 3);
+    }
+}
+
+/// `read_if_changed` returns each completed write once and reports nothing in between.
+#[test]
+fn test_read_if_changed() {
+    let lock = FairSeqlock::new(1u32);
+    let mut seen = 0;
+    assert_eq!(lock.read_if_changed(&mut seen), None);
+    let mut fresh = u64::MAX;
+    assert_eq!(lock.read_if_changed(&mut fresh), Some(1));
+    assert_eq!(fresh, 0);
+
+    lock.set(2);
+    lock.set(3);
+    assert_eq!(lock.version(), 2);
+    assert_eq!(lock.read_if_changed(&mut seen), Some(3));
+    assert_eq!(seen, 2);
+    assert_eq!(lock.read_if_changed(&mut seen), None);
+}
+
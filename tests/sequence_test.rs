@@ -0,0 +1,104 @@
+//! Tests for `WriterSequence` / `ReaderSequence` call-order checks.
+//!
+//! The misordering checks are `debug_assert!`s: they panic in debug builds (`cargo test`) and are
+//! compiled out in release builds (`cargo test --release`).
+
+#![cfg(not(feature = "loom"))]
+
+#[cfg(debug_assertions)]
+use std::panic;
+#[cfg(debug_assertions)]
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use swmr_barrier::{ReaderSequence, WriterSequence};
+
+// Serializes the tests that swap the panic hook.
+// 串行化替换 panic 钩子的测试。
+#[cfg(debug_assertions)]
+static HOOK_LOCK: Mutex<()> = Mutex::new(());
+
+/// Runs `f`, returning the source line the panic was reported at (if it panicked).
+#[cfg(debug_assertions)]
+fn panic_line(f: impl FnOnce() + panic::UnwindSafe) -> Option<(String, u32)> {
+    let _guard = HOOK_LOCK.lock().unwrap();
+    static REPORTED: Mutex<Option<(String, u32)>> = Mutex::new(None);
+    *REPORTED.lock().unwrap() = None;
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        *REPORTED.lock().unwrap() = info.location().map(|l| (l.file().to_owned(), l.line()));
+    }));
+    let result = panic::catch_unwind(f);
+    panic::set_hook(previous);
+    result.err().map(|_| REPORTED.lock().unwrap().take().unwrap())
+}
+
+#[test]
+fn test_correct_order_passes() {
+    let payload = AtomicU64::new(0);
+    let ready = AtomicBool::new(false);
+
+    let mut writer = WriterSequence::new();
+    writer.write(|| payload.store(42, Ordering::Relaxed));
+    writer.heavy();
+    writer.publish(|| ready.store(true, Ordering::Relaxed));
+
+    // Publishing a flag alone needs no barrier.
+    // 仅发布标志无需屏障。
+    writer.publish(|| ready.store(true, Ordering::Relaxed));
+
+    let mut reader = ReaderSequence::new();
+    assert!(reader.observe(|| ready.load(Ordering::Relaxed)));
+    reader.light();
+    assert_eq!(reader.read(|| payload.load(Ordering::Relaxed)), 42);
+    assert_eq!(reader.read(|| payload.load(Ordering::Relaxed)), 42);
+}
+
+/// `heavy()` before the payload write instead of after it.
+#[cfg(debug_assertions)]
+#[test]
+fn test_heavy_before_write_panics_at_caller() {
+    let payload = AtomicU64::new(0);
+    let ready = AtomicBool::new(false);
+    let mut writer = WriterSequence::new();
+    writer.heavy();
+    writer.write(|| payload.store(1, Ordering::Relaxed));
+
+    let line = line!() + 1;
+    let reported = panic_line(move || writer.publish(|| ready.store(true, Ordering::Relaxed)));
+    assert_eq!(reported, Some((file!().to_owned(), line)));
+}
+
+/// `light()` before the observing load instead of after it.
+#[cfg(debug_assertions)]
+#[test]
+fn test_light_before_observe_panics_at_caller() {
+    let payload = AtomicU64::new(0);
+    let ready = AtomicBool::new(true);
+    let mut reader = ReaderSequence::new();
+    reader.light();
+    reader.observe(|| ready.load(Ordering::Relaxed));
+
+    let line = line!() + 1;
+    let reported = panic_line(move || {
+        reader.read(|| payload.load(Ordering::Relaxed));
+    });
+    assert_eq!(reported, Some((file!().to_owned(), line + 1)));
+}
+
+/// Release builds compile the checks out.
+#[cfg(not(debug_assertions))]
+#[test]
+fn test_misordering_is_unchecked_in_release() {
+    let payload = AtomicU64::new(0);
+    let ready = AtomicBool::new(true);
+
+    let mut writer = WriterSequence::new();
+    writer.heavy();
+    writer.write(|| payload.store(1, Ordering::Relaxed));
+    writer.publish(|| ready.store(true, Ordering::Relaxed));
+
+    let mut reader = ReaderSequence::new();
+    reader.light();
+    reader.observe(|| ready.load(Ordering::Relaxed));
+    assert_eq!(reader.read(|| payload.load(Ordering::Relaxed)), 1);
+}
@@ -0,0 +1,31 @@
+//! Tests for the `serde` derives on `BarrierStats` and the types it holds.
+//!
+//! Run with: `cargo test --features serde --test serde_test`
+
+#![cfg(all(feature = "serde", not(feature = "loom")))]
+
+use swmr_barrier::{Backend, BarrierStats, InitReason, Validation};
+
+/// The live snapshot survives a JSON round trip, and `write_json` produces the same text as the
+/// derive.
+#[test]
+fn test_stats_round_trip() {
+    let stats = swmr_barrier::stats();
+    let json = serde_json::to_string(&stats).unwrap();
+    println!("{json}");
+    assert_eq!(serde_json::from_str::<BarrierStats>(&json).unwrap(), stats);
+
+    let mut written = String::new();
+    stats.write_json(&mut written).unwrap();
+    assert_eq!(written, json);
+}
+
+/// Enums serialize by variant name, and a data-carrying `Validation` as an externally tagged object.
+#[test]
+fn test_enum_layout() {
+    assert_eq!(serde_json::to_string(&Backend::Fallback).unwrap(), r#""Fallback""#);
+    assert_eq!(serde_json::to_string(&InitReason::Pending).unwrap(), r#""Pending""#);
+    assert_eq!(serde_json::to_string(&Validation::Failed(1)).unwrap(), r#"{"Failed":1}"#);
+    assert_eq!(serde_json::from_str::<Validation>(r#"{"Failed":38}"#).unwrap(), Validation::Failed(38));
+    assert!(serde_json::from_str::<Backend>(r#""NoSuchBackend""#).is_err());
+}
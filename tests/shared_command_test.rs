@@ -0,0 +1,111 @@
+//! Tests for the `MEMBARRIER_CMD_SHARED` path (Linux 4.3+).
+//!
+//! Detection prefers PRIVATE_EXPEDITED, which every CI kernel has, so these tests force SHARED
+//! through the `testing` hook and check that the compiler-fence light barrier still pairs with it.
+//! They mutate process-global barrier state, so they live in their own binary and are serialized
+//! with a lock.
+//!
+//! Run with: `cargo test --features testing --test shared_command_test`
+
+#![cfg(all(target_os = "linux", feature = "testing", not(feature = "loom"), not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))))]
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use swmr_barrier::{Backend, FenceStrength, heavy_barrier, light_barrier, testing};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// SHARED waits for an RCU grace period, milliseconds per call, so keep the rounds few.
+const ROUNDS: usize = 200;
+
+/// Forces SHARED, or returns `false` if the kernel lacks it (nothing to test then).
+fn force_shared() -> bool {
+    testing::reset_for_test();
+    if !testing::force_shared_command() {
+        eprintln!("MEMBARRIER_CMD_SHARED not advertised; skipping");
+        return false;
+    }
+    true
+}
+
+#[test]
+fn test_forced_shared_backend() {
+    let _guard = LOCK.lock().unwrap();
+    if !force_shared() {
+        return;
+    }
+
+    let stats = swmr_barrier::stats();
+    assert_eq!(stats.backend, Backend::LinuxShared);
+    assert_eq!(stats.light, FenceStrength::Compiler);
+    assert!(swmr_barrier::is_accelerated());
+    assert_eq!(swmr_barrier::heavy_barrier_traced(), Backend::LinuxShared);
+
+    testing::reset_for_test();
+    assert_eq!(swmr_barrier::active_backend(), Backend::LinuxPrivateExpedited);
+}
+
+/// Store buffering, the one reordering x86 allows: writer `x = r; heavy; read y`, reader
+/// `y = r; light; read x`. At least one side must see the other's store in each round.
+#[test]
+fn test_store_buffering_under_shared() {
+    let _guard = LOCK.lock().unwrap();
+    if !force_shared() {
+        return;
+    }
+
+    let (x, y) = (AtomicUsize::new(0), AtomicUsize::new(0));
+    for round in 1..=ROUNDS {
+        let (seen_y, seen_x) = thread::scope(|s| {
+            let writer = s.spawn(|| {
+                x.store(round, Ordering::Relaxed);
+                heavy_barrier();
+                y.load(Ordering::Relaxed)
+            });
+            let reader = s.spawn(|| {
+                y.store(round, Ordering::Relaxed);
+                light_barrier();
+                x.load(Ordering::Relaxed)
+            });
+            (writer.join().unwrap(), reader.join().unwrap())
+        });
+        assert!(
+            seen_y == round || seen_x == round,
+            "round {round}: both sides missed the other's store under SHARED"
+        );
+    }
+    testing::reset_for_test();
+}
+
+/// Message passing: a reader that sees the flag after `light_barrier()` sees the payload written
+/// before the writer's SHARED barrier.
+#[test]
+fn test_message_passing_under_shared() {
+    let _guard = LOCK.lock().unwrap();
+    if !force_shared() {
+        return;
+    }
+
+    let (payload, flag) = (AtomicUsize::new(0), AtomicUsize::new(0));
+    thread::scope(|s| {
+        s.spawn(|| {
+            for round in 1..=ROUNDS {
+                payload.store(round, Ordering::Relaxed);
+                heavy_barrier();
+                flag.store(round, Ordering::Relaxed);
+            }
+        });
+        s.spawn(|| {
+            let mut last = 0;
+            while last < ROUNDS {
+                let seen = flag.load(Ordering::Relaxed);
+                light_barrier();
+                assert!(payload.load(Ordering::Relaxed) >= seen, "saw flag {seen} before its payload");
+                last = seen;
+            }
+        });
+    });
+    assert_eq!(swmr_barrier::active_backend(), Backend::LinuxShared);
+    testing::reset_for_test();
+}
@@ -0,0 +1,87 @@
+//! Tests for `SwmrCell::init_in_place` / `SwmrCell::from_ptr` over externally provided memory.
+//!
+//! One memfd is mapped twice, so the writer and the reader reach the same cell through different
+//! virtual addresses, as two processes sharing the region would.
+
+#![cfg(all(target_os = "linux", not(feature = "loom")))]
+
+use std::thread;
+use swmr_barrier::{BarrierKind, SwmrCell};
+
+type Cell = SwmrCell<[u64; 4], 2, BarrierKind>;
+
+const PUBLISHES: u64 = 100_000;
+
+/// Two `MAP_SHARED` views of one anonymous memory file.
+struct SharedRegion {
+    views: [*mut u8; 2],
+    len: usize,
+}
+
+impl SharedRegion {
+    fn new(len: usize) -> Self {
+        unsafe {
+            let fd = libc::memfd_create(c"swmr-cell".as_ptr(), 0);
+            assert!(fd >= 0, "memfd_create: {}", std::io::Error::last_os_error());
+            assert_eq!(libc::ftruncate(fd, len as libc::off_t), 0);
+            let map = || {
+                let ptr = libc::mmap(
+                    core::ptr::null_mut(),
+                    len,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_SHARED,
+                    fd,
+                    0,
+                );
+                assert_ne!(ptr, libc::MAP_FAILED, "mmap: {}", std::io::Error::last_os_error());
+                ptr.cast::<u8>()
+            };
+            let views = [map(), map()];
+            libc::close(fd);
+            Self { views, len }
+        }
+    }
+}
+
+impl Drop for SharedRegion {
+    fn drop(&mut self) {
+        for view in self.views {
+            unsafe { libc::munmap(view.cast(), self.len) };
+        }
+    }
+}
+
+#[test]
+fn test_cell_in_shared_mapping() {
+    let region = SharedRegion::new(size_of::<Cell>().next_multiple_of(4096));
+    assert_ne!(region.views[0], region.views[1]);
+    // Page-aligned, which covers the cell's cache-line alignment.
+    // 按页对齐，满足单元的缓存行对齐要求。
+    assert_eq!(region.views[0] as usize % align_of::<Cell>(), 0);
+
+    let writer_view = region.views[0] as usize;
+    let reader_view = region.views[1] as usize;
+    let writer = unsafe { Cell::init_in_place(writer_view as *mut Cell, [0; 4], BarrierKind::SymmetricFence) };
+    let reader = unsafe { Cell::from_ptr(reader_view as *const Cell) };
+    assert_eq!(reader.get(), [0; 4]);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            for i in 1..=PUBLISHES {
+                writer.set([i; 4]);
+            }
+        });
+        s.spawn(|| {
+            let mut last = 0;
+            while last < PUBLISHES {
+                let value = reader.get();
+                assert!(value.iter().all(|&v| v == value[0]), "torn read: {value:?}");
+                assert!(value[0] >= last, "went backwards: {} after {last}", value[0]);
+                last = value[0];
+            }
+        });
+    });
+
+    assert_eq!(reader.get(), [PUBLISHES; 4]);
+    assert_eq!(writer.get(), [PUBLISHES; 4]);
+}
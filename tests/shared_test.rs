@@ -0,0 +1,55 @@
+//! Tests for `shared()` and `SharedSwmrCell`.
+//!
+//! Run with: `cargo test --features std --test shared_test`
+
+#![cfg(all(feature = "std", not(feature = "loom")))]
+
+use std::thread;
+use swmr_barrier::{SharedSwmrCell, shared};
+
+#[test]
+fn test_clones_share_one_cell() {
+    let handle = shared(1u32);
+    let other = handle.clone();
+    other.publish(2);
+    assert_eq!(handle.load(), 2);
+    assert_eq!(handle.cell().get(), 2);
+    assert_eq!(format!("{handle:?}"), "SharedSwmrCell(2)");
+}
+
+/// One writer publishes `[i, !i]` pairs through its own clone; readers on other clones never see
+/// a torn pair or a value going backwards, and all see the final publish.
+#[test]
+fn test_writer_and_readers_on_clones() {
+    const READERS: usize = 4;
+    const UPDATES: u64 = 10_000;
+
+    let handle: SharedSwmrCell<[u64; 2]> = shared([0, !0]);
+    let readers: Vec<_> = (0..READERS)
+        .map(|_| {
+            let reader = handle.clone();
+            thread::spawn(move || {
+                let mut last = 0;
+                while last < UPDATES {
+                    let [low, high] = reader.load();
+                    assert_eq!(low, !high, "torn read");
+                    assert!(low >= last, "read went backwards: {low} < {last}");
+                    last = low;
+                }
+            })
+        })
+        .collect();
+
+    let writer = handle.clone();
+    thread::spawn(move || {
+        for i in 1..=UPDATES {
+            writer.publish([i, !i]);
+        }
+    })
+    .join()
+    .unwrap();
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    assert_eq!(handle.load(), [UPDATES, !UPDATES]);
+}
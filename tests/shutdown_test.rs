@@ -0,0 +1,85 @@
+//! Tests for `shutdown()`.
+//!
+//! These tests switch the process-global backend, so they live in their own binary and are
+//! serialized with a lock.
+//!
+//! Run with: `cargo test --test shutdown_test`
+
+#![cfg(not(feature = "loom"))]
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Barrier, Mutex};
+use std::thread;
+use swmr_barrier::{Backend, FenceStrength, heavy_barrier, heavy_barrier_traced, light_barrier};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+const ROUNDS: usize = 20_000;
+
+/// After shutdown, barriers use the fence fallback; `init()` brings acceleration back.
+#[test]
+fn test_shutdown_reverts_to_fallback() {
+    let _guard = LOCK.lock().unwrap();
+    let accelerated = swmr_barrier::init();
+
+    swmr_barrier::shutdown();
+    let stats = swmr_barrier::stats();
+    if cfg!(target_os = "linux") {
+        assert!(!swmr_barrier::is_accelerated());
+        assert_eq!(stats.backend, Backend::Fallback);
+        assert_eq!(stats.light, FenceStrength::Full);
+        assert_eq!(stats.init_reason, swmr_barrier::InitReason::Shutdown);
+        assert_eq!(heavy_barrier_traced(), Backend::Fallback);
+    }
+    heavy_barrier();
+    light_barrier();
+
+    // Idempotent.
+    // 幂等。
+    swmr_barrier::shutdown();
+    heavy_barrier();
+
+    assert_eq!(swmr_barrier::init(), accelerated);
+}
+
+/// Store buffering across a shutdown issued halfway through, while the reader may still be on the
+/// cheap path: the writer and the reader never both miss each other's store.
+#[test]
+fn test_barriers_correct_across_shutdown() {
+    let _guard = LOCK.lock().unwrap();
+    swmr_barrier::init();
+
+    let x = AtomicUsize::new(0);
+    let y = AtomicUsize::new(0);
+    let writer_saw_y = AtomicBool::new(false);
+    let start = Barrier::new(2);
+    let end = Barrier::new(2);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            for round in 1..=ROUNDS {
+                start.wait();
+                y.store(round, Ordering::Relaxed);
+                light_barrier();
+                let seen_x = x.load(Ordering::Relaxed);
+                end.wait();
+                assert!(seen_x == round || writer_saw_y.load(Ordering::Relaxed), "round {round}: both stores missed");
+                end.wait();
+            }
+        });
+
+        for round in 1..=ROUNDS {
+            start.wait();
+            if round == ROUNDS / 2 {
+                swmr_barrier::shutdown();
+            }
+            x.store(round, Ordering::Relaxed);
+            heavy_barrier();
+            writer_saw_y.store(y.load(Ordering::Relaxed) == round, Ordering::Relaxed);
+            end.wait();
+            end.wait();
+        }
+    });
+
+    swmr_barrier::init();
+}
@@ -0,0 +1,50 @@
+//! Tests for `heavy_barrier_async_signal_safe()` called from inside a signal handler (Linux).
+
+#![cfg(all(target_os = "linux", not(feature = "loom")))]
+
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
+use std::thread;
+use swmr_barrier::{heavy_barrier_async_signal_safe, light_barrier};
+
+static DATA: AtomicUsize = AtomicUsize::new(0);
+static READY: AtomicBool = AtomicBool::new(false);
+static ERRNO_SEEN: AtomicI32 = AtomicI32::new(0);
+
+/// Publishes `DATA` from the handler, the way a profiler's `SIGPROF` handler would.
+extern "C" fn on_signal(_: libc::c_int) {
+    DATA.store(42, Ordering::Relaxed);
+    heavy_barrier_async_signal_safe();
+    READY.store(true, Ordering::Relaxed);
+    ERRNO_SEEN.store(unsafe { *libc::__errno_location() }, Ordering::Relaxed);
+}
+
+fn install(signal: libc::c_int) {
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        libc::sigemptyset(&mut action.sa_mask);
+        assert_eq!(libc::sigaction(signal, &action, std::ptr::null_mut()), 0);
+    }
+}
+
+/// A reader on another thread sees the data published by a signal handler, and the handler leaves
+/// the interrupted thread's `errno` untouched.
+#[test]
+fn test_publish_from_signal_handler() {
+    install(libc::SIGUSR1);
+
+    let reader = thread::spawn(|| {
+        while !READY.load(Ordering::Relaxed) {
+            std::hint::spin_loop();
+        }
+        light_barrier();
+        DATA.load(Ordering::Relaxed)
+    });
+
+    unsafe {
+        *libc::__errno_location() = libc::EAGAIN;
+        assert_eq!(libc::raise(libc::SIGUSR1), 0);
+    }
+    assert_eq!(ERRNO_SEEN.load(Ordering::Relaxed), libc::EAGAIN);
+    assert_eq!(reader.join().unwrap(), 42);
+}
@@ -0,0 +1,53 @@
+//! Tests for `assume_single_threaded` (Linux).
+//!
+//! The latency histogram counts membarrier syscalls, which shows whether a heavy barrier reached
+//! the kernel. The flag and counters are process-global, so these tests live in their own binary
+//! and are serialized with a lock.
+//!
+//! Run with: `cargo test --features testing,latency --test single_threaded_test`
+
+#![cfg(all(
+    target_os = "linux",
+    feature = "testing",
+    feature = "latency",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+
+use std::sync::Mutex;
+use swmr_barrier::{assume_single_threaded, barrier_generation, barrier_latency_histogram, heavy_barrier, testing};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn syscalls() -> u64 {
+    barrier_latency_histogram().iter().sum()
+}
+
+/// With the flag set, heavy barriers skip the syscall but still count as generations; clearing it
+/// brings the syscall back.
+#[test]
+fn test_single_threaded_skips_syscall() {
+    let _guard = LOCK.lock().unwrap();
+    testing::reset_for_test();
+    if !swmr_barrier::is_accelerated() {
+        eprintln!("membarrier unavailable; skipping");
+        return;
+    }
+
+    // SAFETY: The lock serializes the tests in this binary and none of them spawns a thread while
+    // the flag is set; the harness threads issue no barriers.
+    unsafe { assume_single_threaded(true) };
+    let (before, generation) = (syscalls(), barrier_generation());
+    for _ in 0..10 {
+        heavy_barrier();
+    }
+    swmr_barrier::heavy_barrier_async_signal_safe();
+    assert_eq!(syscalls(), before, "a heavy barrier reached the kernel");
+    assert_eq!(barrier_generation(), generation + 11);
+    assert_eq!(swmr_barrier::heavy_barrier_traced(), swmr_barrier::active_backend());
+
+    // SAFETY: Clearing the flag is always sound.
+    unsafe { assume_single_threaded(false) };
+    heavy_barrier();
+    assert_eq!(syscalls(), before + 1);
+}
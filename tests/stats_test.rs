@@ -0,0 +1,313 @@
+//! Tests for the runtime introspection API (`stats()`, `active_backend()`).
+
+#![cfg(not(feature = "loom"))]
+
+use swmr_barrier::{AccelerationMatrix, Backend, CostClass, FenceStrength, InitReason, PlatformInfo, Validation};
+
+/// All introspection types are printable with `{:?}` for bug reports.
+#[test]
+fn test_stats_debug_output() {
+    let stats = swmr_barrier::stats();
+    let rendered = format!("{:?}", stats);
+
+    assert!(rendered.contains("BarrierStats"));
+    assert!(rendered.contains(&format!("{:?}", stats.backend)));
+    assert!(rendered.contains(&format!("{:?}", stats.init_reason)));
+    println!("{:?}", stats);
+}
+
+/// `write_json` renders every field in `serde`'s default layout, and reading the fields back
+/// yields the snapshot it came from.
+#[test]
+fn test_stats_json() {
+    let stats = swmr_barrier::stats();
+    let mut json = String::new();
+    stats.write_json(&mut json).unwrap();
+    println!("{json}");
+
+    let field = |name: &str| -> String {
+        let start = json.find(&format!("\"{name}\":")).expect(name) + name.len() + 3;
+        let rest = &json[start..];
+        let end = if rest.starts_with('{') { rest.find('}').unwrap() + 1 } else { rest.find([',', '}']).unwrap() };
+        rest[..end].to_string()
+    };
+    assert_eq!(field("backend"), format!("\"{:?}\"", stats.backend));
+    assert_eq!(field("init_reason"), format!("\"{:?}\"", stats.init_reason));
+    assert_eq!(field("light"), format!("\"{:?}\"", stats.light));
+    assert_eq!(field("heavy"), format!("\"{:?}\"", stats.heavy));
+    assert_eq!(field("registration_errno").parse().ok(), stats.registration_errno);
+    let validation = match stats.validation {
+        Validation::Failed(errno) => format!(r#"{{"Failed":{errno}}}"#),
+        other => format!("\"{other:?}\""),
+    };
+    assert_eq!(field("validation"), validation);
+    assert_eq!(field("sync_core").parse::<bool>().unwrap(), stats.sync_core);
+    let topology = stats.topology.map_or("null".to_string(), |t| {
+        format!(
+            r#"{{"cpus":{},"packages":{},"clusters":{},"capacity_classes":{}}}"#,
+            t.cpus, t.packages, t.clusters, t.capacity_classes
+        )
+    });
+    assert_eq!(field("topology"), topology);
+    assert_eq!(field("qemu_user").parse::<bool>().unwrap(), stats.qemu_user);
+    match stats.slowest_heavy_ns {
+        Some(ns) => assert_eq!(field("slowest_heavy_ns").parse::<u64>().unwrap(), ns),
+        None => assert_eq!(field("slowest_heavy_ns"), "null"),
+    }
+    assert!(json.starts_with('{') && json.ends_with('}'));
+}
+
+/// Snapshots are plain values that compare equal when nothing changed.
+#[test]
+fn test_stats_snapshot_is_copy_and_eq() {
+    let a = swmr_barrier::stats();
+    let b = a;
+    assert_eq!(a, b);
+    assert_eq!(a, swmr_barrier::stats());
+}
+
+/// The snapshot agrees with the individual query functions.
+#[test]
+fn test_stats_consistency() {
+    let stats = swmr_barrier::stats();
+
+    assert_eq!(stats.backend, swmr_barrier::active_backend());
+    assert_eq!(stats.backend.is_accelerated(), swmr_barrier::is_accelerated());
+    assert_eq!(stats.light, stats.backend.light_fence());
+    assert_eq!(stats.heavy, stats.backend.heavy_cost());
+    assert_eq!(stats.registration_errno, swmr_barrier::registration_errno());
+    assert_eq!(stats.sync_core, swmr_barrier::sync_core_available());
+    assert_ne!(stats.init_reason, InitReason::Pending, "detection must run before main");
+
+    if stats.backend.is_accelerated() {
+        assert_eq!(stats.init_reason, InitReason::Accelerated);
+        assert_eq!(stats.light, FenceStrength::Compiler);
+        assert_eq!(stats.heavy, CostClass::Ipi);
+    } else if stats.backend == Backend::Fallback {
+        assert_eq!(stats.light, FenceStrength::Full);
+        assert_eq!(stats.heavy, CostClass::Fence);
+    }
+}
+
+/// Linux kernels 4.3+ report one of the membarrier backends.
+#[test]
+#[cfg(all(target_os = "linux", not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))))]
+fn test_linux_backend() {
+    assert!(matches!(
+        swmr_barrier::active_backend(),
+        Backend::LinuxPrivateExpedited | Backend::LinuxShared
+    ));
+}
+
+/// Windows Vista+ reports FlushProcessWriteBuffers.
+#[test]
+#[cfg(target_os = "windows")]
+fn test_windows_backend() {
+    assert_eq!(swmr_barrier::active_backend(), Backend::WindowsFlushProcessWriteBuffers);
+}
+
+/// On Windows the loaded `FlushProcessWriteBuffers` pointer is the only state, so every view of it
+/// must agree: accelerated, the Windows backend and a compiler-only light barrier.
+#[test]
+#[cfg(target_os = "windows")]
+fn test_windows_accelerated_matches_pointer() {
+    let stats = swmr_barrier::stats();
+    assert_eq!(swmr_barrier::is_accelerated(), stats.backend == Backend::WindowsFlushProcessWriteBuffers);
+    assert_eq!(swmr_barrier::is_accelerated(), stats.light == FenceStrength::Compiler);
+    assert_eq!(swmr_barrier::init(), swmr_barrier::is_accelerated());
+}
+
+/// On DragonFly the helper page is the only state, so every view of it must agree.
+#[test]
+#[cfg(all(target_os = "dragonfly", not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))))]
+fn test_dragonfly_accelerated_matches_page() {
+    let stats = swmr_barrier::stats();
+    assert_eq!(swmr_barrier::is_accelerated(), stats.backend == Backend::MprotectTlbShootdown);
+    assert_eq!(swmr_barrier::is_accelerated(), stats.light == FenceStrength::Compiler);
+    assert_eq!(swmr_barrier::init(), swmr_barrier::is_accelerated());
+    assert_eq!(swmr_barrier::heavy_barrier_traced(), stats.backend);
+}
+
+/// ThreadSanitizer builds use real fences on both sides, whatever the platform.
+#[test]
+#[cfg(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))]
+fn test_thread_sanitizer_backend() {
+    let stats = swmr_barrier::stats();
+    assert_eq!(stats.backend, Backend::ThreadSanitizer);
+    assert_eq!(stats.light, FenceStrength::Full);
+    assert!(!swmr_barrier::is_accelerated());
+}
+
+/// Sync-core is a Linux membarrier command, with `FlushInstructionCache` as the Windows
+/// counterpart; everything else reports it unavailable.
+#[test]
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn test_sync_core_unavailable() {
+    assert!(!swmr_barrier::sync_core_available());
+}
+
+/// The matrix agrees with the selected backend and with `sync_core_available()`.
+#[test]
+fn test_acceleration_matrix() {
+    let matrix = swmr_barrier::acceleration_matrix();
+    println!("{matrix:?}");
+    assert_eq!(matrix.sync_core, swmr_barrier::sync_core_available());
+    assert_eq!(matrix.global, matrix.shared);
+    match swmr_barrier::active_backend() {
+        Backend::LinuxPrivateExpedited => assert!(matrix.private_expedited),
+        Backend::LinuxShared => assert!(matrix.shared),
+        _ => {}
+    }
+    assert_eq!(
+        matrix.flush_process_write_buffers,
+        swmr_barrier::active_backend() == Backend::WindowsFlushProcessWriteBuffers
+    );
+    if !cfg!(any(target_os = "linux", target_os = "windows")) || cfg!(all(swmr_barrier_tsan, not(feature = "tsan-annotate"))) {
+        assert_eq!(matrix, AccelerationMatrix::default());
+    }
+}
+
+/// `PLATFORM` is fixed at compile time and bounds what runtime detection can select.
+#[test]
+fn test_compile_time_platform() {
+    const PLATFORM: PlatformInfo = swmr_barrier::PLATFORM;
+    println!("{PLATFORM:?}");
+
+    assert_eq!(PLATFORM.os, std::env::consts::OS);
+    let os_barrier = cfg!(all(
+        any(target_os = "linux", target_os = "windows", target_os = "dragonfly"),
+        not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+    ));
+    assert_eq!(PLATFORM.can_accelerate, os_barrier);
+    assert!(PLATFORM.can_accelerate || !swmr_barrier::is_accelerated());
+    if os_barrier && cfg!(target_os = "linux") {
+        assert_eq!(PLATFORM.mechanism, "membarrier");
+    }
+}
+
+/// Without `--cfg loom` and the `loom` feature the real OS arm of `sys` is compiled, never the
+/// simulated one.
+#[test]
+fn test_real_arm_selected() {
+    assert_ne!(swmr_barrier::active_backend(), Backend::Loom);
+    assert_ne!(swmr_barrier::stats().backend, Backend::Loom);
+    assert_ne!(swmr_barrier::PLATFORM.mechanism, "fence (loom)");
+}
+
+/// `as_str()` names are metrics label values: pin every one so a rename cannot slip through.
+#[test]
+fn test_stable_names() {
+    let backends = [
+        (Backend::LinuxPrivateExpedited, "linux-private-expedited"),
+        (Backend::LinuxShared, "linux-shared"),
+        (Backend::WindowsFlushProcessWriteBuffers, "windows-fpwb"),
+        (Backend::MprotectTlbShootdown, "mprotect"),
+        (Backend::Fallback, "fallback"),
+        (Backend::SingleThreaded, "single-threaded"),
+        (Backend::Loom, "loom"),
+        (Backend::ThreadSanitizer, "thread-sanitizer"),
+    ];
+    for (backend, name) in backends {
+        assert_eq!(backend.as_str(), name);
+    }
+
+    let reasons = [
+        (InitReason::Pending, "pending"),
+        (InitReason::Accelerated, "accelerated"),
+        (InitReason::QueryFailed, "query-failed"),
+        (InitReason::NotSupported, "not-supported"),
+        (InitReason::Unsupported, "unsupported"),
+        (InitReason::ValidationFailed, "validation-failed"),
+        (InitReason::WatchdogTripped, "watchdog-tripped"),
+        (InitReason::KernelDenylisted, "kernel-denylisted"),
+        (InitReason::Shutdown, "shutdown"),
+        (InitReason::ModeOverride, "mode-override"),
+    ];
+    for (reason, name) in reasons {
+        assert_eq!(reason.as_str(), name);
+    }
+
+    assert_eq!(Validation::NotRun.as_str(), "not-run");
+    assert_eq!(Validation::Passed.as_str(), "passed");
+    assert_eq!(Validation::Failed(1).as_str(), "failed");
+    assert_eq!(FenceStrength::Compiler.as_str(), "compiler");
+    assert_eq!(FenceStrength::Full.as_str(), "full");
+    assert_eq!(CostClass::Free.as_str(), "free");
+    assert_eq!(CostClass::Fence.as_str(), "fence");
+    assert_eq!(CostClass::Ipi.as_str(), "ipi");
+}
+
+/// Introspection values can key a map, e.g. to count hosts per backend.
+#[test]
+fn test_backend_groups_by_hash() {
+    use std::collections::{HashMap, HashSet};
+
+    let mut hosts: HashMap<Backend, usize> = HashMap::new();
+    for backend in [Backend::LinuxShared, Backend::Fallback, Backend::LinuxShared] {
+        *hosts.entry(backend).or_default() += 1;
+    }
+    assert_eq!(hosts[&Backend::LinuxShared], 2);
+    assert_eq!(hosts[&Backend::Fallback], 1);
+
+    let stats = swmr_barrier::stats();
+    let mut by_reason = HashSet::new();
+    assert!(by_reason.insert((stats.backend, stats.init_reason, stats.light, stats.heavy)));
+    assert!(!by_reason.insert((stats.backend, stats.init_reason, stats.light, stats.heavy)));
+}
+
+/// Fixed-size `fmt::Write` sink, as a `no_std` caller without `alloc` would use.
+struct StackBuf<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> core::fmt::Write for StackBuf<N> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        self.buf.get_mut(self.len..end).ok_or(core::fmt::Error)?.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// `write_stats` renders into a fixed buffer and names the backend and the accelerated flag; a
+/// buffer too small for it reports the error instead of truncating silently.
+#[test]
+fn test_write_stats_without_alloc() {
+    let stats = swmr_barrier::stats();
+    let mut out = StackBuf::<256> { buf: [0; 256], len: 0 };
+    swmr_barrier::write_stats(&stats, &mut out).unwrap();
+    let summary = core::str::from_utf8(&out.buf[..out.len]).unwrap();
+    println!("{summary}");
+
+    assert!(summary.starts_with(&format!("backend={} ", stats.backend.as_str())));
+    assert!(summary.contains(&format!("accelerated={}", stats.backend.is_accelerated())));
+    assert!(summary.contains(&format!("reason={}", stats.init_reason.as_str())));
+    assert!(summary.contains(&format!("validation={} ", stats.validation.as_str())));
+    assert!(!summary.contains('\n'));
+
+    let mut failed = stats;
+    failed.validation = Validation::Failed(1);
+    let mut out = StackBuf::<256> { buf: [0; 256], len: 0 };
+    swmr_barrier::write_stats(&failed, &mut out).unwrap();
+    let summary = core::str::from_utf8(&out.buf[..out.len]).unwrap();
+    assert!(summary.contains(" validation=failed validation_errno=1"));
+
+    let mut tiny = StackBuf::<8> { buf: [0; 8], len: 0 };
+    assert!(swmr_barrier::write_stats(&stats, &mut tiny).is_err());
+}
+
+/// Without the `telemetry` feature the counters read as 0 and the snapshot is still usable.
+#[cfg(not(feature = "telemetry"))]
+#[test]
+fn test_runtime_telemetry_stub() {
+    swmr_barrier::heavy_barrier();
+    swmr_barrier::light_barrier();
+
+    let telemetry = swmr_barrier::runtime_telemetry();
+    assert_eq!((telemetry.heavy_count, telemetry.light_count, telemetry.fallback_count), (0, 0, 0));
+    assert!(telemetry.generation >= 1);
+    if swmr_barrier::is_accelerated() {
+        assert!(!telemetry.degraded);
+    }
+}
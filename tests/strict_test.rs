@@ -0,0 +1,107 @@
+//! Tests for the `strict` kernel denylist (Linux).
+//!
+//! These tests mutate process-global barrier state through the `testing` hooks,
+//! so they live in their own binary and are serialized with a lock.
+//!
+//! Run with: `cargo test --features testing,strict --test strict_test`
+//! (and `--features testing,strict-panic` for the panicking variant)
+
+#![cfg(all(
+    target_os = "linux",
+    feature = "testing",
+    feature = "strict",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use swmr_barrier::{InitReason, KernelVersion, testing};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Tolerates a poisoned lock: the `strict-panic` test panics while holding it.
+fn lock() -> MutexGuard<'static, ()> {
+    LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[test]
+fn test_kernel_release_parsing() {
+    let _guard = lock();
+    testing::reset_for_test();
+
+    for (release, expected) in [
+        ("5.15.0-91-generic", Some(KernelVersion::new(5, 15, 0))),
+        ("4.14.9", Some(KernelVersion::new(4, 14, 9))),
+        ("6.1", Some(KernelVersion::new(6, 1, 0))),
+        ("6.8.0+rt3", Some(KernelVersion::new(6, 8, 0))),
+        ("not-a-kernel", None),
+    ] {
+        testing::set_kernel_release(Some(release));
+        assert_eq!(swmr_barrier::kernel_version(), expected, "{release}");
+    }
+    testing::reset_for_test();
+    assert!(KernelVersion::new(4, 14, 9) < KernelVersion::new(4, 14, 10));
+}
+
+/// A denylisted release forces the fallback and records why; a fixed one is accelerated as usual.
+#[cfg(not(feature = "strict-panic"))]
+#[test]
+fn test_denylisted_kernel_falls_back() {
+    let _guard = lock();
+    testing::reset_for_test();
+    let accelerated = swmr_barrier::is_accelerated();
+
+    testing::set_kernel_release(Some("4.14.3-generic"));
+    testing::reinitialize();
+    assert!(!swmr_barrier::init(), "explicit init() must not accelerate a denylisted kernel");
+    let stats = swmr_barrier::stats();
+    assert_eq!(stats.backend, swmr_barrier::Backend::Fallback);
+    assert_eq!(stats.init_reason, InitReason::KernelDenylisted);
+    swmr_barrier::heavy_barrier();
+    swmr_barrier::light_barrier();
+
+    testing::set_kernel_release(Some("4.14.10"));
+    testing::reinitialize();
+    assert_eq!(swmr_barrier::is_accelerated(), accelerated);
+    assert_ne!(swmr_barrier::stats().init_reason, InitReason::KernelDenylisted);
+
+    testing::reset_for_test();
+}
+
+/// An explicit `init()` on an already accelerated process still drops to the fallback on a
+/// denylisted release, and the barriers keep working across the switch.
+#[cfg(not(feature = "strict-panic"))]
+#[test]
+fn test_denylisted_kernel_deactivates_active_command() {
+    let _guard = lock();
+    testing::reset_for_test();
+
+    testing::set_kernel_release(Some("4.14.3-generic"));
+    assert!(!swmr_barrier::init());
+    assert_eq!(swmr_barrier::stats().init_reason, InitReason::KernelDenylisted);
+    assert!(!swmr_barrier::is_accelerated());
+    swmr_barrier::heavy_barrier();
+    swmr_barrier::light_barrier();
+
+    testing::reset_for_test();
+}
+
+/// With `strict-panic`, the explicit `init()` panics on a denylisted release, after falling back.
+#[cfg(feature = "strict-panic")]
+#[test]
+fn test_denylisted_kernel_panics_on_init() {
+    let _guard = lock();
+    testing::reset_for_test();
+
+    testing::set_kernel_release(Some("4.14.3-generic"));
+    // Automatic detection only falls back.
+    // 自动检测只会回退。
+    testing::reinitialize();
+    assert_eq!(swmr_barrier::stats().init_reason, InitReason::KernelDenylisted);
+
+    let result = std::panic::catch_unwind(swmr_barrier::init);
+    assert!(result.is_err(), "init() should panic on a denylisted kernel");
+    assert!(!swmr_barrier::is_accelerated());
+
+    testing::reset_for_test();
+}
@@ -0,0 +1,428 @@
+//! Tests for `SwmrCell` and its word-sized fast path `SwmrWordCell`.
+
+#![cfg(not(feature = "loom"))]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering, fence};
+use std::time::{Duration, Instant};
+use std::thread;
+use swmr_barrier::{SwmrCell, SwmrWordCell};
+
+/// The operations both cells share, so one scenario can drive either path.
+trait Cell<T>: Send + Sync {
+    fn get(&self) -> T;
+    fn set(&self, value: T);
+}
+
+impl<T: Copy + Send + Sync, const SLOTS: usize> Cell<T> for SwmrCell<T, SLOTS> {
+    fn get(&self) -> T {
+        SwmrCell::get(self)
+    }
+    fn set(&self, value: T) {
+        SwmrCell::set(self, value)
+    }
+}
+
+impl Cell<u64> for SwmrWordCell<u64> {
+    fn get(&self) -> u64 {
+        SwmrWordCell::get(self)
+    }
+    fn set(&self, value: u64) {
+        SwmrWordCell::set(self, value)
+    }
+}
+
+/// Single-threaded get/set history.
+fn sequential_history(cell: &dyn Cell<u64>) -> Vec<u64> {
+    let mut seen = vec![cell.get()];
+    for value in [1, u64::MAX, 0, 42] {
+        cell.set(value);
+        seen.push(cell.get());
+    }
+    seen
+}
+
+/// A reader that observes version `n` also observes the payload written before `set(n)`.
+fn check_publish_order(cell: Arc<dyn Cell<u64>>) {
+    const UPDATES: u64 = 10_000;
+
+    let payload = Arc::new(AtomicU64::new(0));
+    let done = Arc::new(AtomicBool::new(false));
+
+    let reader = {
+        let (cell, payload, done) = (cell.clone(), payload.clone(), done.clone());
+        thread::spawn(move || {
+            let mut last = 0;
+            while !done.load(Ordering::Relaxed) {
+                let version = cell.get();
+                assert!(version >= last, "version went backwards: {} < {}", version, last);
+                assert!(payload.load(Ordering::Relaxed) >= version, "saw version {} before its payload", version);
+                last = version;
+            }
+        })
+    };
+
+    for i in 1..=UPDATES {
+        payload.store(i, Ordering::Relaxed);
+        cell.set(i);
+    }
+    done.store(true, Ordering::Relaxed);
+    reader.join().unwrap();
+    assert_eq!(cell.get(), UPDATES);
+}
+
+#[test]
+fn test_word_and_general_paths_match_sequentially() {
+    let general = SwmrCell::new(7u64);
+    let word = SwmrWordCell::new(7u64);
+    assert_eq!(sequential_history(&general), sequential_history(&word));
+}
+
+#[test]
+fn test_general_path_publish_order() {
+    check_publish_order(Arc::new(SwmrCell::new(0u64)));
+}
+
+#[test]
+fn test_word_path_publish_order() {
+    check_publish_order(Arc::new(SwmrWordCell::new(0u64)));
+}
+
+/// Non-word values take the general path.
+#[test]
+fn test_general_path_multi_word_value() {
+    let cell = SwmrCell::new([0u64; 8]);
+    cell.set([3; 8]);
+    assert_eq!(cell.get(), [3; 8]);
+}
+
+/// Every word-sized primitive round-trips, including pointers and `bool`.
+#[test]
+fn test_word_sized_primitives() {
+    let flag = SwmrWordCell::new(false);
+    flag.set(true);
+    assert!(flag.get());
+
+    let signed = SwmrWordCell::new(-1i16);
+    signed.set(i16::MIN);
+    assert_eq!(signed.get(), i16::MIN);
+
+    let mut target = 5u32;
+    let ptr = SwmrWordCell::new(std::ptr::null_mut::<u32>());
+    ptr.set(&mut target);
+    assert_eq!(unsafe { *ptr.get() }, 5);
+
+    assert_eq!(format!("{:?}", SwmrWordCell::new(3u8)), "SwmrWordCell { value: 3 }");
+    assert_eq!(format!("{:?}", SwmrCell::new(3u8)), "SwmrCell { value: 3 }");
+}
+
+/// A barrier whose first `light()` parks the reader mid-read, with its slot pinned, until released.
+#[derive(Clone, Default)]
+struct SlowReader(Arc<SlowReaderState>);
+
+#[derive(Default)]
+struct SlowReaderState {
+    parked: AtomicBool,
+    released: AtomicBool,
+}
+
+unsafe impl swmr_barrier::AsymmetricBarrier for SlowReader {
+    fn heavy(&self) {
+        fence(Ordering::SeqCst);
+    }
+
+    fn light(&self) {
+        fence(Ordering::SeqCst);
+        if !self.0.parked.swap(true, Ordering::SeqCst) {
+            while !self.0.released.load(Ordering::SeqCst) {
+                thread::yield_now();
+            }
+        }
+    }
+
+    fn accelerated(&self) -> bool {
+        false
+    }
+}
+
+/// Starts `sets` commits while one reader is parked inside `get`, and reports whether they all
+/// finished before the reader was released.
+fn sets_pass_slow_reader<const SLOTS: usize>(sets: u64) -> bool {
+    let slow = SlowReader::default();
+    let cell = Arc::new(SwmrCell::<u64, SLOTS, _>::with_barrier(0, slow.clone()));
+    let reader = {
+        let cell = cell.clone();
+        thread::spawn(move || cell.get())
+    };
+    while !slow.0.parked.load(Ordering::SeqCst) {
+        thread::yield_now();
+    }
+
+    let done = Arc::new(AtomicBool::new(false));
+    let writer = {
+        let (cell, done) = (cell.clone(), done.clone());
+        thread::spawn(move || {
+            for i in 1..=sets {
+                cell.set(i);
+            }
+            done.store(true, Ordering::SeqCst);
+        })
+    };
+    let deadline = Instant::now() + Duration::from_millis(200);
+    while !done.load(Ordering::SeqCst) && Instant::now() < deadline {
+        thread::yield_now();
+    }
+    let passed = done.load(Ordering::SeqCst);
+
+    slow.0.released.store(true, Ordering::SeqCst);
+    writer.join().unwrap();
+    // The parked reader's slot went stale, so it retries and sees a committed value.
+    // 被挂起读者的槽已过期，因此它会重试并看到已提交的值。
+    assert!(reader.join().unwrap() <= sets);
+    assert_eq!(cell.get(), sets);
+    passed
+}
+
+/// With two slots the second `set` must wait for a reader pinning the initial slot; with three,
+/// two `set`s run ahead of it.
+#[test]
+fn test_extra_slots_let_writer_pass_slow_reader() {
+    assert!(!sets_pass_slow_reader::<2>(2), "two slots: the writer should wait for the reader");
+    assert!(sets_pass_slow_reader::<3>(2), "three slots: the writer should not wait");
+    assert!(sets_pass_slow_reader::<4>(3), "four slots: the writer should not wait");
+}
+
+/// Many slots still publish every value in order.
+#[test]
+fn test_multi_slot_publish_order() {
+    check_publish_order(Arc::new(SwmrCell::<u64, 4>::with_slots(0)));
+}
+
+/// Sequential `update`s from the writer are observed by a reader in order, never skipping back.
+#[test]
+fn test_update_observed_in_order() {
+    const UPDATES: u64 = 10_000;
+
+    let cell = Arc::new(SwmrCell::new((0u64, 0u64)));
+    let done = Arc::new(AtomicBool::new(false));
+
+    let reader = {
+        let (cell, done) = (cell.clone(), done.clone());
+        thread::spawn(move || {
+            let mut last = 0;
+            while !done.load(Ordering::Relaxed) {
+                let (count, doubled) = cell.get();
+                assert_eq!(doubled, count * 2, "torn value");
+                assert!(count >= last, "update went backwards: {} < {}", count, last);
+                last = count;
+            }
+        })
+    };
+
+    for _ in 0..UPDATES {
+        cell.update(|(count, _)| (count + 1, (count + 1) * 2));
+    }
+    done.store(true, Ordering::Relaxed);
+    reader.join().unwrap();
+    assert_eq!(cell.get(), (UPDATES, UPDATES * 2));
+}
+
+/// Writers racing through `update` are serialized, so no increment is lost, and a panicking `f`
+/// does not leave the cell locked.
+#[test]
+fn test_concurrent_updates_are_not_lost() {
+    const UPDATES: u64 = 2_000;
+
+    let cell = Arc::new(SwmrCell::new(0u64));
+    let writers: Vec<_> = (0..2)
+        .map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                for _ in 0..UPDATES {
+                    cell.update(|v| v + 1);
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    assert_eq!(cell.get(), 2 * UPDATES);
+
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| cell.update(|_| panic!("boom"))));
+    assert!(panicked.is_err());
+    cell.update(|v| v + 1);
+    assert_eq!(cell.get(), 2 * UPDATES + 1);
+}
+
+/// Polling with no writer returns the value once, then `None` until the next commit, including
+/// commits that return to a previously active slot.
+#[test]
+fn test_reader_skips_unchanged_value() {
+    let cell = SwmrCell::<[u64; 32], 3>::with_slots([0; 32]);
+    let mut reader = cell.reader();
+
+    assert_eq!(reader.read_if_newer(), Some([0; 32]));
+    for _ in 0..100 {
+        assert_eq!(reader.read_if_newer(), None);
+    }
+
+    for round in 1..=7 {
+        cell.set([round; 32]);
+        assert_eq!(reader.read_if_newer(), Some([round; 32]), "round {round}");
+        assert_eq!(reader.read_if_newer(), None, "round {round}");
+    }
+
+    // Two commits between polls: only the latest is returned.
+    // 两次轮询之间有两次提交：只返回最新的值。
+    cell.set([8; 32]);
+    cell.set([9; 32]);
+    assert_eq!(reader.read_if_newer(), Some([9; 32]));
+    assert_eq!(reader.read_if_newer(), None);
+
+    // Each handle tracks its own position.
+    // 每个句柄独立跟踪自己的位置。
+    assert_eq!(cell.reader().read_if_newer(), Some([9; 32]));
+}
+
+/// A polling reader racing the writer sees every value at most once, in order, ending on the last.
+#[test]
+fn test_reader_polls_concurrent_commits() {
+    const COMMITS: u64 = 10_000;
+
+    let cell = Arc::new(SwmrCell::new((0u64, 0u64)));
+    let reader = {
+        let cell = cell.clone();
+        thread::spawn(move || {
+            let mut reader = cell.reader();
+            let mut last = None;
+            while last != Some(COMMITS) {
+                if let Some((count, doubled)) = reader.read_if_newer() {
+                    assert_eq!(doubled, count * 2, "torn value");
+                    assert!(last < Some(count), "value returned twice or out of order: {count} after {last:?}");
+                    last = Some(count);
+                }
+            }
+        })
+    };
+
+    for count in 1..=COMMITS {
+        cell.set((count, count * 2));
+    }
+    reader.join().unwrap();
+}
+
+/// Commits to `SABOTAGED_CELL` from inside `light()` while sabotage remains, so every pin that
+/// goes through `light()` finds its slot already replaced and must retry.
+struct SabotageBarrier;
+
+static SABOTAGE: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+static SABOTAGED_CELL: std::sync::OnceLock<SwmrCell<(u64, u64), 2, SabotageBarrier>> = std::sync::OnceLock::new();
+
+// SAFETY: Both sides issue `fence(SeqCst)`, which satisfies the asymmetric contract.
+unsafe impl swmr_barrier::AsymmetricBarrier for SabotageBarrier {
+    fn heavy(&self) {
+        fence(Ordering::SeqCst);
+    }
+
+    fn light(&self) {
+        fence(Ordering::SeqCst);
+        if SABOTAGE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1)).is_ok() {
+            let cell = SABOTAGED_CELL.get().unwrap();
+            cell.update(|(count, _)| (count + 1, (count + 1) * 2));
+        }
+    }
+
+    fn accelerated(&self) -> bool {
+        false
+    }
+}
+
+/// More than `threshold` retries in a row switch the reader to full fences, which the sabotage
+/// cannot hook, so the read completes with a consistent value; the upgrade wears off later.
+#[test]
+fn test_resilient_reader_upgrades_after_repeated_retries() {
+    const THRESHOLD: u32 = 8;
+
+    let cell = SABOTAGED_CELL.get_or_init(|| SwmrCell::with_barrier((0, 0), SabotageBarrier));
+    let mut reader = cell.resilient_reader(THRESHOLD);
+
+    assert_eq!(reader.get(), (0, 0));
+    assert!(!reader.is_upgraded());
+
+    // A few retries stay under the threshold.
+    // 少量重试不超过阈值。
+    SABOTAGE.store(3, Ordering::Relaxed);
+    assert_eq!(reader.get(), (3, 6));
+    assert!(!reader.is_upgraded());
+
+    // The streak carries over: 3 + 6 retries exceed the threshold mid-read.
+    // 连续次数会延续：3 + 6 次重试在读取中途超过阈值。
+    SABOTAGE.store(100, Ordering::Relaxed);
+    let (count, doubled) = reader.get();
+    assert_eq!(doubled, count * 2, "torn value");
+    assert_eq!(count, 3 + (THRESHOLD as u64 + 1 - 3));
+    assert!(reader.is_upgraded());
+    assert_eq!(reader.upgrades(), 1);
+    assert_eq!(SABOTAGE.load(Ordering::Relaxed), 100 - (THRESHOLD + 1 - 3), "upgraded reads must not call light()");
+
+    // Upgraded reads succeed first time, then the reader returns to `light()`.
+    // 升级后的读取一次成功，之后读者恢复使用 `light()`。
+    for _ in 0..1024 {
+        assert_eq!(reader.get(), (count, doubled));
+    }
+    assert!(!reader.is_upgraded());
+    SABOTAGE.store(1, Ordering::Relaxed);
+    assert_eq!(reader.get(), (count + 1, (count + 1) * 2));
+    assert_eq!(SABOTAGE.load(Ordering::Relaxed), 0);
+    assert_eq!(reader.upgrades(), 1);
+}
+
+/// A payload of exactly `MAX_CELL_BYTES` is accepted.
+#[test]
+fn test_payload_at_max_cell_bytes() {
+    let cell = SwmrCell::new([7u8; swmr_barrier::MAX_CELL_BYTES]);
+    cell.set([9u8; swmr_barrier::MAX_CELL_BYTES]);
+    assert_eq!(cell.get(), [9u8; swmr_barrier::MAX_CELL_BYTES]);
+}
+
+/// One byte past `MAX_CELL_BYTES` is rejected in debug builds.
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "exceeds MAX_CELL_BYTES")]
+fn test_payload_over_max_cell_bytes() {
+    let _ = SwmrCell::new([0u8; swmr_barrier::MAX_CELL_BYTES + 1]);
+}
+
+static SHARED: SwmrCell<u64> = SwmrCell::new_const(0);
+
+/// A cell built by `new_const` works as a `static` shared by every thread.
+#[test]
+fn test_static_cell() {
+    const SETS: u64 = 100_000;
+    thread::scope(|s| {
+        for _ in 0..2 {
+            s.spawn(|| {
+                let mut last = 0;
+                while last < SETS {
+                    let value = SHARED.get();
+                    assert!(value >= last, "value went backwards: {value} < {last}");
+                    last = value;
+                }
+            });
+        }
+        s.spawn(|| {
+            for i in 1..=SETS {
+                SHARED.set(i);
+            }
+        });
+    });
+    assert_eq!(SHARED.get(), SETS);
+}
+
+#[test]
+fn test_default() {
+    assert_eq!(SwmrCell::<(u32, [u8; 3])>::default().get(), (0, [0; 3]));
+    assert_eq!(SwmrWordCell::<u64>::default().get(), 0);
+    assert_eq!(swmr_barrier::ChecksummedCell::<u32>::default().get(), 0);
+}
@@ -0,0 +1,91 @@
+//! Tests for `runtime_telemetry()` with the `telemetry` counters.
+//!
+//! These tests reset process-global counters through the `testing` hooks,
+//! so they live in their own binary and are serialized with a lock.
+//!
+//! Run with: `cargo test --features testing,telemetry,latency --test telemetry_test`
+
+#![cfg(all(feature = "telemetry", feature = "testing", not(feature = "loom")))]
+
+use std::sync::Mutex;
+use std::thread;
+use swmr_barrier::{heavy_barrier, light_barrier, runtime_telemetry, testing};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// The snapshot counts the barriers issued since the reset, including the light barrier it
+/// issues itself.
+#[test]
+fn test_snapshot_reflects_activity() {
+    let _guard = LOCK.lock().unwrap();
+    testing::reset_for_test();
+    let before = runtime_telemetry();
+    assert_eq!((before.heavy_count, before.light_count, before.fallback_count), (0, 1, 0));
+
+    for _ in 0..5 {
+        heavy_barrier();
+    }
+    for _ in 0..7 {
+        light_barrier();
+    }
+    let after = runtime_telemetry();
+    assert_eq!(after.heavy_count, 5);
+    assert_eq!(after.light_count, 1 + 7 + 1);
+    assert!(after.generation >= before.generation + 5);
+    if swmr_barrier::is_accelerated() {
+        assert_eq!(after.fallback_count, 0);
+        assert!(!after.degraded);
+        #[cfg(all(target_os = "linux", feature = "latency"))]
+        assert!(after.max_latency_ns > 0);
+    } else {
+        assert_eq!(after.fallback_count, 5);
+    }
+    println!("{after:?}");
+}
+
+/// A membarrier call that fails and falls back to a fence is counted and marks the process
+/// degraded, while the configured backend stays accelerated.
+#[cfg(target_os = "linux")]
+#[test]
+fn test_failed_barrier_counts_as_fallback() {
+    const EINTR: i32 = 4;
+    let _guard = LOCK.lock().unwrap();
+    testing::reset_for_test();
+    if !swmr_barrier::init() {
+        eprintln!("membarrier unavailable; skipping");
+        return;
+    }
+    assert!(!runtime_telemetry().degraded);
+
+    testing::inject_barrier_failure(EINTR, 1);
+    heavy_barrier();
+    heavy_barrier();
+
+    let snapshot = runtime_telemetry();
+    assert!(swmr_barrier::is_accelerated());
+    assert_eq!(snapshot.heavy_count, 2);
+    assert_eq!(snapshot.fallback_count, 1);
+    assert!(snapshot.degraded);
+    testing::reset_for_test();
+}
+
+/// Scrapes racing with writers never see more fallbacks than heavy barriers, or more heavy
+/// barriers than the generation.
+#[test]
+fn test_concurrent_snapshots_stay_ordered() {
+    let _guard = LOCK.lock().unwrap();
+    testing::reset_for_test();
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            for _ in 0..2_000 {
+                heavy_barrier();
+            }
+        });
+        for _ in 0..2_000 {
+            let snapshot = runtime_telemetry();
+            assert!(snapshot.fallback_count <= snapshot.heavy_count, "{snapshot:?}");
+            assert!(snapshot.heavy_count <= snapshot.generation, "{snapshot:?}");
+        }
+    });
+}
@@ -0,0 +1,120 @@
+//! Tests for `cpu_topology_hint()` and the sysfs parser behind it.
+
+#![cfg(not(feature = "loom"))]
+
+use swmr_barrier::TopologyHint;
+
+/// `stats()` carries the hint read on its first call, which matches a fresh read while no CPU is
+/// hotplugged, and the live machine yields plausible counts on Linux with `std`.
+#[test]
+fn test_live_topology() {
+    let hint = swmr_barrier::cpu_topology_hint();
+    assert_eq!(swmr_barrier::stats().topology, hint);
+
+    if cfg!(all(feature = "std", target_os = "linux")) {
+        let TopologyHint { cpus, packages, clusters, capacity_classes, .. } = hint.expect("sysfs topology");
+        assert!(cpus >= 1 && packages >= 1 && capacity_classes >= 1);
+        assert!(clusters >= packages && clusters <= cpus);
+    } else {
+        assert_eq!(hint, None);
+    }
+}
+
+#[cfg(all(feature = "testing", feature = "std", target_os = "linux"))]
+mod sysfs {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use swmr_barrier::testing::read_cpu_topology;
+
+    /// A scratch directory removed on drop.
+    struct FakeSysfs(PathBuf);
+
+    impl FakeSysfs {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!("swmr-topology-{}-{name}", std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(&root).unwrap();
+            Self(root)
+        }
+
+        fn write(&self, path: &str, contents: &str) {
+            let path = self.0.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        /// Adds `cpuN` with the given ids; `None` leaves the attribute out.
+        fn cpu(&self, n: usize, package: &str, cluster: Option<&str>, capacity: Option<&str>) {
+            self.write(&format!("cpu{n}/topology/physical_package_id"), package);
+            if let Some(cluster) = cluster {
+                self.write(&format!("cpu{n}/topology/cluster_id"), cluster);
+            }
+            if let Some(capacity) = capacity {
+                self.write(&format!("cpu{n}/cpu_capacity"), capacity);
+            }
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for FakeSysfs {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// A phone-style big.LITTLE layout: one package, two clusters with different capacities.
+    #[test]
+    fn test_big_little() {
+        let sysfs = FakeSysfs::new("big-little");
+        for n in 0..4 {
+            sysfs.cpu(n, "0\n", Some("0\n"), Some("446\n"));
+        }
+        for n in 4..8 {
+            sysfs.cpu(n, "0\n", Some("1\n"), Some("1024\n"));
+        }
+
+        let hint = read_cpu_topology(sysfs.path()).unwrap();
+        assert_eq!((hint.cpus, hint.packages, hint.clusters, hint.capacity_classes), (8, 1, 2, 2));
+        assert!(hint.is_heterogeneous());
+    }
+
+    /// Kernels before 5.16 have no `cluster_id`, and x86 has no `cpu_capacity`.
+    #[test]
+    fn test_two_sockets_without_cluster_or_capacity() {
+        let sysfs = FakeSysfs::new("two-sockets");
+        for n in 0..4 {
+            sysfs.cpu(n, if n < 2 { "0" } else { "1" }, None, None);
+        }
+
+        let hint = read_cpu_topology(sysfs.path()).unwrap();
+        assert_eq!((hint.cpus, hint.packages, hint.clusters, hint.capacity_classes), (4, 2, 2, 1));
+        assert!(!hint.is_heterogeneous());
+    }
+
+    /// Offline CPUs, unrelated entries, malformed files and `cluster_id` of -1 are tolerated.
+    #[test]
+    fn test_malformed_entries_are_skipped() {
+        let sysfs = FakeSysfs::new("malformed");
+        sysfs.cpu(0, "0", Some("-1"), None);
+        sysfs.cpu(1, "0", Some("garbage"), None);
+        sysfs.cpu(2, "not-a-number", Some("0"), None);
+        fs::create_dir_all(sysfs.path().join("cpu3")).unwrap(); // offline: no topology
+        fs::create_dir_all(sysfs.path().join("cpufreq/policy0")).unwrap();
+        sysfs.write("cpuidle/current_driver", "none");
+        sysfs.write("online", "0-3");
+
+        let hint = read_cpu_topology(sysfs.path()).unwrap();
+        assert_eq!((hint.cpus, hint.packages, hint.clusters, hint.capacity_classes), (2, 1, 1, 1));
+    }
+
+    /// A missing or CPU-less directory gives `None`.
+    #[test]
+    fn test_unavailable_sysfs() {
+        let sysfs = FakeSysfs::new("empty");
+        assert_eq!(read_cpu_topology(sysfs.path()), None);
+        assert_eq!(read_cpu_topology(&sysfs.path().join("missing")), None);
+    }
+}
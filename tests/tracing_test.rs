@@ -0,0 +1,55 @@
+//! Tests for the `tracing` feature.
+
+#![cfg(all(feature = "tracing", not(feature = "loom")))]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Counts events emitted under the crate's target.
+struct EventCounter {
+    heavy: Arc<AtomicUsize>,
+}
+
+impl Subscriber for EventCounter {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        if event.metadata().target() == "swmr_barrier" {
+            self.heavy.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+/// Exactly one event per `heavy_barrier()`, none for `light_barrier()`.
+#[test]
+fn test_event_per_heavy_barrier() {
+    let heavy = Arc::new(AtomicUsize::new(0));
+    let subscriber = EventCounter { heavy: heavy.clone() };
+
+    tracing::subscriber::with_default(subscriber, || {
+        for _ in 0..5 {
+            swmr_barrier::heavy_barrier();
+        }
+        for _ in 0..100 {
+            swmr_barrier::light_barrier();
+        }
+    });
+
+    assert_eq!(heavy.load(Ordering::Relaxed), 5);
+}
@@ -0,0 +1,63 @@
+//! Tests for `init_with_validation` (Linux).
+//!
+//! These tests mutate process-global barrier state through the `testing` hooks,
+//! so they live in their own binary and are serialized with a lock.
+//!
+//! Run with: `cargo test --features testing,std --test validation_test`
+
+#![cfg(all(
+    target_os = "linux",
+    feature = "testing",
+    feature = "std",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+
+use std::sync::Mutex;
+use swmr_barrier::{Backend, InitReason, init_with_validation, testing};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+const EINTR: i32 = 4;
+
+/// Returns `false` if membarrier is unavailable here (nothing to validate then).
+fn accelerated_start() -> bool {
+    testing::reset_for_test();
+    if !swmr_barrier::init() {
+        eprintln!("membarrier unavailable; skipping");
+        return false;
+    }
+    true
+}
+
+#[test]
+fn test_healthy_barrier_passes_every_round() {
+    let _guard = LOCK.lock().unwrap();
+    if !accelerated_start() {
+        return;
+    }
+    assert_eq!(init_with_validation(200), InitReason::Accelerated);
+    assert!(swmr_barrier::is_accelerated());
+}
+
+/// One syscall failure among the rounds, after `init()`'s own validation barrier passed, is
+/// enough to fall back.
+#[test]
+fn test_intermittent_failure_selects_fallback() {
+    let _guard = LOCK.lock().unwrap();
+    if !accelerated_start() {
+        return;
+    }
+    testing::inject_barrier_failure(EINTR, 1);
+    assert_eq!(init_with_validation(200), InitReason::ValidationFailed);
+    assert!(!swmr_barrier::is_accelerated());
+    assert_eq!(swmr_barrier::active_backend(), Backend::Fallback);
+    assert_eq!(swmr_barrier::stats().init_reason, InitReason::ValidationFailed);
+
+    // Barriers keep working on the fallback.
+    swmr_barrier::heavy_barrier();
+    swmr_barrier::light_barrier();
+
+    testing::reset_for_test();
+    assert!(swmr_barrier::is_accelerated());
+}
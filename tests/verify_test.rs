@@ -0,0 +1,22 @@
+//! Tests for the `verify` integrity monitor.
+
+#![cfg(all(feature = "verify", not(feature = "loom")))]
+
+use std::thread;
+use std::time::{Duration, Instant};
+use swmr_barrier::{integrity_ok, integrity_rounds};
+
+/// With correct barriers the canary pair completes many rounds without ever poisoning the monitor.
+#[test]
+fn test_integrity_stays_ok() {
+    const ROUNDS: u64 = 500;
+
+    assert!(integrity_ok());
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while integrity_rounds() < ROUNDS {
+        assert!(Instant::now() < deadline, "canary made only {} rounds", integrity_rounds());
+        assert!(integrity_ok(), "barrier violation after {} rounds", integrity_rounds());
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert!(integrity_ok());
+}
@@ -0,0 +1,61 @@
+//! Tests for `heavy_barrier_with_deadline()` (Linux).
+//!
+//! These tests mutate process-global barrier state through the `testing` hooks,
+//! so they live in their own binary and are serialized with a lock.
+//!
+//! Run with: `cargo test --features testing,std --test watchdog_test`
+
+#![cfg(all(
+    target_os = "linux",
+    feature = "testing",
+    feature = "std",
+    not(feature = "loom"),
+    not(all(swmr_barrier_tsan, not(feature = "tsan-annotate")))
+))]
+
+use std::sync::Mutex;
+use std::time::Duration;
+use swmr_barrier::{Backend, InitReason, heavy_barrier_with_deadline, testing};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// A membarrier that overruns its deadline trips the watchdog: the call reports the overrun and
+/// the process falls back to fences.
+#[test]
+fn test_stuck_barrier_trips_watchdog() {
+    let _guard = LOCK.lock().unwrap();
+    testing::reset_for_test();
+    if !swmr_barrier::is_accelerated() {
+        return;
+    }
+
+    testing::inject_barrier_delay(Duration::from_millis(50), 1);
+    let err = heavy_barrier_with_deadline(10_000_000).unwrap_err();
+    assert!(err.elapsed_ns >= 50_000_000, "{err}");
+
+    let stats = swmr_barrier::stats();
+    assert!(!swmr_barrier::is_accelerated());
+    assert_eq!(stats.backend, Backend::Fallback);
+    assert_eq!(stats.init_reason, InitReason::WatchdogTripped);
+    assert!(stats.slowest_heavy_ns.unwrap() >= err.elapsed_ns);
+
+    // Later barriers no longer reach the syscall.
+    // 之后的屏障不再进入系统调用。
+    assert!(heavy_barrier_with_deadline(u64::MAX).is_ok());
+
+    testing::reset_for_test();
+    assert!(swmr_barrier::is_accelerated());
+}
+
+/// A barrier within its deadline leaves acceleration untouched and records its latency.
+#[test]
+fn test_barrier_within_deadline() {
+    let _guard = LOCK.lock().unwrap();
+    testing::reset_for_test();
+    assert_eq!(swmr_barrier::stats().slowest_heavy_ns, None);
+
+    let accelerated = swmr_barrier::is_accelerated();
+    assert!(heavy_barrier_with_deadline(u64::MAX).is_ok());
+    assert_eq!(swmr_barrier::is_accelerated(), accelerated);
+    assert!(swmr_barrier::stats().slowest_heavy_ns.is_some());
+}